@@ -1,16 +1,42 @@
 #![no_std]
 
 use remitwise_common::{
-    clamp_limit, EventCategory, EventPriority, RemitwiseEvents, ARCHIVE_BUMP_AMOUNT,
+    clamp_limit, EventCategory, EventPriority, FamilyRole, RemitwiseEvents, ARCHIVE_BUMP_AMOUNT,
     ARCHIVE_LIFETIME_THRESHOLD, CONTRACT_VERSION, DEFAULT_PAGE_LIMIT, INSTANCE_BUMP_AMOUNT,
     INSTANCE_LIFETIME_THRESHOLD, MAX_BATCH_SIZE, MAX_PAGE_LIMIT,
 };
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, Address, BytesN, Env, Map, String, Symbol, Vec,
 };
 
+/// FX oracle interface used to normalize bills in different tokens.
+#[contractclient(name = "FxOracleClient")]
+pub trait FxOracle {
+    /// Units of `quote` per one unit of `base`, scaled by `FX_RATE_SCALE`.
+    fn get_rate(env: Env, base: Address, quote: Address) -> i128;
+}
+
+/// Fixed-point scale of oracle rates (7 decimals, as for Stellar assets).
+pub const FX_RATE_SCALE: i128 = 10_000_000;
+
+/// Family member record as returned by the family wallet contract.
+#[contracttype]
+#[derive(Clone)]
+pub struct FamilyMember {
+    pub address: Address,
+    pub role: FamilyRole,
+    pub spending_limit: i128,
+    pub added_at: u64,
+}
+
+/// Family wallet interface used to check approver roles.
+#[contractclient(name = "FamilyWalletClient")]
+pub trait FamilyWallet {
+    fn get_family_member(env: Env, member: Address) -> Option<FamilyMember>;
+}
+
 #[derive(Clone, Debug)]
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -30,6 +56,363 @@ pub struct Bill {
     /// Intended currency/asset for this bill (e.g. "XLM", "USDC", "NGN").
     /// Defaults to "XLM" for entries created before this field was introduced.
     pub currency: String,
+    /// Who receives the funds when the bill is paid.
+    pub payee: BillPayee,
+    /// Token contract used to settle the bill.
+    pub token: Address,
+    /// Amount settled so far through full or partial payments.
+    pub amount_paid: i128,
+    /// Penalty that accrues while the bill is overdue.
+    pub late_fee: LateFeePolicy,
+    /// Pay automatically from the owner's allowance once due.
+    pub autopay: bool,
+    /// Position of this bill in its recurring series, starting at 1.
+    pub occurrence: u32,
+    /// Stop regenerating after this many occurrences.
+    pub max_occurrences: Option<u32>,
+    /// Stop regenerating once the next due date would fall after this time.
+    pub end_date: Option<u64>,
+    pub category: BillCategory,
+    pub approval: ApprovalStatus,
+    /// A due-soon reminder has been emitted for this bill.
+    pub reminder_sent: bool,
+    /// SHA-256 hash of the invoice document this bill settles; all zeros
+    /// when no invoice is attached.
+    pub invoice_hash: BytesN<32>,
+    /// Invoice number as printed on the payee's invoice; empty when no
+    /// invoice is attached.
+    pub invoice_number: String,
+    pub priority: BillPriority,
+    /// Whether the next occurrence is due `frequency_days` after this
+    /// bill's due date or after the date it was paid.
+    pub recurrence_base: RecurrenceBase,
+    /// Recurring series is on hold: the bill cannot be paid and is left out
+    /// of overdue queries, reminders and autopay until resumed.
+    pub suspended: bool,
+    /// Short labels for grouping related bills, e.g. `school2025`.
+    pub tags: Vec<Symbol>,
+    /// Seconds the payee has to confirm receipt of an escrowed payment;
+    /// 0 pays the payee directly.
+    pub escrow_window: u64,
+}
+
+impl Bill {
+    /// Whether an invoice has been attached to this bill.
+    pub fn has_invoice(&self) -> bool {
+        self.invoice_hash.to_array() != [0; 32]
+    }
+
+    /// Amount still outstanding on this bill.
+    pub fn remaining(&self) -> i128 {
+        self.amount - self.amount_paid
+    }
+
+    /// Due date of the occurrence following this one. Bills counted from
+    /// the payment date fall back to the due date until they are paid.
+    pub fn next_due_date(&self) -> u64 {
+        let base = match (self.recurrence_base, self.paid_at) {
+            (RecurrenceBase::PaymentDate, Some(paid_at)) => paid_at,
+            _ => self.due_date,
+        };
+        base + self.frequency_days as u64 * 86400
+    }
+
+    /// Whether paying or skipping this bill should produce another
+    /// occurrence of its recurring series.
+    pub fn has_next_occurrence(&self) -> bool {
+        if !self.recurring {
+            return false;
+        }
+        if let Some(max) = self.max_occurrences {
+            if self.occurrence >= max {
+                return false;
+            }
+        }
+        if let Some(end) = self.end_date {
+            if self.next_due_date() > end {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Late fee accrued on the remaining balance as of `as_of`.
+    /// Basis-point fees accrue per full day past the due date.
+    pub fn late_fee_at(&self, as_of: u64) -> i128 {
+        if self.paid || as_of <= self.due_date {
+            return 0;
+        }
+        match self.late_fee {
+            LateFeePolicy::None => 0,
+            LateFeePolicy::Flat(fee) => fee,
+            LateFeePolicy::BpsPerDay(bps) => {
+                let days_late = ((as_of - self.due_date) / 86400) as i128;
+                self.remaining()
+                    .saturating_mul(bps as i128)
+                    .saturating_mul(days_late)
+                    / 10_000
+            }
+        }
+    }
+}
+
+/// One bill to create with `create_bills`. Fields match the arguments of
+/// `create_bill`, plus invoice details (see `attach_invoice`); pass a zero
+/// hash and an empty number for bills without an invoice. `tags` may be
+/// empty.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NewBill {
+    pub name: String,
+    pub amount: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    pub currency: String,
+    pub payee: Address,
+    pub token: Address,
+    pub invoice_hash: BytesN<32>,
+    pub invoice_number: String,
+    pub tags: Vec<Symbol>,
+}
+
+/// Payment reliability summary returned by `get_payment_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentStats {
+    /// Bills settled within the period.
+    pub bills_paid: u32,
+    /// Principal paid within the period, including partial payments.
+    pub total_paid: i128,
+    pub late_fees: i128,
+    /// Share of settled bills paid by their due date, 0–100.
+    pub on_time_pct: u32,
+    /// Average whole days (rounded up) by which late bills were settled
+    /// after their due date.
+    pub avg_days_late: u32,
+}
+
+/// A payment of a bill's full balance set to run at `execute_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPayment {
+    pub bill_id: u32,
+    pub owner: Address,
+    pub execute_at: u64,
+    pub created_at: u64,
+}
+
+/// Payment held by the contract until the payee confirms receipt.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowHold {
+    pub bill_id: u32,
+    pub receipt_id: u32,
+    pub payer: Address,
+    pub payee: Address,
+    pub token: Address,
+    /// Principal of the payment, excluding any late fee.
+    pub principal: i128,
+    /// Total held, late fee included.
+    pub amount: i128,
+    pub locked_at: u64,
+    /// Last moment the payee can confirm; the payer can reclaim after it.
+    pub release_by: u64,
+}
+
+/// Payload of the `reminder` event emitted by `emit_due_reminders`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DueReminder {
+    pub bill_id: u32,
+    pub owner: Address,
+    pub amount_due: i128,
+    pub token: Address,
+    pub due_date: u64,
+}
+
+/// Spending category of a bill
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BillCategory {
+    Utilities = 1,
+    Education = 2,
+    Housing = 3,
+    Health = 4,
+    Other = 5,
+}
+
+/// Reference date from which the next occurrence of a recurring bill is
+/// scheduled.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RecurrenceBase {
+    /// `frequency_days` after the previous due date (default).
+    DueDate = 1,
+    /// `frequency_days` after the previous occurrence was paid, as with
+    /// many prepaid utilities.
+    PaymentDate = 2,
+}
+
+/// How urgently a bill should be funded when money is short.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BillPriority {
+    High = 1,
+    Normal = 2,
+    Low = 3,
+}
+
+/// Whether a bill needs family approval before it can be paid.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ApprovalStatus {
+    NotRequired = 1,
+    Pending = 2,
+    Approved = 3,
+}
+
+/// Owner's large-bill approval settings.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ApprovalConfig {
+    /// Family wallet whose Owner/Admin members may approve bills.
+    pub family_wallet: Address,
+    /// Bills with an amount above this need approval.
+    pub threshold: i128,
+}
+
+/// One member's part of a split bill.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BillShare {
+    pub member: Address,
+    /// Portion of the bill in basis points (all shares sum to 10 000).
+    pub bps: u32,
+    pub amount: i128,
+    pub paid: bool,
+}
+
+/// Proof of a single bill payment.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Receipt {
+    pub id: u32,
+    pub bill_id: u32,
+    pub payer: Address,
+    /// Address that received the funds.
+    pub payee: Address,
+    pub token: Address,
+    /// Amount applied to the bill balance.
+    pub amount: i128,
+    /// Late fee collected on top of `amount`.
+    pub late_fee: i128,
+    pub timestamp: u64,
+    /// State of the bill right after the payment.
+    pub bill: Bill,
+    /// Set once the payment has been returned by `refund_bill`.
+    pub refunded: bool,
+}
+
+/// Lifecycle of a bill dispute.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DisputeStatus {
+    Open = 1,
+    /// The bill was found correct and can be paid again.
+    Upheld = 2,
+    /// The bill was withdrawn and removed.
+    Cancelled = 3,
+}
+
+/// Decision passed to `resolve_dispute`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DisputeOutcome {
+    Upheld = 1,
+    Cancelled = 2,
+}
+
+/// A contested bill. The bill cannot be paid while the dispute is open.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Dispute {
+    pub bill_id: u32,
+    pub opened_by: Address,
+    /// Hash of the off-chain reason / evidence.
+    pub reason_hash: BytesN<32>,
+    pub opened_at: u64,
+    pub status: DisputeStatus,
+    /// 0 while the dispute is open.
+    pub resolved_at: u64,
+}
+
+/// Amount paid on bills of one category over a period.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CategoryTotal {
+    pub category: BillCategory,
+    pub total: i128,
+    pub count: u32,
+}
+
+/// Spending against a category budget in the current budget period.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BudgetStatus {
+    pub category: BillCategory,
+    /// Monthly limit, 0 when no budget is set.
+    pub limit: i128,
+    pub spent: i128,
+    pub remaining: i128,
+    pub exceeded: bool,
+    pub period_start: u64,
+}
+
+/// Amount paid in a category during one budget period.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CategorySpend {
+    pub period: u64,
+    pub spent: i128,
+}
+
+/// Late-fee policy applied to a bill once it is past its due date.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LateFeePolicy {
+    None,
+    /// Fixed fee charged once the bill is overdue.
+    Flat(i128),
+    /// Basis points of the remaining balance charged per day overdue.
+    BpsPerDay(u32),
+}
+
+/// Recipient of a bill's payments.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BillPayee {
+    /// Paid to this address.
+    Address(Address),
+    /// Paid to the current address of this registered payee.
+    Registered(u32),
+}
+
+/// Bills a delegated payer may settle on the owner's behalf.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayerScope {
+    /// Only the listed bills.
+    Bills(Vec<u32>),
+    /// Any of the owner's bills, until the payer's payments under the grant,
+    /// late fees included, add up to this amount.
+    UpTo(i128),
 }
 
 
@@ -45,11 +428,67 @@ pub struct BillPage {
     pub count: u32,
 }
 
+/// A recurring payee registered once by an owner and referenced by bills.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Payee {
+    pub id: u32,
+    pub owner: Address,
+    /// Address that receives payments for bills linked to this payee.
+    pub address: Address,
+    pub name: String,
+    /// Free-form category, e.g. `utility`, `school`, `rent`.
+    pub category: Symbol,
+    pub active: bool,
+    pub created_at: u64,
+}
+
+/// A single scheduled installment of an installment plan.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Installment {
+    /// Position in the plan, starting at 0.
+    pub index: u32,
+    pub amount: i128,
+    pub due_date: u64,
+}
+
+/// Splits the remaining balance of a bill into scheduled installments.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InstallmentPlan {
+    pub bill_id: u32,
+    pub installments: Vec<Installment>,
+    pub interval_days: u32,
+    /// `amount_paid` on the bill when the plan was created. Payments made
+    /// after this point count towards the installments in order.
+    pub paid_before_plan: i128,
+    pub created_at: u64,
+}
+
+/// Progress through an installment plan.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstallmentProgress {
+    pub bill_id: u32,
+    pub total_installments: u32,
+    pub paid_installments: u32,
+    /// Amount paid towards the plan so far.
+    pub amount_paid: i128,
+    pub amount_remaining: i128,
+    /// Index of the first installment not yet fully covered, if any.
+    pub next_index: Option<u32>,
+    pub next_due_date: Option<u64>,
+    /// Amount still owed on the next installment.
+    pub next_outstanding: i128,
+}
+
 pub mod pause_functions {
     use soroban_sdk::symbol_short;
     pub const CREATE_BILL: soroban_sdk::Symbol = symbol_short!("crt_bill");
     pub const PAY_BILL: soroban_sdk::Symbol = symbol_short!("pay_bill");
     pub const CANCEL_BILL: soroban_sdk::Symbol = symbol_short!("can_bill");
+    pub const UPDATE_BILL: soroban_sdk::Symbol = symbol_short!("upd_bill");
     pub const ARCHIVE: soroban_sdk::Symbol = symbol_short!("archive");
     pub const RESTORE: soroban_sdk::Symbol = symbol_short!("restore");
 }
@@ -57,6 +496,41 @@ pub mod pause_functions {
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
 const STORAGE_UNPAID_TOTALS: Symbol = symbol_short!("UNPD_TOT");
+const STORAGE_INSTALLMENT_PLANS: Symbol = symbol_short!("INST_PLAN");
+const MAX_INSTALLMENTS: u32 = 52;
+const MAX_LATE_FEE_BPS: u32 = 10_000;
+const STORAGE_CATEGORY_BUDGETS: Symbol = symbol_short!("CAT_BUDG");
+const STORAGE_CATEGORY_SPEND: Symbol = symbol_short!("CAT_SPEND");
+/// Budgets reset every 30 days, counted from the Unix epoch.
+const BUDGET_PERIOD_SECS: u64 = 30 * 86400;
+const STORAGE_FX_ORACLE: Symbol = symbol_short!("FX_ORACLE");
+const STORAGE_APPROVAL_CONFIG: Symbol = symbol_short!("APPR_CFG");
+const STORAGE_BILL_SHARES: Symbol = symbol_short!("SHARES");
+const MAX_SHARES: u32 = 20;
+const STORAGE_DISPUTES: Symbol = symbol_short!("DISPUTES");
+const KEY_RECEIPT: Symbol = symbol_short!("RECEIPT");
+const KEY_OWNER_RECEIPTS: Symbol = symbol_short!("RCPT_OWN");
+const KEY_BILL_RECEIPTS: Symbol = symbol_short!("RCPT_BILL");
+const KEY_BILL: Symbol = symbol_short!("BILL");
+const KEY_OWNER_UNPAID: Symbol = symbol_short!("OWN_UNPD");
+const KEY_OWNER_PAID: Symbol = symbol_short!("OWN_PAID");
+const KEY_ARCHIVE_TOTALS: Symbol = symbol_short!("ARCH_TOT");
+const STORAGE_SCHEDULED_PAYMENTS: Symbol = symbol_short!("SCHED_PAY");
+const KEY_BILLS_BALANCE: Symbol = symbol_short!("BILL_BAL");
+const KEY_PAYER: Symbol = symbol_short!("PAYER");
+const KEY_PAYER_SPENT: Symbol = symbol_short!("PAYER_SPT");
+const KEY_ESCROW: Symbol = symbol_short!("ESCROW");
+const KEY_DAY_BUCKET: Symbol = symbol_short!("DAY_BKT");
+const KEY_DAY_LIST: Symbol = symbol_short!("DAY_LIST");
+const INDEX_DUE: Symbol = symbol_short!("DUE");
+const INDEX_AUTOPAY: Symbol = symbol_short!("AUTOPAY");
+const INDEX_PAID: Symbol = symbol_short!("PAID");
+const KEY_LEGACY_BILLS: Symbol = symbol_short!("BILLS");
+const KEY_LEGACY_MIGRATION: Symbol = symbol_short!("LEG_MIG");
+const SECONDS_PER_DAY: u64 = 86400;
+/// Bill IDs examined per call by `get_all_bills`.
+const MAX_SCAN_IDS: u32 = 200;
+const MAX_TAGS_PER_BILL: u32 = 10;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -75,6 +549,49 @@ pub enum Error {
     InvalidLimit = 11,
     InvalidTag = 12,
     EmptyTags = 13,
+    TransferFailed = 14,
+    PayeeNotFound = 15,
+    PayeeInactive = 16,
+    AmountExceedsBalance = 17,
+    InvalidInstallments = 18,
+    InstallmentPlanExists = 19,
+    InstallmentPlanNotFound = 20,
+    OracleNotSet = 21,
+    FxRateUnavailable = 22,
+    ApprovalRequired = 23,
+    ApprovalNotPending = 24,
+    InvalidShares = 25,
+    BillIsSplit = 26,
+    ShareNotFound = 27,
+    NothingToRefund = 28,
+    BillDisputed = 29,
+    DisputeNotFound = 30,
+    PaymentAlreadyScheduled = 31,
+    ScheduledPaymentNotFound = 32,
+    InvalidExecutionTime = 33,
+    InsufficientAllowance = 34,
+    InsufficientBalance = 35,
+    BillSuspended = 36,
+    InvalidPayerScope = 37,
+    PayerNotFound = 38,
+    EscrowRequiresFullPayment = 39,
+    EscrowNotFound = 40,
+    EscrowWindowOpen = 41,
+    EscrowWindowClosed = 42,
+    InvalidFamilyWallet = 43,
+    RefundNotSupported = 44,
+    EscrowNotSupported = 45,
+}
+
+/// Where the funds for a bill payment come from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Funding {
+    /// Transferred directly from the payer, who authorized the call.
+    Direct,
+    /// Pulled from the owner through the allowance granted to this contract.
+    Allowance,
+    /// Taken from the owner's deposited bills balance held by this contract.
+    Balance,
 }
 
 #[contracttype]
@@ -90,6 +607,48 @@ pub struct ArchivedBill {
     pub archived_at: u64,
     /// Intended currency/asset carried over from the originating `Bill`.
     pub currency: String,
+    pub payee: BillPayee,
+    pub token: Address,
+}
+
+/// Bill record as kept in the instance `BILLS` map by earlier versions.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyBill {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub amount: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    pub paid: bool,
+    pub created_at: u64,
+    pub paid_at: Option<u64>,
+    pub schedule_id: Option<u32>,
+    pub currency: String,
+}
+
+/// Archive record as kept by earlier versions, before bills had a payee.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyArchivedBill {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub amount: i128,
+    pub paid_at: u64,
+    pub archived_at: u64,
+    pub currency: String,
+}
+
+/// Running totals of an owner's archived bills. Not reduced when archive
+/// records are purged, so history aggregates survive cleanup.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchiveTotals {
+    pub bill_count: u32,
+    pub total_amount: i128,
 }
 
 
@@ -276,6 +835,7 @@ impl BillPayments {
             pause_functions::CREATE_BILL,
             pause_functions::PAY_BILL,
             pause_functions::CANCEL_BILL,
+            pause_functions::UPDATE_BILL,
             pause_functions::ARCHIVE,
             pause_functions::RESTORE,
         ] {
@@ -339,6 +899,92 @@ impl BillPayments {
         Ok(())
     }
 
+    /// Move up to `limit` bills from the instance `BILLS` map written by
+    /// earlier versions into per-bill storage and the owner and day indexes.
+    ///
+    /// Upgrade admin only. Legacy bills had no payee or token: each migrated
+    /// bill is payable to its owner in `token`. The legacy archive is
+    /// converted on the first call, and storage stats are rebuilt as bills
+    /// move. Call repeatedly until it returns 0.
+    ///
+    /// # Returns
+    /// Number of legacy bills still waiting to be migrated
+    pub fn migrate_legacy_bills(
+        env: Env,
+        caller: Address,
+        token: Address,
+        limit: u32,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+        let Some(mut legacy) = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, LegacyBill>>(&KEY_LEGACY_BILLS)
+        else {
+            return Ok(0);
+        };
+
+        if !env.storage().instance().has(&KEY_LEGACY_MIGRATION) {
+            env.storage().instance().set(&KEY_LEGACY_MIGRATION, &true);
+            env.storage().instance().remove(&symbol_short!("STOR_STAT"));
+            Self::migrate_legacy_archive(&env, &token);
+        }
+
+        let limit = clamp_limit(limit);
+        for (id, old) in legacy.iter().take(limit as usize) {
+            let mut bill = Self::new_bill(
+                &env,
+                &old.owner,
+                NewBill {
+                    name: old.name,
+                    amount: old.amount,
+                    due_date: old.due_date,
+                    recurring: old.recurring,
+                    frequency_days: old.frequency_days,
+                    currency: old.currency,
+                    payee: old.owner.clone(),
+                    token: token.clone(),
+                    invoice_hash: BytesN::from_array(&env, &[0; 32]),
+                    invoice_number: String::from_str(&env, ""),
+                    tags: Vec::new(&env),
+                },
+            )?;
+            bill.id = id;
+            bill.created_at = old.created_at;
+            bill.paid = old.paid;
+            bill.paid_at = old.paid_at;
+            bill.schedule_id = old.schedule_id;
+            if bill.paid {
+                bill.amount_paid = bill.amount;
+            } else {
+                Self::adjust_storage_stats(&env, 0, 0, bill.amount, 0);
+            }
+            Self::save_bill(&env, &bill);
+            legacy.remove(id);
+        }
+
+        let remaining = legacy.len();
+        if remaining == 0 {
+            env.storage().instance().remove(&KEY_LEGACY_BILLS);
+            env.storage().instance().remove(&KEY_LEGACY_MIGRATION);
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::System,
+                EventPriority::High,
+                symbol_short!("migrated"),
+                Self::get_storage_stats(env.clone()).active_bills,
+            );
+        } else {
+            env.storage().instance().set(&KEY_LEGACY_BILLS, &legacy);
+        }
+        Ok(remaining)
+    }
+
     // -----------------------------------------------------------------------
     // Core bill operations
     // -----------------------------------------------------------------------
@@ -353,10 +999,93 @@ impl BillPayments {
         recurring: bool,
         frequency_days: u32,
         currency: String,
+        payee: Address,
+        token: Address,
+    ) -> Result<u32, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+
+        let item = NewBill {
+            name,
+            amount,
+            due_date,
+            recurring,
+            frequency_days,
+            currency,
+            payee,
+            token,
+            invoice_hash: BytesN::from_array(&env, &[0; 32]),
+            invoice_number: String::from_str(&env, ""),
+            tags: Vec::new(&env),
+        };
+        let bill = Self::new_bill(&env, &owner, item)?;
+        Ok(Self::insert_bill(&env, bill))
+    }
+
+    /// Create several bills for `owner` in one call, e.g. a month of rent,
+    /// utilities and school fees.
+    ///
+    /// Every item is validated before any bill is stored, and each bill
+    /// emits its own `created` event.
+    ///
+    /// # Returns
+    /// IDs of the new bills, in the order of `items`
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` – more than `MAX_BATCH_SIZE` items
+    /// * `InvalidAmount` / `InvalidFrequency` – as for `create_bill`
+    pub fn create_bills(env: Env, owner: Address, items: Vec<NewBill>) -> Result<Vec<u32>, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        if items.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        let mut bills = Vec::new(&env);
+        for item in items.iter() {
+            bills.push_back(Self::new_bill(&env, &owner, item)?);
+        }
+
+        let mut ids = Vec::new(&env);
+        for bill in bills.iter() {
+            ids.push_back(Self::insert_bill(&env, bill));
+        }
+        Ok(ids)
+    }
+
+    /// Create a bill for a payee from the owner's payee registry.
+    ///
+    /// The bill takes its name from the registered payee and references it by
+    /// `payee_id`, so the payee's details live in one place. At payment time
+    /// the payee's current registered address receives the funds.
+    ///
+    /// # Errors
+    /// * `PayeeNotFound` – no payee with this ID
+    /// * `Unauthorized` – the payee belongs to a different owner
+    /// * `PayeeInactive` – the payee has been removed
+    /// * `InvalidAmount` / `InvalidFrequency` – as for `create_bill`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_bill_for_payee(
+        env: Env,
+        owner: Address,
+        payee_id: u32,
+        amount: i128,
+        due_date: u64,
+        recurring: bool,
+        frequency_days: u32,
+        currency: String,
+        token: Address,
     ) -> Result<u32, Error> {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
 
+        let payee = Self::get_payee(env.clone(), payee_id).ok_or(Error::PayeeNotFound)?;
+        if payee.owner != owner {
+            return Err(Error::Unauthorized);
+        }
+        if !payee.active {
+            return Err(Error::PayeeInactive);
+        }
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -364,1277 +1093,7377 @@ impl BillPayments {
             return Err(Error::InvalidFrequency);
         }
 
-        // Resolve default currency: blank input → "XLM"
         let resolved_currency = if currency.is_empty() {
             String::from_str(&env, "XLM")
         } else {
             currency
         };
 
-        Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let next_id = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32)
-            + 1;
-
-        let current_time = env.ledger().timestamp();
         let bill = Bill {
-            id: next_id,
-            owner: owner.clone(),
-            name: name.clone(),
+            id: 0,
+            owner,
+            name: payee.name,
             amount,
             due_date,
             recurring,
             frequency_days,
             paid: false,
-            created_at: current_time,
+            created_at: env.ledger().timestamp(),
             paid_at: None,
             schedule_id: None,
             currency: resolved_currency,
+            payee: BillPayee::Registered(payee_id),
+            token,
+            amount_paid: 0,
+            late_fee: LateFeePolicy::None,
+            autopay: false,
+            occurrence: 1,
+            max_occurrences: None,
+            end_date: None,
+            category: BillCategory::Other,
+            approval: ApprovalStatus::NotRequired,
+            reminder_sent: false,
+            invoice_hash: BytesN::from_array(&env, &[0; 32]),
+            invoice_number: String::from_str(&env, ""),
+            priority: BillPriority::Normal,
+            recurrence_base: RecurrenceBase::DueDate,
+            suspended: false,
+            tags: Vec::new(&env),
+            escrow_window: 0,
         };
 
-        let bill_owner = bill.owner.clone();
-        bills.set(next_id, bill);
+        Ok(Self::insert_bill(&env, bill))
+    }
+
+    // -----------------------------------------------------------------------
+    // Payee registry
+    // -----------------------------------------------------------------------
+
+    /// Register a recurring payee (utility company, school, landlord, ...).
+    ///
+    /// Bills can then be created with `create_bill_for_payee` instead of
+    /// repeating the payee details on every bill.
+    ///
+    /// # Returns
+    /// The new payee ID
+    pub fn register_payee(
+        env: Env,
+        owner: Address,
+        address: Address,
+        name: String,
+        category: Symbol,
+    ) -> Result<u32, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut payees: Map<u32, Payee> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PAYEES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_PAYE"))
+            .unwrap_or(0u32)
+            + 1;
+
+        let payee = Payee {
+            id: next_id,
+            owner: owner.clone(),
+            address: address.clone(),
+            name,
+            category,
+            active: true,
+            created_at: env.ledger().timestamp(),
+        };
+        payees.set(next_id, payee);
         env.storage()
             .instance()
-            .set(&symbol_short!("BILLS"), &bills);
+            .set(&symbol_short!("PAYEES"), &payees);
         env.storage()
             .instance()
-            .set(&symbol_short!("NEXT_ID"), &next_id);
-        Self::adjust_unpaid_total(&env, &bill_owner, amount);
+            .set(&symbol_short!("NEXT_PAYE"), &next_id);
 
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("created"),
-            (next_id, bill_owner, amount, due_date),
+            EventPriority::Low,
+            symbol_short!("payee_reg"),
+            (next_id, owner, address),
         );
-
         Ok(next_id)
     }
 
-    pub fn pay_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+    /// Update a registered payee's address, name and category.
+    /// Unpaid bills linked to the payee settle to the new address.
+    pub fn update_payee(
+        env: Env,
+        caller: Address,
+        payee_id: u32,
+        address: Address,
+        name: String,
+        category: Symbol,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
-
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
+
+        let mut payees: Map<u32, Payee> = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
+            .get(&symbol_short!("PAYEES"))
             .unwrap_or_else(|| Map::new(&env));
-
-        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
-
-        if bill.owner != caller {
+        let mut payee = payees.get(payee_id).ok_or(Error::PayeeNotFound)?;
+        if payee.owner != caller {
             return Err(Error::Unauthorized);
         }
-        if bill.paid {
-            return Err(Error::BillAlreadyPaid);
-        }
 
-        let current_time = env.ledger().timestamp();
-        bill.paid = true;
-        bill.paid_at = Some(current_time);
+        payee.address = address.clone();
+        payee.name = name;
+        payee.category = category;
+        payees.set(payee_id, payee);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PAYEES"), &payees);
 
-        if bill.recurring {
-            let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
-            let next_id = env
-                .storage()
-                .instance()
-                .get(&symbol_short!("NEXT_ID"))
-                .unwrap_or(0u32)
-                + 1;
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("payee_upd"),
+            (payee_id, caller, address),
+        );
+        Ok(())
+    }
 
-            let next_bill = Bill {
-                id: next_id,
-                owner: bill.owner.clone(),
-                name: bill.name.clone(),
-                amount: bill.amount,
-                due_date: next_due_date,
-                recurring: true,
-                frequency_days: bill.frequency_days,
-                paid: false,
-                created_at: current_time,
-                paid_at: None,
-                schedule_id: bill.schedule_id,
-                currency: bill.currency.clone(),
-            };
-            bills.set(next_id, next_bill);
-            env.storage()
-                .instance()
-                .set(&symbol_short!("NEXT_ID"), &next_id);
+    /// Deactivate a payee so no new bills can be created for it.
+    /// Existing bills that reference the payee remain payable.
+    pub fn remove_payee(env: Env, caller: Address, payee_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut payees: Map<u32, Payee> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PAYEES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut payee = payees.get(payee_id).ok_or(Error::PayeeNotFound)?;
+        if payee.owner != caller {
+            return Err(Error::Unauthorized);
         }
 
-        let paid_amount = bill.amount;
-        let was_recurring = bill.recurring;
-        bills.set(bill_id, bill);
+        payee.active = false;
+        payees.set(payee_id, payee);
         env.storage()
             .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if !was_recurring {
-            Self::adjust_unpaid_total(&env, &caller, -paid_amount);
-        }
+            .set(&symbol_short!("PAYEES"), &payees);
 
         RemitwiseEvents::emit(
             &env,
-            EventCategory::Transaction,
-            EventPriority::High,
-            symbol_short!("paid"),
-            (bill_id, caller, paid_amount),
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("payee_rem"),
+            (payee_id, caller),
         );
-
         Ok(())
     }
 
-    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
-        let bills: Map<u32, Bill> = env
+    pub fn get_payee(env: Env, payee_id: u32) -> Option<Payee> {
+        let payees: Map<u32, Payee> = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
+            .get(&symbol_short!("PAYEES"))
             .unwrap_or_else(|| Map::new(&env));
-        bills.get(bill_id)
+        payees.get(payee_id)
     }
 
-    // -----------------------------------------------------------------------
-    // PAGINATED LIST QUERIES
-    // -----------------------------------------------------------------------
-
-    /// Get a page of unpaid bills for `owner`.
-    ///
-    /// # Arguments
-    /// * `owner`  – whose bills to return
-    /// * `cursor` – start after this bill ID (pass 0 for the first page)
-    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
-    ///
-    /// # Returns
-    /// `BillPage { items, next_cursor, count }`.
-    /// When `next_cursor == 0` there are no more pages.
-    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
-        let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
+    /// All active payees registered by `owner`.
+    pub fn get_payees(env: Env, owner: Address) -> Vec<Payee> {
+        let payees: Map<u32, Payee> = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
+            .get(&symbol_short!("PAYEES"))
             .unwrap_or_else(|| Map::new(&env));
-
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.paid {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
+        let mut result = Vec::new(&env);
+        for (_, payee) in payees.iter() {
+            if payee.owner == owner && payee.active {
+                result.push_back(payee);
             }
         }
+        result
+    }
 
-        Self::build_page(&env, staging, limit)
+    pub fn pay_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::apply_payment(&env, &caller, bill_id, None, Funding::Direct)
     }
 
-    /// Get a page of ALL bills (paid + unpaid) for `owner`.
+    /// Pay part of a bill.
     ///
-    /// Same cursor/limit semantics as `get_unpaid_bills`.
-    pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+    /// `amount` is transferred to the payee and added to `amount_paid`. The
+    /// bill stays unpaid until the full amount has been settled, at which
+    /// point it is marked paid exactly as with `pay_bill`.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` – amount ≤ 0
+    /// * `AmountExceedsBalance` – amount is larger than the remaining balance
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid` / `TransferFailed`
+    pub fn pay_bill_partial(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        amount: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::apply_payment(&env, &caller, bill_id, Some(amount), Funding::Direct)
+    }
+
+    /// Amount still owed on a bill (`amount - amount_paid`).
+    pub fn get_remaining_balance(env: Env, bill_id: u32) -> Result<i128, Error> {
+        let bill = Self::get_bill(env, bill_id).ok_or(Error::BillNotFound)?;
+        Ok(bill.remaining())
+    }
+
+    // -----------------------------------------------------------------------
+    // Bills balance
+    // -----------------------------------------------------------------------
+
+    /// Deposit the bills portion of a remittance for `owner`.
+    ///
+    /// `amount` of `token` is transferred from `owner` into this contract and
+    /// tracked as the owner's bills balance for that token. The balance is
+    /// spent with `pay_bill_from_balance` and can be taken back with
+    /// `withdraw_allocation`. Intended to be called by remittance_split or the
+    /// orchestrator as part of a remittance flow.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` – amount ≤ 0
+    /// * `TransferFailed` – the token transfer into the contract failed
+    pub fn deposit_allocation(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
         owner.require_auth();
-        let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        match TokenClient::new(&env, &token).try_transfer(
+            &owner,
+            &env.current_contract_address(),
+            &amount,
+        ) {
+            Ok(Ok(())) => {}
+            _ => return Err(Error::TransferFailed),
         }
+        let balance = Self::get_bills_balance(env.clone(), owner.clone(), token.clone())
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::set_bills_balance(&env, &owner, &token, balance);
 
-        Self::build_page(&env, staging, limit)
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("deposit"),
+            (owner, token, amount, balance),
+        );
+        Ok(balance)
     }
 
-    /// Get a page of overdue (unpaid + past due_date) bills across all owners.
+    /// Return `amount` of the owner's unused bills balance in `token`.
     ///
-    /// Same cursor/limit semantics.
-    pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
-        let limit = clamp_limit(limit);
-        let current_time = env.ledger().timestamp();
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// # Errors
+    /// * `InvalidAmount` – amount ≤ 0
+    /// * `InsufficientBalance` – amount exceeds the tracked balance
+    /// * `TransferFailed` – the token transfer out of the contract failed
+    pub fn withdraw_allocation(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.paid || bill.due_date >= current_time {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        let balance = Self::get_bills_balance(env.clone(), owner.clone(), token.clone());
+        if amount > balance {
+            return Err(Error::InsufficientBalance);
+        }
+        match TokenClient::new(&env, &token).try_transfer(
+            &env.current_contract_address(),
+            &owner,
+            &amount,
+        ) {
+            Ok(Ok(())) => {}
+            _ => return Err(Error::TransferFailed),
         }
+        let balance = balance - amount;
+        Self::set_bills_balance(&env, &owner, &token, balance);
 
-        Self::build_page(&env, staging, limit)
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("withdraw"),
+            (owner, token, amount, balance),
+        );
+        Ok(balance)
     }
 
-    /// Admin-only: get ALL bills (any owner), paginated.
-    pub fn get_all_bills(
-        env: Env,
-        caller: Address,
-        cursor: u32,
-        limit: u32,
-    ) -> Result<BillPage, Error> {
+    /// Pay a bill in full (plus any accrued late fee) out of the owner's
+    /// deposited bills balance in the bill's token.
+    ///
+    /// # Errors
+    /// * `InsufficientBalance` – the balance does not cover the amount due
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid` / `TransferFailed`
+    pub fn pay_bill_from_balance(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
-        if admin != caller {
-            return Err(Error::Unauthorized);
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::apply_payment(&env, &caller, bill_id, None, Funding::Balance)
+    }
+
+    /// Bills balance `owner` holds in `token`.
+    pub fn get_bills_balance(env: Env, owner: Address, token: Address) -> i128 {
+        let key = (KEY_BILLS_BALANCE, owner, token);
+        let balance: Option<i128> = env.storage().persistent().get(&key);
+        if balance.is_some() {
+            Self::extend_persistent_ttl(&env, &key);
         }
+        balance.unwrap_or(0)
+    }
 
-        let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+    // -----------------------------------------------------------------------
+    // Delegated payers
+    // -----------------------------------------------------------------------
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
+    /// Let `payer` settle some of `owner`'s bills, e.g. a relative or the
+    /// orchestrator. The payer calls `pay_bill`, `pay_bill_partial` or
+    /// `pay_installment` as usual and the funds come from the payer. A new
+    /// grant replaces any previous one for the same payer and starts its
+    /// `UpTo` cap afresh.
+    ///
+    /// # Errors
+    /// * `InvalidPayerScope` – payer is the owner, the bill list is empty or
+    ///   the cap is not positive
+    /// * `BillNotFound` / `Unauthorized` – a listed bill does not exist or
+    ///   belongs to someone else
+    pub fn grant_payer(
+        env: Env,
+        owner: Address,
+        payer: Address,
+        scope: PayerScope,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        if payer == owner {
+            return Err(Error::InvalidPayerScope);
+        }
+        match &scope {
+            PayerScope::Bills(ids) => {
+                if ids.is_empty() {
+                    return Err(Error::InvalidPayerScope);
+                }
+                for id in ids.iter() {
+                    let bill = Self::load_bill(&env, id).ok_or(Error::BillNotFound)?;
+                    if bill.owner != owner {
+                        return Err(Error::Unauthorized);
+                    }
+                }
             }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
+            PayerScope::UpTo(cap) => {
+                if *cap <= 0 {
+                    return Err(Error::InvalidPayerScope);
+                }
             }
         }
 
-        Ok(Self::build_page(&env, staging, limit))
+        let key = (KEY_PAYER, owner.clone(), payer.clone());
+        env.storage().persistent().set(&key, &scope);
+        Self::extend_persistent_ttl(&env, &key);
+        env.storage()
+            .persistent()
+            .remove(&(KEY_PAYER_SPENT, owner.clone(), payer.clone()));
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("payer_gr"),
+            (owner, payer, scope),
+        );
+        Ok(())
     }
 
-    /// Build a `BillPage` from a staging buffer of up to `limit+1` matching items.
-    /// `next_cursor` is set to the last *returned* item's ID so the next call's
-    /// `id <= cursor` filter correctly skips past it.
-    fn build_page(env: &Env, staging: Vec<(u32, Bill)>, limit: u32) -> BillPage {
-        let n = staging.len();
-        let has_next = n > limit;
-        let mut items = Vec::new(env);
-        let mut next_cursor: u32 = 0;
-
-        // Emit all items, or all-but-last if there is a next page
-        let take = if has_next { n - 1 } else { n };
+    /// Withdraw a payer's delegation.
+    ///
+    /// # Errors
+    /// * `PayerNotFound` – `payer` holds no grant from `owner`
+    pub fn revoke_payer(env: Env, owner: Address, payer: Address) -> Result<(), Error> {
+        owner.require_auth();
+        Self::extend_instance_ttl(&env);
 
-        for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
-                items.push_back(bill);
-            }
+        let key = (KEY_PAYER, owner.clone(), payer.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::PayerNotFound);
         }
+        env.storage().persistent().remove(&key);
+        env.storage()
+            .persistent()
+            .remove(&(KEY_PAYER_SPENT, owner.clone(), payer.clone()));
 
-        // next_cursor = last returned item's ID (NOT the first skipped item)
-        if has_next {
-            if let Some((id, _)) = staging.get(take - 1) {
-                next_cursor = id;
-            }
-        }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("payer_rev"),
+            (owner, payer),
+        );
+        Ok(())
+    }
 
-        let count = items.len();
-        BillPage {
-            items,
-            next_cursor,
-            count,
+    /// Scope granted to `payer` by `owner`, if any.
+    pub fn get_payer_scope(env: Env, owner: Address, payer: Address) -> Option<PayerScope> {
+        let key = (KEY_PAYER, owner, payer);
+        let scope: Option<PayerScope> = env.storage().persistent().get(&key);
+        if scope.is_some() {
+            Self::extend_persistent_ttl(&env, &key);
         }
+        scope
     }
 
-    // -----------------------------------------------------------------------
-    // Backward-compat helpers
-    // -----------------------------------------------------------------------
-
-    /// Legacy helper: returns ALL unpaid bills for owner in one Vec.
-    /// Only safe for owners with a small number of bills. Prefer the
-    /// paginated `get_unpaid_bills` for production use.
-    pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut result = Vec::new(&env);
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner {
-                result.push_back(bill);
-            }
-        }
-        result
+    /// Total `payer` has paid on `owner`'s bills under its current grant,
+    /// late fees included.
+    pub fn get_payer_spent(env: Env, owner: Address, payer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(KEY_PAYER_SPENT, owner, payer))
+            .unwrap_or(0)
     }
 
     // -----------------------------------------------------------------------
-    // Archived bill queries (paginated)
+    // Installment plans
     // -----------------------------------------------------------------------
 
-    /// Get a page of archived bills for `owner`.
-    pub fn get_archived_bills(
+    /// Split the remaining balance of a bill into `installments` equal parts.
+    ///
+    /// The first installment is due on the bill's due date and each following
+    /// one `interval_days` later. Any rounding remainder is added to the last
+    /// installment. Installments are paid with `pay_installment` (or any
+    /// partial payment) and are covered in order.
+    ///
+    /// # Errors
+    /// * `InvalidInstallments` – fewer than 2 or more than `MAX_INSTALLMENTS`
+    ///   installments, or a balance too small to split
+    /// * `InvalidFrequency` – `interval_days` is 0
+    /// * `InstallmentPlanExists` – the bill already has a plan
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn create_installment_plan(
         env: Env,
-        owner: Address,
-        cursor: u32,
-        limit: u32,
-    ) -> ArchivedBillPage {
-        let limit = clamp_limit(limit);
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+        caller: Address,
+        bill_id: u32,
+        installments: u32,
+        interval_days: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        Self::extend_instance_ttl(&env);
 
-        let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
-        for (id, bill) in archived.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if interval_days == 0 {
+            return Err(Error::InvalidFrequency);
+        }
+        let remaining = bill.remaining();
+        if !(2..=MAX_INSTALLMENTS).contains(&installments) || remaining < installments as i128 {
+            return Err(Error::InvalidInstallments);
         }
 
-        let has_next = staging.len() > limit;
-        let mut items = Vec::new(&env);
-        let mut next_cursor: u32 = 0;
-        let take = if has_next {
-            staging.len() - 1
-        } else {
-            staging.len()
-        };
-
-        for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
-                items.push_back(bill);
-            }
+        let mut plans = Self::get_installment_plans(&env);
+        if plans.contains_key(bill_id) {
+            return Err(Error::InstallmentPlanExists);
         }
-        if has_next {
-            if let Some((id, _)) = staging.get(take - 1) {
-                next_cursor = id;
-            }
+        if Self::get_shares_map(&env).contains_key(bill_id) {
+            return Err(Error::BillIsSplit);
         }
 
-        let count = items.len();
-        ArchivedBillPage {
-            items,
-            next_cursor,
-            count,
+        let base = remaining / installments as i128;
+        let mut schedule = Vec::new(&env);
+        for index in 0..installments {
+            let amount = if index == installments - 1 {
+                remaining - base * (installments as i128 - 1)
+            } else {
+                base
+            };
+            schedule.push_back(Installment {
+                index,
+                amount,
+                due_date: bill.due_date + index as u64 * interval_days as u64 * 86400,
+            });
         }
-    }
 
-    pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
+        plans.set(
+            bill_id,
+            InstallmentPlan {
+                bill_id,
+                installments: schedule,
+                interval_days,
+                paid_before_plan: bill.amount_paid,
+                created_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        archived.get(bill_id)
+            .set(&STORAGE_INSTALLMENT_PLANS, &plans);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("inst_plan"),
+            (bill_id, caller, installments, interval_days),
+        );
+        Ok(())
+    }
+
+    /// Pay whatever is still owed on the next installment of a bill's plan.
+    ///
+    /// # Errors
+    /// * `InstallmentPlanNotFound` – the bill has no installment plan
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid` / `TransferFailed`
+    pub fn pay_installment(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+
+        let progress = Self::get_installment_progress(env.clone(), bill_id)?;
+        if progress.next_index.is_none() {
+            return Err(Error::BillAlreadyPaid);
+        }
+        Self::apply_payment(
+            &env,
+            &caller,
+            bill_id,
+            Some(progress.next_outstanding),
+            Funding::Direct,
+        )
+    }
+
+    pub fn get_installment_plan(env: Env, bill_id: u32) -> Option<InstallmentPlan> {
+        Self::get_installment_plans(&env).get(bill_id)
+    }
+
+    /// The first installment of a bill's plan that is not yet fully paid.
+    pub fn get_next_installment(env: Env, bill_id: u32) -> Option<Installment> {
+        let plan = Self::get_installment_plans(&env).get(bill_id)?;
+        let index = Self::get_installment_progress(env, bill_id)
+            .ok()?
+            .next_index?;
+        plan.installments.get(index)
+    }
+
+    /// How far a bill is through its installment plan.
+    pub fn get_installment_progress(env: Env, bill_id: u32) -> Result<InstallmentProgress, Error> {
+        let plan = Self::get_installment_plans(&env)
+            .get(bill_id)
+            .ok_or(Error::InstallmentPlanNotFound)?;
+        let bill = Self::get_bill(env, bill_id).ok_or(Error::BillNotFound)?;
+
+        let paid_towards_plan = bill.amount_paid - plan.paid_before_plan;
+        let mut paid_installments = 0u32;
+        let mut scheduled_total = 0i128;
+        let mut next: Option<Installment> = None;
+        let mut next_outstanding = 0i128;
+        for installment in plan.installments.iter() {
+            scheduled_total += installment.amount;
+            if scheduled_total <= paid_towards_plan {
+                paid_installments += 1;
+            } else if next.is_none() {
+                next_outstanding = scheduled_total - paid_towards_plan;
+                next = Some(installment);
+            }
+        }
+
+        Ok(InstallmentProgress {
+            bill_id,
+            total_installments: plan.installments.len(),
+            paid_installments,
+            amount_paid: paid_towards_plan,
+            amount_remaining: bill.remaining(),
+            next_index: next.as_ref().map(|i| i.index),
+            next_due_date: next.as_ref().map(|i| i.due_date),
+            next_outstanding,
+        })
     }
 
     // -----------------------------------------------------------------------
-    // Remaining operations
+    // Categories
     // -----------------------------------------------------------------------
 
-    pub fn cancel_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+    /// Set the spending category of a bill. New bills start as `Other` and
+    /// recurring bills carry their category to the next occurrence.
+    pub fn set_bill_category(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        category: BillCategory,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
         if bill.owner != caller {
             return Err(Error::Unauthorized);
         }
-        let removed_unpaid_amount = if bill.paid { 0 } else { bill.amount };
-        bills.remove(bill_id);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if removed_unpaid_amount > 0 {
-            Self::adjust_unpaid_total(&env, &caller, -removed_unpaid_amount);
-        }
+
+        bill.category = category;
+        Self::save_bill(&env, &bill);
+
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("canceled"),
-            bill_id,
+            EventPriority::Low,
+            symbol_short!("cat_set"),
+            (bill_id, category),
         );
         Ok(())
     }
 
-    pub fn archive_paid_bills(
+    /// Totals of `owner`'s bills paid within `[from, to]`, one entry per
+    /// category in `BillCategory` order.
+    pub fn get_totals_by_category(
         env: Env,
-        caller: Address,
-        before_timestamp: u64,
-    ) -> Result<u32, Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
-        Self::extend_instance_ttl(&env);
-
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let current_time = env.ledger().timestamp();
-        let mut archived_count = 0u32;
-        let mut to_remove: Vec<u32> = Vec::new(&env);
+        owner: Address,
+        from: u64,
+        to: u64,
+    ) -> Vec<CategoryTotal> {
+        let mut totals = Vec::new(&env);
+        for category in [
+            BillCategory::Utilities,
+            BillCategory::Education,
+            BillCategory::Housing,
+            BillCategory::Health,
+            BillCategory::Other,
+        ] {
+            totals.push_back(CategoryTotal {
+                category,
+                total: 0,
+                count: 0,
+            });
+        }
 
-        for (id, bill) in bills.iter() {
-            if let Some(paid_at) = bill.paid_at {
-                if bill.paid && paid_at < before_timestamp {
-                    let archived_bill = ArchivedBill {
-                        id: bill.id,
-                        owner: bill.owner.clone(),
-                        name: bill.name.clone(),
-                        amount: bill.amount,
-                        paid_at,
-                        archived_at: current_time,
-                        currency: bill.currency.clone(),
-                    };
-                    archived.set(id, archived_bill);
-                    to_remove.push_back(id);
-                    archived_count += 1;
-                }
+        for bill in Self::owner_bills(&env, &owner, true).iter() {
+            let paid_at = bill.paid_at.unwrap_or(0);
+            if paid_at < from || paid_at > to {
+                continue;
+            }
+            let index = bill.category as u32 - 1;
+            if let Some(mut entry) = totals.get(index) {
+                entry.total += bill.amount;
+                entry.count += 1;
+                totals.set(index, entry);
             }
         }
+        totals
+    }
 
-        for id in to_remove.iter() {
-            bills.remove(id);
+    /// Set a monthly spending limit for one of the owner's bill categories.
+    /// Pass 0 to remove the budget.
+    ///
+    /// Payments are counted in 30-day periods. When a payment pushes the
+    /// period's total for the category over the limit a `budget_ex` event
+    /// is emitted.
+    pub fn set_category_budget(
+        env: Env,
+        owner: Address,
+        category: BillCategory,
+        monthly_limit: i128,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        if monthly_limit < 0 {
+            return Err(Error::InvalidAmount);
         }
+        Self::extend_instance_ttl(&env);
 
-        env.storage()
+        let mut budgets: Map<(Address, BillCategory), i128> = env
+            .storage()
             .instance()
-            .set(&symbol_short!("BILLS"), &bills);
+            .get(&STORAGE_CATEGORY_BUDGETS)
+            .unwrap_or_else(|| Map::new(&env));
+        let key = (owner.clone(), category);
+        if monthly_limit == 0 {
+            budgets.remove(key);
+        } else {
+            budgets.set(key, monthly_limit);
+        }
         env.storage()
             .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
-
-        Self::extend_archive_ttl(&env);
-        Self::update_storage_stats(&env);
+            .set(&STORAGE_CATEGORY_BUDGETS, &budgets);
 
-        RemitwiseEvents::emit_batch(
+        RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            symbol_short!("archived"),
-            archived_count,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("budget"),
+            (owner, category, monthly_limit),
         );
-
-        Ok(archived_count)
+        Ok(())
     }
 
-    pub fn restore_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::RESTORE)?;
-        Self::extend_instance_ttl(&env);
-
-        let mut archived: Map<u32, ArchivedBill> = env
+    /// Spending against the owner's budget for `category` in the current
+    /// 30-day period.
+    pub fn get_budget_status(env: Env, owner: Address, category: BillCategory) -> BudgetStatus {
+        let period = env.ledger().timestamp() / BUDGET_PERIOD_SECS;
+        let key = (owner, category);
+        let limit = env
             .storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        let archived_bill = archived.get(bill_id).ok_or(Error::BillNotFound)?;
-
-        if archived_bill.owner != caller {
-            return Err(Error::Unauthorized);
+            .get::<_, Map<(Address, BillCategory), i128>>(&STORAGE_CATEGORY_BUDGETS)
+            .and_then(|budgets| budgets.get(key.clone()))
+            .unwrap_or(0);
+        let spent = env
+            .storage()
+            .instance()
+            .get::<_, Map<(Address, BillCategory), CategorySpend>>(&STORAGE_CATEGORY_SPEND)
+            .and_then(|spend| spend.get(key))
+            .filter(|entry| entry.period == period)
+            .map(|entry| entry.spent)
+            .unwrap_or(0);
+
+        BudgetStatus {
+            category,
+            limit,
+            spent,
+            remaining: if limit > 0 { limit - spent } else { 0 },
+            exceeded: limit > 0 && spent > limit,
+            period_start: period * BUDGET_PERIOD_SECS,
         }
+    }
 
-        let mut bills: Map<u32, Bill> = env
+    /// Add a payment to the owner's category total for the current period
+    /// and emit `budget_ex` if it takes the total over budget.
+    fn record_category_spend(env: &Env, bill: &Bill, amount: i128) {
+        let period = env.ledger().timestamp() / BUDGET_PERIOD_SECS;
+        let key = (bill.owner.clone(), bill.category);
+        let mut spend: Map<(Address, BillCategory), CategorySpend> = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let restored_bill = Bill {
-            id: archived_bill.id,
-            owner: archived_bill.owner.clone(),
-            name: archived_bill.name.clone(),
-            amount: archived_bill.amount,
-            due_date: env.ledger().timestamp() + 2592000,
-            recurring: false,
-            frequency_days: 0,
-            paid: true,
-            created_at: archived_bill.paid_at,
-            paid_at: Some(archived_bill.paid_at),
-            schedule_id: None,
-            currency: archived_bill.currency.clone(),
-        };
+            .get(&STORAGE_CATEGORY_SPEND)
+            .unwrap_or_else(|| Map::new(env));
+        let previous = spend
+            .get(key.clone())
+            .filter(|entry| entry.period == period)
+            .map(|entry| entry.spent)
+            .unwrap_or(0);
+        let spent = previous.saturating_add(amount);
+        spend.set(key.clone(), CategorySpend { period, spent });
+        env.storage()
+            .instance()
+            .set(&STORAGE_CATEGORY_SPEND, &spend);
 
-        bills.set(bill_id, restored_bill);
-        archived.remove(bill_id);
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        env.storage()
+        let limit = env
+            .storage()
             .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
+            .get::<_, Map<(Address, BillCategory), i128>>(&STORAGE_CATEGORY_BUDGETS)
+            .and_then(|budgets| budgets.get(key))
+            .unwrap_or(0);
+        if limit > 0 && previous <= limit && spent > limit {
+            RemitwiseEvents::emit(
+                env,
+                EventCategory::Alert,
+                EventPriority::High,
+                symbol_short!("budget_ex"),
+                (bill.owner.clone(), bill.category, limit, spent),
+            );
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Recurring series
+    // -----------------------------------------------------------------------
+
+    /// Limit how long a recurring bill keeps regenerating.
+    ///
+    /// `max_occurrences` caps the total number of bills in the series
+    /// (counting this one and those already paid); `end_date` stops the
+    /// series once the next due date would fall after it. Pass `None` to
+    /// remove a limit.
+    ///
+    /// # Errors
+    /// * `InvalidFrequency` – the bill is not recurring
+    /// * `InvalidAmount` – `max_occurrences` is lower than this bill's position
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn set_series_end(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        max_occurrences: Option<u32>,
+        end_date: Option<u64>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.recurring {
+            return Err(Error::InvalidFrequency);
+        }
+        if max_occurrences.is_some_and(|max| max < bill.occurrence) {
+            return Err(Error::InvalidAmount);
+        }
 
-        Self::update_storage_stats(&env);
+        bill.max_occurrences = max_occurrences;
+        bill.end_date = end_date;
+        Self::save_bill(&env, &bill);
 
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("restored"),
-            bill_id,
+            EventPriority::Low,
+            symbol_short!("ser_set"),
+            (bill_id, max_occurrences, end_date),
         );
         Ok(())
     }
 
-    pub fn bulk_cleanup_bills(
+    /// Choose whether the next occurrence of a recurring bill is scheduled
+    /// from its due date or from the date it is paid. The setting carries
+    /// over to later occurrences.
+    ///
+    /// # Errors
+    /// * `InvalidFrequency` – the bill is not recurring
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn set_recurrence_base(
         env: Env,
         caller: Address,
-        before_timestamp: u64,
-    ) -> Result<u32, Error> {
+        bill_id: u32,
+        base: RecurrenceBase,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
         Self::extend_instance_ttl(&env);
 
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut deleted_count = 0u32;
-        let mut to_remove: Vec<u32> = Vec::new(&env);
-
-        for (id, bill) in archived.iter() {
-            if bill.archived_at < before_timestamp {
-                to_remove.push_back(id);
-                deleted_count += 1;
-            }
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
         }
-
-        for id in to_remove.iter() {
-            archived.remove(id);
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.recurring {
+            return Err(Error::InvalidFrequency);
         }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
-        Self::update_storage_stats(&env);
+        bill.recurrence_base = base;
+        Self::save_bill(&env, &bill);
 
-        RemitwiseEvents::emit_batch(
+        RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            symbol_short!("cleaned"),
-            deleted_count,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("rec_base"),
+            (bill_id, base),
         );
-        Ok(deleted_count)
+        Ok(())
     }
 
-    pub fn batch_pay_bills(env: Env, caller: Address, bill_ids: Vec<u32>) -> Result<u32, Error> {
+    /// Put a recurring series on hold, e.g. school fees during holidays.
+    ///
+    /// The current occurrence stays in place with its history but cannot be
+    /// paid and is left out of overdue queries, reminders and autopay until
+    /// `resume_recurring` is called.
+    ///
+    /// # Errors
+    /// * `InvalidFrequency` – the bill is not recurring
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn suspend_recurring(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
-        if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap() {
-            return Err(Error::BatchTooLarge);
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
         }
-        let bills_map: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        for id in bill_ids.iter() {
-            let bill = bills_map.get(id).ok_or(Error::BillNotFound)?;
-            if bill.owner != caller {
-                return Err(Error::Unauthorized);
-            }
-            if bill.paid {
-                return Err(Error::BillAlreadyPaid);
-            }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
         }
-        Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let current_time = env.ledger().timestamp();
-        let mut next_id: u32 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
-        let mut paid_count = 0u32;
-        let mut unpaid_delta = 0i128;
-        for id in bill_ids.iter() {
-            let mut bill = bills.get(id).ok_or(Error::BillNotFound)?;
-            if bill.owner != caller || bill.paid {
-                return Err(Error::BatchValidationFailed);
-            }
-            let amount = bill.amount;
-            bill.paid = true;
-            bill.paid_at = Some(current_time);
-            if bill.recurring {
-                next_id = next_id.saturating_add(1);
-                let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
-                let next_bill = Bill {
-                    id: next_id,
-                    owner: bill.owner.clone(),
-                    name: bill.name.clone(),
-                    amount: bill.amount,
-                    due_date: next_due_date,
-                    recurring: true,
-                    frequency_days: bill.frequency_days,
-                    paid: false,
-                    created_at: current_time,
-                    paid_at: None,
-                    schedule_id: bill.schedule_id,
-                    currency: bill.currency.clone(),
-                };
-                bills.set(next_id, next_bill);
-            } else {
-                unpaid_delta = unpaid_delta.saturating_sub(amount);
-            }
-            bills.set(id, bill);
-            paid_count += 1;
-            RemitwiseEvents::emit(
-                &env,
-                EventCategory::Transaction,
-                EventPriority::High,
-                symbol_short!("paid"),
-                (id, caller.clone(), amount),
-            );
+        if !bill.recurring {
+            return Err(Error::InvalidFrequency);
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &next_id);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if unpaid_delta != 0 {
-            Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
+        if bill.suspended {
+            return Ok(());
         }
-        Self::update_storage_stats(&env);
+
+        bill.suspended = true;
+        Self::save_bill(&env, &bill);
+
         RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
+            EventCategory::State,
             EventPriority::Medium,
-            symbol_short!("batch_pay"),
-            (paid_count, caller),
+            symbol_short!("suspended"),
+            (bill_id, caller),
         );
-        Ok(paid_count)
+        Ok(())
     }
 
-    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
-        if let Some(totals) = Self::get_unpaid_totals_map(&env) {
-            if let Some(total) = totals.get(owner.clone()) {
-                return total;
-            }
+    /// Resume a suspended recurring series.
+    ///
+    /// If the bill fell due while suspended, its due date moves forward in
+    /// steps of `frequency_days` to the first date not in the past, so the
+    /// series keeps its cadence without the missed periods becoming overdue.
+    ///
+    /// # Errors
+    /// * `BillNotFound` / `Unauthorized`
+    pub fn resume_recurring(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if !bill.suspended {
+            return Ok(());
         }
 
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner {
-                total += bill.amount;
-            }
+        let current_time = env.ledger().timestamp();
+        let step = bill.frequency_days as u64 * 86400;
+        if bill.due_date < current_time && step > 0 {
+            let periods = (current_time - bill.due_date).div_ceil(step);
+            bill.due_date += periods * step;
+            bill.reminder_sent = false;
         }
-        total
+        bill.suspended = false;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("resumed"),
+            (bill_id, caller, bill.due_date),
+        );
+        Ok(())
     }
 
-    pub fn get_storage_stats(env: Env) -> StorageStats {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("STOR_STAT"))
-            .unwrap_or(StorageStats {
-                active_bills: 0,
-                archived_bills: 0,
-                total_unpaid_amount: 0,
-                total_archived_amount: 0,
-                last_updated: 0,
-            })
+    /// Skip the upcoming occurrence of a recurring bill without paying it.
+    ///
+    /// The bill moves to the following due date and counts as the next
+    /// occurrence of the series, with nothing paid towards it yet. Any partial
+    /// payments, installment plan, shares or scheduled payment of the skipped
+    /// occurrence are discarded. If the series has no further occurrence the
+    /// bill is removed and a `seriesend` event is emitted.
+    ///
+    /// # Errors
+    /// * `InvalidFrequency` – the bill is not recurring
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn skip_next_occurrence(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.recurring {
+            return Err(Error::InvalidFrequency);
+        }
+
+        let skipped_due_date = bill.due_date;
+        if bill.has_next_occurrence() {
+            // Partial payments, plans and shares belong to the skipped
+            // occurrence; the next one starts from a clean balance.
+            Self::adjust_unpaid_total(&env, &caller, bill.amount_paid);
+            Self::remove_bill_links(&env, bill_id);
+            bill.due_date = bill.next_due_date();
+            bill.occurrence += 1;
+            bill.amount_paid = 0;
+            bill.reminder_sent = false;
+            Self::save_bill(&env, &bill);
+        } else {
+            Self::adjust_unpaid_total(&env, &caller, -bill.remaining());
+            Self::emit_series_ended(&env, &bill);
+            Self::remove_bill_links(&env, bill_id);
+            Self::delete_bill(&env, &bill);
+        }
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("skipped"),
+            (bill_id, caller, skipped_due_date),
+        );
+        Ok(())
     }
 
     // -----------------------------------------------------------------------
-    // Currency-filter helper queries
+    // Priorities
     // -----------------------------------------------------------------------
 
-    /// Get a page of ALL bills (paid + unpaid) for `owner` that match `currency`.
+    /// Set how urgently an unpaid bill should be funded. New bills start as
+    /// `Normal` and recurring bills carry their priority forward.
     ///
-    /// # Arguments
-    /// * `owner`    – whose bills to return
-    /// * `currency` – currency code to filter by, e.g. `"USDC"`, `"XLM"`
-    /// * `cursor`   – start after this bill ID (pass 0 for the first page)
-    /// * `limit`    – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// # Errors
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn set_bill_priority(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        priority: BillPriority,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.priority = priority;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("prio_set"),
+            (bill_id, priority),
+        );
+        Ok(())
+    }
+
+    /// All of `owner`'s unpaid bills, highest priority first and, within a
+    /// priority, earliest due date first. Ties keep bill ID order.
+    pub fn get_unpaid_bills_sorted(env: Env, owner: Address) -> Vec<Bill> {
+        let mut sorted: Vec<Bill> = Vec::new(&env);
+        for bill in Self::owner_bills(&env, &owner, false).iter() {
+            let key = (bill.priority as u32, bill.due_date);
+            let mut pos = sorted.len();
+            for (i, other) in sorted.iter().enumerate() {
+                if key < (other.priority as u32, other.due_date) {
+                    pos = i as u32;
+                    break;
+                }
+            }
+            sorted.insert(pos, bill);
+        }
+        sorted
+    }
+
+    // -----------------------------------------------------------------------
+    // Tags
+    // -----------------------------------------------------------------------
+
+    fn validate_tags(tags: &Vec<Symbol>) -> Result<(), Error> {
+        if tags.is_empty() {
+            return Err(Error::EmptyTags);
+        }
+        if tags.len() > MAX_TAGS_PER_BILL {
+            return Err(Error::InvalidTag);
+        }
+        for tag in tags.iter() {
+            if tag == symbol_short!("") {
+                return Err(Error::InvalidTag);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add tags to a bill. Tags it already carries are ignored.
     ///
-    /// # Returns
-    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
-    pub fn get_bills_by_currency(
+    /// # Errors
+    /// * `EmptyTags` – no tags given
+    /// * `InvalidTag` – a tag is empty, or the bill would carry more than
+    ///   `MAX_TAGS_PER_BILL` tags
+    /// * `BillNotFound` / `Unauthorized`
+    pub fn add_tags_to_bill(
         env: Env,
-        owner: Address,
-        currency: String,
-        cursor: u32,
+        caller: Address,
+        bill_id: u32,
+        tags: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::validate_tags(&tags)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        for tag in tags.iter() {
+            if !bill.tags.contains(&tag) {
+                bill.tags.push_back(tag);
+            }
+        }
+        if bill.tags.len() > MAX_TAGS_PER_BILL {
+            return Err(Error::InvalidTag);
+        }
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("tags_add"),
+            (bill_id, caller, tags),
+        );
+        Ok(())
+    }
+
+    /// Remove tags from a bill. Tags it does not carry are ignored.
+    ///
+    /// # Errors
+    /// * `EmptyTags` / `InvalidTag` – as for `add_tags_to_bill`
+    /// * `BillNotFound` / `Unauthorized`
+    pub fn remove_tags_from_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        tags: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::validate_tags(&tags)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        let mut kept = Vec::new(&env);
+        for tag in bill.tags.iter() {
+            if !tags.contains(&tag) {
+                kept.push_back(tag);
+            }
+        }
+        bill.tags = kept;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("tags_rem"),
+            (bill_id, caller, tags),
+        );
+        Ok(())
+    }
+
+    /// `owner`'s bills (paid and unpaid, by ID) carrying `tag`.
+    ///
+    /// Skips the first `offset` matches and returns at most `limit` bills
+    /// (0 means the default page size, capped at `MAX_PAGE_LIMIT`).
+    pub fn get_bills_by_tag(
+        env: Env,
+        owner: Address,
+        tag: Symbol,
+        offset: u32,
         limit: u32,
-    ) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
+    ) -> Vec<Bill> {
+        let limit = clamp_limit(limit);
+        let mut result = Vec::new(&env);
+        let mut skipped = 0u32;
+        for id in Self::owner_all_bill_ids(&env, &owner).iter() {
+            let Some(bill) = Self::load_bill(&env, id) else {
+                continue;
+            };
+            if !bill.tags.contains(&tag) {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            result.push_back(bill);
+            if result.len() >= limit {
+                break;
+            }
+        }
+        result
+    }
+
+    // -----------------------------------------------------------------------
+    // Invoices
+    // -----------------------------------------------------------------------
+
+    /// Attach (or replace) the invoice a bill settles.
+    ///
+    /// `invoice_hash` is the SHA-256 hash of the invoice document, so an
+    /// off-chain copy can later be checked against the bill and the receipts
+    /// issued for it. Only unpaid bills can be changed.
+    ///
+    /// # Errors
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn attach_invoice(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        invoice_hash: BytesN<32>,
+        invoice_number: String,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.invoice_hash = invoice_hash.clone();
+        bill.invoice_number = invoice_number.clone();
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("invoice"),
+            (bill_id, invoice_hash, invoice_number),
+        );
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Large-bill approval
+    // -----------------------------------------------------------------------
+
+    /// Require family approval for the owner's bills above `threshold`.
+    ///
+    /// Applies to bills created (or re-priced) from now on: they start as
+    /// `Pending` and cannot be paid until an Owner or Admin member of
+    /// `family_wallet` calls `approve_bill`. The owner must be a member of
+    /// `family_wallet`. Once configured, the owner may only tighten the
+    /// requirement by lowering the threshold on the same wallet; switching
+    /// wallets or loosening it needs `remove_bill_approval` first.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` – `threshold` is not positive
+    /// * `InvalidFamilyWallet` – `owner` is not a member of `family_wallet`
+    /// * `Unauthorized` – the change would loosen an existing requirement
+    pub fn configure_bill_approval(
+        env: Env,
+        owner: Address,
+        family_wallet: Address,
+        threshold: i128,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        if threshold <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if Self::family_member(&env, &family_wallet, &owner).is_none() {
+            return Err(Error::InvalidFamilyWallet);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut configs: Map<Address, ApprovalConfig> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_APPROVAL_CONFIG)
+            .unwrap_or_else(|| Map::new(&env));
+        if let Some(current) = configs.get(owner.clone()) {
+            if current.family_wallet != family_wallet || threshold > current.threshold {
+                return Err(Error::Unauthorized);
+            }
+        }
+        configs.set(
+            owner.clone(),
+            ApprovalConfig {
+                family_wallet: family_wallet.clone(),
+                threshold,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&STORAGE_APPROVAL_CONFIG, &configs);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("appr_cfg"),
+            (owner, family_wallet, threshold),
+        );
+        Ok(())
+    }
+
+    /// Turn off the approval requirement for `owner`'s bills.
+    ///
+    /// Only an Owner or Admin member of the configured family wallet can
+    /// remove it. The owner's bills still waiting for approval no longer
+    /// need it and become payable.
+    ///
+    /// # Returns
+    /// Number of pending bills released
+    ///
+    /// # Errors
+    /// * `ApprovalNotPending` – `owner` has no approval requirement
+    /// * `Unauthorized` – `approver` is not an Owner or Admin of the wallet
+    pub fn remove_bill_approval(env: Env, approver: Address, owner: Address) -> Result<u32, Error> {
+        approver.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut configs: Map<Address, ApprovalConfig> = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
+            .get(&STORAGE_APPROVAL_CONFIG)
             .unwrap_or_else(|| Map::new(&env));
+        let config = configs
+            .get(owner.clone())
+            .ok_or(Error::ApprovalNotPending)?;
+        Self::require_approver(&env, &config, &approver)?;
+        configs.remove(owner.clone());
+        env.storage()
+            .instance()
+            .set(&STORAGE_APPROVAL_CONFIG, &configs);
+
+        let mut released = 0u32;
+        for id in Self::owner_bill_ids(&env, &owner, false).iter() {
+            let Some(mut bill) = Self::load_bill(&env, id) else {
+                continue;
+            };
+            if bill.approval == ApprovalStatus::Pending {
+                bill.approval = ApprovalStatus::NotRequired;
+                Self::save_bill(&env, &bill);
+                released += 1;
+            }
+        }
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("appr_rem"),
+            (owner, approver, released),
+        );
+        Ok(released)
+    }
+
+    pub fn get_approval_config(env: Env, owner: Address) -> Option<ApprovalConfig> {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, ApprovalConfig>>(&STORAGE_APPROVAL_CONFIG)
+            .and_then(|configs| configs.get(owner))
+    }
+
+    /// Approve a pending bill so it can be paid.
+    ///
+    /// # Errors
+    /// * `ApprovalNotPending` – the bill does not need approval or is already approved
+    /// * `Unauthorized` – `approver` is not an Owner or Admin of the bill
+    ///   owner's family wallet
+    /// * `BillNotFound`
+    pub fn approve_bill(env: Env, approver: Address, bill_id: u32) -> Result<(), Error> {
+        approver.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.approval != ApprovalStatus::Pending {
+            return Err(Error::ApprovalNotPending);
+        }
+
+        let config = Self::get_approval_config(env.clone(), bill.owner.clone())
+            .ok_or(Error::Unauthorized)?;
+        Self::require_approver(&env, &config, &approver)?;
+
+        bill.approval = ApprovalStatus::Approved;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::High,
+            symbol_short!("approved"),
+            (bill_id, approver),
+        );
+        Ok(())
+    }
+
+    /// `member`'s record in `family_wallet`, if the wallet answers and knows
+    /// the address.
+    fn family_member(env: &Env, family_wallet: &Address, member: &Address) -> Option<FamilyMember> {
+        FamilyWalletClient::new(env, family_wallet)
+            .try_get_family_member(member)
+            .ok()
+            .and_then(|res| res.ok())
+            .flatten()
+    }
+
+    /// Check that `approver` is an Owner or Admin of the config's wallet.
+    fn require_approver(
+        env: &Env,
+        config: &ApprovalConfig,
+        approver: &Address,
+    ) -> Result<(), Error> {
+        match Self::family_member(env, &config.family_wallet, approver) {
+            Some(member) if matches!(member.role, FamilyRole::Owner | FamilyRole::Admin) => Ok(()),
+            _ => Err(Error::Unauthorized),
+        }
+    }
+
+    /// Approval state for a new or re-priced bill of `owner`.
+    fn approval_for(env: &Env, owner: &Address, amount: i128) -> ApprovalStatus {
+        match Self::get_approval_config(env.clone(), owner.clone()) {
+            Some(config) if amount > config.threshold => ApprovalStatus::Pending,
+            _ => ApprovalStatus::NotRequired,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Autopay
+    // -----------------------------------------------------------------------
+
+    /// Approve this contract to spend up to `amount` of `token` on the
+    /// owner's behalf until `expiration_ledger`. Autopay bills are paid
+    /// from this allowance.
+    pub fn grant_autopay_allowance(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let spender = env.current_contract_address();
+        TokenClient::new(&env, &token)
+            .try_approve(&owner, &spender, &amount, &expiration_ledger)
+            .map_err(|_| Error::TransferFailed)?
+            .map_err(|_| Error::TransferFailed)?;
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("allowance"),
+            (owner, token, amount, expiration_ledger),
+        );
+        Ok(())
+    }
+
+    /// Remaining allowance `owner` has granted this contract for `token`.
+    pub fn get_autopay_allowance(env: Env, owner: Address, token: Address) -> i128 {
+        TokenClient::new(&env, &token).allowance(&owner, &env.current_contract_address())
+    }
+
+    /// Turn autopay on or off for an unpaid bill. Recurring bills carry
+    /// the setting over to the next occurrence.
+    pub fn set_autopay(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.autopay = enabled;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("auto_set"),
+            (bill_id, caller, enabled),
+        );
+        Ok(())
+    }
+
+    /// Pay up to `limit` due autopay bills from their owners' allowances.
+    ///
+    /// Callable by anyone, typically a keeper. A bill whose payment fails
+    /// (e.g. insufficient allowance or balance) is left unpaid and the run
+    /// continues. An `autopay` event with `(bill_id, owner, success)` is
+    /// emitted for every bill attempted.
+    ///
+    /// # Returns
+    /// Number of bills paid
+    pub fn execute_autopay(env: Env, limit: u32) -> Result<u32, Error> {
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let limit = clamp_limit(limit);
+        let current_time = env.ledger().timestamp();
+
+        let due = Self::collect_by_day(
+            &env,
+            &INDEX_AUTOPAY,
+            (0, 0),
+            current_time / SECONDS_PER_DAY,
+            limit,
+            |bill| bill.due_date <= current_time,
+        );
+
+        let mut paid_count = 0u32;
+        for (id, bill) in due.iter() {
+            let owner = bill.owner;
+            let success = Self::apply_payment(&env, &owner, id, None, Funding::Allowance).is_ok();
+            if success {
+                paid_count += 1;
+            }
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Transaction,
+                EventPriority::Medium,
+                symbol_short!("autopay"),
+                (id, owner, success),
+            );
+        }
+        Ok(paid_count)
+    }
+
+    /// Emit a `reminder` event for up to `limit` unpaid bills falling due
+    /// within the next `window_secs` seconds.
+    ///
+    /// Callable by anyone, typically a keeper feeding an off-chain
+    /// notification service. Bills are read from the due-date index,
+    /// soonest first. Each bill is reminded at most once; moving its
+    /// due date (`update_bill`, `skip_next_occurrence`) re-arms the reminder,
+    /// and every recurring occurrence gets its own.
+    ///
+    /// # Returns
+    /// Number of reminders emitted
+    pub fn emit_due_reminders(env: Env, window_secs: u64, limit: u32) -> u32 {
+        let limit = clamp_limit(limit);
+        let current_time = env.ledger().timestamp();
+        let window_end = current_time.saturating_add(window_secs);
+
+        let due = Self::collect_by_day(
+            &env,
+            &INDEX_DUE,
+            (current_time / SECONDS_PER_DAY, 0),
+            window_end / SECONDS_PER_DAY,
+            limit,
+            |bill| {
+                !bill.reminder_sent && bill.due_date >= current_time && bill.due_date <= window_end
+            },
+        );
+
+        let mut sent = 0u32;
+        for (id, mut bill) in due.iter() {
+            bill.reminder_sent = true;
+            Self::save_bill(&env, &bill);
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Alert,
+                EventPriority::Medium,
+                symbol_short!("reminder"),
+                DueReminder {
+                    bill_id: id,
+                    owner: bill.owner.clone(),
+                    amount_due: bill.remaining(),
+                    token: bill.token.clone(),
+                    due_date: bill.due_date,
+                },
+            );
+            sent += 1;
+        }
+        sent
+    }
+
+    // -----------------------------------------------------------------------
+    // Scheduled payments
+    // -----------------------------------------------------------------------
+
+    /// Schedule the full payment of a bill for `execute_at`.
+    ///
+    /// The payment is drawn from the allowance the owner granted with
+    /// `grant_autopay_allowance`, which must already cover the balance plus
+    /// any late fee accrued by `execute_at`. A keeper runs due payments with
+    /// `execute_scheduled`; the owner can call `cancel_scheduled_payment`
+    /// until then.
+    ///
+    /// # Errors
+    /// * `InvalidExecutionTime` – `execute_at` is not in the future
+    /// * `PaymentAlreadyScheduled` – the bill already has a scheduled payment
+    /// * `InsufficientAllowance` – the allowance does not cover the payment
+    /// * `BillIsSplit` / `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn schedule_payment(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        execute_at: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::get_shares_map(&env).contains_key(bill_id) {
+            return Err(Error::BillIsSplit);
+        }
+        let current_time = env.ledger().timestamp();
+        if execute_at <= current_time {
+            return Err(Error::InvalidExecutionTime);
+        }
+        let mut schedules = Self::get_scheduled_payments(&env);
+        if schedules.contains_key(bill_id) {
+            return Err(Error::PaymentAlreadyScheduled);
+        }
+        let needed = bill.remaining() + bill.late_fee_at(execute_at);
+        if Self::get_autopay_allowance(env.clone(), caller.clone(), bill.token.clone()) < needed {
+            return Err(Error::InsufficientAllowance);
+        }
+
+        schedules.set(
+            bill_id,
+            ScheduledPayment {
+                bill_id,
+                owner: caller.clone(),
+                execute_at,
+                created_at: current_time,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&STORAGE_SCHEDULED_PAYMENTS, &schedules);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Low,
+            symbol_short!("pay_sched"),
+            (bill_id, caller, execute_at),
+        );
+        Ok(())
+    }
+
+    /// Cancel a bill's scheduled payment before it runs.
+    ///
+    /// # Errors
+    /// * `ScheduledPaymentNotFound` – nothing is scheduled for the bill
+    /// * `Unauthorized` – caller is not the bill owner
+    pub fn cancel_scheduled_payment(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules = Self::get_scheduled_payments(&env);
+        let scheduled = schedules
+            .get(bill_id)
+            .ok_or(Error::ScheduledPaymentNotFound)?;
+        if scheduled.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        schedules.remove(bill_id);
+        env.storage()
+            .instance()
+            .set(&STORAGE_SCHEDULED_PAYMENTS, &schedules);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Low,
+            symbol_short!("sched_can"),
+            (bill_id, caller),
+        );
+        Ok(())
+    }
+
+    pub fn get_scheduled_payment(env: Env, bill_id: u32) -> Option<ScheduledPayment> {
+        Self::get_scheduled_payments(&env).get(bill_id)
+    }
+
+    /// Run up to `limit` scheduled payments whose time has come.
+    ///
+    /// Callable by anyone, typically a keeper. Each due schedule is removed
+    /// whether or not its payment succeeds, and a `sched_run` event with
+    /// `(bill_id, owner, success)` is emitted for it.
+    ///
+    /// # Returns
+    /// Number of bills paid
+    pub fn execute_scheduled(env: Env, limit: u32) -> Result<u32, Error> {
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::extend_instance_ttl(&env);
+        let limit = clamp_limit(limit);
+        let current_time = env.ledger().timestamp();
+
+        let mut schedules = Self::get_scheduled_payments(&env);
+        let mut due: Vec<ScheduledPayment> = Vec::new(&env);
+        for (_, scheduled) in schedules.iter() {
+            if scheduled.execute_at <= current_time {
+                due.push_back(scheduled);
+                if due.len() >= limit {
+                    break;
+                }
+            }
+        }
+        for scheduled in due.iter() {
+            schedules.remove(scheduled.bill_id);
+        }
+        env.storage()
+            .instance()
+            .set(&STORAGE_SCHEDULED_PAYMENTS, &schedules);
+
+        let mut paid_count = 0u32;
+        for scheduled in due.iter() {
+            let success = Self::apply_payment(
+                &env,
+                &scheduled.owner,
+                scheduled.bill_id,
+                None,
+                Funding::Allowance,
+            )
+            .is_ok();
+            if success {
+                paid_count += 1;
+            }
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Transaction,
+                EventPriority::Medium,
+                symbol_short!("sched_run"),
+                (scheduled.bill_id, scheduled.owner, success),
+            );
+        }
+        Ok(paid_count)
+    }
+
+    fn get_scheduled_payments(env: &Env) -> Map<u32, ScheduledPayment> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_SCHEDULED_PAYMENTS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Shared bills
+    // -----------------------------------------------------------------------
+
+    /// Divide the remaining balance of a bill among family members.
+    ///
+    /// `shares` pairs each member with their portion in basis points; the
+    /// portions must sum to 10 000. Rounding is absorbed by the last share.
+    /// Each member then pays their part with `pay_share`, and the bill is
+    /// marked paid once every share is settled. A split bill can no longer
+    /// be paid through `pay_bill`.
+    ///
+    /// # Errors
+    /// * `InvalidShares` – empty, too many, duplicate members, zero portions
+    ///   or portions not summing to 10 000
+    /// * `BillIsSplit` – the bill has already been split
+    /// * `InstallmentPlanExists` – the bill is on an installment plan
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn split_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        shares: Vec<(Address, u32)>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        let mut splits = Self::get_shares_map(&env);
+        if splits.contains_key(bill_id) {
+            return Err(Error::BillIsSplit);
+        }
+        if Self::get_installment_plans(&env).contains_key(bill_id) {
+            return Err(Error::InstallmentPlanExists);
+        }
+
+        let count = shares.len();
+        let remaining = bill.remaining();
+        if count == 0 || count > MAX_SHARES || remaining < count as i128 {
+            return Err(Error::InvalidShares);
+        }
+        let mut total_bps = 0u32;
+        let mut members: Map<Address, bool> = Map::new(&env);
+        for (member, bps) in shares.iter() {
+            if bps == 0 || members.contains_key(member.clone()) {
+                return Err(Error::InvalidShares);
+            }
+            members.set(member, true);
+            total_bps = total_bps.saturating_add(bps);
+        }
+        if total_bps != 10_000 {
+            return Err(Error::InvalidShares);
+        }
+
+        let mut records = Vec::new(&env);
+        let mut allocated = 0i128;
+        for (i, (member, bps)) in shares.iter().enumerate() {
+            let amount = if i as u32 == count - 1 {
+                remaining - allocated
+            } else {
+                remaining * bps as i128 / 10_000
+            };
+            allocated += amount;
+            records.push_back(BillShare {
+                member,
+                bps,
+                amount,
+                paid: false,
+            });
+        }
+        splits.set(bill_id, records);
+        env.storage().instance().set(&STORAGE_BILL_SHARES, &splits);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("split"),
+            (bill_id, caller, count),
+        );
+        Ok(())
+    }
+
+    /// Pay the caller's share of a split bill.
+    ///
+    /// # Errors
+    /// * `ShareNotFound` – the bill is not split or `member` has no share
+    /// * `BillAlreadyPaid` – the share has already been paid
+    /// * `TransferFailed` / `ApprovalRequired`
+    pub fn pay_share(env: Env, member: Address, bill_id: u32) -> Result<(), Error> {
+        member.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut splits = Self::get_shares_map(&env);
+        let mut shares = splits.get(bill_id).ok_or(Error::ShareNotFound)?;
+        let index = shares
+            .iter()
+            .position(|share| share.member == member)
+            .ok_or(Error::ShareNotFound)? as u32;
+        let mut share = shares.get(index).ok_or(Error::ShareNotFound)?;
+        if share.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        let bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        Self::record_payment(&env, &member, bill, Some(share.amount), Funding::Direct)?;
+
+        share.paid = true;
+        let amount = share.amount;
+        shares.set(index, share);
+        splits.set(bill_id, shares);
+        env.storage().instance().set(&STORAGE_BILL_SHARES, &splits);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("share_pd"),
+            (bill_id, member, amount),
+        );
+        Ok(())
+    }
+
+    /// Shares of a split bill, empty if the bill is not split.
+    pub fn get_bill_shares(env: Env, bill_id: u32) -> Vec<BillShare> {
+        Self::get_shares_map(&env)
+            .get(bill_id)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Late fees
+    // -----------------------------------------------------------------------
+
+    /// Set the late-fee policy for an unpaid bill. The fee accrues once the
+    /// bill is past its due date and is collected with the final payment.
+    /// Recurring bills carry the policy over to the next occurrence.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` – negative flat fee or more than 10 000 bps per day
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn set_late_fee_policy(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        policy: LateFeePolicy,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        match policy {
+            LateFeePolicy::Flat(fee) if fee < 0 => return Err(Error::InvalidAmount),
+            LateFeePolicy::BpsPerDay(bps) if bps > MAX_LATE_FEE_BPS => {
+                return Err(Error::InvalidAmount)
+            }
+            _ => {}
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.late_fee = policy.clone();
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("fee_pol"),
+            (bill_id, policy),
+        );
+        Ok(())
+    }
+
+    /// Remaining balance plus any late fee accrued as of now. This is the
+    /// amount transferred by `pay_bill`.
+    pub fn get_amount_due(env: Env, bill_id: u32) -> Result<i128, Error> {
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Ok(bill.remaining() + bill.late_fee_at(env.ledger().timestamp()))
+    }
+
+    /// Settle `amount` (or the full remaining balance when `None`) of a bill
+    /// and mark it paid once nothing is left outstanding, drawing the funds
+    /// from `funding`.
+    fn apply_payment(
+        env: &Env,
+        caller: &Address,
+        bill_id: u32,
+        amount: Option<i128>,
+        funding: Funding,
+    ) -> Result<(), Error> {
+        Self::extend_instance_ttl(env);
+        let bill = Self::load_bill(env, bill_id).ok_or(Error::BillNotFound)?;
+
+        if Self::get_shares_map(env).contains_key(bill_id) {
+            return Err(Error::BillIsSplit);
+        }
+        if bill.owner == *caller {
+            return Self::record_payment(env, caller, bill, amount, funding);
+        }
+
+        // Delegated payers settle from their own funds only. Under an `UpTo`
+        // grant everything they pay, late fees included, counts towards the cap.
+        if funding != Funding::Direct {
+            return Err(Error::Unauthorized);
+        }
+        let remaining = bill.remaining();
+        let payment = amount.unwrap_or(remaining);
+        let charged = if payment == remaining {
+            payment.saturating_add(bill.late_fee_at(env.ledger().timestamp()))
+        } else {
+            payment
+        };
+        let owner = bill.owner.clone();
+        let capped = match Self::get_payer_scope(env.clone(), owner.clone(), caller.clone()) {
+            Some(PayerScope::Bills(ids)) if ids.contains(bill_id) => false,
+            Some(PayerScope::UpTo(cap)) => {
+                let spent = Self::get_payer_spent(env.clone(), owner.clone(), caller.clone());
+                if spent.saturating_add(charged) > cap {
+                    return Err(Error::Unauthorized);
+                }
+                true
+            }
+            _ => return Err(Error::Unauthorized),
+        };
+        Self::record_payment(env, caller, bill, amount, funding)?;
+        if capped {
+            let spent = Self::get_payer_spent(env.clone(), owner.clone(), caller.clone());
+            let key = (KEY_PAYER_SPENT, owner, caller.clone());
+            env.storage()
+                .persistent()
+                .set(&key, &spent.saturating_add(charged));
+            Self::extend_persistent_ttl(env, &key);
+        }
+        Ok(())
+    }
+
+    /// Collect a payment from `payer` for `bill` and update its balance,
+    /// marking it paid (and scheduling the next occurrence) once settled.
+    fn record_payment(
+        env: &Env,
+        payer: &Address,
+        mut bill: Bill,
+        amount: Option<i128>,
+        funding: Funding,
+    ) -> Result<(), Error> {
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if bill.approval == ApprovalStatus::Pending {
+            return Err(Error::ApprovalRequired);
+        }
+        if bill.suspended {
+            return Err(Error::BillSuspended);
+        }
+        if Self::is_disputed(env, bill.id) {
+            return Err(Error::BillDisputed);
+        }
+
+        let bill_id = bill.id;
+        let owner = bill.owner.clone();
+        let remaining = bill.remaining();
+        let payment = amount.unwrap_or(remaining);
+        if payment > remaining {
+            return Err(Error::AmountExceedsBalance);
+        }
+        if bill.escrow_window > 0 && payment != remaining {
+            return Err(Error::EscrowRequiresFullPayment);
+        }
+
+        // Accrued late fees are settled together with the final payment.
+        let current_time = env.ledger().timestamp();
+        let late_fee = if payment == remaining {
+            bill.late_fee_at(current_time)
+        } else {
+            0
+        };
+        match funding {
+            Funding::Direct => Self::settle(env, payer, &bill, payment + late_fee)?,
+            Funding::Allowance => {
+                Self::settle_from_allowance(env, payer, &bill, payment + late_fee)?
+            }
+            Funding::Balance => Self::settle_from_balance(env, payer, &bill, payment + late_fee)?,
+        }
+        Self::record_category_spend(env, &bill, payment);
+        bill.amount_paid += payment;
+        let mut unpaid_delta = -payment;
+
+        if bill.amount_paid < bill.amount {
+            Self::issue_receipt(env, payer, &bill, payment, 0);
+            Self::save_bill(env, &bill);
+            Self::adjust_unpaid_total(env, &owner, unpaid_delta);
+
+            RemitwiseEvents::emit(
+                env,
+                EventCategory::Transaction,
+                EventPriority::Medium,
+                symbol_short!("partial"),
+                (bill_id, payer.clone(), payment, remaining - payment),
+            );
+            return Ok(());
+        }
+
+        bill.paid = true;
+        bill.paid_at = Some(current_time);
+
+        if bill.has_next_occurrence() {
+            let next_id = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("NEXT_ID"))
+                .unwrap_or(0u32)
+                + 1;
+            Self::save_bill(
+                env,
+                &Self::next_recurring_bill(env, &bill, next_id, current_time),
+            );
+            env.storage()
+                .instance()
+                .set(&symbol_short!("NEXT_ID"), &next_id);
+            unpaid_delta += bill.amount;
+        } else if bill.recurring {
+            Self::emit_series_ended(env, &bill);
+        }
+
+        let paid_amount = bill.amount;
+        let receipt_id = Self::issue_receipt(env, payer, &bill, payment, late_fee);
+        if bill.escrow_window > 0 {
+            Self::hold_in_escrow(env, payer, &bill, receipt_id, payment, late_fee);
+        }
+        Self::save_bill(env, &bill);
+        Self::adjust_unpaid_total(env, &owner, unpaid_delta);
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("paid"),
+            (bill_id, payer.clone(), paid_amount),
+        );
+        if late_fee > 0 {
+            RemitwiseEvents::emit(
+                env,
+                EventCategory::Transaction,
+                EventPriority::Medium,
+                symbol_short!("late_fee"),
+                (bill_id, payer.clone(), late_fee),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn emit_series_ended(env: &Env, bill: &Bill) {
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("seriesend"),
+            (bill.id, bill.owner.clone(), bill.occurrence),
+        );
+    }
+
+    /// Build the next occurrence of a recurring bill.
+    fn next_recurring_bill(env: &Env, bill: &Bill, next_id: u32, current_time: u64) -> Bill {
+        Bill {
+            id: next_id,
+            owner: bill.owner.clone(),
+            name: bill.name.clone(),
+            amount: bill.amount,
+            due_date: bill.next_due_date(),
+            recurring: true,
+            frequency_days: bill.frequency_days,
+            paid: false,
+            created_at: current_time,
+            paid_at: None,
+            schedule_id: bill.schedule_id,
+            currency: bill.currency.clone(),
+            payee: bill.payee.clone(),
+            token: bill.token.clone(),
+            amount_paid: 0,
+            late_fee: bill.late_fee.clone(),
+            autopay: bill.autopay,
+            occurrence: bill.occurrence + 1,
+            max_occurrences: bill.max_occurrences,
+            end_date: bill.end_date,
+            category: bill.category,
+            approval: if bill.approval == ApprovalStatus::NotRequired {
+                ApprovalStatus::NotRequired
+            } else {
+                ApprovalStatus::Pending
+            },
+            reminder_sent: false,
+            invoice_hash: BytesN::from_array(env, &[0; 32]),
+            invoice_number: String::from_str(env, ""),
+            priority: bill.priority,
+            recurrence_base: bill.recurrence_base,
+            suspended: false,
+            tags: bill.tags.clone(),
+            escrow_window: 0,
+        }
+    }
+
+    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
+        Self::load_bill(&env, bill_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // PAGINATED LIST QUERIES
+    // -----------------------------------------------------------------------
+
+    /// Get a page of unpaid bills for `owner`.
+    ///
+    /// # Arguments
+    /// * `owner`  – whose bills to return
+    /// * `cursor` – start after this bill ID (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `BillPage { items, next_cursor, count }`.
+    /// When `next_cursor == 0` there are no more pages.
+    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+        let limit = clamp_limit(limit);
+        let ids = Self::owner_bill_ids(&env, &owner, false);
+        Self::collect_page(&env, ids, cursor, limit, |_| true)
+    }
+
+    /// Get a page of ALL bills (paid + unpaid) for `owner`.
+    ///
+    /// Same cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+        owner.require_auth();
+        let limit = clamp_limit(limit);
+        let ids = Self::owner_all_bill_ids(&env, &owner);
+        Self::collect_page(&env, ids, cursor, limit, |_| true)
+    }
+
+    /// Get a page of overdue (unpaid + past due_date) bills across all owners.
+    ///
+    /// Same cursor/limit semantics.
+    pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
+        let current_time = env.ledger().timestamp();
+        Self::get_overdue_bills_as_of(env, current_time, cursor, limit)
+    }
+
+    /// Get a page of bills that are unpaid and past their due date at `as_of`.
+    ///
+    /// Lets callers look ahead (or back) in time, e.g. to warn about bills
+    /// that will be overdue by the end of the week. Bills come oldest due
+    /// day first, read from the due-date index, so only matching bills are
+    /// loaded. Pass the returned `next_cursor` to continue; if that bill has
+    /// been removed in the meantime the listing starts over.
+    pub fn get_overdue_bills_as_of(env: Env, as_of: u64, cursor: u32, limit: u32) -> BillPage {
+        let limit = clamp_limit(limit);
+        if as_of == 0 {
+            return Self::build_page(&env, Vec::new(&env), limit);
+        }
+        let after = Self::day_cursor(&env, cursor);
+        let last_day = (as_of - 1) / SECONDS_PER_DAY;
+        let staging = Self::collect_by_day(&env, &INDEX_DUE, after, last_day, limit + 1, |bill| {
+            bill.due_date < as_of
+        });
+        Self::build_page(&env, staging, limit)
+    }
+
+    /// Get a page of `owner`'s unpaid bills due within `[from_ts, to_ts]`.
+    ///
+    /// Backs "due this week/month" views with a single read. Same
+    /// cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_bills_due_between(
+        env: Env,
+        owner: Address,
+        from_ts: u64,
+        to_ts: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = clamp_limit(limit);
+        let ids = Self::owner_bill_ids(&env, &owner, false);
+        Self::collect_page(&env, ids, cursor, limit, |bill| {
+            bill.due_date >= from_ts && bill.due_date <= to_ts
+        })
+    }
+
+    /// Admin-only: get ALL bills (any owner), paginated.
+    ///
+    /// Each call examines at most `MAX_SCAN_IDS` bill IDs, so a page can hold
+    /// fewer than `limit` bills while `next_cursor` is still non-zero.
+    pub fn get_all_bills(
+        env: Env,
+        caller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<BillPage, Error> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        let limit = clamp_limit(limit);
+        let last = Self::last_bill_id(&env);
+        let scan_end = cursor.saturating_add(MAX_SCAN_IDS).min(last);
+        let ids = cursor.saturating_add(1)..=scan_end;
+        let mut page = Self::collect_page(&env, ids, cursor, limit, |_| true);
+        if page.next_cursor == 0 && scan_end < last {
+            page.next_cursor = scan_end;
+        }
+        Ok(page)
+    }
+
+    /// Load the bills in `ids` (ascending) that come after `cursor` and
+    /// satisfy `keep`, stopping once a page of `limit` items is known to be
+    /// followed by at least one more.
+    fn collect_page<I, F>(env: &Env, ids: I, cursor: u32, limit: u32, keep: F) -> BillPage
+    where
+        I: IntoIterator<Item = u32>,
+        F: Fn(&Bill) -> bool,
+    {
+        let mut staging: Vec<(u32, Bill)> = Vec::new(env);
+        for id in ids {
+            if id <= cursor {
+                continue;
+            }
+            let Some(bill) = Self::load_bill(env, id) else {
+                continue;
+            };
+            if !keep(&bill) {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Self::build_page(env, staging, limit)
+    }
+
+    /// Build a `BillPage` from a staging buffer of up to `limit+1` matching items.
+    /// `next_cursor` is set to the last *returned* item's ID so the next call's
+    /// `id <= cursor` filter correctly skips past it.
+    fn build_page(env: &Env, staging: Vec<(u32, Bill)>, limit: u32) -> BillPage {
+        let n = staging.len();
+        let has_next = n > limit;
+        let mut items = Vec::new(env);
+        let mut next_cursor: u32 = 0;
+
+        // Emit all items, or all-but-last if there is a next page
+        let take = if has_next { n - 1 } else { n };
+
+        for i in 0..take {
+            if let Some((_, bill)) = staging.get(i) {
+                items.push_back(bill);
+            }
+        }
+
+        // next_cursor = last returned item's ID (NOT the first skipped item)
+        if has_next {
+            if let Some((id, _)) = staging.get(take - 1) {
+                next_cursor = id;
+            }
+        }
+
+        let count = items.len();
+        BillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Backward-compat helpers
+    // -----------------------------------------------------------------------
+
+    /// Legacy helper: returns ALL unpaid bills for owner in one Vec.
+    /// Only safe for owners with a small number of bills. Prefer the
+    /// paginated `get_unpaid_bills` for production use.
+    pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
+        Self::owner_bills(&env, &owner, false)
+    }
+
+    // -----------------------------------------------------------------------
+    // Archived bill queries (paginated)
+    // -----------------------------------------------------------------------
+
+    /// Get a page of archived bills for `owner`.
+    pub fn get_archived_bills(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> ArchivedBillPage {
+        let limit = clamp_limit(limit);
+        let archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
+        for (id, bill) in archived.iter() {
+            if id <= cursor {
+                continue;
+            }
+            if bill.owner != owner {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        let has_next = staging.len() > limit;
+        let mut items = Vec::new(&env);
+        let mut next_cursor: u32 = 0;
+        let take = if has_next {
+            staging.len() - 1
+        } else {
+            staging.len()
+        };
+
+        for i in 0..take {
+            if let Some((_, bill)) = staging.get(i) {
+                items.push_back(bill);
+            }
+        }
+        if has_next {
+            if let Some((id, _)) = staging.get(take - 1) {
+                next_cursor = id;
+            }
+        }
+
+        let count = items.len();
+        ArchivedBillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
+        let archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        archived.get(bill_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Remaining operations
+    // -----------------------------------------------------------------------
+
+    /// Correct the amount and due date of an unpaid bill.
+    ///
+    /// The new amount must exceed what has already been paid. Bills with an
+    /// installment plan keep their amount, as the plan was built from it.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` – `new_amount` ≤ `amount_paid`
+    /// * `InstallmentPlanExists` – amount change on a bill with a plan
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn update_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        new_amount: i128,
+        new_due_date: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if new_amount <= bill.amount_paid {
+            return Err(Error::InvalidAmount);
+        }
+        if new_amount != bill.amount && Self::get_installment_plans(&env).contains_key(bill_id) {
+            return Err(Error::InstallmentPlanExists);
+        }
+
+        let old_amount = bill.amount;
+        let old_due_date = bill.due_date;
+        if new_amount != old_amount {
+            bill.approval = Self::approval_for(&env, &caller, new_amount);
+        }
+        bill.amount = new_amount;
+        if new_due_date != old_due_date {
+            bill.reminder_sent = false;
+        }
+        bill.due_date = new_due_date;
+        Self::save_bill(&env, &bill);
+        Self::adjust_unpaid_total(&env, &caller, new_amount - old_amount);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("updated"),
+            (
+                bill_id,
+                caller,
+                old_amount,
+                new_amount,
+                old_due_date,
+                new_due_date,
+            ),
+        );
+        Ok(())
+    }
+
+    /// Remove an unpaid bill. Only the owner can cancel, and paid bills are
+    /// kept for history (see `archive_paid_bills`).
+    pub fn cancel_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
+        let bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        let removed_unpaid_amount = bill.remaining();
+        Self::delete_bill(&env, &bill);
+        Self::remove_bill_links(&env, bill_id);
+        if removed_unpaid_amount > 0 {
+            Self::adjust_unpaid_total(&env, &caller, -removed_unpaid_amount);
+        }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("canceled"),
+            bill_id,
+        );
+        Ok(())
+    }
+
+    /// Move up to `limit` bills paid before `before_timestamp` into compact
+    /// archive records, removing them from active storage.
+    ///
+    /// Owners archive their own bills; the pause admin archives across all
+    /// owners. Each owner's `ArchiveTotals` keep counting archived bills and
+    /// amounts even after the records are purged by `bulk_cleanup_bills`.
+    ///
+    /// # Returns
+    /// Number of bills archived
+    pub fn archive_paid_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+        limit: u32,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let limit = clamp_limit(limit);
+        let is_admin = Self::get_pause_admin(&env).is_some_and(|admin| admin == caller);
+        let archived_count = if is_admin {
+            let last_day = before_timestamp.saturating_sub(1) / SECONDS_PER_DAY;
+            let paid = Self::collect_by_day(&env, &INDEX_PAID, (0, 0), last_day, limit, |bill| {
+                bill.paid_at
+                    .is_some_and(|paid_at| paid_at < before_timestamp)
+                    && Self::get_escrow(env.clone(), bill.id).is_none()
+            });
+            let mut ids = Vec::new(&env);
+            for (id, _) in paid.iter() {
+                ids.push_back(id);
+            }
+            Self::archive_bills(&env, ids, before_timestamp, limit)
+        } else {
+            let ids = Self::owner_bill_ids(&env, &caller, true);
+            Self::archive_bills(&env, ids, before_timestamp, limit)
+        };
+
+        Self::extend_archive_ttl(&env);
+
+        RemitwiseEvents::emit_batch(
+            &env,
+            EventCategory::System,
+            symbol_short!("archived"),
+            archived_count,
+        );
+
+        Ok(archived_count)
+    }
+
+    /// Aggregate of everything `owner` has archived so far.
+    pub fn get_archive_totals(env: Env, owner: Address) -> ArchiveTotals {
+        let key = (KEY_ARCHIVE_TOTALS, owner);
+        let totals: Option<ArchiveTotals> = env.storage().persistent().get(&key);
+        match totals {
+            Some(totals) => {
+                Self::extend_persistent_ttl(&env, &key);
+                totals
+            }
+            None => ArchiveTotals {
+                bill_count: 0,
+                total_amount: 0,
+            },
+        }
+    }
+
+    /// Archive the paid bills among `ids` that were paid before `before`,
+    /// stopping after `limit` bills.
+    fn archive_bills<I>(env: &Env, ids: I, before: u64, limit: u32) -> u32
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        let mut archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut totals: Map<Address, ArchiveTotals> = Map::new(env);
+
+        let current_time = env.ledger().timestamp();
+        let mut archived_count = 0u32;
+        let mut archived_amount = 0i128;
+        for id in ids {
+            if archived_count >= limit {
+                break;
+            }
+            let Some(bill) = Self::load_bill(env, id) else {
+                continue;
+            };
+            let Some(paid_at) = bill.paid_at.filter(|_| bill.paid) else {
+                continue;
+            };
+            if paid_at >= before || Self::get_escrow(env.clone(), id).is_some() {
+                continue;
+            }
+
+            archived.set(
+                id,
+                ArchivedBill {
+                    id: bill.id,
+                    owner: bill.owner.clone(),
+                    name: bill.name.clone(),
+                    amount: bill.amount,
+                    paid_at,
+                    archived_at: current_time,
+                    currency: bill.currency.clone(),
+                    payee: bill.payee.clone(),
+                    token: bill.token.clone(),
+                },
+            );
+            let mut owner_totals = totals
+                .get(bill.owner.clone())
+                .unwrap_or_else(|| Self::get_archive_totals(env.clone(), bill.owner.clone()));
+            owner_totals.bill_count += 1;
+            owner_totals.total_amount = owner_totals.total_amount.saturating_add(bill.amount);
+            totals.set(bill.owner.clone(), owner_totals);
+
+            archived_amount = archived_amount.saturating_add(bill.amount);
+            Self::remove_bill_links(env, id);
+            Self::delete_bill(env, &bill);
+            archived_count += 1;
+        }
+
+        if archived_count > 0 {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("ARCH_BILL"), &archived);
+            Self::adjust_storage_stats(env, 0, archived_count as i32, 0, archived_amount);
+        }
+        for (owner, owner_totals) in totals.iter() {
+            Self::set_archive_totals(env, &owner, &owner_totals);
+        }
+        archived_count
+    }
+
+    fn set_archive_totals(env: &Env, owner: &Address, totals: &ArchiveTotals) {
+        let key = (KEY_ARCHIVE_TOTALS, owner.clone());
+        env.storage().persistent().set(&key, totals);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    pub fn restore_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::RESTORE)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        let archived_bill = archived.get(bill_id).ok_or(Error::BillNotFound)?;
+
+        if archived_bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        let restored_bill = Bill {
+            id: archived_bill.id,
+            owner: archived_bill.owner.clone(),
+            name: archived_bill.name.clone(),
+            amount: archived_bill.amount,
+            due_date: env.ledger().timestamp() + 2592000,
+            recurring: false,
+            frequency_days: 0,
+            paid: true,
+            created_at: archived_bill.paid_at,
+            paid_at: Some(archived_bill.paid_at),
+            schedule_id: None,
+            currency: archived_bill.currency.clone(),
+            payee: archived_bill.payee.clone(),
+            token: archived_bill.token.clone(),
+            amount_paid: archived_bill.amount,
+            late_fee: LateFeePolicy::None,
+            autopay: false,
+            occurrence: 1,
+            max_occurrences: None,
+            end_date: None,
+            category: BillCategory::Other,
+            approval: ApprovalStatus::NotRequired,
+            reminder_sent: false,
+            invoice_hash: BytesN::from_array(&env, &[0; 32]),
+            invoice_number: String::from_str(&env, ""),
+            priority: BillPriority::Normal,
+            recurrence_base: RecurrenceBase::DueDate,
+            suspended: false,
+            tags: Vec::new(&env),
+            escrow_window: 0,
+        };
+
+        Self::save_bill(&env, &restored_bill);
+        archived.remove(bill_id);
+
+        let mut totals = Self::get_archive_totals(env.clone(), caller.clone());
+        totals.bill_count = totals.bill_count.saturating_sub(1);
+        totals.total_amount = totals.total_amount.saturating_sub(archived_bill.amount);
+        Self::set_archive_totals(&env, &caller, &totals);
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCH_BILL"), &archived);
+        Self::adjust_storage_stats(&env, 0, -1, 0, -archived_bill.amount);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("restored"),
+            bill_id,
+        );
+        Ok(())
+    }
+
+    pub fn bulk_cleanup_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut deleted_count = 0u32;
+        let mut deleted_amount = 0i128;
+        let mut to_remove: Vec<u32> = Vec::new(&env);
+
+        for (id, bill) in archived.iter() {
+            if bill.archived_at < before_timestamp {
+                to_remove.push_back(id);
+                deleted_count += 1;
+                deleted_amount = deleted_amount.saturating_add(bill.amount);
+            }
+        }
+
+        for id in to_remove.iter() {
+            archived.remove(id);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCH_BILL"), &archived);
+        Self::adjust_storage_stats(&env, 0, -(deleted_count as i32), 0, -deleted_amount);
+
+        RemitwiseEvents::emit_batch(
+            &env,
+            EventCategory::System,
+            symbol_short!("cleaned"),
+            deleted_count,
+        );
+        Ok(deleted_count)
+    }
+
+    pub fn batch_pay_bills(env: Env, caller: Address, bill_ids: Vec<u32>) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap() {
+            return Err(Error::BatchTooLarge);
+        }
+        let shares = Self::get_shares_map(&env);
+        for id in bill_ids.iter() {
+            let bill = Self::load_bill(&env, id).ok_or(Error::BillNotFound)?;
+            if bill.owner != caller {
+                return Err(Error::Unauthorized);
+            }
+            if bill.paid {
+                return Err(Error::BillAlreadyPaid);
+            }
+            if bill.approval == ApprovalStatus::Pending {
+                return Err(Error::ApprovalRequired);
+            }
+            if shares.contains_key(id) {
+                return Err(Error::BillIsSplit);
+            }
+            if Self::is_disputed(&env, id) {
+                return Err(Error::BillDisputed);
+            }
+        }
+        Self::extend_instance_ttl(&env);
+        let current_time = env.ledger().timestamp();
+        let mut next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut paid_count = 0u32;
+        let mut unpaid_delta = 0i128;
+        for id in bill_ids.iter() {
+            let mut bill = Self::load_bill(&env, id).ok_or(Error::BillNotFound)?;
+            if bill.owner != caller || bill.paid {
+                return Err(Error::BatchValidationFailed);
+            }
+            let amount = bill.amount;
+            let remaining = bill.remaining();
+            let late_fee = bill.late_fee_at(current_time);
+            Self::settle(&env, &caller, &bill, remaining + late_fee)?;
+            Self::record_category_spend(&env, &bill, remaining);
+            bill.amount_paid = amount;
+            bill.paid = true;
+            bill.paid_at = Some(current_time);
+            let receipt_id = Self::issue_receipt(&env, &caller, &bill, remaining, late_fee);
+            if bill.escrow_window > 0 {
+                Self::hold_in_escrow(&env, &caller, &bill, receipt_id, remaining, late_fee);
+            }
+            unpaid_delta = unpaid_delta.saturating_sub(remaining);
+            if bill.has_next_occurrence() {
+                next_id = next_id.saturating_add(1);
+                Self::save_bill(
+                    &env,
+                    &Self::next_recurring_bill(&env, &bill, next_id, current_time),
+                );
+                unpaid_delta = unpaid_delta.saturating_add(amount);
+            } else if bill.recurring {
+                Self::emit_series_ended(&env, &bill);
+            }
+            Self::save_bill(&env, &bill);
+            paid_count += 1;
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Transaction,
+                EventPriority::High,
+                symbol_short!("paid"),
+                (id, caller.clone(), amount),
+            );
+            if late_fee > 0 {
+                RemitwiseEvents::emit(
+                    &env,
+                    EventCategory::Transaction,
+                    EventPriority::Medium,
+                    symbol_short!("late_fee"),
+                    (id, caller.clone(), late_fee),
+                );
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+        if unpaid_delta != 0 {
+            Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
+        }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("batch_pay"),
+            (paid_count, caller),
+        );
+        Ok(paid_count)
+    }
+
+    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
+        if let Some(totals) = Self::get_unpaid_totals_map(&env) {
+            if let Some(total) = totals.get(owner.clone()) {
+                return total;
+            }
+        }
+
+        let mut total = 0i128;
+        for bill in Self::owner_bills(&env, &owner, false).iter() {
+            total += bill.remaining();
+        }
+        total
+    }
+
+    pub fn get_storage_stats(env: Env) -> StorageStats {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STOR_STAT"))
+            .unwrap_or(StorageStats {
+                active_bills: 0,
+                archived_bills: 0,
+                total_unpaid_amount: 0,
+                total_archived_amount: 0,
+                last_updated: 0,
+            })
+    }
+
+    // -----------------------------------------------------------------------
+    // Receipts
+    // -----------------------------------------------------------------------
+
+    pub fn get_receipt(env: Env, receipt_id: u32) -> Option<Receipt> {
+        let key = (KEY_RECEIPT, receipt_id);
+        let receipt = env.storage().persistent().get(&key);
+        if receipt.is_some() {
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        receipt
+    }
+
+    /// Receipts for payments on `owner`'s bills, oldest first.
+    ///
+    /// Skips the first `offset` receipts and returns at most `limit`
+    /// (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT).
+    pub fn get_receipts(env: Env, owner: Address, offset: u32, limit: u32) -> Vec<Receipt> {
+        let limit = clamp_limit(limit);
+        let index_key = (KEY_OWNER_RECEIPTS, owner);
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut result = Vec::new(&env);
+        let end = ids.len().min(offset.saturating_add(limit));
+        for i in offset..end {
+            if let Some(receipt) = ids.get(i).and_then(|id| Self::get_receipt(env.clone(), id)) {
+                result.push_back(receipt);
+            }
+        }
+        result
+    }
+
+    /// Payment reliability of `owner` over receipts issued within
+    /// `[from, to]`, e.g. for the reporting contract or a lender.
+    ///
+    /// A bill counts as paid by the receipt that settled it, and as on time
+    /// when that receipt is no later than its due date. Refunded receipts are
+    /// ignored. Only receipts issued within the range are loaded: the owner's
+    /// index is in issue order, so the first one is found by binary search.
+    pub fn get_payment_stats(env: Env, owner: Address, from: u64, to: u64) -> PaymentStats {
+        let index_key = (KEY_OWNER_RECEIPTS, owner);
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        let (mut lo, mut hi) = (0u32, ids.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let issued = ids
+                .get(mid)
+                .and_then(|id| Self::get_receipt(env.clone(), id))
+                .map_or(0, |receipt| receipt.timestamp);
+            if issued < from {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let mut stats = PaymentStats {
+            bills_paid: 0,
+            total_paid: 0,
+            late_fees: 0,
+            on_time_pct: 0,
+            avg_days_late: 0,
+        };
+        let mut on_time = 0u32;
+        let mut late_days = 0u64;
+        for id in ids.slice(lo..).iter() {
+            let Some(receipt) = Self::get_receipt(env.clone(), id) else {
+                continue;
+            };
+            if receipt.timestamp > to {
+                break;
+            }
+            if receipt.refunded {
+                continue;
+            }
+
+            stats.total_paid = stats.total_paid.saturating_add(receipt.amount);
+            stats.late_fees = stats.late_fees.saturating_add(receipt.late_fee);
+            if !receipt.bill.paid {
+                continue;
+            }
+            stats.bills_paid += 1;
+            if receipt.timestamp <= receipt.bill.due_date {
+                on_time += 1;
+            } else {
+                late_days += (receipt.timestamp - receipt.bill.due_date).div_ceil(86400);
+            }
+        }
+
+        stats.on_time_pct = (on_time * 100).checked_div(stats.bills_paid).unwrap_or(0);
+        let late_count = (stats.bills_paid - on_time) as u64;
+        stats.avg_days_late = late_days.checked_div(late_count).unwrap_or(0) as u32;
+        stats
+    }
+
+    /// Store a receipt for a payment and add it to the owner's and the
+    /// bill's index.
+    fn issue_receipt(env: &Env, payer: &Address, bill: &Bill, amount: i128, late_fee: i128) -> u32 {
+        let receipt_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_RCPT"))
+            .unwrap_or(0u32)
+            + 1;
+        let receipt = Receipt {
+            id: receipt_id,
+            bill_id: bill.id,
+            payer: payer.clone(),
+            payee: Self::resolve_payee(env, bill),
+            token: bill.token.clone(),
+            amount,
+            late_fee,
+            timestamp: env.ledger().timestamp(),
+            bill: bill.clone(),
+            refunded: false,
+        };
+        let key = (KEY_RECEIPT, receipt_id);
+        env.storage().persistent().set(&key, &receipt);
+        Self::extend_persistent_ttl(env, &key);
+
+        Self::index_insert(env, &(KEY_OWNER_RECEIPTS, bill.owner.clone()), receipt_id);
+        Self::index_insert(env, &(KEY_BILL_RECEIPTS, bill.id), receipt_id);
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_RCPT"), &receipt_id);
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::Low,
+            symbol_short!("receipt"),
+            (receipt_id, bill.id, payer.clone()),
+        );
+        receipt_id
+    }
+
+    /// Record the final payment of `bill` as held in escrow until the payee
+    /// confirms receipt or the window runs out.
+    fn hold_in_escrow(
+        env: &Env,
+        payer: &Address,
+        bill: &Bill,
+        receipt_id: u32,
+        principal: i128,
+        late_fee: i128,
+    ) {
+        let locked_at = env.ledger().timestamp();
+        let hold = EscrowHold {
+            bill_id: bill.id,
+            receipt_id,
+            payer: payer.clone(),
+            payee: Self::resolve_payee(env, bill),
+            token: bill.token.clone(),
+            principal,
+            amount: principal + late_fee,
+            locked_at,
+            release_by: locked_at.saturating_add(bill.escrow_window),
+        };
+        let key = (KEY_ESCROW, bill.id);
+        env.storage().persistent().set(&key, &hold);
+        Self::extend_persistent_ttl(env, &key);
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("escrowed"),
+            (bill.id, payer.clone(), hold.amount, hold.release_by),
+        );
+    }
+
+    /// Return the payments made on a bill and flip it back to unpaid.
+    ///
+    /// Callable by the bill's payee or the contract admin. Every payment
+    /// recorded in a receipt for this bill, late fees included, is
+    /// transferred from the payee back to whoever paid it, so the payee
+    /// must authorize the transfers even when the admin initiates the
+    /// refund. Refunded payments made in the current budget period are taken
+    /// off the category spend, and any installment plan is dropped. The
+    /// owner can then correct the bill with `update_bill` and pay it again.
+    ///
+    /// Split bills, and paid recurring bills whose next occurrence has
+    /// already been created, cannot be refunded.
+    ///
+    /// # Returns
+    /// Total amount returned to payers
+    ///
+    /// # Errors
+    /// * `ContractPaused` / `FunctionPaused` – payments are paused
+    /// * `Unauthorized` – caller is neither the payee nor the admin
+    /// * `BillDisputed` – a dispute is open for the bill
+    /// * `BillIsSplit` – the bill is paid through shares
+    /// * `RefundNotSupported` – the bill's next occurrence already exists
+    /// * `NothingToRefund` – no unrefunded payments exist for the bill
+    /// * `TransferFailed` – a transfer back to a payer failed
+    /// * `BillNotFound`
+    pub fn refund_bill(env: Env, caller: Address, bill_id: u32) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        let payee = Self::resolve_payee(&env, &bill);
+        let is_admin = Self::get_pause_admin(&env).is_some_and(|admin| admin == caller);
+        if caller != payee && !is_admin {
+            return Err(Error::Unauthorized);
+        }
+        if Self::is_disputed(&env, bill_id) {
+            return Err(Error::BillDisputed);
+        }
+        if Self::get_escrow(env.clone(), bill_id).is_some() {
+            return Err(Error::EscrowWindowOpen);
+        }
+        if Self::get_shares_map(&env).contains_key(bill_id) {
+            return Err(Error::BillIsSplit);
+        }
+        if bill.paid && bill.has_next_occurrence() {
+            return Err(Error::RefundNotSupported);
+        }
+
+        let receipt_ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&(KEY_BILL_RECEIPTS, bill_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let token = TokenClient::new(&env, &bill.token);
+        let period = env.ledger().timestamp() / BUDGET_PERIOD_SECS;
+        let mut refunded_principal = 0i128;
+        let mut refunded_this_period = 0i128;
+        let mut refunded_total = 0i128;
+        for receipt_id in receipt_ids.iter() {
+            let key = (KEY_RECEIPT, receipt_id);
+            let Some(mut receipt) = env.storage().persistent().get::<_, Receipt>(&key) else {
+                continue;
+            };
+            if receipt.refunded {
+                continue;
+            }
+            let amount = receipt.amount + receipt.late_fee;
+            match token.try_transfer(&receipt.payee, &receipt.payer, &amount) {
+                Ok(Ok(())) => {}
+                _ => return Err(Error::TransferFailed),
+            }
+            refunded_principal += receipt.amount;
+            if receipt.timestamp / BUDGET_PERIOD_SECS == period {
+                refunded_this_period += receipt.amount;
+            }
+            refunded_total += amount;
+            receipt.refunded = true;
+            env.storage().persistent().set(&key, &receipt);
+            Self::extend_persistent_ttl(&env, &key);
+        }
+        if refunded_total == 0 {
+            return Err(Error::NothingToRefund);
+        }
+        if refunded_this_period > 0 {
+            Self::record_category_spend(&env, &bill, -refunded_this_period);
+        }
+        let mut plans = Self::get_installment_plans(&env);
+        if plans.remove(bill_id).is_some() {
+            env.storage()
+                .instance()
+                .set(&STORAGE_INSTALLMENT_PLANS, &plans);
+        }
+
+        bill.amount_paid = 0;
+        bill.paid = false;
+        bill.paid_at = None;
+        let owner = bill.owner.clone();
+        Self::save_bill(&env, &bill);
+        Self::adjust_unpaid_total(&env, &owner, refunded_principal);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("refunded"),
+            (bill_id, caller, refunded_total),
+        );
+        Ok(refunded_total)
+    }
+
+    // -----------------------------------------------------------------------
+    // Escrow
+    // -----------------------------------------------------------------------
+
+    /// Pay a bill into escrow instead of straight to the payee.
+    ///
+    /// With a non-zero `window_secs`, the final payment of the bill is held
+    /// by the contract; the payee releases it with `confirm_receipt` within
+    /// `window_secs`, after which the payer can take it back with
+    /// `reclaim_escrow`. This guards against paying a wrong vendor address.
+    /// Escrowed bills must be paid in full. Pass 0 to pay directly again.
+    ///
+    /// Recurring bills cannot be escrowed: paying one creates its next
+    /// occurrence, which a reclaimed payment could not take back.
+    ///
+    /// # Errors
+    /// * `BillIsSplit` – the bill is paid through shares
+    /// * `EscrowNotSupported` – the bill is recurring
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn set_escrow_window(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        window_secs: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UPDATE_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::get_shares_map(&env).contains_key(bill_id) {
+            return Err(Error::BillIsSplit);
+        }
+        if bill.recurring && window_secs > 0 {
+            return Err(Error::EscrowNotSupported);
+        }
+
+        bill.escrow_window = window_secs;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("escr_set"),
+            (bill_id, window_secs),
+        );
+        Ok(())
+    }
+
+    /// Called by the payee to release an escrowed payment to itself.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` – no payment is held for the bill
+    /// * `Unauthorized` – caller is not the payee the payment was made to
+    /// * `EscrowWindowClosed` – the confirmation window has passed
+    /// * `TransferFailed`
+    pub fn confirm_receipt(env: Env, caller: Address, bill_id: u32) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let hold = Self::get_escrow(env.clone(), bill_id).ok_or(Error::EscrowNotFound)?;
+        if hold.payee != caller {
+            return Err(Error::Unauthorized);
+        }
+        if env.ledger().timestamp() > hold.release_by {
+            return Err(Error::EscrowWindowClosed);
+        }
+        match TokenClient::new(&env, &hold.token).try_transfer(
+            &env.current_contract_address(),
+            &hold.payee,
+            &hold.amount,
+        ) {
+            Ok(Ok(())) => {}
+            _ => return Err(Error::TransferFailed),
+        }
+        env.storage().persistent().remove(&(KEY_ESCROW, bill_id));
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("released"),
+            (bill_id, caller, hold.amount),
+        );
+        Ok(hold.amount)
+    }
+
+    /// Called by the payer once the confirmation window has passed without
+    /// the payee confirming. The held funds go back to the payer, the
+    /// receipt is marked refunded, the payment is taken off the category
+    /// spend if made this budget period, and the bill becomes unpaid again.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` – no payment is held for the bill
+    /// * `Unauthorized` – caller is not the payer
+    /// * `EscrowWindowOpen` – the payee can still confirm
+    /// * `TransferFailed`
+    pub fn reclaim_escrow(env: Env, caller: Address, bill_id: u32) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let hold = Self::get_escrow(env.clone(), bill_id).ok_or(Error::EscrowNotFound)?;
+        if hold.payer != caller {
+            return Err(Error::Unauthorized);
+        }
+        if env.ledger().timestamp() <= hold.release_by {
+            return Err(Error::EscrowWindowOpen);
+        }
+        match TokenClient::new(&env, &hold.token).try_transfer(
+            &env.current_contract_address(),
+            &hold.payer,
+            &hold.amount,
+        ) {
+            Ok(Ok(())) => {}
+            _ => return Err(Error::TransferFailed),
+        }
+        env.storage().persistent().remove(&(KEY_ESCROW, bill_id));
+
+        let receipt_key = (KEY_RECEIPT, hold.receipt_id);
+        if let Some(mut receipt) = env.storage().persistent().get::<_, Receipt>(&receipt_key) {
+            receipt.refunded = true;
+            env.storage().persistent().set(&receipt_key, &receipt);
+            Self::extend_persistent_ttl(&env, &receipt_key);
+            if receipt.timestamp / BUDGET_PERIOD_SECS
+                == env.ledger().timestamp() / BUDGET_PERIOD_SECS
+            {
+                Self::record_category_spend(&env, &receipt.bill, -hold.principal);
+            }
+        }
+        if let Some(mut bill) = Self::load_bill(&env, bill_id) {
+            bill.amount_paid -= hold.principal;
+            bill.paid = false;
+            bill.paid_at = None;
+            let owner = bill.owner.clone();
+            Self::save_bill(&env, &bill);
+            Self::adjust_unpaid_total(&env, &owner, hold.principal);
+        }
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("reclaimed"),
+            (bill_id, caller, hold.amount),
+        );
+        Ok(hold.amount)
+    }
+
+    /// Payment held in escrow for a bill, if any.
+    pub fn get_escrow(env: Env, bill_id: u32) -> Option<EscrowHold> {
+        env.storage().persistent().get(&(KEY_ESCROW, bill_id))
+    }
+
+    // -----------------------------------------------------------------------
+    // Disputes
+    // -----------------------------------------------------------------------
+
+    /// Contest an unpaid bill. The bill is frozen – no payment, including
+    /// autopay, goes through – until the dispute is resolved.
+    ///
+    /// # Errors
+    /// * `BillDisputed` – a dispute is already open for the bill
+    /// * `BillNotFound` / `Unauthorized` / `BillAlreadyPaid`
+    pub fn dispute_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        reason_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        let mut disputes = Self::get_disputes_map(&env);
+        if disputes
+            .get(bill_id)
+            .is_some_and(|d| d.status == DisputeStatus::Open)
+        {
+            return Err(Error::BillDisputed);
+        }
+
+        disputes.set(
+            bill_id,
+            Dispute {
+                bill_id,
+                opened_by: caller.clone(),
+                reason_hash: reason_hash.clone(),
+                opened_at: env.ledger().timestamp(),
+                status: DisputeStatus::Open,
+                resolved_at: 0,
+            },
+        );
+        env.storage().instance().set(&STORAGE_DISPUTES, &disputes);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Alert,
+            EventPriority::High,
+            symbol_short!("disp_open"),
+            (bill_id, caller, reason_hash),
+        );
+        Ok(())
+    }
+
+    /// Close an open dispute. Callable by the bill's payee or the admin.
+    ///
+    /// `Upheld` unfreezes the bill so it can be paid; `Cancelled` removes
+    /// the bill from the owner's unpaid bills.
+    ///
+    /// # Errors
+    /// * `DisputeNotFound` – no open dispute for the bill
+    /// * `Unauthorized` – caller is neither the payee nor the admin
+    pub fn resolve_dispute(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        outcome: DisputeOutcome,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut disputes = Self::get_disputes_map(&env);
+        let mut dispute = disputes
+            .get(bill_id)
+            .filter(|d| d.status == DisputeStatus::Open)
+            .ok_or(Error::DisputeNotFound)?;
+        let bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        let is_admin = Self::get_pause_admin(&env).is_some_and(|admin| admin == caller);
+        if caller != Self::resolve_payee(&env, &bill) && !is_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        dispute.status = match outcome {
+            DisputeOutcome::Upheld => DisputeStatus::Upheld,
+            DisputeOutcome::Cancelled => {
+                Self::delete_bill(&env, &bill);
+                Self::adjust_unpaid_total(&env, &bill.owner, -bill.remaining());
+                Self::remove_bill_links(&env, bill_id);
+                DisputeStatus::Cancelled
+            }
+        };
+        dispute.resolved_at = env.ledger().timestamp();
+        disputes.set(bill_id, dispute);
+        env.storage().instance().set(&STORAGE_DISPUTES, &disputes);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Alert,
+            EventPriority::High,
+            symbol_short!("disp_done"),
+            (bill_id, caller, outcome),
+        );
+        Ok(())
+    }
+
+    pub fn get_dispute(env: Env, bill_id: u32) -> Option<Dispute> {
+        Self::get_disputes_map(&env).get(bill_id)
+    }
+
+    fn get_disputes_map(env: &Env) -> Map<u32, Dispute> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_DISPUTES)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn is_disputed(env: &Env, bill_id: u32) -> bool {
+        Self::get_disputes_map(env)
+            .get(bill_id)
+            .is_some_and(|d| d.status == DisputeStatus::Open)
+    }
+
+    // -----------------------------------------------------------------------
+    // FX normalization
+    // -----------------------------------------------------------------------
+
+    /// Admin-only: set the FX oracle used by `get_total_unpaid_in`.
+    pub fn set_fx_oracle(env: Env, caller: Address, oracle: Address) -> Result<(), Error> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+        env.storage().instance().set(&STORAGE_FX_ORACLE, &oracle);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("fx_oracle"),
+            oracle,
+        );
+        Ok(())
+    }
+
+    pub fn get_fx_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_FX_ORACLE)
+    }
+
+    /// Sum of `owner`'s unpaid balances converted into `home_token`.
+    ///
+    /// Bills settled in `home_token` count at face value; every other token
+    /// is converted with the rate reported by the FX oracle.
+    ///
+    /// # Errors
+    /// * `OracleNotSet` – a conversion is needed but no oracle is configured
+    /// * `FxRateUnavailable` – the oracle call failed or returned a rate ≤ 0
+    pub fn get_total_unpaid_in(
+        env: Env,
+        owner: Address,
+        home_token: Address,
+    ) -> Result<i128, Error> {
+        let mut rates: Map<Address, i128> = Map::new(&env);
+        let mut total = 0i128;
+        for bill in Self::owner_bills(&env, &owner, false).iter() {
+            if bill.token == home_token {
+                total = total.saturating_add(bill.remaining());
+                continue;
+            }
+            let rate = match rates.get(bill.token.clone()) {
+                Some(rate) => rate,
+                None => {
+                    let rate = Self::fx_rate(&env, &bill.token, &home_token)?;
+                    rates.set(bill.token.clone(), rate);
+                    rate
+                }
+            };
+            total = total.saturating_add(bill.remaining().saturating_mul(rate) / FX_RATE_SCALE);
+        }
+        Ok(total)
+    }
+
+    fn fx_rate(env: &Env, base: &Address, quote: &Address) -> Result<i128, Error> {
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&STORAGE_FX_ORACLE)
+            .ok_or(Error::OracleNotSet)?;
+        match FxOracleClient::new(env, &oracle).try_get_rate(base, quote) {
+            Ok(Ok(rate)) if rate > 0 => Ok(rate),
+            _ => Err(Error::FxRateUnavailable),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Currency-filter helper queries
+    // -----------------------------------------------------------------------
+
+    /// Get a page of ALL bills (paid + unpaid) for `owner` that match `currency`.
+    ///
+    /// # Arguments
+    /// * `owner`    – whose bills to return
+    /// * `currency` – currency code to filter by, e.g. `"USDC"`, `"XLM"`
+    /// * `cursor`   – start after this bill ID (pass 0 for the first page)
+    /// * `limit`    – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
+    pub fn get_bills_by_currency(
+        env: Env,
+        owner: Address,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let ids = Self::owner_all_bill_ids(&env, &owner);
+        Self::collect_page(&env, ids, cursor, limit, |bill| bill.currency == currency)
+    }
+
+    /// Get a page of **unpaid** bills for `owner` that match `currency`.
+    ///
+    /// Same cursor/limit semantics as `get_bills_by_currency`.
+    pub fn get_unpaid_bills_by_currency(
+        env: Env,
+        owner: Address,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let ids = Self::owner_bill_ids(&env, &owner, false);
+        Self::collect_page(&env, ids, cursor, limit, |bill| bill.currency == currency)
+    }
+
+    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
+    ///
+    /// # Example
+    /// ```text
+    /// let usdc_owed = client.get_total_unpaid_by_currency(&owner, &String::from_str(&env, "USDC"));
+    /// ```
+    pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
+        let mut total = 0i128;
+        for bill in Self::owner_bills(&env, &owner, false).iter() {
+            if bill.currency == currency {
+                total += bill.remaining();
+            }
+        }
+        total
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    // -----------------------------------------------------------------------
+    // Bill storage
+    //
+    // Each bill lives in its own persistent entry keyed by ID. Every owner
+    // has two ascending ID indexes, one for unpaid and one for paid bills,
+    // so owner queries only touch that owner's bills. Queries across all
+    // owners read day indexes instead: open bills by due day (`DUE`, and
+    // `AUTOPAY` for those on autopay) and paid bills by payment day
+    // (`PAID`). Each index keeps a bucket of bill IDs per day plus the
+    // sorted list of days that have a bucket.
+    // -----------------------------------------------------------------------
+
+    fn load_bill(env: &Env, bill_id: u32) -> Option<Bill> {
+        let key = (KEY_BILL, bill_id);
+        let bill: Option<Bill> = env.storage().persistent().get(&key);
+        if bill.is_some() {
+            Self::extend_persistent_ttl(env, &key);
+        }
+        bill
+    }
+
+    /// Write `bill` and file its ID under the owner's paid or unpaid index
+    /// and the day indexes it belongs in.
+    fn save_bill(env: &Env, bill: &Bill) {
+        let key = (KEY_BILL, bill.id);
+        let previous: Option<Bill> = env.storage().persistent().get(&key);
+        env.storage().persistent().set(&key, bill);
+        Self::extend_persistent_ttl(env, &key);
+
+        let old_slots = previous
+            .as_ref()
+            .map(Self::day_slots)
+            .unwrap_or([None, None, None]);
+        for (old, new) in old_slots.iter().zip(Self::day_slots(bill).iter()) {
+            if old == new {
+                continue;
+            }
+            if let Some((kind, day)) = old {
+                Self::day_index_remove(env, kind, *day, bill.id);
+            }
+            if let Some((kind, day)) = new {
+                Self::day_index_insert(env, kind, *day, bill.id);
+            }
+        }
+        if previous.is_none() {
+            Self::adjust_storage_stats(env, 1, 0, 0, 0);
+        }
+
+        let (keep, drop) = if bill.paid {
+            (KEY_OWNER_PAID, KEY_OWNER_UNPAID)
+        } else {
+            (KEY_OWNER_UNPAID, KEY_OWNER_PAID)
+        };
+        Self::index_insert(env, &(keep, bill.owner.clone()), bill.id);
+        Self::index_remove(env, &(drop, bill.owner.clone()), bill.id);
+    }
+
+    fn delete_bill(env: &Env, bill: &Bill) {
+        let key = (KEY_BILL, bill.id);
+        let Some(stored) = env.storage().persistent().get::<_, Bill>(&key) else {
+            return;
+        };
+        env.storage().persistent().remove(&key);
+        Self::index_remove(env, &(KEY_OWNER_UNPAID, bill.owner.clone()), bill.id);
+        Self::index_remove(env, &(KEY_OWNER_PAID, bill.owner.clone()), bill.id);
+        for (kind, day) in Self::day_slots(&stored).iter().flatten() {
+            Self::day_index_remove(env, kind, *day, bill.id);
+        }
+        Self::adjust_storage_stats(env, -1, 0, 0, 0);
+    }
+
+    /// Day-index entries `bill` belongs in: open bills under their due day,
+    /// again under `AUTOPAY` when autopay is on, and paid bills under the
+    /// day they were paid.
+    fn day_slots(bill: &Bill) -> [Option<(Symbol, u64)>; 3] {
+        let open = !bill.paid && !bill.suspended;
+        let due_day = bill.due_date / SECONDS_PER_DAY;
+        [
+            open.then_some((INDEX_DUE, due_day)),
+            (open && bill.autopay).then_some((INDEX_AUTOPAY, due_day)),
+            bill.paid_at
+                .filter(|_| bill.paid)
+                .map(|paid_at| (INDEX_PAID, paid_at / SECONDS_PER_DAY)),
+        ]
+    }
+
+    fn day_index_insert(env: &Env, kind: &Symbol, day: u64, bill_id: u32) {
+        let bucket = (KEY_DAY_BUCKET, kind.clone(), day);
+        if !env.storage().persistent().has(&bucket) {
+            let list_key = (KEY_DAY_LIST, kind.clone());
+            let mut days = Self::index_days(env, kind);
+            if let Err(pos) = days.binary_search(day) {
+                days.insert(pos, day);
+                env.storage().persistent().set(&list_key, &days);
+                Self::extend_persistent_ttl(env, &list_key);
+            }
+        }
+        Self::index_insert(env, &bucket, bill_id);
+    }
+
+    fn day_index_remove(env: &Env, kind: &Symbol, day: u64, bill_id: u32) {
+        let bucket = (KEY_DAY_BUCKET, kind.clone(), day);
+        Self::index_remove(env, &bucket, bill_id);
+        if env.storage().persistent().has(&bucket) {
+            return;
+        }
+        let list_key = (KEY_DAY_LIST, kind.clone());
+        let mut days = Self::index_days(env, kind);
+        if let Ok(pos) = days.binary_search(day) {
+            days.remove(pos);
+            if days.is_empty() {
+                env.storage().persistent().remove(&list_key);
+            } else {
+                env.storage().persistent().set(&list_key, &days);
+                Self::extend_persistent_ttl(env, &list_key);
+            }
+        }
+    }
+
+    /// Days that have a bucket in the `kind` index, ascending.
+    fn index_days(env: &Env, kind: &Symbol) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(KEY_DAY_LIST, kind.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Position in a day index to resume after: the due day and ID of the
+    /// `cursor` bill, or the start when there is no cursor.
+    fn day_cursor(env: &Env, cursor: u32) -> (u64, u32) {
+        if cursor == 0 {
+            return (0, 0);
+        }
+        Self::load_bill(env, cursor)
+            .map(|bill| (bill.due_date / SECONDS_PER_DAY, cursor))
+            .unwrap_or((0, 0))
+    }
+
+    /// Bills filed in the `kind` day index, in day then ID order, that come
+    /// after the `(day, bill_id)` position `after`, fall on or before
+    /// `last_day` and satisfy `keep`. Stops once `max` bills are found.
+    fn collect_by_day<F>(
+        env: &Env,
+        kind: &Symbol,
+        after: (u64, u32),
+        last_day: u64,
+        max: u32,
+        keep: F,
+    ) -> Vec<(u32, Bill)>
+    where
+        F: Fn(&Bill) -> bool,
+    {
+        let mut found = Vec::new(env);
+        let days = Self::index_days(env, kind);
+        let (Ok(start) | Err(start)) = days.binary_search(after.0);
+        for day in days.slice(start..).iter() {
+            if day > last_day {
+                break;
+            }
+            let bucket: Vec<u32> = env
+                .storage()
+                .persistent()
+                .get(&(KEY_DAY_BUCKET, kind.clone(), day))
+                .unwrap_or_else(|| Vec::new(env));
+            for id in bucket.iter() {
+                if day == after.0 && id <= after.1 {
+                    continue;
+                }
+                let Some(bill) = Self::load_bill(env, id) else {
+                    continue;
+                };
+                if !keep(&bill) {
+                    continue;
+                }
+                found.push_back((id, bill));
+                if found.len() >= max {
+                    return found;
+                }
+            }
+        }
+        found
+    }
+
+    fn index_insert<K>(env: &Env, key: &K, id: u32)
+    where
+        K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Err(pos) = ids.binary_search(id) {
+            ids.insert(pos, id);
+            env.storage().persistent().set(key, &ids);
+        }
+        Self::extend_persistent_ttl(env, key);
+    }
+
+    fn index_remove<K>(env: &Env, key: &K, id: u32)
+    where
+        K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        let ids: Option<Vec<u32>> = env.storage().persistent().get(key);
+        let Some(mut ids) = ids else {
+            return;
+        };
+        if let Ok(pos) = ids.binary_search(id) {
+            ids.remove(pos);
+            if ids.is_empty() {
+                env.storage().persistent().remove(key);
+                return;
+            }
+            env.storage().persistent().set(key, &ids);
+        }
+        Self::extend_persistent_ttl(env, key);
+    }
+
+    /// IDs of `owner`'s paid or unpaid bills, ascending.
+    fn owner_bill_ids(env: &Env, owner: &Address, paid: bool) -> Vec<u32> {
+        let key = if paid {
+            (KEY_OWNER_PAID, owner.clone())
+        } else {
+            (KEY_OWNER_UNPAID, owner.clone())
+        };
+        let ids: Option<Vec<u32>> = env.storage().persistent().get(&key);
+        match ids {
+            Some(ids) => {
+                Self::extend_persistent_ttl(env, &key);
+                ids
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    /// IDs of all of `owner`'s bills, ascending.
+    fn owner_all_bill_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        let unpaid = Self::owner_bill_ids(env, owner, false);
+        let paid = Self::owner_bill_ids(env, owner, true);
+        let mut merged = Vec::new(env);
+        let (mut i, mut j) = (0u32, 0u32);
+        while i < unpaid.len() || j < paid.len() {
+            match (unpaid.get(i), paid.get(j)) {
+                (Some(a), Some(b)) if b < a => {
+                    merged.push_back(b);
+                    j += 1;
+                }
+                (Some(a), _) => {
+                    merged.push_back(a);
+                    i += 1;
+                }
+                (None, Some(b)) => {
+                    merged.push_back(b);
+                    j += 1;
+                }
+                (None, None) => break,
+            }
+        }
+        merged
+    }
+
+    fn owner_bills(env: &Env, owner: &Address, paid: bool) -> Vec<Bill> {
+        let mut bills = Vec::new(env);
+        for id in Self::owner_bill_ids(env, owner, paid).iter() {
+            if let Some(bill) = Self::load_bill(env, id) {
+                bills.push_back(bill);
+            }
+        }
+        bills
+    }
+
+    /// Highest bill ID assigned so far.
+    fn last_bill_id(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32)
+    }
+
+    /// Validate `item` and build an unsaved bill for `owner` from it.
+    fn new_bill(env: &Env, owner: &Address, item: NewBill) -> Result<Bill, Error> {
+        if item.amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if item.recurring && item.frequency_days == 0 {
+            return Err(Error::InvalidFrequency);
+        }
+        if !item.tags.is_empty() {
+            Self::validate_tags(&item.tags)?;
+        }
+
+        // Resolve default currency: blank input → "XLM"
+        let resolved_currency = if item.currency.is_empty() {
+            String::from_str(env, "XLM")
+        } else {
+            item.currency
+        };
+
+        Ok(Bill {
+            id: 0,
+            owner: owner.clone(),
+            name: item.name,
+            amount: item.amount,
+            due_date: item.due_date,
+            recurring: item.recurring,
+            frequency_days: item.frequency_days,
+            paid: false,
+            created_at: env.ledger().timestamp(),
+            paid_at: None,
+            schedule_id: None,
+            currency: resolved_currency,
+            payee: BillPayee::Address(item.payee),
+            token: item.token,
+            amount_paid: 0,
+            late_fee: LateFeePolicy::None,
+            autopay: false,
+            occurrence: 1,
+            max_occurrences: None,
+            end_date: None,
+            category: BillCategory::Other,
+            approval: ApprovalStatus::NotRequired,
+            reminder_sent: false,
+            invoice_hash: item.invoice_hash,
+            invoice_number: item.invoice_number,
+            priority: BillPriority::Normal,
+            recurrence_base: RecurrenceBase::DueDate,
+            suspended: false,
+            tags: item.tags,
+            escrow_window: 0,
+        })
+    }
+
+    /// Assign the next bill ID to `bill`, store it and emit the `created` event.
+    fn insert_bill(env: &Env, mut bill: Bill) -> u32 {
+        Self::extend_instance_ttl(env);
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32)
+            + 1;
+        bill.id = next_id;
+        bill.approval = Self::approval_for(env, &bill.owner, bill.amount);
+
+        let bill_owner = bill.owner.clone();
+        let amount = bill.amount;
+        let due_date = bill.due_date;
+        let approval = bill.approval;
+        Self::save_bill(env, &bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+        Self::adjust_unpaid_total(env, &bill_owner, amount);
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("created"),
+            (next_id, bill_owner, amount, due_date),
+        );
+        if approval == ApprovalStatus::Pending {
+            RemitwiseEvents::emit(
+                env,
+                EventCategory::Access,
+                EventPriority::Medium,
+                symbol_short!("appr_req"),
+                next_id,
+            );
+        }
+
+        next_id
+    }
+
+    /// Address that should receive payment for `bill`. Bills linked to a
+    /// registered payee follow the payee's current address; payees are only
+    /// ever deactivated, never deleted, so the lookup always succeeds.
+    fn resolve_payee(env: &Env, bill: &Bill) -> Address {
+        match &bill.payee {
+            BillPayee::Address(address) => address.clone(),
+            BillPayee::Registered(id) => Self::get_payee(env.clone(), *id)
+                .map(|payee| payee.address)
+                .unwrap_or_else(|| panic!("registered payee missing")),
+        }
+    }
+
+    /// Where payments for `bill` are sent: the payee, or this contract when
+    /// the bill is paid into escrow.
+    fn recipient(env: &Env, bill: &Bill) -> Address {
+        if bill.escrow_window > 0 {
+            env.current_contract_address()
+        } else {
+            Self::resolve_payee(env, bill)
+        }
+    }
+
+    /// Transfer `amount` of `bill.token` from `payer` to the bill's payee.
+    fn settle(env: &Env, payer: &Address, bill: &Bill, amount: i128) -> Result<(), Error> {
+        let payee = Self::recipient(env, bill);
+        match TokenClient::new(env, &bill.token).try_transfer(payer, &payee, &amount) {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(Error::TransferFailed),
+        }
+    }
+
+    /// Pull `amount` of `bill.token` from `owner` to the bill's payee using
+    /// the allowance the owner granted to this contract.
+    fn settle_from_allowance(
+        env: &Env,
+        owner: &Address,
+        bill: &Bill,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let payee = Self::recipient(env, bill);
+        let spender = env.current_contract_address();
+        match TokenClient::new(env, &bill.token).try_transfer_from(&spender, owner, &payee, &amount)
+        {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(Error::TransferFailed),
+        }
+    }
+
+    /// Pay `amount` of `bill.token` to the bill's payee out of the balance
+    /// `owner` deposited with `deposit_allocation`.
+    fn settle_from_balance(
+        env: &Env,
+        owner: &Address,
+        bill: &Bill,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let balance = Self::get_bills_balance(env.clone(), owner.clone(), bill.token.clone());
+        if balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        // Escrowed payments stay in the contract, only the balance moves.
+        if bill.escrow_window == 0 {
+            let payee = Self::resolve_payee(env, bill);
+            match TokenClient::new(env, &bill.token).try_transfer(
+                &env.current_contract_address(),
+                &payee,
+                &amount,
+            ) {
+                Ok(Ok(())) => {}
+                _ => return Err(Error::TransferFailed),
+            }
+        }
+        Self::set_bills_balance(env, owner, &bill.token, balance - amount);
+        Ok(())
+    }
+
+    fn set_bills_balance(env: &Env, owner: &Address, token: &Address, balance: i128) {
+        let key = (KEY_BILLS_BALANCE, owner.clone(), token.clone());
+        if balance == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &balance);
+            Self::extend_persistent_ttl(env, &key);
+        }
+    }
+
+    fn extend_persistent_ttl<K>(env: &Env, key: &K)
+    where
+        K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        env.storage().persistent().extend_ttl(
+            key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+    }
+
+    fn extend_archive_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+    }
+
+    /// Rewrite the legacy `ARCH_BILL` map in the current archive format,
+    /// with each bill's owner as its payee, and count it in the stats.
+    fn migrate_legacy_archive(env: &Env, token: &Address) {
+        let Some(legacy) = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, LegacyArchivedBill>>(&symbol_short!("ARCH_BILL"))
+        else {
+            return;
+        };
+        let mut archived: Map<u32, ArchivedBill> = Map::new(env);
+        let mut archived_amount = 0i128;
+        for (id, old) in legacy.iter() {
+            archived_amount = archived_amount.saturating_add(old.amount);
+            archived.set(
+                id,
+                ArchivedBill {
+                    id,
+                    owner: old.owner.clone(),
+                    name: old.name,
+                    amount: old.amount,
+                    paid_at: old.paid_at,
+                    archived_at: old.archived_at,
+                    currency: old.currency,
+                    payee: BillPayee::Address(old.owner),
+                    token: token.clone(),
+                },
+            );
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCH_BILL"), &archived);
+        Self::adjust_storage_stats(env, 0, archived.len() as i32, 0, archived_amount);
+    }
+
+    /// Apply changes to the running `StorageStats`.
+    fn adjust_storage_stats(
+        env: &Env,
+        active_delta: i32,
+        archived_delta: i32,
+        unpaid_delta: i128,
+        archived_amount_delta: i128,
+    ) {
+        let mut stats = Self::get_storage_stats(env.clone());
+        stats.active_bills = stats.active_bills.saturating_add_signed(active_delta);
+        stats.archived_bills = stats.archived_bills.saturating_add_signed(archived_delta);
+        stats.total_unpaid_amount = stats.total_unpaid_amount.saturating_add(unpaid_delta);
+        stats.total_archived_amount = stats
+            .total_archived_amount
+            .saturating_add(archived_amount_delta);
+        stats.last_updated = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STOR_STAT"), &stats);
+    }
+
+    /// Drop the installment plan, shares and scheduled payment attached to a
+    /// removed bill.
+    fn remove_bill_links(env: &Env, bill_id: u32) {
+        let mut plans = Self::get_installment_plans(env);
+        if plans.contains_key(bill_id) {
+            plans.remove(bill_id);
+            env.storage()
+                .instance()
+                .set(&STORAGE_INSTALLMENT_PLANS, &plans);
+        }
+        let mut shares = Self::get_shares_map(env);
+        if shares.contains_key(bill_id) {
+            shares.remove(bill_id);
+            env.storage().instance().set(&STORAGE_BILL_SHARES, &shares);
+        }
+        let mut schedules = Self::get_scheduled_payments(env);
+        if schedules.contains_key(bill_id) {
+            schedules.remove(bill_id);
+            env.storage()
+                .instance()
+                .set(&STORAGE_SCHEDULED_PAYMENTS, &schedules);
+        }
+    }
+
+    fn get_shares_map(env: &Env) -> Map<u32, Vec<BillShare>> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_BILL_SHARES)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn get_installment_plans(env: &Env) -> Map<u32, InstallmentPlan> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_INSTALLMENT_PLANS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn get_unpaid_totals_map(env: &Env) -> Option<Map<Address, i128>> {
+        env.storage().instance().get(&STORAGE_UNPAID_TOTALS)
+    }
+
+    fn adjust_unpaid_total(env: &Env, owner: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let mut totals: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_UNPAID_TOTALS)
+            .unwrap_or_else(|| Map::new(env));
+        let current = totals.get(owner.clone()).unwrap_or(0);
+        let next = if delta >= 0 {
+            current.saturating_add(delta)
+        } else {
+            current.saturating_sub(delta.saturating_abs())
+        };
+        totals.set(owner.clone(), next);
+        env.storage()
+            .instance()
+            .set(&STORAGE_UNPAID_TOTALS, &totals);
+        Self::adjust_storage_stats(env, 0, 0, next - current, 0);
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Events, Ledger},
+        token::{StellarAssetClient, TokenClient},
+        Env, String, TryFromVal,
+    };
+
+    fn make_env() -> Env {
+        Env::default()
+    }
+
+    /// Address a bill created with a direct payee pays out to.
+    fn payee_of(bill: &Bill) -> Address {
+        match &bill.payee {
+            BillPayee::Address(address) => address.clone(),
+            BillPayee::Registered(_) => panic!("bill uses a registered payee"),
+        }
+    }
+
+    /// Register a test token and mint a generous balance to `payer`.
+    fn setup_token(env: &Env, payer: &Address) -> Address {
+        let admin = Address::generate(env);
+        let token = env.register_stellar_asset_contract_v2(admin).address();
+        StellarAssetClient::new(env, &token).mint(payer, &1_000_000_000_000);
+        token
+    }
+
+    /// Create `count` bills with a static name. Returns their IDs.
+    /// Due dates are set in the future so they are NOT overdue.
+    fn setup_bills(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        count: u32,
+    ) -> Vec<u32> {
+        let payee = Address::generate(env);
+        let token = setup_token(env, owner);
+        let mut ids = Vec::new(env);
+        for i in 0..count {
+            let id = client.create_bill(
+                owner,
+                &String::from_str(env, "Test Bill"),
+                &(100i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &String::from_str(env, "XLM"),
+                &payee,
+                &token,
+            );
+            ids.push_back(id);
+        }
+        ids
+    }
+
+    /// Create a monthly recurring bill due in 30 days.
+    fn create_monthly_bill(env: &Env, client: &BillPaymentsClient, owner: &Address) -> u32 {
+        let payee = Address::generate(env);
+        let token = setup_token(env, owner);
+        client.create_bill(
+            owner,
+            &String::from_str(env, "Internet"),
+            &50,
+            &(86400 * 30),
+            &true,
+            &30,
+            &String::from_str(env, "XLM"),
+            &payee,
+            &token,
+        )
+    }
+
+    // --- get_unpaid_bills ---
+
+    #[test]
+    fn test_get_unpaid_bills_empty() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let page = client.get_unpaid_bills(&owner, &0, &0);
+        assert_eq!(page.count, 0);
+        assert_eq!(page.next_cursor, 0);
+        assert_eq!(page.items.len(), 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_single_page() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 5);
+
+        let page = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+        assert_eq!(page.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_multiple_pages() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 7);
+
+        let page1 = client.get_unpaid_bills(&owner, &0, &3);
+        assert_eq!(page1.count, 3);
+        assert!(page1.next_cursor > 0, "expected a next cursor");
+
+        let page2 = client.get_unpaid_bills(&owner, &page1.next_cursor, &3);
+        assert_eq!(page2.count, 3);
+        assert!(page2.next_cursor > 0);
+
+        let page3 = client.get_unpaid_bills(&owner, &page2.next_cursor, &3);
+        assert_eq!(page3.count, 1);
+        assert_eq!(page3.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_excludes_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 4);
+        let second_id = ids.get(1).unwrap();
+        client.pay_bill(&owner, &second_id);
+
+        let page = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_excludes_other_owner() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner_a, 3);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        let page = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page.count, 3);
+        for bill in page.items.iter() {
+            assert_eq!(bill.owner, owner_a);
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_bidirectional() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner_a, 2);
+        setup_bills(&env, &client, &owner_b, 3);
+
+        // owner_a sees only their own bills
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 2);
+        for bill in page_a.items.iter() {
+            assert_eq!(
+                bill.owner, owner_a,
+                "owner_a page must not contain owner_b bills"
+            );
+        }
+
+        // owner_b sees only their own bills
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 3);
+        for bill in page_b.items.iter() {
+            assert_eq!(
+                bill.owner, owner_b,
+                "owner_b page must not contain owner_a bills"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_after_one_pays() {
+        // If owner_a pays their bill, owner_b's unpaid bills are unaffected
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        let ids_a = setup_bills(&env, &client, &owner_a, 2);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        // owner_a pays one of their bills
+        client.pay_bill(&owner_a, &ids_a.get(0).unwrap());
+
+        // owner_a now has 1 unpaid
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 1);
+        for bill in page_a.items.iter() {
+            assert_eq!(bill.owner, owner_a, "Should only see owner_a bills");
+            assert!(!bill.paid, "Should only see unpaid bills");
+        }
+
+        // owner_b still has 2 unpaid — unaffected by owner_a's payment
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 2);
+        for bill in page_b.items.iter() {
+            assert_eq!(bill.owner, owner_b, "Should only see owner_b bills");
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_one_owner_no_bills() {
+        // owner_b has bills but owner_a has none — owner_a gets empty page
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        // Only owner_b creates bills
+        setup_bills(&env, &client, &owner_b, 3);
+
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 0, "owner_a should see no bills");
+        assert_eq!(page_a.next_cursor, 0);
+
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 3, "owner_b should see all their bills");
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_all_paid_other_owner_unpaid() {
+        // owner_a pays all their bills — owner_b's unpaid still isolated correctly
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        let ids_a = setup_bills(&env, &client, &owner_a, 3);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        // owner_a pays all their bills
+        for id in ids_a.iter() {
+            client.pay_bill(&owner_a, &id);
+        }
+
+        // owner_a has zero unpaid
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 0, "owner_a should have no unpaid bills left");
+
+        // owner_b still has 2 unpaid — not polluted by owner_a's paid bills
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 2);
+        for bill in page_b.items.iter() {
+            assert_eq!(bill.owner, owner_b);
+            assert!(!bill.paid);
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_pagination_does_not_leak() {
+        // With many owners, paginating through owner_a's results never leaks owner_b's bills
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner_a);
+
+        // Interleave bills: a, b, a, b, a, b ...
+        for i in 0..4u32 {
+            client.create_bill(
+                &owner_a,
+                &String::from_str(&env, "Bill A"),
+                &(100i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &payee,
+                &token,
+            );
+            client.create_bill(
+                &owner_b,
+                &String::from_str(&env, "Bill B"),
+                &(200i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &payee,
+                &token,
+            );
+        }
+
+        // Paginate through owner_a with small page size
+        let mut all_a_bills: soroban_sdk::Vec<Bill> = soroban_sdk::Vec::new(&env);
+        let mut cursor = 0u32;
+        loop {
+            let page = client.get_unpaid_bills(&owner_a, &cursor, &2);
+            for bill in page.items.iter() {
+                assert_eq!(
+                    bill.owner, owner_a,
+                    "Paginated result must never contain owner_b's bill"
+                );
+                all_a_bills.push_back(bill);
+            }
+            if page.next_cursor == 0 {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(
+            all_a_bills.len(),
+            4,
+            "owner_a should have exactly 4 bills across all pages"
+        );
+    }
+
+    // --- get_overdue_bills ---
+
+    #[test]
+    fn test_get_overdue_bills_not_overdue() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 3);
+        let page = client.get_overdue_bills(&0, &10);
+        assert_eq!(page.count, 0);
+    }
+
+    #[test]
+    fn test_get_overdue_bills_pagination() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        for _ in 0..6u32 {
+            client.create_bill(
+                &owner,
+                &String::from_str(&env, "Overdue Bill"),
+                &100,
+                &0,
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &payee,
+                &token,
+            );
+        }
+
+        env.ledger().set_timestamp(1);
+
+        let page1 = client.get_overdue_bills(&0, &4);
+        assert_eq!(page1.count, 4);
+        assert!(page1.next_cursor > 0);
+
+        let page2 = client.get_overdue_bills(&page1.next_cursor, &4);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_overdue_bills_as_of() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Due after 1, 2 and 3 days.
+        setup_bills(&env, &client, &owner, 3);
+
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+        assert_eq!(
+            client
+                .get_overdue_bills_as_of(&(86400 * 2 + 1), &0, &10)
+                .count,
+            2
+        );
+        assert_eq!(
+            client.get_overdue_bills_as_of(&(86400 * 4), &0, &10).count,
+            3
+        );
+    }
+
+    #[test]
+    fn test_get_overdue_bills_oldest_due_first() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let mut ids = Vec::new(&env);
+        for due_days in [3u64, 1, 2] {
+            ids.push_back(client.create_bill(
+                &owner,
+                &String::from_str(&env, "Bill"),
+                &100,
+                &(86400 * due_days),
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &payee,
+                &token,
+            ));
+        }
+        env.ledger().set_timestamp(86400 * 5);
+
+        let page1 = client.get_overdue_bills(&0, &2);
+        assert_eq!(page1.items.get(0).unwrap().id, ids.get(1).unwrap());
+        assert_eq!(page1.items.get(1).unwrap().id, ids.get(2).unwrap());
+        let page2 = client.get_overdue_bills(&page1.next_cursor, &2);
+        assert_eq!(page2.count, 1);
+        assert_eq!(page2.items.get(0).unwrap().id, ids.get(0).unwrap());
+        assert_eq!(page2.next_cursor, 0);
+
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+        let page = client.get_overdue_bills(&0, &10);
+        assert_eq!(page.count, 2);
+        assert_eq!(page.items.get(0).unwrap().id, ids.get(2).unwrap());
+    }
+
+    #[test]
+    fn test_storage_stats_follow_bill_changes() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Amounts 100, 200 and 300.
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let stats = client.get_storage_stats();
+        assert_eq!(stats.active_bills, 3);
+        assert_eq!(stats.total_unpaid_amount, 600);
+
+        env.ledger().set_timestamp(1000);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        client.cancel_bill(&owner, &ids.get(2).unwrap());
+        let stats = client.get_storage_stats();
+        assert_eq!(stats.active_bills, 2);
+        assert_eq!(stats.total_unpaid_amount, 200);
+
+        client.archive_paid_bills(&owner, &1001, &10);
+        let stats = client.get_storage_stats();
+        assert_eq!(stats.active_bills, 1);
+        assert_eq!(stats.archived_bills, 1);
+        assert_eq!(stats.total_archived_amount, 100);
+        assert_eq!(stats.last_updated, 1000);
+    }
+
+    // --- categories ---
+
+    #[test]
+    fn test_get_totals_by_category() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Amounts 100, 200, 300, 400
+        let ids = setup_bills(&env, &client, &owner, 4);
+        client.set_bill_category(&owner, &ids.get(0).unwrap(), &BillCategory::Utilities);
+        client.set_bill_category(&owner, &ids.get(1).unwrap(), &BillCategory::Utilities);
+        client.set_bill_category(&owner, &ids.get(2).unwrap(), &BillCategory::Education);
+
+        env.ledger().set_timestamp(1_000);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        client.pay_bill(&owner, &ids.get(2).unwrap());
+        client.pay_bill(&owner, &ids.get(3).unwrap());
+        env.ledger().set_timestamp(5_000);
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+
+        let totals = client.get_totals_by_category(&owner, &0, &2_000);
+        assert_eq!(totals.len(), 5);
+        let utilities = totals.get(0).unwrap();
+        assert_eq!(utilities.category, BillCategory::Utilities);
+        assert_eq!((utilities.total, utilities.count), (100, 1));
+        let education = totals.get(1).unwrap();
+        assert_eq!((education.total, education.count), (300, 1));
+        let other = totals.get(4).unwrap();
+        assert_eq!((other.total, other.count), (400, 1));
+
+        let totals = client.get_totals_by_category(&owner, &0, &10_000);
+        assert_eq!(totals.get(0).unwrap().total, 300);
+    }
+
+    #[test]
+    fn test_category_carried_to_next_occurrence() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().category,
+            BillCategory::Other
+        );
+        client.set_bill_category(&owner, &bill_id, &BillCategory::Housing);
+        client.pay_bill(&owner, &bill_id);
+
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.category, BillCategory::Housing);
+    }
+
+    // --- category budgets ---
+
+    /// Number of Remitwise events emitted so far with `action`.
+    fn count_events(env: &Env, action: Symbol) -> usize {
+        env.events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                topics
+                    .get(3)
+                    .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+                    == Some(action.clone())
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_budget_exceeded_event_and_status() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Amounts 100, 200, 300
+        let ids = setup_bills(&env, &client, &owner, 3);
+        for id in ids.iter() {
+            client.set_bill_category(&owner, &id, &BillCategory::Utilities);
+        }
+        client.set_category_budget(&owner, &BillCategory::Utilities, &250);
+
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        assert_eq!(count_events(&env, symbol_short!("budget_ex")), 0);
+        let status = client.get_budget_status(&owner, &BillCategory::Utilities);
+        assert_eq!(
+            (status.limit, status.spent, status.remaining),
+            (250, 100, 150)
+        );
+        assert!(!status.exceeded);
+
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+        assert_eq!(count_events(&env, symbol_short!("budget_ex")), 1);
+        let status = client.get_budget_status(&owner, &BillCategory::Utilities);
+        assert_eq!(status.spent, 300);
+        assert!(status.exceeded);
+
+        // Only the payment that crosses the limit raises the event.
+        client.pay_bill(&owner, &ids.get(2).unwrap());
+        assert_eq!(count_events(&env, symbol_short!("budget_ex")), 1);
+    }
+
+    #[test]
+    fn test_budget_resets_each_period() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        client.set_category_budget(&owner, &BillCategory::Other, &150);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        assert_eq!(
+            client.get_budget_status(&owner, &BillCategory::Other).spent,
+            100
+        );
+
+        env.ledger().set_timestamp(BUDGET_PERIOD_SECS);
+        let status = client.get_budget_status(&owner, &BillCategory::Other);
+        assert_eq!(status.spent, 0);
+        assert_eq!(status.period_start, BUDGET_PERIOD_SECS);
+
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+        let status = client.get_budget_status(&owner, &BillCategory::Other);
+        assert_eq!(status.spent, 200);
+        assert!(status.exceeded);
+    }
+
+    // --- recurrence base ---
+
+    #[test]
+    fn test_next_due_from_payment_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Due on day 30, monthly; paid late on day 37.
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        client.set_recurrence_base(&owner, &bill_id, &RecurrenceBase::PaymentDate);
+        env.ledger().set_timestamp(37 * 86400);
+        client.pay_bill(&owner, &bill_id);
+
+        let next = client
+            .get_unpaid_bills(&owner, &0, &10)
+            .items
+            .get(0)
+            .unwrap();
+        assert_eq!(next.due_date, 67 * 86400);
+        assert_eq!(next.recurrence_base, RecurrenceBase::PaymentDate);
+    }
+
+    #[test]
+    fn test_next_due_from_due_date_by_default() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        env.ledger().set_timestamp(37 * 86400);
+        client.pay_bill(&owner, &bill_id);
+
+        let next = client
+            .get_unpaid_bills(&owner, &0, &10)
+            .items
+            .get(0)
+            .unwrap();
+        assert_eq!(next.due_date, 60 * 86400);
+    }
+
+    #[test]
+    fn test_payment_date_base_respects_end_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        client.set_recurrence_base(&owner, &bill_id, &RecurrenceBase::PaymentDate);
+        client.set_series_end(&owner, &bill_id, &None, &Some(65 * 86400));
+
+        // Paid on time the next bill (day 60) fits; paid late (day 67) it
+        // would fall after the end date.
+        env.ledger().set_timestamp(37 * 86400);
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 0);
+    }
+
+    #[test]
+    fn test_set_recurrence_base_requires_recurring() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let result = client.try_set_recurrence_base(&owner, &bill_id, &RecurrenceBase::PaymentDate);
+        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+    }
+
+    // --- suspending recurring series ---
+
+    #[test]
+    fn test_suspended_series_is_held_and_resumes_on_cadence() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        client.suspend_recurring(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().suspended);
+        assert_eq!(
+            client.try_pay_bill(&owner, &bill_id),
+            Err(Ok(Error::BillSuspended))
+        );
+
+        // Two months pass; the held bill is not reported overdue.
+        env.ledger().set_timestamp(86400 * 75);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+
+        client.resume_recurring(&owner, &bill_id);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.suspended);
+        assert_eq!(bill.due_date, 86400 * 90);
+        assert_eq!(bill.occurrence, 1);
+
+        client.pay_bill(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(count_events(&env, symbol_short!("suspended")), 1);
+        assert_eq!(count_events(&env, symbol_short!("resumed")), 1);
+    }
+
+    #[test]
+    fn test_suspend_recurring_requires_recurring_owner() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let one_off = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        assert_eq!(
+            client.try_suspend_recurring(&owner, &one_off),
+            Err(Ok(Error::InvalidFrequency))
+        );
+
+        let monthly = create_monthly_bill(&env, &client, &owner);
+        assert_eq!(
+            client.try_suspend_recurring(&other, &monthly),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            client.try_resume_recurring(&other, &monthly),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    // --- recurring series end conditions ---
+
+    #[test]
+    fn test_series_stops_after_max_occurrences() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let first = create_monthly_bill(&env, &client, &owner);
+        client.set_series_end(&owner, &first, &Some(2), &None);
+
+        client.pay_bill(&owner, &first);
+        let second = client.get_bill(&(first + 1)).unwrap();
+        assert_eq!(second.occurrence, 2);
+
+        client.pay_bill(&owner, &second.id);
+        assert!(client.get_bill(&(first + 2)).is_none());
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+    }
+
+    #[test]
+    fn test_series_stops_at_end_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let first = create_monthly_bill(&env, &client, &owner);
+        // Due on day 30 and 60; day 90 is past the end date.
+        client.set_series_end(&owner, &first, &None, &Some(86400 * 75));
+
+        client.pay_bill(&owner, &first);
+        client.pay_bill(&owner, &(first + 1));
+        assert!(client.get_bill(&(first + 2)).is_none());
+    }
+
+    #[test]
+    fn test_skip_next_occurrence() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        client.set_series_end(&owner, &bill_id, &Some(2), &None);
+
+        client.pay_bill_partial(&owner, &bill_id, &20);
+        client.skip_next_occurrence(&owner, &bill_id);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.due_date, 86400 * 60);
+        assert_eq!(bill.occurrence, 2);
+        assert_eq!(bill.amount_paid, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 50);
+
+        // Skipping the last occurrence ends the series.
+        client.skip_next_occurrence(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).is_none());
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+    }
+
+    #[test]
+    fn test_series_end_requires_recurring_bill() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let result = client.try_set_series_end(&owner, &ids.get(0).unwrap(), &Some(3), &None);
+        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+        let result = client.try_skip_next_occurrence(&owner, &ids.get(0).unwrap());
+        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+    }
+
+    // --- autopay ---
+
+    #[test]
+    fn test_execute_autopay_pays_due_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let bill = client.get_bill(&ids.get(0).unwrap()).unwrap();
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &1_000,
+            &(env.ledger().sequence() + 1000),
+        );
+        assert_eq!(client.get_autopay_allowance(&owner, &bill.token), 1_000);
+
+        client.set_autopay(&owner, &ids.get(0).unwrap(), &true);
+        client.set_autopay(&owner, &ids.get(2).unwrap(), &true);
+
+        // Only the first bill is due after two days.
+        env.ledger().set_timestamp(86400 * 2);
+        assert_eq!(client.execute_autopay(&10), 1);
+        assert!(client.get_bill(&ids.get(0).unwrap()).unwrap().paid);
+        assert!(!client.get_bill(&ids.get(1).unwrap()).unwrap().paid);
+        assert!(!client.get_bill(&ids.get(2).unwrap()).unwrap().paid);
+
+        env.ledger().set_timestamp(86400 * 3);
+        assert_eq!(client.execute_autopay(&10), 1);
+        assert!(client.get_bill(&ids.get(2).unwrap()).unwrap().paid);
+        assert_eq!(
+            TokenClient::new(&env, &bill.token).balance(&payee_of(&bill)),
+            400
+        );
+        assert_eq!(client.get_autopay_allowance(&owner, &bill.token), 600);
+    }
+
+    #[test]
+    fn test_execute_autopay_skips_bill_over_allowance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let bill = client.get_bill(&ids.get(0).unwrap()).unwrap();
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &150,
+            &(env.ledger().sequence() + 1000),
+        );
+        client.set_autopay(&owner, &ids.get(0).unwrap(), &true);
+        client.set_autopay(&owner, &ids.get(1).unwrap(), &true);
+
+        env.ledger().set_timestamp(86400 * 5);
+        // 100 fits in the allowance, the 200 bill does not.
+        assert_eq!(client.execute_autopay(&10), 1);
+        assert!(client.get_bill(&ids.get(0).unwrap()).unwrap().paid);
+        assert!(!client.get_bill(&ids.get(1).unwrap()).unwrap().paid);
+        assert_eq!(client.get_total_unpaid(&owner), 200);
+    }
+
+    // --- scheduled payments ---
+
+    #[test]
+    fn test_scheduled_payment_runs_once_due() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let bill = client.get_bill(&ids.get(1).unwrap()).unwrap();
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &1_000,
+            &(env.ledger().sequence() + 1000),
+        );
+        client.schedule_payment(&owner, &bill.id, &(86400 * 2));
+        assert_eq!(
+            client.get_scheduled_payment(&bill.id).unwrap().execute_at,
+            86400 * 2
+        );
+
+        env.ledger().set_timestamp(86400);
+        assert_eq!(client.execute_scheduled(&10), 0);
+        assert!(!client.get_bill(&bill.id).unwrap().paid);
+
+        env.ledger().set_timestamp(86400 * 2);
+        assert_eq!(client.execute_scheduled(&10), 1);
+        assert!(client.get_bill(&bill.id).unwrap().paid);
+        assert!(client.get_scheduled_payment(&bill.id).is_none());
+        assert_eq!(
+            TokenClient::new(&env, &bill.token).balance(&payee_of(&bill)),
+            200
+        );
+        assert_eq!(client.execute_scheduled(&10), 0);
+    }
+
+    #[test]
+    fn test_cancel_scheduled_payment() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &1_000,
+            &(env.ledger().sequence() + 1000),
+        );
+        client.schedule_payment(&owner, &bill_id, &100);
+        assert_eq!(
+            client.try_schedule_payment(&owner, &bill_id, &200),
+            Err(Ok(Error::PaymentAlreadyScheduled))
+        );
+        assert_eq!(
+            client.try_cancel_scheduled_payment(&other, &bill_id),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        client.cancel_scheduled_payment(&owner, &bill_id);
+        env.ledger().set_timestamp(100);
+        assert_eq!(client.execute_scheduled(&10), 0);
+        assert!(!client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(
+            client.try_cancel_scheduled_payment(&owner, &bill_id),
+            Err(Ok(Error::ScheduledPaymentNotFound))
+        );
+    }
+
+    #[test]
+    fn test_schedule_payment_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(
+            client.try_schedule_payment(&owner, &bill_id, &100),
+            Err(Ok(Error::InsufficientAllowance))
+        );
+
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &100,
+            &(env.ledger().sequence() + 1000),
+        );
+        env.ledger().set_timestamp(500);
+        assert_eq!(
+            client.try_schedule_payment(&owner, &bill_id, &500),
+            Err(Ok(Error::InvalidExecutionTime))
+        );
+
+        // Canceling the bill drops its schedule.
+        client.schedule_payment(&owner, &bill_id, &600);
+        client.cancel_bill(&owner, &bill_id);
+        assert!(client.get_scheduled_payment(&bill_id).is_none());
+    }
+
+    #[test]
+    fn test_failed_scheduled_payment_is_dropped() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &100,
+            &(env.ledger().sequence() + 1000),
+        );
+        client.schedule_payment(&owner, &bill_id, &100);
+        client.grant_autopay_allowance(&owner, &bill.token, &0, &(env.ledger().sequence() + 1000));
+
+        env.ledger().set_timestamp(100);
+        assert_eq!(client.execute_scheduled(&10), 0);
+        assert_eq!(count_events(&env, symbol_short!("sched_run")), 1);
+        assert!(client.get_scheduled_payment(&bill_id).is_none());
+        assert!(!client.get_bill(&bill_id).unwrap().paid);
+    }
+
+    // --- due reminders ---
+
+    #[test]
+    fn test_emit_due_reminders_once_per_bill() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Due after one, two and three days.
+        let ids = setup_bills(&env, &client, &owner, 3);
+        assert_eq!(client.emit_due_reminders(&(2 * 86400), &10), 2);
+        assert_eq!(count_events(&env, symbol_short!("reminder")), 2);
+
+        let (_, _, data) = env.events().all().last().unwrap();
+        let reminder = DueReminder::try_from_val(&env, &data).unwrap();
+        assert_eq!(reminder.bill_id, ids.get(1).unwrap());
+        assert_eq!(reminder.owner, owner);
+        assert_eq!(reminder.amount_due, 200);
+
+        assert_eq!(client.emit_due_reminders(&(2 * 86400), &10), 0);
+
+        env.ledger().set_timestamp(86400);
+        assert_eq!(client.emit_due_reminders(&(2 * 86400), &10), 1);
+        assert!(client.get_bill(&ids.get(2).unwrap()).unwrap().reminder_sent);
+    }
+
+    #[test]
+    fn test_emit_due_reminders_respects_limit_and_rearms() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        assert_eq!(client.emit_due_reminders(&(7 * 86400), &1), 1);
+        assert_eq!(client.emit_due_reminders(&(7 * 86400), &10), 2);
+
+        // Moving the due date re-arms the reminder.
+        let first_id = ids.get(0).unwrap();
+        client.update_bill(&owner, &first_id, &100, &(4 * 86400));
+        assert_eq!(client.emit_due_reminders(&(7 * 86400), &10), 1);
+
+        // Paid bills are never reminded.
+        client.update_bill(&owner, &first_id, &100, &(5 * 86400));
+        client.pay_bill(&owner, &first_id);
+        assert_eq!(client.emit_due_reminders(&(7 * 86400), &10), 0);
+    }
+
+    #[test]
+    fn test_emit_due_reminders_each_recurring_occurrence() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        env.ledger().set_timestamp(29 * 86400);
+        assert_eq!(client.emit_due_reminders(&(2 * 86400), &10), 1);
+
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(client.emit_due_reminders(&(2 * 86400), &10), 0);
+        env.ledger().set_timestamp(59 * 86400);
+        assert_eq!(client.emit_due_reminders(&(2 * 86400), &10), 1);
+    }
+
+    #[test]
+    fn test_emit_due_reminders_soonest_due_first() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Due after one, two and three days; move the first one last.
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let first_id = ids.get(0).unwrap();
+        client.update_bill(&owner, &first_id, &100, &(4 * 86400));
+
+        assert_eq!(client.emit_due_reminders(&(7 * 86400), &1), 1);
+        assert!(client.get_bill(&ids.get(1).unwrap()).unwrap().reminder_sent);
+        assert!(!client.get_bill(&first_id).unwrap().reminder_sent);
+    }
+
+    // --- shared bills ---
+
+    #[test]
+    fn test_split_bill_paid_when_all_shares_settled() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        // 300 split 60/40
+        let bill_id = ids.get(2).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        StellarAssetClient::new(&env, &bill.token).mint(&sibling, &1_000);
+
+        let mut shares = Vec::new(&env);
+        shares.push_back((owner.clone(), 6_000u32));
+        shares.push_back((sibling.clone(), 4_000u32));
+        client.split_bill(&owner, &bill_id, &shares);
+
+        let recorded = client.get_bill_shares(&bill_id);
+        assert_eq!(recorded.get(0).unwrap().amount, 180);
+        assert_eq!(recorded.get(1).unwrap().amount, 120);
+
+        client.pay_share(&sibling, &bill_id);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.amount_paid, 120);
+        assert_eq!(TokenClient::new(&env, &bill.token).balance(&sibling), 880);
+
+        let result = client.try_pay_share(&sibling, &bill_id);
+        assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
+
+        client.pay_share(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert!(client
+            .get_bill_shares(&bill_id)
+            .iter()
+            .all(|share| share.paid));
+        assert_eq!(client.get_total_unpaid(&owner), 100 + 200);
+    }
+
+    #[test]
+    fn test_split_bill_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let outsider = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+
+        let mut uneven = Vec::new(&env);
+        uneven.push_back((owner.clone(), 5_000u32));
+        uneven.push_back((sibling.clone(), 4_000u32));
+        let result = client.try_split_bill(&owner, &bill_id, &uneven);
+        assert_eq!(result, Err(Ok(Error::InvalidShares)));
+
+        let mut duplicate = Vec::new(&env);
+        duplicate.push_back((owner.clone(), 5_000u32));
+        duplicate.push_back((owner.clone(), 5_000u32));
+        let result = client.try_split_bill(&owner, &bill_id, &duplicate);
+        assert_eq!(result, Err(Ok(Error::InvalidShares)));
+
+        let mut shares = Vec::new(&env);
+        shares.push_back((owner.clone(), 5_000u32));
+        shares.push_back((sibling.clone(), 5_000u32));
+        client.split_bill(&owner, &bill_id, &shares);
+
+        let result = client.try_pay_bill(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::BillIsSplit)));
+        let result = client.try_pay_share(&outsider, &bill_id);
+        assert_eq!(result, Err(Ok(Error::ShareNotFound)));
+    }
+
+    // --- receipts ---
+
+    #[test]
+    fn test_receipt_recorded_for_each_payment() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        env.ledger().set_timestamp(500);
+        client.pay_bill_partial(&owner, &ids.get(0).unwrap(), &40);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+
+        let receipts = client.get_receipts(&owner, &0, &10);
+        assert_eq!(receipts.len(), 3);
+
+        let first = receipts.get(0).unwrap();
+        assert_eq!(first.bill_id, ids.get(0).unwrap());
+        assert_eq!(first.payer, owner);
+        assert_eq!(first.amount, 40);
+        assert_eq!(first.timestamp, 500);
+        assert!(!first.bill.paid);
+
+        let second = receipts.get(1).unwrap();
+        assert_eq!(second.amount, 60);
+        assert!(second.bill.paid);
+        assert_eq!(BillPayee::Address(second.payee), second.bill.payee);
+        assert_eq!(
+            client.get_receipt(&second.id).unwrap().bill_id,
+            first.bill_id
+        );
+    }
+
+    #[test]
+    fn test_get_receipts_offset_and_owner_isolation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 4);
+        client.batch_pay_bills(&owner, &ids);
+        let other_ids = setup_bills(&env, &client, &other, 1);
+        client.pay_bill(&other, &other_ids.get(0).unwrap());
+
+        let page = client.get_receipts(&owner, &1, &2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().bill_id, ids.get(1).unwrap());
+        assert_eq!(page.get(1).unwrap().bill_id, ids.get(2).unwrap());
+
+        assert_eq!(client.get_receipts(&owner, &4, &10).len(), 0);
+        assert_eq!(client.get_receipts(&other, &0, &10).len(), 1);
+    }
+
+    // --- payment statistics ---
+
+    #[test]
+    fn test_payment_stats_on_time_and_late() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Due after one to four days.
+        let ids = setup_bills(&env, &client, &owner, 4);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        client.pay_bill_partial(&owner, &ids.get(1).unwrap(), &50);
+
+        // Bill 3 (due day 3) settled 1 second late: 1 day late.
+        env.ledger().set_timestamp(86400 * 3 + 1);
+        client.pay_bill(&owner, &ids.get(2).unwrap());
+        // Bill 2 (due day 2) settled on day 4 plus an hour: 3 days late.
+        env.ledger().set_timestamp(86400 * 4 + 3600);
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+
+        let stats = client.get_payment_stats(&owner, &0, &u64::MAX);
+        assert_eq!(stats.bills_paid, 3);
+        assert_eq!(stats.total_paid, 600);
+        assert_eq!(stats.on_time_pct, 33);
+        assert_eq!(stats.avg_days_late, 2);
+
+        // Only the first payment and the partial fall in day 0.
+        let stats = client.get_payment_stats(&owner, &0, &86400);
+        assert_eq!(stats.bills_paid, 1);
+        assert_eq!(stats.total_paid, 150);
+        assert_eq!(stats.on_time_pct, 100);
+        assert_eq!(stats.avg_days_late, 0);
+
+        // Only the two late settlements fall from day 3 on.
+        let stats = client.get_payment_stats(&owner, &(86400 * 3), &u64::MAX);
+        assert_eq!(stats.bills_paid, 2);
+        assert_eq!(stats.total_paid, 450);
+        assert_eq!(stats.on_time_pct, 0);
+    }
+
+    #[test]
+    fn test_payment_stats_empty() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let stats = client.get_payment_stats(&owner, &0, &u64::MAX);
+        assert_eq!(stats.bills_paid, 0);
+        assert_eq!(stats.total_paid, 0);
+        assert_eq!(stats.on_time_pct, 0);
+    }
+
+    // --- delegated payers ---
+
+    #[test]
+    fn test_delegated_payer_for_listed_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let relative = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let first = client.get_bill(&ids.get(0).unwrap()).unwrap();
+        StellarAssetClient::new(&env, &first.token).mint(&relative, &1_000);
+
+        assert_eq!(
+            client.try_pay_bill(&relative, &first.id),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.grant_payer(
+            &owner,
+            &relative,
+            &PayerScope::Bills(Vec::from_array(&env, [first.id])),
+        );
+        client.pay_bill(&relative, &first.id);
+        assert!(client.get_bill(&first.id).unwrap().paid);
+        assert_eq!(TokenClient::new(&env, &first.token).balance(&relative), 900);
+        assert_eq!(
+            client.try_pay_bill(&relative, &ids.get(1).unwrap()),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_delegated_payer_cap_and_revoke() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payer = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let token = client.get_bill(&ids.get(0).unwrap()).unwrap().token;
+        StellarAssetClient::new(&env, &token).mint(&payer, &1_000);
+
+        client.grant_payer(&owner, &payer, &PayerScope::UpTo(450));
+        client.pay_bill(&payer, &ids.get(0).unwrap());
+        client.pay_bill(&payer, &ids.get(1).unwrap());
+        assert_eq!(client.get_payer_spent(&owner, &payer), 300);
+        // The 300 bill would take the total past the cap, 150 of it would not.
+        assert_eq!(
+            client.try_pay_bill(&payer, &ids.get(2).unwrap()),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.pay_bill_partial(&payer, &ids.get(2).unwrap(), &150);
+        assert_eq!(
+            client.try_pay_bill_partial(&payer, &ids.get(2).unwrap(), &1),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // A new grant starts the cap afresh.
+        client.grant_payer(&owner, &payer, &PayerScope::UpTo(450));
+        assert_eq!(client.get_payer_spent(&owner, &payer), 0);
+
+        client.revoke_payer(&owner, &payer);
+        assert!(client.get_payer_scope(&owner, &payer).is_none());
+        assert_eq!(
+            client.try_pay_bill_partial(&payer, &ids.get(2).unwrap(), &150),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            client.try_revoke_payer(&owner, &payer),
+            Err(Ok(Error::PayerNotFound))
+        );
+    }
+
+    #[test]
+    fn test_delegated_payer_cap_includes_late_fee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payer = Address::generate(&env);
+
+        // Due after one day, amount 100.
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let token = client.get_bill(&bill_id).unwrap().token;
+        StellarAssetClient::new(&env, &token).mint(&payer, &1_000);
+        client.set_late_fee_policy(&owner, &bill_id, &LateFeePolicy::Flat(15));
+        client.grant_payer(&owner, &payer, &PayerScope::UpTo(110));
+
+        env.ledger().set_timestamp(2 * 86400);
+        assert_eq!(
+            client.try_pay_bill(&payer, &bill_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.grant_payer(&owner, &payer, &PayerScope::UpTo(115));
+        client.pay_bill(&payer, &bill_id);
+        assert_eq!(client.get_payer_spent(&owner, &payer), 115);
+    }
+
+    #[test]
+    fn test_grant_payer_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let payer = Address::generate(&env);
+
+        let other_bill = setup_bills(&env, &client, &other, 1).get(0).unwrap();
+        assert_eq!(
+            client.try_grant_payer(&owner, &owner, &PayerScope::UpTo(100)),
+            Err(Ok(Error::InvalidPayerScope))
+        );
+        assert_eq!(
+            client.try_grant_payer(&owner, &payer, &PayerScope::UpTo(0)),
+            Err(Ok(Error::InvalidPayerScope))
+        );
+        assert_eq!(
+            client.try_grant_payer(&owner, &payer, &PayerScope::Bills(Vec::new(&env))),
+            Err(Ok(Error::InvalidPayerScope))
+        );
+        assert_eq!(
+            client.try_grant_payer(
+                &owner,
+                &payer,
+                &PayerScope::Bills(Vec::from_array(&env, [other_bill]))
+            ),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    // --- escrow ---
+
+    #[test]
+    fn test_escrowed_payment_released_on_confirmation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill = client
+            .get_bill(&setup_bills(&env, &client, &owner, 1).get(0).unwrap())
+            .unwrap();
+        let token = TokenClient::new(&env, &bill.token);
+        client.set_escrow_window(&owner, &bill.id, &86400);
+        client.pay_bill(&owner, &bill.id);
+
+        assert!(client.get_bill(&bill.id).unwrap().paid);
+        assert_eq!(token.balance(&cid), 100);
+        assert_eq!(token.balance(&payee_of(&bill)), 0);
+        let hold = client.get_escrow(&bill.id).unwrap();
+        assert_eq!(hold.payer, owner);
+        assert_eq!(hold.release_by, 86400);
+        assert_eq!(
+            client.try_reclaim_escrow(&owner, &bill.id),
+            Err(Ok(Error::EscrowWindowOpen))
+        );
+        assert_eq!(
+            client.try_confirm_receipt(&owner, &bill.id),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        env.ledger().set_timestamp(86400);
+        assert_eq!(client.confirm_receipt(&payee_of(&bill), &bill.id), 100);
+        assert_eq!(token.balance(&payee_of(&bill)), 100);
+        assert_eq!(token.balance(&cid), 0);
+        assert!(client.get_escrow(&bill.id).is_none());
+        assert_eq!(count_events(&env, symbol_short!("released")), 1);
+    }
+
+    #[test]
+    fn test_unconfirmed_escrow_is_reclaimed_and_bill_reopened() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill = client
+            .get_bill(&setup_bills(&env, &client, &owner, 1).get(0).unwrap())
+            .unwrap();
+        let token = TokenClient::new(&env, &bill.token);
+        let start = token.balance(&owner);
+        client.set_escrow_window(&owner, &bill.id, &86400);
+        client.pay_bill(&owner, &bill.id);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+
+        env.ledger().set_timestamp(86401);
+        assert_eq!(
+            client.try_confirm_receipt(&payee_of(&bill), &bill.id),
+            Err(Ok(Error::EscrowWindowClosed))
+        );
+        assert_eq!(client.reclaim_escrow(&owner, &bill.id), 100);
+        assert_eq!(token.balance(&owner), start);
+
+        let reopened = client.get_bill(&bill.id).unwrap();
+        assert!(!reopened.paid);
+        assert_eq!(reopened.amount_paid, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 100);
+        assert!(client.get_receipt(&1).unwrap().refunded);
+        assert_eq!(
+            client.try_reclaim_escrow(&owner, &bill.id),
+            Err(Ok(Error::EscrowNotFound))
+        );
+    }
+
+    #[test]
+    fn test_recurring_bill_cannot_be_escrowed() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        let result = client.try_set_escrow_window(&owner, &bill_id, &86400);
+        assert_eq!(result, Err(Ok(Error::EscrowNotSupported)));
+
+        // Paying the bill creates exactly one next occurrence.
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 1);
+        assert!(client.get_escrow(&bill_id).is_none());
+    }
+
+    #[test]
+    fn test_escrowed_bill_requires_full_payment() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        client.set_escrow_window(&owner, &bill_id, &86400);
+        assert_eq!(
+            client.try_pay_bill_partial(&owner, &bill_id, &40),
+            Err(Ok(Error::EscrowRequiresFullPayment))
+        );
+    }
+
+    // --- bills balance ---
+
+    #[test]
+    fn test_bills_paid_from_deposited_balance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let bill = client.get_bill(&ids.get(0).unwrap()).unwrap();
+        let token = TokenClient::new(&env, &bill.token);
+        let start = token.balance(&owner);
+
+        assert_eq!(client.deposit_allocation(&owner, &bill.token, &250), 250);
+        assert_eq!(client.get_bills_balance(&owner, &bill.token), 250);
+        assert_eq!(token.balance(&cid), 250);
+        assert_eq!(token.balance(&owner), start - 250);
+
+        client.pay_bill_from_balance(&owner, &bill.id);
+        assert!(client.get_bill(&bill.id).unwrap().paid);
+        assert_eq!(token.balance(&payee_of(&bill)), 100);
+        assert_eq!(client.get_bills_balance(&owner, &bill.token), 150);
+
+        // The 200 bill is not covered by what is left.
+        assert_eq!(
+            client.try_pay_bill_from_balance(&owner, &ids.get(1).unwrap()),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        assert!(!client.get_bill(&ids.get(1).unwrap()).unwrap().paid);
+        assert_eq!(client.get_bills_balance(&owner, &bill.token), 150);
+    }
+
+    #[test]
+    fn test_withdraw_allocation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        client.deposit_allocation(&owner, &token, &100);
+        assert_eq!(
+            client.try_withdraw_allocation(&owner, &token, &101),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        assert_eq!(client.withdraw_allocation(&owner, &token, &40), 60);
+        assert_eq!(client.withdraw_allocation(&owner, &token, &60), 0);
+        assert_eq!(client.get_bills_balance(&owner, &token), 0);
+        assert_eq!(TokenClient::new(&env, &token).balance(&cid), 0);
+        assert_eq!(count_events(&env, symbol_short!("withdraw")), 2);
+    }
+
+    #[test]
+    fn test_withdraw_allocation_blocked_while_paused() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        client.deposit_allocation(&owner, &token, &100);
+        client.set_pause_admin(&owner, &owner);
+        client.pause(&owner);
+        assert_eq!(
+            client.try_withdraw_allocation(&owner, &token, &40),
+            Err(Ok(Error::ContractPaused))
+        );
+        assert_eq!(client.get_bills_balance(&owner, &token), 100);
+    }
+
+    #[test]
+    fn test_deposit_allocation_rejects_non_positive_amount() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        assert_eq!(
+            client.try_deposit_allocation(&owner, &token, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(client.get_bills_balance(&owner, &token), 0);
+    }
+
+    // --- refunds ---
+
+    #[test]
+    fn test_refund_bill_returns_funds_and_reopens_bill() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        let token = TokenClient::new(&env, &bill.token);
+        let start_balance = token.balance(&owner);
+
+        client.pay_bill_partial(&owner, &bill_id, &30);
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(token.balance(&payee_of(&bill)), 100);
+
+        assert_eq!(client.refund_bill(&payee_of(&bill), &bill_id), 100);
+        assert_eq!(token.balance(&payee_of(&bill)), 0);
+        assert_eq!(token.balance(&owner), start_balance);
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.amount_paid, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 100);
+        assert!(client
+            .get_receipts(&owner, &0, &10)
+            .iter()
+            .all(|r| r.refunded));
+
+        let result = client.try_refund_bill(&payee_of(&bill), &bill_id);
+        assert_eq!(result, Err(Ok(Error::NothingToRefund)));
+    }
+
+    #[test]
+    fn test_refund_bill_rolls_back_spend_and_plan() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        client.set_bill_category(&owner, &bill_id, &BillCategory::Utilities);
+        client.create_installment_plan(&owner, &bill_id, &2, &30);
+        client.pay_installment(&owner, &bill_id);
+        client.pay_installment(&owner, &bill_id);
+        let status = client.get_budget_status(&owner, &BillCategory::Utilities);
+        assert_eq!(status.spent, 100);
+
+        client.refund_bill(&payee_of(&bill), &bill_id);
+        let status = client.get_budget_status(&owner, &BillCategory::Utilities);
+        assert_eq!(status.spent, 0);
+        assert!(client.get_installment_plan(&bill_id).is_none());
+        client.pay_bill(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+    }
+
+    #[test]
+    fn test_refund_rejected_for_split_and_spawned_recurring_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let recurring_id = create_monthly_bill(&env, &client, &owner);
+        let recurring = client.get_bill(&recurring_id).unwrap();
+        client.pay_bill(&owner, &recurring_id);
+        let result = client.try_refund_bill(&payee_of(&recurring), &recurring_id);
+        assert_eq!(result, Err(Ok(Error::RefundNotSupported)));
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let split_id = ids.get(0).unwrap();
+        let split = client.get_bill(&split_id).unwrap();
+        let mut shares = Vec::new(&env);
+        shares.push_back((owner.clone(), 10_000u32));
+        client.split_bill(&owner, &split_id, &shares);
+        client.pay_share(&owner, &split_id);
+        let result = client.try_refund_bill(&payee_of(&split), &split_id);
+        assert_eq!(result, Err(Ok(Error::BillIsSplit)));
+    }
+
+    #[test]
+    fn test_refund_bill_authorization() {
+        let env = make_env();
+        // The payee signs the transfer back even when the admin calls.
+        env.mock_all_auths_allowing_non_root_auth();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        client.pay_bill(&owner, &bill_id);
+
+        let result = client.try_refund_bill(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.set_pause_admin(&admin, &admin);
+        assert_eq!(client.refund_bill(&admin, &bill_id), 100);
+    }
+
+    #[test]
+    fn test_refund_bill_blocked_while_paused_or_disputed() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        let payee = payee_of(&client.get_bill(&bill_id).unwrap());
+        client.pay_bill_partial(&owner, &bill_id, &30);
+
+        client.dispute_bill(&owner, &bill_id, &BytesN::from_array(&env, &[3u8; 32]));
+        let result = client.try_refund_bill(&payee, &bill_id);
+        assert_eq!(result, Err(Ok(Error::BillDisputed)));
+        client.resolve_dispute(&payee, &bill_id, &DisputeOutcome::Upheld);
+
+        client.set_pause_admin(&owner, &owner);
+        client.pause_function(&owner, &pause_functions::PAY_BILL);
+        let result = client.try_refund_bill(&payee, &bill_id);
+        assert_eq!(result, Err(Ok(Error::FunctionPaused)));
+
+        client.unpause_function(&owner, &pause_functions::PAY_BILL);
+        assert_eq!(client.refund_bill(&payee, &bill_id), 30);
+    }
+
+    // --- disputes ---
+
+    #[test]
+    fn test_disputed_bill_is_frozen_until_upheld() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        let reason = BytesN::from_array(&env, &[7u8; 32]);
+
+        client.dispute_bill(&owner, &bill_id, &reason);
+        let dispute = client.get_dispute(&bill_id).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Open);
+        assert_eq!(dispute.reason_hash, reason);
+
+        let result = client.try_pay_bill(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::BillDisputed)));
+
+        // Frozen bills are skipped by autopay.
+        client.grant_autopay_allowance(
+            &owner,
+            &bill.token,
+            &1_000,
+            &(env.ledger().sequence() + 100),
+        );
+        client.set_autopay(&owner, &bill_id, &true);
+        env.ledger().set_timestamp(86400 * 2);
+        assert_eq!(client.execute_autopay(&10), 0);
+
+        client.resolve_dispute(&payee_of(&bill), &bill_id, &DisputeOutcome::Upheld);
+        assert_eq!(
+            client.get_dispute(&bill_id).unwrap().status,
+            DisputeStatus::Upheld
+        );
+        client.pay_bill(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+    }
+
+    #[test]
+    fn test_cancelled_dispute_removes_bill() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let bill_id = ids.get(1).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        client.dispute_bill(&owner, &bill_id, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let result = client.try_resolve_dispute(&owner, &bill_id, &DisputeOutcome::Cancelled);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.resolve_dispute(&payee_of(&bill), &bill_id, &DisputeOutcome::Cancelled);
+        assert!(client.get_bill(&bill_id).is_none());
+        assert_eq!(client.get_total_unpaid(&owner), 100);
+
+        let result =
+            client.try_resolve_dispute(&payee_of(&bill), &bill_id, &DisputeOutcome::Upheld);
+        assert_eq!(result, Err(Ok(Error::DisputeNotFound)));
+    }
+
+    // --- late fees ---
+
+    #[test]
+    fn test_flat_late_fee_collected_on_payment() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        client.set_late_fee_policy(&owner, &bill_id, &LateFeePolicy::Flat(15));
+
+        assert_eq!(client.get_amount_due(&bill_id), 100);
+        env.ledger().set_timestamp(86400 + 1);
+        assert_eq!(client.get_amount_due(&bill_id), 115);
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(
+            TokenClient::new(&env, &bill.token).balance(&payee_of(&bill)),
+            115
+        );
+        assert_eq!(client.get_amount_due(&bill_id), 0);
+    }
+
+    #[test]
+    fn test_bps_late_fee_accrues_per_day() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &10_000,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &payee,
+            &token,
+        );
+        // 0.5% per day
+        client.set_late_fee_policy(&owner, &bill_id, &LateFeePolicy::BpsPerDay(50));
+
+        env.ledger().set_timestamp(86400 + 86400 * 3);
+        assert_eq!(client.get_amount_due(&bill_id), 10_150);
+
+        // Partial payments reduce the balance the fee accrues on.
+        client.pay_bill_partial(&owner, &bill_id, &6_000);
+        assert_eq!(client.get_amount_due(&bill_id), 4_060);
+
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(TokenClient::new(&env, &token).balance(&payee), 10_060);
+    }
+
+    #[test]
+    fn test_set_late_fee_policy_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+
+        let result = client.try_set_late_fee_policy(&owner, &bill_id, &LateFeePolicy::Flat(-1));
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        let result =
+            client.try_set_late_fee_policy(&owner, &bill_id, &LateFeePolicy::BpsPerDay(10_001));
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        let result = client.try_set_late_fee_policy(&other, &bill_id, &LateFeePolicy::Flat(5));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    // --- get_bills_due_between ---
+
+    #[test]
+    fn test_get_bills_due_between() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        // Due after 1..=5 days.
+        let ids = setup_bills(&env, &client, &owner, 5);
+        setup_bills(&env, &client, &other, 5);
+        client.pay_bill(&owner, &ids.get(2).unwrap());
+
+        let page = client.get_bills_due_between(&owner, &(86400 * 2), &(86400 * 4), &0, &10);
+        assert_eq!(page.count, 2);
+        for bill in page.items.iter() {
+            assert_eq!(bill.owner, owner);
+            assert!(bill.due_date >= 86400 * 2 && bill.due_date <= 86400 * 4);
+        }
+
+        let empty = client.get_bills_due_between(&owner, &(86400 * 10), &(86400 * 20), &0, &10);
+        assert_eq!(empty.count, 0);
+    }
+
+    #[test]
+    fn test_get_bills_due_between_pagination() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 5);
+
+        let page1 = client.get_bills_due_between(&owner, &0, &(86400 * 7), &0, &3);
+        assert_eq!(page1.count, 3);
+        assert!(page1.next_cursor > 0);
+        let page2 = client.get_bills_due_between(&owner, &0, &(86400 * 7), &page1.next_cursor, &3);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    // --- get_all_bills_for_owner ---
+
+    #[test]
+    fn test_get_all_bills_for_owner_includes_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 5);
+        let first_id = ids.get(0).unwrap();
+        client.pay_bill(&owner, &first_id);
+
+        let page = client.get_all_bills_for_owner(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+    }
+
+    // --- batch creation ---
+
+    fn new_bill_item(
+        env: &Env,
+        name: &str,
+        amount: i128,
+        payee: &Address,
+        token: &Address,
+    ) -> NewBill {
+        NewBill {
+            name: String::from_str(env, name),
+            amount,
+            due_date: env.ledger().timestamp() + 30 * 86400,
+            recurring: false,
+            frequency_days: 0,
+            currency: String::from_str(env, ""),
+            payee: payee.clone(),
+            token: token.clone(),
+            invoice_hash: BytesN::from_array(env, &[0; 32]),
+            invoice_number: String::from_str(env, ""),
+            tags: Vec::new(env),
+        }
+    }
+
+    #[test]
+    fn test_create_bills_returns_ids_in_order() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let mut items = Vec::new(&env);
+        items.push_back(new_bill_item(&env, "Rent", 800, &payee, &token));
+        items.push_back(new_bill_item(&env, "Power", 60, &payee, &token));
+        items.push_back(new_bill_item(&env, "School fees", 250, &payee, &token));
+
+        let ids = client.create_bills(&owner, &items);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(count_events(&env, symbol_short!("created")), 3);
+        for i in 0..3 {
+            let bill = client.get_bill(&ids.get(i).unwrap()).unwrap();
+            assert_eq!(bill.owner, owner);
+            assert_eq!(bill.name, items.get(i).unwrap().name);
+            assert_eq!(bill.currency, String::from_str(&env, "XLM"));
+        }
+        assert_eq!(client.get_total_unpaid(&owner), 1110);
+    }
+
+    #[test]
+    fn test_create_bills_rejects_invalid_item() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let mut items = Vec::new(&env);
+        items.push_back(new_bill_item(&env, "Rent", 800, &payee, &token));
+        items.push_back(new_bill_item(&env, "Water", 0, &payee, &token));
+
+        let result = client.try_create_bills(&owner, &items);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 0);
+    }
+
+    #[test]
+    fn test_create_bills_rejects_oversized_batch() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let mut items = Vec::new(&env);
+        for _ in 0..=MAX_BATCH_SIZE {
+            items.push_back(new_bill_item(&env, "Bill", 10, &payee, &token));
+        }
+        let result = client.try_create_bills(&owner, &items);
+        assert_eq!(result, Err(Ok(Error::BatchTooLarge)));
+    }
+
+    // --- tags ---
+
+    #[test]
+    fn test_get_bills_by_tag_pages_with_offset() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+        let school = Symbol::new(&env, "school2025");
+
+        let mut items = Vec::new(&env);
+        for name in ["Tuition", "Rent", "Uniforms", "Books"] {
+            let mut item = new_bill_item(&env, name, 100, &payee, &token);
+            if name != "Rent" {
+                item.tags.push_back(school.clone());
+            }
+            items.push_back(item);
+        }
+        let ids = client.create_bills(&owner, &items);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+
+        let all = client.get_bills_by_tag(&owner, &school, &0, &10);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all.get(0).unwrap().id, ids.get(0).unwrap());
+        assert_eq!(all.get(1).unwrap().id, ids.get(2).unwrap());
+        assert_eq!(all.get(2).unwrap().id, ids.get(3).unwrap());
+
+        let page = client.get_bills_by_tag(&owner, &school, &1, &1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().id, ids.get(2).unwrap());
+
+        let other = Address::generate(&env);
+        assert_eq!(client.get_bills_by_tag(&other, &school, &0, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_add_and_remove_bill_tags() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let tag = symbol_short!("household");
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        client.add_tags_to_bill(&owner, &bill_id, &Vec::from_array(&env, [tag.clone()]));
+        client.add_tags_to_bill(&owner, &bill_id, &Vec::from_array(&env, [tag.clone()]));
+        assert_eq!(client.get_bill(&bill_id).unwrap().tags.len(), 1);
+
+        // Tags carry over to the next occurrence of the series.
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(client.get_bills_by_tag(&owner, &tag, &0, &10).len(), 2);
+
+        client.remove_tags_from_bill(&owner, &bill_id, &Vec::from_array(&env, [tag.clone()]));
+        assert_eq!(client.get_bill(&bill_id).unwrap().tags.len(), 0);
+        assert_eq!(client.get_bills_by_tag(&owner, &tag, &0, &10).len(), 1);
+    }
+
+    #[test]
+    fn test_bill_tag_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        assert_eq!(
+            client.try_add_tags_to_bill(&owner, &bill_id, &Vec::new(&env)),
+            Err(Ok(Error::EmptyTags))
+        );
+        assert_eq!(
+            client.try_add_tags_to_bill(
+                &owner,
+                &bill_id,
+                &Vec::from_array(&env, [symbol_short!("")])
+            ),
+            Err(Ok(Error::InvalidTag))
+        );
+        assert_eq!(
+            client.try_add_tags_to_bill(
+                &other,
+                &bill_id,
+                &Vec::from_array(&env, [symbol_short!("fees")])
+            ),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    // --- priorities ---
+
+    #[test]
+    fn test_get_unpaid_bills_sorted_by_priority_then_due_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Due after one to five days, all Normal priority.
+        let ids = setup_bills(&env, &client, &owner, 5);
+        client.set_bill_priority(&owner, &ids.get(4).unwrap(), &BillPriority::High);
+        client.set_bill_priority(&owner, &ids.get(2).unwrap(), &BillPriority::High);
+        client.set_bill_priority(&owner, &ids.get(0).unwrap(), &BillPriority::Low);
+        client.pay_bill(&owner, &ids.get(3).unwrap());
+
+        let sorted = client.get_unpaid_bills_sorted(&owner);
+        assert_eq!(sorted.len(), 4);
+        let expected = [2u32, 4, 1, 0];
+        for (i, index) in expected.iter().enumerate() {
+            assert_eq!(sorted.get(i as u32).unwrap().id, ids.get(*index).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_priority_carries_to_next_occurrence() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_monthly_bill(&env, &client, &owner);
+        client.set_bill_priority(&owner, &bill_id, &BillPriority::High);
+        client.pay_bill(&owner, &bill_id);
+
+        let next = client.get_unpaid_bills_sorted(&owner).get(0).unwrap();
+        assert_ne!(next.id, bill_id);
+        assert_eq!(next.priority, BillPriority::High);
+
+        let result = client.try_set_bill_priority(&owner, &bill_id, &BillPriority::Low);
+        assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
+    }
+
+    // --- invoices ---
+
+    #[test]
+    fn test_attach_invoice_and_receipt_snapshot() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let hash = BytesN::from_array(&env, &[7u8; 32]);
+        let number = String::from_str(&env, "INV-2024-0042");
+        client.attach_invoice(&owner, &bill_id, &hash, &number);
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.has_invoice());
+        assert_eq!(bill.invoice_hash, hash);
+        assert_eq!(bill.invoice_number, number);
+
+        client.pay_bill(&owner, &bill_id);
+        let receipts = client.get_receipts(&owner, &0, &10);
+        let receipt = receipts.get(0).unwrap();
+        assert_eq!(receipt.bill.invoice_hash, hash);
+
+        let result = client.try_attach_invoice(&owner, &bill_id, &hash, &number);
+        assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
+    }
+
+    #[test]
+    fn test_attach_invoice_owner_only() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let bill_id = setup_bills(&env, &client, &owner, 1).get(0).unwrap();
+        let result = client.try_attach_invoice(
+            &other,
+            &bill_id,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &String::from_str(&env, "INV-1"),
+        );
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_create_bills_with_invoice() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let mut item = new_bill_item(&env, "School fees", 250, &payee, &token);
+        item.invoice_hash = BytesN::from_array(&env, &[9u8; 32]);
+        item.invoice_number = String::from_str(&env, "TERM-3");
+        let mut items = Vec::new(&env);
+        items.push_back(item.clone());
+
+        let bill_id = client.create_bills(&owner, &items).get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.invoice_hash, item.invoice_hash);
+        assert_eq!(bill.invoice_number, item.invoice_number);
+    }
+
+    // --- persistent bill storage ---
+
+    #[test]
+    fn test_bills_stored_in_persistent_entries() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let first_id = ids.get(0).unwrap();
+
+        env.as_contract(&cid, || {
+            assert!(env.storage().persistent().has(&(KEY_BILL, first_id)));
+            assert!(!env.storage().instance().has(&symbol_short!("BILLS")));
+        });
+
+        client.cancel_bill(&owner, &first_id);
+        env.as_contract(&cid, || {
+            assert!(!env.storage().persistent().has(&(KEY_BILL, first_id)));
+        });
+    }
+
+    #[test]
+    fn test_owner_indexes_follow_paid_status() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 4);
+        setup_bills(&env, &client, &other, 2);
+        client.pay_bill(&owner, &ids.get(1).unwrap());
+        client.pay_bill(&owner, &ids.get(3).unwrap());
+
+        let unpaid = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(unpaid.count, 2);
+        assert_eq!(unpaid.items.get(0).unwrap().id, ids.get(0).unwrap());
+        assert_eq!(unpaid.items.get(1).unwrap().id, ids.get(2).unwrap());
+
+        // Paid and unpaid bills come back interleaved in ID order.
+        let all = client.get_all_bills_for_owner(&owner, &0, &3);
+        assert_eq!(all.count, 3);
+        for i in 0..3 {
+            assert_eq!(all.items.get(i).unwrap().id, ids.get(i).unwrap());
+        }
+        let rest = client.get_all_bills_for_owner(&owner, &all.next_cursor, &3);
+        assert_eq!(rest.count, 1);
+        assert_eq!(rest.items.get(0).unwrap().id, ids.get(3).unwrap());
+        assert_eq!(rest.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_migrate_legacy_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+        client.set_upgrade_admin(&admin, &admin);
+
+        let legacy_bill = |id: u32, amount: i128, paid: bool| LegacyBill {
+            id,
+            owner: owner.clone(),
+            name: String::from_str(&env, "Rent"),
+            amount,
+            due_date: 86400 * id as u64,
+            recurring: false,
+            frequency_days: 0,
+            paid,
+            created_at: 0,
+            paid_at: if paid { Some(10) } else { None },
+            schedule_id: None,
+            currency: String::from_str(&env, "XLM"),
+        };
+        env.as_contract(&cid, || {
+            let mut bills: Map<u32, LegacyBill> = Map::new(&env);
+            bills.set(1, legacy_bill(1, 100, false));
+            bills.set(2, legacy_bill(2, 200, true));
+            bills.set(3, legacy_bill(3, 300, false));
+            env.storage().instance().set(&KEY_LEGACY_BILLS, &bills);
+            let mut archived: Map<u32, LegacyArchivedBill> = Map::new(&env);
+            archived.set(
+                4,
+                LegacyArchivedBill {
+                    id: 4,
+                    owner: owner.clone(),
+                    name: String::from_str(&env, "Old"),
+                    amount: 50,
+                    paid_at: 5,
+                    archived_at: 6,
+                    currency: String::from_str(&env, "XLM"),
+                },
+            );
+            env.storage()
+                .instance()
+                .set(&symbol_short!("ARCH_BILL"), &archived);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("NEXT_ID"), &4u32);
+        });
+
+        let result = client.try_migrate_legacy_bills(&owner, &token, &10);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        assert_eq!(client.migrate_legacy_bills(&admin, &token, &2), 1);
+        assert_eq!(client.migrate_legacy_bills(&admin, &token, &2), 0);
+        env.as_contract(&cid, || {
+            assert!(!env.storage().instance().has(&KEY_LEGACY_BILLS));
+        });
+
+        let unpaid = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(unpaid.count, 2);
+        assert_eq!(payee_of(&unpaid.items.get(0).unwrap()), owner);
+        assert!(client.get_bill(&2).unwrap().paid);
+        assert_eq!(client.get_archived_bills(&owner, &0, &10).count, 1);
+
+        env.ledger().set_timestamp(86400 * 2 + 1);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 1);
+
+        let stats = client.get_storage_stats();
+        assert_eq!(stats.active_bills, 3);
+        assert_eq!(stats.archived_bills, 1);
+        assert_eq!(stats.total_unpaid_amount, 400);
+        assert_eq!(stats.total_archived_amount, 50);
+
+        let next_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "New"),
+            &10,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &owner,
+            &token,
+        );
+        assert_eq!(next_id, 5);
+    }
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.currency != currency {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
+    // --- token settlement ---
 
-        Self::build_page(&env, staging, limit)
+    #[test]
+    fn test_pay_bill_transfers_to_payee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+        let token_client = TokenClient::new(&env, &token);
+        let starting_balance = token_client.balance(&owner);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &750,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &payee,
+            &token,
+        );
+        client.pay_bill(&owner, &bill_id);
+
+        assert_eq!(token_client.balance(&payee), 750);
+        assert_eq!(token_client.balance(&owner), starting_balance - 750);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
     }
 
-    /// Get a page of **unpaid** bills for `owner` that match `currency`.
-    ///
-    /// Same cursor/limit semantics as `get_bills_by_currency`.
-    pub fn get_unpaid_bills_by_currency(
-        env: Env,
-        owner: Address,
-        currency: String,
-        cursor: u32,
-        limit: u32,
-    ) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+    #[test]
+    fn test_pay_bill_insufficient_balance_fails() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token_admin = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        StellarAssetClient::new(&env, &token).mint(&owner, &100);
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.paid || bill.currency != currency {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School Fees"),
+            &500,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &payee,
+            &token,
+        );
 
-        Self::build_page(&env, staging, limit)
+        let result = client.try_pay_bill(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::TransferFailed)));
+        assert!(!client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(TokenClient::new(&env, &token).balance(&payee), 0);
     }
 
-    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
-    ///
-    /// # Example
-    /// ```text
-    /// let usdc_owed = client.get_total_unpaid_by_currency(&owner, &String::from_str(&env, "USDC"));
-    /// ```
-    pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner && bill.currency == currency {
-                total += bill.amount;
-            }
-        }
-        total
-    }
+    #[test]
+    fn test_batch_pay_bills_transfers_each_bill() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
 
-    // -----------------------------------------------------------------------
-    // Internal helpers
-    // -----------------------------------------------------------------------
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let payee = payee_of(&client.get_bill(&ids.get(0).unwrap()).unwrap());
+        let token = client.get_bill(&ids.get(0).unwrap()).unwrap().token;
 
-    fn extend_instance_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-    }
+        client.batch_pay_bills(&owner, &ids);
 
-    fn extend_archive_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+        // setup_bills creates amounts 100, 200, 300
+        assert_eq!(TokenClient::new(&env, &token).balance(&payee), 600);
     }
 
-    fn update_storage_stats(env: &Env) {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(env));
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(env));
+    // --- payee registry ---
 
-        let mut active_count = 0u32;
-        let mut unpaid_amount = 0i128;
-        for (_, bill) in bills.iter() {
-            active_count += 1;
-            if !bill.paid {
-                unpaid_amount = unpaid_amount.saturating_add(bill.amount);
-            }
-        }
+    #[test]
+    fn test_create_bill_for_registered_payee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let utility = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
-        let mut archived_count = 0u32;
-        let mut archived_amount = 0i128;
-        for (_, bill) in archived.iter() {
-            archived_count += 1;
-            archived_amount = archived_amount.saturating_add(bill.amount);
-        }
+        let payee_id = client.register_payee(
+            &owner,
+            &utility,
+            &String::from_str(&env, "City Power"),
+            &symbol_short!("utility"),
+        );
+        let bill_id = client.create_bill_for_payee(
+            &owner,
+            &payee_id,
+            &300,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &token,
+        );
 
-        let stats = StorageStats {
-            active_bills: active_count,
-            archived_bills: archived_count,
-            total_unpaid_amount: unpaid_amount,
-            total_archived_amount: archived_amount,
-            last_updated: env.ledger().timestamp(),
-        };
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.payee, BillPayee::Registered(payee_id));
+        assert_eq!(bill.name, String::from_str(&env, "City Power"));
+        assert_eq!(client.get_payees(&owner).len(), 1);
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("STOR_STAT"), &stats);
-    }
-    fn get_unpaid_totals_map(env: &Env) -> Option<Map<Address, i128>> {
-        env.storage().instance().get(&STORAGE_UNPAID_TOTALS)
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(TokenClient::new(&env, &token).balance(&utility), 300);
     }
 
-    fn adjust_unpaid_total(env: &Env, owner: &Address, delta: i128) {
-        if delta == 0 {
-            return;
-        }
-        let mut totals: Map<Address, i128> = env
-            .storage()
-            .instance()
-            .get(&STORAGE_UNPAID_TOTALS)
-            .unwrap_or_else(|| Map::new(env));
-        let current = totals.get(owner.clone()).unwrap_or(0);
-        let next = if delta >= 0 {
-            current.saturating_add(delta)
-        } else {
-            current.saturating_sub(delta.saturating_abs())
-        };
-        totals.set(owner.clone(), next);
-        env.storage()
-            .instance()
-            .set(&STORAGE_UNPAID_TOTALS, &totals);
-    }
-}
+    #[test]
+    fn test_updated_payee_address_used_at_payment() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let old_address = Address::generate(&env);
+        let new_address = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
-// -----------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Env, String,
-    };
+        let payee_id = client.register_payee(
+            &owner,
+            &old_address,
+            &String::from_str(&env, "Landlord"),
+            &symbol_short!("rent"),
+        );
+        let bill_id = client.create_bill_for_payee(
+            &owner,
+            &payee_id,
+            &1000,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &token,
+        );
+        client.update_payee(
+            &owner,
+            &payee_id,
+            &new_address,
+            &String::from_str(&env, "Landlord"),
+            &symbol_short!("rent"),
+        );
 
-    fn make_env() -> Env {
-        Env::default()
+        client.pay_bill(&owner, &bill_id);
+        let token_client = TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&new_address), 1000);
+        assert_eq!(token_client.balance(&old_address), 0);
     }
 
-    /// Create `count` bills with a static name. Returns their IDs.
-    /// Due dates are set in the future so they are NOT overdue.
-    fn setup_bills(
-        env: &Env,
-        client: &BillPaymentsClient,
-        owner: &Address,
-        count: u32,
-    ) -> Vec<u32> {
-        let mut ids = Vec::new(env);
-        for i in 0..count {
-            let id = client.create_bill(
-                owner,
-                &String::from_str(env, "Test Bill"),
-                &(100i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-                &String::from_str(env, "XLM"),
-            );
-            ids.push_back(id);
-        }
-        ids
+    #[test]
+    fn test_create_bill_for_removed_or_foreign_payee_fails() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+
+        let payee_id = client.register_payee(
+            &owner,
+            &Address::generate(&env),
+            &String::from_str(&env, "School"),
+            &symbol_short!("school"),
+        );
+
+        let result = client.try_create_bill_for_payee(
+            &other,
+            &payee_id,
+            &100,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &token,
+        );
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        client.remove_payee(&owner, &payee_id);
+        assert_eq!(client.get_payees(&owner).len(), 0);
+        let result = client.try_create_bill_for_payee(
+            &owner,
+            &payee_id,
+            &100,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &token,
+        );
+        assert_eq!(result, Err(Ok(Error::PayeeInactive)));
     }
 
-    // --- get_unpaid_bills ---
+    // --- partial payments ---
 
     #[test]
-    fn test_get_unpaid_bills_empty() {
+    fn test_partial_payments_settle_bill() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
-        let page = client.get_unpaid_bills(&owner, &0, &0);
-        assert_eq!(page.count, 0);
-        assert_eq!(page.next_cursor, 0);
-        assert_eq!(page.items.len(), 0);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School Fees"),
+            &1000,
+            &(env.ledger().timestamp() + 86400 * 30),
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &payee,
+            &token,
+        );
+
+        client.pay_bill_partial(&owner, &bill_id, &400);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.amount_paid, 400);
+        assert_eq!(client.get_remaining_balance(&bill_id), 600);
+        assert_eq!(client.get_total_unpaid(&owner), 600);
+
+        client.pay_bill_partial(&owner, &bill_id, &600);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(client.get_remaining_balance(&bill_id), 0);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert_eq!(TokenClient::new(&env, &token).balance(&payee), 1000);
     }
 
     #[test]
-    fn test_get_unpaid_bills_single_page() {
+    fn test_partial_payment_exceeding_balance_fails() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner, 5);
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        client.pay_bill_partial(&owner, &bill_id, &60);
 
-        let page = client.get_unpaid_bills(&owner, &0, &10);
-        assert_eq!(page.count, 5);
-        assert_eq!(page.next_cursor, 0);
+        let result = client.try_pay_bill_partial(&owner, &bill_id, &50);
+        assert_eq!(result, Err(Ok(Error::AmountExceedsBalance)));
+        let result = client.try_pay_bill_partial(&owner, &bill_id, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
     }
 
     #[test]
-    fn test_get_unpaid_bills_multiple_pages() {
+    fn test_pay_bill_after_partial_pays_remaining() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner, 7);
-
-        let page1 = client.get_unpaid_bills(&owner, &0, &3);
-        assert_eq!(page1.count, 3);
-        assert!(page1.next_cursor > 0, "expected a next cursor");
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        let bill = client.get_bill(&bill_id).unwrap();
 
-        let page2 = client.get_unpaid_bills(&owner, &page1.next_cursor, &3);
-        assert_eq!(page2.count, 3);
-        assert!(page2.next_cursor > 0);
+        client.pay_bill_partial(&owner, &bill_id, &30);
+        client.pay_bill(&owner, &bill_id);
 
-        let page3 = client.get_unpaid_bills(&owner, &page2.next_cursor, &3);
-        assert_eq!(page3.count, 1);
-        assert_eq!(page3.next_cursor, 0);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(
+            TokenClient::new(&env, &bill.token).balance(&payee_of(&bill)),
+            bill.amount
+        );
     }
 
+    // --- update / cancel ---
+
     #[test]
-    fn test_get_unpaid_bills_excludes_paid() {
+    fn test_update_bill_amount_and_due_date() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        let ids = setup_bills(&env, &client, &owner, 4);
-        let second_id = ids.get(1).unwrap();
-        client.pay_bill(&owner, &second_id);
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        client.update_bill(&owner, &bill_id, &250, &(86400 * 9));
 
-        let page = client.get_unpaid_bills(&owner, &0, &10);
-        assert_eq!(page.count, 3);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.amount, 250);
+        assert_eq!(bill.due_date, 86400 * 9);
+        assert_eq!(client.get_total_unpaid(&owner), 250);
     }
 
     #[test]
-    fn test_get_unpaid_bills_excludes_other_owner() {
+    fn test_update_bill_rules() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner_a, 3);
-        setup_bills(&env, &client, &owner_b, 2);
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let bill_id = ids.get(0).unwrap();
+        client.pay_bill_partial(&owner, &bill_id, &40);
 
-        let page = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page.count, 3);
-        for bill in page.items.iter() {
-            assert_eq!(bill.owner, owner_a);
-        }
+        let result = client.try_update_bill(&other, &bill_id, &200, &86400);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_update_bill(&owner, &bill_id, &40, &86400);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        let paid_id = ids.get(1).unwrap();
+        client.pay_bill(&owner, &paid_id);
+        let result = client.try_update_bill(&owner, &paid_id, &500, &86400);
+        assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_bidirectional() {
+    fn test_cancel_bill_only_while_unpaid() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner_a, 2);
-        setup_bills(&env, &client, &owner_b, 3);
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let result = client.try_cancel_bill(&other, &ids.get(0).unwrap());
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        // owner_a sees only their own bills
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 2);
-        for bill in page_a.items.iter() {
-            assert_eq!(
-                bill.owner, owner_a,
-                "owner_a page must not contain owner_b bills"
-            );
-        }
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        let result = client.try_cancel_bill(&owner, &ids.get(0).unwrap());
+        assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
 
-        // owner_b sees only their own bills
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 3);
-        for bill in page_b.items.iter() {
-            assert_eq!(
-                bill.owner, owner_b,
-                "owner_b page must not contain owner_a bills"
-            );
-        }
+        client.cancel_bill(&owner, &ids.get(1).unwrap());
+        let (_, _, data) = env.events().all().last().unwrap();
+        assert_eq!(u32::try_from_val(&env, &data).unwrap(), ids.get(1).unwrap());
+        assert!(client.get_bill(&ids.get(1).unwrap()).is_none());
+        assert_eq!(client.get_total_unpaid(&owner), 0);
     }
 
+    // --- installment plans ---
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_after_one_pays() {
-        // If owner_a pays their bill, owner_b's unpaid bills are unaffected
+    fn test_installment_plan_schedule() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        let ids_a = setup_bills(&env, &client, &owner_a, 2);
-        setup_bills(&env, &client, &owner_b, 2);
-
-        // owner_a pays one of their bills
-        client.pay_bill(&owner_a, &ids_a.get(0).unwrap());
-
-        // owner_a now has 1 unpaid
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 1);
-        for bill in page_a.items.iter() {
-            assert_eq!(bill.owner, owner_a, "Should only see owner_a bills");
-            assert!(!bill.paid, "Should only see unpaid bills");
-        }
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
+        let due_date = env.ledger().timestamp() + 86400;
 
-        // owner_b still has 2 unpaid — unaffected by owner_a's payment
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 2);
-        for bill in page_b.items.iter() {
-            assert_eq!(bill.owner, owner_b, "Should only see owner_b bills");
-        }
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Tuition"),
+            &1000,
+            &due_date,
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &payee,
+            &token,
+        );
+        client.create_installment_plan(&owner, &bill_id, &3, &30);
+
+        let plan = client.get_installment_plan(&bill_id).unwrap();
+        assert_eq!(plan.installments.len(), 3);
+        assert_eq!(plan.installments.get(0).unwrap().amount, 333);
+        assert_eq!(plan.installments.get(1).unwrap().amount, 333);
+        let last = plan.installments.get(2).unwrap();
+        assert_eq!(last.amount, 334);
+        assert_eq!(last.due_date, due_date + 60 * 86400);
+
+        let progress = client.get_installment_progress(&bill_id);
+        assert_eq!(progress.paid_installments, 0);
+        assert_eq!(progress.amount_remaining, 1000);
+        assert_eq!(client.get_next_installment(&bill_id).unwrap().index, 0);
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_one_owner_no_bills() {
-        // owner_b has bills but owner_a has none — owner_a gets empty page
+    fn test_pay_installments_until_settled() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        // Only owner_b creates bills
-        setup_bills(&env, &client, &owner_b, 3);
-
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 0, "owner_a should see no bills");
-        assert_eq!(page_a.next_cursor, 0);
+        let owner = Address::generate(&env);
 
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 3, "owner_b should see all their bills");
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+        client.create_installment_plan(&owner, &bill_id, &4, &7);
+
+        client.pay_installment(&owner, &bill_id);
+        let progress = client.get_installment_progress(&bill_id);
+        assert_eq!(progress.paid_installments, 1);
+        assert_eq!(progress.amount_paid, 25);
+        assert_eq!(progress.next_index, Some(1));
+
+        // A partial payment covers part of the next installment.
+        client.pay_bill_partial(&owner, &bill_id, &10);
+        let progress = client.get_installment_progress(&bill_id);
+        assert_eq!(progress.paid_installments, 1);
+        assert_eq!(progress.next_outstanding, 15);
+
+        client.pay_installment(&owner, &bill_id);
+        client.pay_installment(&owner, &bill_id);
+        client.pay_installment(&owner, &bill_id);
+
+        let progress = client.get_installment_progress(&bill_id);
+        assert_eq!(progress.paid_installments, 4);
+        assert!(progress.next_index.is_none());
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        let result = client.try_pay_installment(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_all_paid_other_owner_unpaid() {
-        // owner_a pays all their bills — owner_b's unpaid still isolated correctly
+    fn test_create_installment_plan_validation() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 1);
+        let bill_id = ids.get(0).unwrap();
+
+        let result = client.try_create_installment_plan(&owner, &bill_id, &1, &30);
+        assert_eq!(result, Err(Ok(Error::InvalidInstallments)));
+        let result = client.try_create_installment_plan(&owner, &bill_id, &101, &30);
+        assert_eq!(result, Err(Ok(Error::InvalidInstallments)));
+        let result = client.try_create_installment_plan(&owner, &bill_id, &2, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+        let result = client.try_create_installment_plan(&other, &bill_id, &2, &30);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_get_installment_progress(&bill_id);
+        assert_eq!(result, Err(Ok(Error::InstallmentPlanNotFound)));
+
+        client.create_installment_plan(&owner, &bill_id, &2, &30);
+        let result = client.try_create_installment_plan(&owner, &bill_id, &2, &30);
+        assert_eq!(result, Err(Ok(Error::InstallmentPlanExists)));
+    }
 
-        let ids_a = setup_bills(&env, &client, &owner_a, 3);
-        setup_bills(&env, &client, &owner_b, 2);
+    // --- FX normalization ---
 
-        // owner_a pays all their bills
-        for id in ids_a.iter() {
-            client.pay_bill(&owner_a, &id);
-        }
+    #[contract]
+    struct MockFxOracle;
 
-        // owner_a has zero unpaid
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 0, "owner_a should have no unpaid bills left");
+    #[contractimpl]
+    impl MockFxOracle {
+        pub fn set_rate(env: Env, base: Address, quote: Address, rate: i128) {
+            env.storage().instance().set(&(base, quote), &rate);
+        }
 
-        // owner_b still has 2 unpaid — not polluted by owner_a's paid bills
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 2);
-        for bill in page_b.items.iter() {
-            assert_eq!(bill.owner, owner_b);
-            assert!(!bill.paid);
+        pub fn get_rate(env: Env, base: Address, quote: Address) -> i128 {
+            env.storage().instance().get(&(base, quote)).unwrap()
         }
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_pagination_does_not_leak() {
-        // With many owners, paginating through owner_a's results never leaks owner_b's bills
+    fn test_get_total_unpaid_in_home_token() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let home = setup_token(&env, &owner);
+        let foreign = setup_token(&env, &owner);
 
-        // Interleave bills: a, b, a, b, a, b ...
-        for i in 0..4u32 {
-            client.create_bill(
-                &owner_a,
-                &String::from_str(&env, "Bill A"),
-                &(100i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-                &String::from_str(&env, "XLM"),
-            );
+        for (amount, token) in [(500i128, &home), (200i128, &foreign), (300i128, &foreign)] {
             client.create_bill(
-                &owner_b,
-                &String::from_str(&env, "Bill B"),
-                &(200i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &owner,
+                &String::from_str(&env, "Bill"),
+                &amount,
+                &86400,
                 &false,
                 &0,
                 &String::from_str(&env, "XLM"),
+                &payee,
+                token,
             );
         }
 
-        // Paginate through owner_a with small page size
-        let mut all_a_bills: soroban_sdk::Vec<Bill> = soroban_sdk::Vec::new(&env);
-        let mut cursor = 0u32;
-        loop {
-            let page = client.get_unpaid_bills(&owner_a, &cursor, &2);
-            for bill in page.items.iter() {
-                assert_eq!(
-                    bill.owner, owner_a,
-                    "Paginated result must never contain owner_b's bill"
-                );
-                all_a_bills.push_back(bill);
-            }
-            if page.next_cursor == 0 {
-                break;
-            }
-            cursor = page.next_cursor;
+        let result = client.try_get_total_unpaid_in(&owner, &home);
+        assert_eq!(result, Err(Ok(Error::OracleNotSet)));
+
+        let oracle = env.register_contract(None, MockFxOracle);
+        // 1 foreign = 1.5 home
+        MockFxOracleClient::new(&env, &oracle).set_rate(&foreign, &home, &15_000_000);
+        client.set_pause_admin(&admin, &admin);
+        client.set_fx_oracle(&admin, &oracle);
+
+        assert_eq!(client.get_total_unpaid_in(&owner, &home), 500 + 750);
+    }
+
+    #[test]
+    fn test_get_total_unpaid_in_without_conversion() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        // All bills share one token, so no oracle is needed.
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let token = client.get_bill(&ids.get(0).unwrap()).unwrap().token;
+        assert_eq!(client.get_total_unpaid_in(&owner, &token), 600);
+
+        let result = client.try_set_fx_oracle(&other, &other);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    // --- large-bill approval ---
+
+    #[contract]
+    struct MockFamilyWallet;
+
+    #[contractimpl]
+    impl MockFamilyWallet {
+        pub fn add_member(env: Env, member: Address, role: FamilyRole) {
+            let record = FamilyMember {
+                address: member.clone(),
+                role,
+                spending_limit: 0,
+                added_at: 0,
+            };
+            env.storage().instance().set(&member, &record);
         }
 
-        assert_eq!(
-            all_a_bills.len(),
-            4,
-            "owner_a should have exactly 4 bills across all pages"
-        );
+        pub fn get_family_member(env: Env, member: Address) -> Option<FamilyMember> {
+            env.storage().instance().get(&member)
+        }
     }
 
-    // --- get_overdue_bills ---
+    fn setup_approval(env: &Env, client: &BillPaymentsClient, owner: &Address) -> Address {
+        let wallet = env.register_contract(None, MockFamilyWallet);
+        let wallet_client = MockFamilyWalletClient::new(env, &wallet);
+        wallet_client.add_member(owner, &FamilyRole::Owner);
+        client.configure_bill_approval(owner, &wallet, &150);
+        wallet
+    }
 
     #[test]
-    fn test_get_overdue_bills_not_overdue() {
+    fn test_large_bill_requires_approval() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let wallet = setup_approval(&env, &client, &owner);
+        let admin = Address::generate(&env);
+        MockFamilyWalletClient::new(&env, &wallet).add_member(&admin, &FamilyRole::Admin);
+
+        // Amounts 100 and 200; only the second is above the threshold.
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let small = client.get_bill(&ids.get(0).unwrap()).unwrap();
+        let large = client.get_bill(&ids.get(1).unwrap()).unwrap();
+        assert_eq!(small.approval, ApprovalStatus::NotRequired);
+        assert_eq!(large.approval, ApprovalStatus::Pending);
+
+        client.pay_bill(&owner, &small.id);
+        let result = client.try_pay_bill(&owner, &large.id);
+        assert_eq!(result, Err(Ok(Error::ApprovalRequired)));
+
+        client.approve_bill(&admin, &large.id);
+        assert_eq!(
+            client.get_bill(&large.id).unwrap().approval,
+            ApprovalStatus::Approved
+        );
+        client.pay_bill(&owner, &large.id);
+        assert!(client.get_bill(&large.id).unwrap().paid);
 
-        setup_bills(&env, &client, &owner, 3);
-        let page = client.get_overdue_bills(&0, &10);
-        assert_eq!(page.count, 0);
+        let result = client.try_approve_bill(&admin, &large.id);
+        assert_eq!(result, Err(Ok(Error::ApprovalNotPending)));
     }
 
     #[test]
-    fn test_get_overdue_bills_pagination() {
+    fn test_approve_bill_requires_owner_or_admin_role() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let wallet = setup_approval(&env, &client, &owner);
+        let member = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        MockFamilyWalletClient::new(&env, &wallet).add_member(&member, &FamilyRole::Member);
 
-        for _ in 0..6u32 {
-            client.create_bill(
-                &owner,
-                &String::from_str(&env, "Overdue Bill"),
-                &100,
-                &0,
-                &false,
-                &0,
-                &String::from_str(&env, "XLM"),
-            );
-        }
-
-        env.ledger().set_timestamp(1);
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let large = ids.get(1).unwrap();
 
-        let page1 = client.get_overdue_bills(&0, &4);
-        assert_eq!(page1.count, 4);
-        assert!(page1.next_cursor > 0);
+        let result = client.try_approve_bill(&member, &large);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_approve_bill(&outsider, &large);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
-        let page2 = client.get_overdue_bills(&page1.next_cursor, &4);
-        assert_eq!(page2.count, 2);
-        assert_eq!(page2.next_cursor, 0);
+        client.approve_bill(&owner, &large);
+        client.pay_bill(&owner, &large);
     }
 
-    // --- get_all_bills_for_owner ---
-
     #[test]
-    fn test_get_all_bills_for_owner_includes_paid() {
+    fn test_configure_bill_approval_validation() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let wallet = setup_approval(&env, &client, &owner);
+
+        let result = client.try_configure_bill_approval(&owner, &wallet, &0);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+        // The owner must belong to the wallet.
+        let other_wallet = env.register_contract(None, MockFamilyWallet);
+        let result = client.try_configure_bill_approval(&owner, &other_wallet, &100);
+        assert_eq!(result, Err(Ok(Error::InvalidFamilyWallet)));
+        let result = client.try_configure_bill_approval(&owner, &Address::generate(&env), &100);
+        assert_eq!(result, Err(Ok(Error::InvalidFamilyWallet)));
+
+        // Tightening is allowed, loosening or switching wallets is not.
+        MockFamilyWalletClient::new(&env, &other_wallet).add_member(&owner, &FamilyRole::Owner);
+        client.configure_bill_approval(&owner, &wallet, &100);
+        let result = client.try_configure_bill_approval(&owner, &wallet, &500);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let result = client.try_configure_bill_approval(&owner, &other_wallet, &100);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.get_approval_config(&owner).unwrap().threshold, 100);
+    }
 
-        let ids = setup_bills(&env, &client, &owner, 5);
-        let first_id = ids.get(0).unwrap();
-        client.pay_bill(&owner, &first_id);
+    #[test]
+    fn test_remove_bill_approval_releases_pending_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let wallet = env.register_contract(None, MockFamilyWallet);
+        let wallet_client = MockFamilyWalletClient::new(&env, &wallet);
+        wallet_client.add_member(&owner, &FamilyRole::Member);
+        let admin = Address::generate(&env);
+        wallet_client.add_member(&admin, &FamilyRole::Admin);
+        client.configure_bill_approval(&owner, &wallet, &150);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let result = client.try_remove_bill_approval(&owner, &owner);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+        assert_eq!(client.remove_bill_approval(&admin, &owner), 2);
+        assert!(client.get_approval_config(&owner).is_none());
+        for id in ids.iter() {
+            assert_eq!(
+                client.get_bill(&id).unwrap().approval,
+                ApprovalStatus::NotRequired
+            );
+        }
+        client.pay_bill(&owner, &ids.get(2).unwrap());
 
-        let page = client.get_all_bills_for_owner(&owner, &0, &10);
-        assert_eq!(page.count, 5);
+        let result = client.try_remove_bill_approval(&admin, &owner);
+        assert_eq!(result, Err(Ok(Error::ApprovalNotPending)));
     }
 
     // --- limit clamping ---
@@ -1682,7 +8511,7 @@ mod test {
         for bill_id in ids.iter() {
             client.pay_bill(&owner, &bill_id);
         }
-        client.archive_paid_bills(&owner, &u64::MAX);
+        client.archive_paid_bills(&owner, &u64::MAX, &0);
 
         let page1 = client.get_archived_bills(&owner, &0, &4);
         assert_eq!(page1.count, 4);
@@ -1693,6 +8522,84 @@ mod test {
         assert_eq!(page2.next_cursor, 0);
     }
 
+    #[test]
+    fn test_archive_paid_bills_owner_scope_and_limit() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        let other_ids = setup_bills(&env, &client, &other, 1);
+        env.ledger().set_timestamp(1000);
+        for bill_id in ids.iter() {
+            client.pay_bill(&owner, &bill_id);
+        }
+        client.pay_bill(&other, &other_ids.get(0).unwrap());
+
+        // Bills paid at the cutoff are kept.
+        assert_eq!(client.archive_paid_bills(&owner, &1000, &10), 0);
+
+        assert_eq!(client.archive_paid_bills(&owner, &1001, &2), 2);
+        assert_eq!(client.archive_paid_bills(&owner, &1001, &10), 1);
+        assert!(client.get_bill(&ids.get(0).unwrap()).is_none());
+        assert!(client.get_bill(&other_ids.get(0).unwrap()).is_some());
+        assert_eq!(client.get_all_bills_for_owner(&owner, &0, &10).count, 0);
+
+        let totals = client.get_archive_totals(&owner);
+        assert_eq!(totals.bill_count, 3);
+        assert_eq!(totals.total_amount, 600);
+    }
+
+    #[test]
+    fn test_admin_archives_across_owners() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        client.set_pause_admin(&admin, &admin);
+
+        let ids = setup_bills(&env, &client, &owner, 2);
+        let other_ids = setup_bills(&env, &client, &other, 1);
+        client.pay_bill(&owner, &ids.get(0).unwrap());
+        client.pay_bill(&other, &other_ids.get(0).unwrap());
+
+        assert_eq!(client.archive_paid_bills(&admin, &u64::MAX, &10), 2);
+        assert!(client.get_bill(&ids.get(1).unwrap()).is_some());
+        assert_eq!(client.get_archive_totals(&owner).bill_count, 1);
+        assert_eq!(client.get_archive_totals(&other).total_amount, 100);
+    }
+
+    #[test]
+    fn test_archive_totals_survive_cleanup_and_follow_restore() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 3);
+        for bill_id in ids.iter() {
+            client.pay_bill(&owner, &bill_id);
+        }
+        client.archive_paid_bills(&owner, &u64::MAX, &0);
+
+        client.restore_bill(&owner, &ids.get(0).unwrap());
+        let totals = client.get_archive_totals(&owner);
+        assert_eq!(totals.bill_count, 2);
+        assert_eq!(totals.total_amount, 500);
+
+        env.ledger().set_timestamp(env.ledger().timestamp() + 1);
+        client.bulk_cleanup_bills(&owner, &u64::MAX);
+        assert_eq!(client.get_archived_bills(&owner, &0, &10).count, 0);
+        assert_eq!(client.get_archive_totals(&owner), totals);
+    }
+
     // -----------------------------------------------------------------------
     // RECURRING BILLS DATE MATH TESTS
     // -----------------------------------------------------------------------
@@ -1708,6 +8615,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1718,6 +8627,8 @@ mod test {
             &true, // recurring
             &1,    // frequency_days = 1
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay the bill
@@ -1742,6 +8653,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1752,6 +8665,8 @@ mod test {
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay the bill
@@ -1779,6 +8694,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1789,6 +8706,8 @@ mod test {
             &true, // recurring
             &365,  // frequency_days = 365
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay the bill
@@ -1819,6 +8738,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1829,6 +8750,8 @@ mod test {
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay the bill (at time 1_000_500, which is 500 seconds after due_date)
@@ -1863,6 +8786,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1873,6 +8798,8 @@ mod test {
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay first bill
@@ -1912,6 +8839,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1922,6 +8851,8 @@ mod test {
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay first bill
@@ -1958,6 +8889,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due_date = 1_000_000u64;
         let bill_id = client.create_bill(
@@ -1968,6 +8901,8 @@ mod test {
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay the bill early (at time 500_000)
@@ -1996,6 +8931,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let frequency = 7u32; // Weekly
         let bill_id = client.create_bill(
@@ -2006,6 +8943,8 @@ mod test {
             &true,
             &frequency,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay first bill
@@ -2032,6 +8971,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let amount = 999i128;
         let bill_id = client.create_bill(
@@ -2042,6 +8983,8 @@ mod test {
             &true,
             &30,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay first bill
@@ -2068,6 +9011,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let bill_id = client.create_bill(
             &owner,
@@ -2077,6 +9022,8 @@ mod test {
             &true,
             &30,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         // Pay first bill
@@ -2106,6 +9053,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let base_due = 1_000_000u64;
         let freq = 14u32;
@@ -2117,6 +9066,8 @@ mod test {
             &true,
             &freq,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         client.pay_bill(&owner, &bill_id);
@@ -2138,6 +9089,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         let original_due_date: u64 = 1_000_000;
         let frequency: u32 = 30;
@@ -2152,6 +9105,8 @@ mod test {
             &true,      // recurring
             &frequency, // frequency_days
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         client.pay_bill(&owner, &bill_id);
@@ -2211,6 +9166,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         client.create_bill(
             &owner,
@@ -2220,6 +9177,8 @@ mod test {
             &false,
             &0,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         let page = client.get_overdue_bills(&0, &100);
@@ -2240,6 +9199,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         client.create_bill(
             &owner,
@@ -2249,6 +9210,8 @@ mod test {
             &false,
             &0,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         let page = client.get_overdue_bills(&0, &100);
@@ -2273,6 +9236,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         client.create_bill(
             &owner,
@@ -2282,6 +9247,8 @@ mod test {
             &false,
             &0,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
         client.create_bill(
             &owner,
@@ -2291,6 +9258,8 @@ mod test {
             &false,
             &0,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
         client.create_bill(
             &owner,
@@ -2300,6 +9269,8 @@ mod test {
             &false,
             &0,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         let page = client.get_overdue_bills(&0, &100);
@@ -2322,6 +9293,8 @@ mod test {
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner);
 
         client.create_bill(
             &owner,
@@ -2331,6 +9304,8 @@ mod test {
             &false,
             &0,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
 
         let page = client.get_overdue_bills(&0, &100);
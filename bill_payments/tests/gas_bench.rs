@@ -42,6 +42,10 @@ fn bench_get_total_unpaid_worst_case() {
     let contract_id = env.register_contract(None, BillPayments);
     let client = BillPaymentsClient::new(&env, &contract_id);
     let owner = <Address as AddressTrait>::generate(&env);
+    let payee = <Address as AddressTrait>::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(<Address as AddressTrait>::generate(&env))
+        .address();
 
     let name = String::from_str(&env, "BenchBill");
     for _ in 0..100 {
@@ -53,6 +57,8 @@ fn bench_get_total_unpaid_worst_case() {
             &false,
             &0u32,
             &String::from_str(&env, "XLM"),
+            &payee,
+            &token,
         );
     }
 
@@ -12,11 +12,16 @@ fn test_notification_flow() {
     let contract_id = e.register_contract(None, BillPayments);
     let client = BillPaymentsClient::new(&e, &contract_id);
 
-    // Setup: Create a User
+    // Setup: Create a User, the payee and the token bills are paid in
     let user = Address::generate(&e);
+    let payee = Address::generate(&e);
+    let token = e
+        .register_stellar_asset_contract_v2(Address::generate(&e))
+        .address();
 
     // Mock authorization so 'require_auth' passes
     e.mock_all_auths();
+    soroban_sdk::token::StellarAssetClient::new(&e, &token).mint(&user, &1000);
 
     // Create Bill
     let bill_id = client.create_bill(
@@ -27,6 +32,8 @@ fn test_notification_flow() {
         &false,
         &0,
         &soroban_sdk::String::from_str(&e, "XLM"),
+        &payee,
+        &token,
     );
 
     // VERIFY: Get Events
@@ -18,12 +18,15 @@ pub enum InsuranceError {
     FunctionPaused = 6,
     InvalidTimestamp = 7,
     BatchTooLarge = 8,
+    InsufficientPayment = 9,
 }
 
 // Event topics
 const POLICY_CREATED: Symbol = symbol_short!("created");
 const PREMIUM_PAID: Symbol = symbol_short!("paid");
 const POLICY_DEACTIVATED: Symbol = symbol_short!("deactive");
+const CREDIT_ADDED: Symbol = symbol_short!("cred_add");
+const CREDIT_USED: Symbol = symbol_short!("cred_use");
 
 // Event data structures
 #[derive(Clone)]
@@ -47,6 +50,15 @@ pub struct PremiumPaidEvent {
     pub timestamp: u64,
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct CreditEvent {
+    pub policy_id: u32,
+    pub amount: i128,
+    pub balance: i128,
+    pub timestamp: u64,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct PolicyDeactivatedEvent {
@@ -62,6 +74,7 @@ const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
 const STORAGE_PREMIUM_TOTALS: Symbol = symbol_short!("PRM_TOT");
+const STORAGE_CREDITS: Symbol = symbol_short!("CREDITS");
 
 /// Pagination constants
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
@@ -532,6 +545,9 @@ impl Insurance {
 
     /// Pays a premium for a specific policy.
     ///
+    /// Any credit balance left over from earlier overpayments is applied
+    /// first, so the caller only settles the remainder of the premium.
+    ///
     /// # Arguments
     /// * `caller` - Address of the policy owner (must authorize)
     /// * `policy_id` - ID of the policy to pay premium for
@@ -547,6 +563,50 @@ impl Insurance {
     /// # Panics
     /// * If `caller` does not authorize the transaction
     pub fn pay_premium(env: Env, caller: Address, policy_id: u32) -> Result<(), InsuranceError> {
+        Self::pay_premium_internal(env, caller, policy_id, None)
+    }
+
+    /// Pays a premium with an explicit amount sent by the payer.
+    ///
+    /// Existing credit is applied to the premium first. If `amount` exceeds
+    /// what is still due, the surplus is recorded as credit on the policy and
+    /// automatically applied to the next premium.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the policy owner (must authorize)
+    /// * `policy_id` - ID of the policy to pay premium for
+    /// * `amount` - Amount sent by the payer (must be > 0)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `InsufficientPayment` - If amount plus available credit is below the premium
+    /// * `PolicyNotFound` / `Unauthorized` / `PolicyInactive` - As for `pay_premium`
+    pub fn pay_premium_with_amount(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        amount: i128,
+    ) -> Result<(), InsuranceError> {
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Self::pay_premium_internal(env, caller, policy_id, Some(amount))
+    }
+
+    /// Get the overpayment credit currently held for a policy.
+    ///
+    /// # Returns
+    /// Credit balance in stroops (0 if the policy has no credit)
+    pub fn get_credit_balance(env: Env, policy_id: u32) -> i128 {
+        Self::get_credits_map(&env).get(policy_id).unwrap_or(0)
+    }
+
+    fn pay_premium_internal(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        amount: Option<i128>,
+    ) -> Result<(), InsuranceError> {
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::PAY_PREMIUM)?;
         Self::extend_instance_ttl(&env);
@@ -569,6 +629,19 @@ impl Insurance {
             return Err(InsuranceError::PolicyInactive);
         }
 
+        let credit = Self::get_credit_balance(env.clone(), policy_id);
+        let applied = credit.min(policy.monthly_premium);
+        let due = policy.monthly_premium - applied;
+        let surplus = match amount {
+            Some(sent) if sent < due => return Err(InsuranceError::InsufficientPayment),
+            Some(sent) => sent - due,
+            None => 0,
+        };
+        Self::consume_credit(&env, policy_id, applied);
+        if surplus > 0 {
+            Self::add_credit(&env, policy_id, surplus);
+        }
+
         policy.next_payment_date = env.ledger().timestamp() + (30 * 86400);
         policies.set(policy_id, policy.clone());
         env.storage()
@@ -626,6 +699,7 @@ impl Insurance {
         let mut paid_count = 0;
         for id in policy_ids.iter() {
             let mut policy = policies_map.get(id).unwrap();
+            Self::consume_credit(&env, id, policy.monthly_premium);
             policy.next_payment_date = current_time + (30 * 86400);
             let event = PremiumPaidEvent {
                 policy_id: id,
@@ -812,6 +886,60 @@ impl Insurance {
             .set(&STORAGE_PREMIUM_TOTALS, &totals);
     }
 
+    fn get_credits_map(env: &Env) -> Map<u32, i128> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_CREDITS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Record `amount` of overpayment as credit on `policy_id`.
+    fn add_credit(env: &Env, policy_id: u32, amount: i128) {
+        let mut credits = Self::get_credits_map(env);
+        let balance = credits.get(policy_id).unwrap_or(0).saturating_add(amount);
+        credits.set(policy_id, balance);
+        env.storage().instance().set(&STORAGE_CREDITS, &credits);
+
+        env.events().publish(
+            (CREDIT_ADDED,),
+            CreditEvent {
+                policy_id,
+                amount,
+                balance,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Apply up to `max_amount` of the policy's credit towards a premium.
+    /// Returns the amount of credit actually consumed.
+    fn consume_credit(env: &Env, policy_id: u32, max_amount: i128) -> i128 {
+        let mut credits = Self::get_credits_map(env);
+        let credit = credits.get(policy_id).unwrap_or(0);
+        let used = credit.min(max_amount);
+        if used <= 0 {
+            return 0;
+        }
+        let balance = credit - used;
+        if balance == 0 {
+            credits.remove(policy_id);
+        } else {
+            credits.set(policy_id, balance);
+        }
+        env.storage().instance().set(&STORAGE_CREDITS, &credits);
+
+        env.events().publish(
+            (CREDIT_USED,),
+            CreditEvent {
+                policy_id,
+                amount: used,
+                balance,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        used
+    }
+
     // -----------------------------------------------------------------------
     // Schedule operations (unchanged)
     // -----------------------------------------------------------------------
@@ -1012,6 +1140,7 @@ impl Insurance {
 
             if let Some(mut policy) = policies.get(schedule.policy_id) {
                 if policy.active {
+                    Self::consume_credit(&env, schedule.policy_id, policy.monthly_premium);
                     policy.next_payment_date = current_time + (30 * 86400);
                     policies.set(schedule.policy_id, policy.clone());
 
@@ -1500,3 +1500,86 @@ fn test_time_drift_no_double_execution_after_schedule_advances() {
         "Schedule must not re-execute before the new next_due"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Overpayment credits
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_overpayment_recorded_as_credit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health Cover"),
+        &CoverageType::Health,
+        &100,
+        &10000,
+    );
+    assert_eq!(client.get_credit_balance(&policy_id), 0);
+
+    client.pay_premium_with_amount(&owner, &policy_id, &250);
+    assert_eq!(client.get_credit_balance(&policy_id), 150);
+}
+
+#[test]
+fn test_credit_applied_to_next_premium() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life Cover"),
+        &CoverageType::Life,
+        &100,
+        &10000,
+    );
+    client.pay_premium_with_amount(&owner, &policy_id, &160);
+    assert_eq!(client.get_credit_balance(&policy_id), 60);
+
+    // 60 of credit covers part of the next premium, so 40 settles it.
+    client.pay_premium_with_amount(&owner, &policy_id, &40);
+    assert_eq!(client.get_credit_balance(&policy_id), 0);
+
+    // Without credit, 40 is no longer enough.
+    let result = client.try_pay_premium_with_amount(&owner, &policy_id, &40);
+    assert_eq!(result, Err(Ok(InsuranceError::InsufficientPayment)));
+}
+
+#[test]
+fn test_pay_premium_consumes_credit_and_emits_event() {
+    use soroban_sdk::testutils::Events;
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Property Cover"),
+        &CoverageType::Property,
+        &100,
+        &10000,
+    );
+    client.pay_premium_with_amount(&owner, &policy_id, &350);
+    assert_eq!(client.get_credit_balance(&policy_id), 250);
+
+    let events_before = env.events().all().len();
+    client.pay_premium(&owner, &policy_id);
+    assert_eq!(client.get_credit_balance(&policy_id), 150);
+
+    // cred_use + PremiumPaid topic + enum events
+    let events_after = env.events().all().len();
+    assert_eq!(events_after - events_before, 3);
+}
@@ -21,6 +21,8 @@ fn test_multi_contract_user_flow() {
 
     // Generate test user address
     let user = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
 
     // Deploy all contracts
     let remittance_contract_id = env.register_contract(None, RemittanceSplit);
@@ -68,6 +70,8 @@ fn test_multi_contract_user_flow() {
         &recurring,
         &frequency_days,
         &SorobanString::from_str(&env, "XLM"),
+        &payee,
+        &token,
     );
     assert_eq!(bill_id, 1u32, "Bill ID should be 1");
 
@@ -88,7 +92,7 @@ fn test_multi_contract_user_flow() {
 
     // Step 5: Calculate split for a remittance amount
     let total_remittance = 10_000i128;
-    let amounts = remittance_client.calculate_split(&total_remittance);
+    let amounts = remittance_client.calculate_split(&user, &total_remittance);
     assert_eq!(amounts.len(), 4, "Should have 4 allocation amounts");
 
     // Extract amounts
@@ -149,7 +153,7 @@ fn test_split_with_rounding() {
 
     // Calculate split for an amount that will have rounding
     let total = 1_000i128;
-    let amounts = remittance_client.calculate_split(&total);
+    let amounts = remittance_client.calculate_split(&user, &total);
 
     let spending = amounts.get(0).unwrap();
     let savings = amounts.get(1).unwrap();
@@ -178,6 +182,8 @@ fn test_multiple_entities_creation() {
     env.mock_all_auths();
 
     let user = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let token = Address::generate(&env);
 
     // Deploy contracts
     let savings_contract_id = env.register_contract(None, SavingsGoalContract);
@@ -215,6 +221,8 @@ fn test_multiple_entities_creation() {
         &true,
         &30u32,
         &SorobanString::from_str(&env, "XLM"),
+        &payee,
+        &token,
     );
     assert_eq!(bill1, 1u32);
 
@@ -226,6 +234,8 @@ fn test_multiple_entities_creation() {
         &true,
         &30u32,
         &SorobanString::from_str(&env, "XLM"),
+        &payee,
+        &token,
     );
     assert_eq!(bill2, 2u32);
 
@@ -253,3 +263,53 @@ fn test_multiple_entities_creation() {
     println!("   Created 2 bills");
     println!("   Created 2 insurance policies");
 }
+
+/// Rebalancing suggestions only count savings goals that are still below
+/// target, read from a real savings goals contract.
+#[test]
+fn test_suggest_rebalance_skips_completed_goals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let user = Address::generate(&env);
+
+    let remittance_contract_id = env.register_contract(None, RemittanceSplit);
+    let remittance_client = RemittanceSplitClient::new(&env, &remittance_contract_id);
+
+    let savings_contract_id = env.register_contract(None, SavingsGoalContract);
+    let savings_client = SavingsGoalContractClient::new(&env, &savings_contract_id);
+
+    remittance_client.initialize_split(&user, &0, &40u32, &30u32, &20u32, &10u32);
+    remittance_client.set_destination(
+        &user,
+        &soroban_sdk::symbol_short!("SAVINGS"),
+        &savings_contract_id,
+    );
+
+    let deadline = env.ledger().timestamp() + 180 * 86400;
+    let done = savings_client.create_goal(
+        &user,
+        &SorobanString::from_str(&env, "Phone"),
+        &1_000i128,
+        &deadline,
+    );
+    let open = savings_client.create_goal(
+        &user,
+        &SorobanString::from_str(&env, "School Fees"),
+        &5_000i128,
+        &deadline,
+    );
+    savings_client.add_to_goal(&user, &done, &None, &1_000i128);
+    savings_client.add_to_goal(&user, &open, &None, &1_000i128);
+
+    // Only the school fees goal is still below target.
+    let suggestion = remittance_client.suggest_rebalance(&user);
+    assert_eq!(suggestion.active_goals, 1);
+    assert_eq!(suggestion.suggested.get(1).unwrap().bps, 3_500);
+
+    // Once it is complete too, there is nothing to nudge.
+    savings_client.add_to_goal(&user, &open, &None, &4_000i128);
+    let suggestion = remittance_client.suggest_rebalance(&user);
+    assert_eq!(suggestion.active_goals, 0);
+    assert_eq!(suggestion.suggested, suggestion.current);
+}
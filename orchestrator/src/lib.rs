@@ -98,14 +98,15 @@ pub trait RemittanceSplitTrait {
     /// Calculate split amounts from a total remittance amount
     ///
     /// # Arguments
+    /// * `owner` - Address whose split configuration applies
     /// * `total_amount` - The total amount to split (must be positive)
     ///
     /// # Returns
-    /// Vec containing [spending, savings, bills, insurance] amounts
+    /// One allocation per category in the owner's split, tagged by name
     ///
     /// # Gas Estimation
     /// ~3000 gas
-    fn calculate_split(env: Env, total_amount: i128) -> Vec<i128>;
+    fn get_split_allocations(env: Env, owner: Address, total_amount: i128) -> Vec<Allocation>;
 }
 
 /// Savings Goals contract client interface
@@ -119,6 +120,8 @@ pub trait SavingsGoalsTrait {
     /// # Arguments
     /// * `caller` - Address of the caller (must be the goal owner)
     /// * `goal_id` - ID of the goal
+    /// * `token` - Token to transfer from the caller, or `None` to only
+    ///   record the amount
     /// * `amount` - Amount to add (must be positive)
     ///
     /// # Returns
@@ -126,7 +129,13 @@ pub trait SavingsGoalsTrait {
     ///
     /// # Gas Estimation
     /// ~4000 gas
-    fn add_to_goal(env: Env, caller: Address, goal_id: u32, amount: i128) -> i128;
+    fn add_to_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        token: Option<Address>,
+        amount: i128,
+    ) -> i128;
 }
 
 /// Bill Payments contract client interface
@@ -194,6 +203,18 @@ pub enum OrchestratorError {
     CrossContractCallFailed = 9,
 }
 
+/// One category's amount as returned by the Remittance Split contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allocation {
+    /// Category name (SPENDING, SAVINGS, BILLS, INSURANCE or a custom name)
+    pub category: Symbol,
+    /// Amount allocated to the category
+    pub amount: i128,
+    /// Change made by the owner's floors and caps
+    pub adjustment: i128,
+}
+
 /// Result of a complete remittance flow execution
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -391,11 +412,13 @@ impl Orchestrator {
     /// # Arguments
     /// * `env` - The contract environment
     /// * `remittance_split_addr` - Address of the Remittance Split contract
+    /// * `owner` - Address whose split configuration applies
     /// * `total_amount` - Total remittance amount to split (must be positive)
     ///
     /// # Returns
     /// Ok(Vec<i128>) containing [spending, savings, bills, insurance] amounts
-    /// Err(OrchestratorError) if validation fails or cross-contract call fails
+    /// Err(OrchestratorError) if validation fails, the cross-contract call
+    /// fails, or the split has a category outside those four
     ///
     /// # Gas Estimation
     /// ~3000 gas for cross-contract split calculation
@@ -403,11 +426,12 @@ impl Orchestrator {
     /// # Cross-Contract Call Flow
     /// 1. Validate that total_amount is positive
     /// 2. Create RemittanceSplitClient instance
-    /// 3. Call calculate_split via cross-contract call
-    /// 4. Return the allocation vector
+    /// 3. Call get_split_allocations via cross-contract call
+    /// 4. Match each allocation to its standard category by name
     fn extract_allocations(
         env: &Env,
         remittance_split_addr: &Address,
+        owner: &Address,
         total_amount: i128,
     ) -> Result<Vec<i128>, OrchestratorError> {
         // Validate amount is positive
@@ -420,10 +444,29 @@ impl Orchestrator {
 
         // Gas estimation: ~3000 gas
         // Call the remittance split contract to calculate allocations
-        // This returns Vec<i128> with [spending, savings, bills, insurance]
-        let allocations = split_client.calculate_split(&total_amount);
+        let allocations = split_client.get_split_allocations(owner, &total_amount);
+
+        // Place each amount by category name; the flow can only route the
+        // four standard categories, so any other split is rejected
+        let categories = [
+            symbol_short!("SPENDING"),
+            symbol_short!("SAVINGS"),
+            symbol_short!("BILLS"),
+            symbol_short!("INSURANCE"),
+        ];
+        let mut amounts = Vec::from_array(env, [0i128; 4]);
+        for allocation in allocations.iter() {
+            let index = categories
+                .iter()
+                .position(|name| *name == allocation.category)
+                .ok_or(OrchestratorError::RemittanceSplitFailed)?;
+            amounts.set(
+                index as u32,
+                amounts.get(index as u32).unwrap() + allocation.amount,
+            );
+        }
 
-        Ok(allocations)
+        Ok(amounts)
     }
 
     // ============================================================================
@@ -454,6 +497,9 @@ impl Orchestrator {
     /// 2. Call add_to_goal via cross-contract call
     /// 3. If the call panics (goal not found, invalid amount), transaction reverts
     /// 4. Return success if call completes
+    ///
+    /// The orchestrator moves no tokens, so the deposit is recorded without
+    /// a token; goals denominated with `set_goal_asset` refuse it.
     fn deposit_to_savings(
         env: &Env,
         savings_addr: &Address,
@@ -468,7 +514,7 @@ impl Orchestrator {
         // Call add_to_goal on the savings contract
         // This will panic if the goal doesn't exist or amount is invalid
         // The panic will cause the entire transaction to revert (atomicity)
-        savings_client.add_to_goal(owner, &goal_id, &amount);
+        savings_client.add_to_goal(owner, &goal_id, &None, &amount);
 
         Ok(())
     }
@@ -978,11 +1024,18 @@ impl Orchestrator {
         )?;
 
         // Step 4: Extract allocations from remittance split
-        let allocations = Self::extract_allocations(&env, &remittance_split_addr, total_amount)
-            .map_err(|e| {
-                Self::emit_error_event(&env, &caller, symbol_short!("split"), e as u32, timestamp);
-                e
-            })?;
+        let allocations =
+            Self::extract_allocations(&env, &remittance_split_addr, &caller, total_amount)
+                .map_err(|e| {
+                    Self::emit_error_event(
+                        &env,
+                        &caller,
+                        symbol_short!("split"),
+                        e as u32,
+                        timestamp,
+                    );
+                    e
+                })?;
 
         // Extract individual amounts
         let spending_amount = allocations.get(0).unwrap_or(0);
@@ -1,7 +1,17 @@
 // Integration tests for the orchestrator contract
 
-use crate::{Orchestrator, OrchestratorClient, OrchestratorError};
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Vec};
+use crate::{Allocation, Orchestrator, OrchestratorClient, OrchestratorError};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, Address, Env, Symbol, Vec,
+};
+
+fn allocation(category: Symbol, amount: i128) -> Allocation {
+    Allocation {
+        category,
+        amount,
+        adjustment: 0,
+    }
+}
 
 // ============================================================================
 // Mock Contract Implementations
@@ -26,17 +36,48 @@ pub struct MockRemittanceSplit;
 
 #[contractimpl]
 impl MockRemittanceSplit {
-    /// Mock implementation of calculate_split
-    /// Returns [40%, 30%, 20%, 10%] split
-    pub fn calculate_split(env: Env, total_amount: i128) -> Vec<i128> {
-        let spending = (total_amount * 40) / 100;
-        let savings = (total_amount * 30) / 100;
-        let bills = (total_amount * 20) / 100;
-        let insurance = (total_amount * 10) / 100;
-
-        Vec::from_array(&env, [spending, savings, bills, insurance])
+    /// Mock implementation of get_split_allocations
+    /// Returns a 40% spending, 30% savings, 20% bills, 10% insurance split,
+    /// listed out of the standard order
+    pub fn get_split_allocations(env: Env, _owner: Address, total_amount: i128) -> Vec<Allocation> {
+        Vec::from_array(
+            &env,
+            [
+                allocation(symbol_short!("INSURANCE"), (total_amount * 10) / 100),
+                allocation(symbol_short!("SPENDING"), (total_amount * 40) / 100),
+                allocation(symbol_short!("BILLS"), (total_amount * 20) / 100),
+                allocation(symbol_short!("SAVINGS"), (total_amount * 30) / 100),
+            ],
+        )
+    }
+}
+
+/// Mock Remittance Split contract whose split has a custom category
+mod custom_split {
+    use super::*;
+
+    #[contract]
+    pub struct MockCustomSplit;
+
+    #[contractimpl]
+    impl MockCustomSplit {
+        /// Returns a 50% spending, 50% travel split
+        pub fn get_split_allocations(
+            env: Env,
+            _owner: Address,
+            total_amount: i128,
+        ) -> Vec<Allocation> {
+            Vec::from_array(
+                &env,
+                [
+                    allocation(symbol_short!("SPENDING"), total_amount / 2),
+                    allocation(symbol_short!("TRAVEL"), total_amount / 2),
+                ],
+            )
+        }
     }
 }
+use custom_split::MockCustomSplit;
 
 /// Mock Savings Goals contract for testing
 #[contract]
@@ -46,7 +87,13 @@ pub struct MockSavingsGoals;
 impl MockSavingsGoals {
     /// Mock implementation of add_to_goal
     /// Panics if goal_id == 999 (simulating goal not found)
-    pub fn add_to_goal(_env: Env, _caller: Address, goal_id: u32, amount: i128) -> i128 {
+    pub fn add_to_goal(
+        _env: Env,
+        _caller: Address,
+        goal_id: u32,
+        _token: Option<Address>,
+        amount: i128,
+    ) -> i128 {
         if goal_id == 999 {
             panic!("Goal not found");
         }
@@ -494,6 +541,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remittance_flow_rejects_non_standard_split() {
+        let (
+            env,
+            orchestrator_id,
+            family_wallet_id,
+            _remittance_split_id,
+            savings_id,
+            bills_id,
+            insurance_id,
+            user,
+        ) = setup_test_env();
+        let custom_split_id = env.register_contract(None, MockCustomSplit);
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+
+        // A split with a category the flow cannot route must not be
+        // read as [spending, savings, bills, insurance]
+        let result = client.try_execute_remittance_flow(
+            &user,
+            &10000,
+            &family_wallet_id,
+            &custom_split_id,
+            &savings_id,
+            &bills_id,
+            &insurance_id,
+            &1, // goal_id
+            &1, // bill_id
+            &1, // policy_id
+        );
+
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            OrchestratorError::RemittanceSplitFailed
+        );
+    }
+
     #[test]
     fn test_get_execution_stats() {
         let (env, orchestrator_id, _, _, _, _, _, _) = setup_test_env();
@@ -1,11 +1,62 @@
 #![no_std]
 mod test;
 
+use remitwise_common::FamilyRole;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, vec,
-    Address, Env, Map, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, vec, Address, Env, Map, Symbol, Vec,
 };
 
+/// FX oracle interface used to convert remittances into the home asset.
+#[contractclient(name = "FxOracleClient")]
+pub trait FxOracle {
+    /// Units of `quote` per one unit of `base`, scaled by `FX_RATE_SCALE`.
+    fn get_rate(env: Env, base: Address, quote: Address) -> i128;
+}
+
+/// Fixed-point scale of oracle rates (7 decimals, as for Stellar assets).
+pub const FX_RATE_SCALE: i128 = 10_000_000;
+
+/// Family member record as returned by the family wallet contract.
+#[contracttype]
+#[derive(Clone)]
+pub struct FamilyMember {
+    pub address: Address,
+    pub role: FamilyRole,
+    pub spending_limit: i128,
+    pub added_at: u64,
+}
+
+/// Family wallet interface used to check approver roles.
+#[contractclient(name = "FamilyWalletClient")]
+pub trait FamilyWallet {
+    fn get_family_member(env: Env, member: Address) -> Option<FamilyMember>;
+}
+
+/// Bill payments interface read by split rules.
+#[contractclient(name = "BillPaymentsClient")]
+pub trait BillPaymentsTrait {
+    fn get_total_unpaid(env: Env, owner: Address) -> i128;
+}
+
+/// Savings statistics as returned by the savings goals contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsStats {
+    pub total_contributed: i128,
+    pub total_withdrawn: i128,
+    pub net_saved: i128,
+    pub active_goals: u32,
+    pub average_monthly_savings: i128,
+}
+
+/// Savings goals interface read by split rules and rebalancing suggestions.
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsTrait {
+    fn is_goal_completed(env: Env, goal_id: u32) -> bool;
+    fn get_savings_stats(env: Env, owner: Address, from: u64, to: u64) -> SavingsStats;
+}
+
 // Event topics
 const SPLIT_INITIALIZED: Symbol = symbol_short!("init");
 const SPLIT_CALCULATED: Symbol = symbol_short!("calc");
@@ -14,10 +65,11 @@ const SPLIT_CALCULATED: Symbol = symbol_short!("calc");
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct SplitInitializedEvent {
-    pub spending_percent: u32,
-    pub savings_percent: u32,
-    pub bills_percent: u32,
-    pub insurance_percent: u32,
+    pub owner: Address,
+    pub spending_bps: u32,
+    pub savings_bps: u32,
+    pub bills_bps: u32,
+    pub insurance_bps: u32,
     pub timestamp: u64,
 }
 
@@ -36,13 +88,66 @@ pub enum RemittanceSplitError {
     ChecksumMismatch = 9,
     InvalidDueDate = 10,
     ScheduleNotFound = 11,
+    InvalidCategories = 12,
+    DestinationNotSet = 13,
+    InsufficientBalance = 14,
+    ProfileNotFound = 15,
+    TooManyProfiles = 16,
+    ChangeNotFound = 17,
+    TooManyScheduledChanges = 18,
+    InvalidLimits = 19,
+    LimitsUnsatisfiable = 20,
+    ConfigLocked = 21,
+    ScheduledChangeConflict = 22,
+    TemplateNotFound = 23,
+    TooManyTemplates = 24,
+    InvalidShares = 25,
+    FxNotConfigured = 26,
+    FxRateUnavailable = 27,
+    SlippageExceeded = 28,
+    InvalidFee = 29,
+    InvalidRules = 30,
+    ApprovalNotPending = 31,
+    ApprovalRequired = 32,
+    CorridorNotFound = 33,
+    TooManyCorridors = 34,
+    CooldownActive = 35,
+    InvalidWindow = 36,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Allocation {
     pub category: Symbol,
     pub amount: i128,
+    /// Change made by the owner's floors and caps (zero when none applied).
+    pub adjustment: i128,
+}
+
+/// Absolute bounds on one category's amount. Zero means no floor / no cap.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CategoryLimit {
+    pub category: Symbol,
+    pub floor: i128,
+    pub cap: i128,
+}
+
+/// A family member's cut of the spending portion, in basis points of that
+/// portion, paid to them directly by `execute_split`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SpendingShare {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+/// A named split category and its share of each remittance in basis points.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CategoryAlloc {
+    pub name: Symbol,
+    pub bps: u32,
 }
 
 #[derive(Clone)]
@@ -58,10 +163,49 @@ pub struct AccountGroup {
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
 
-/// Split configuration with owner tracking for access control
+/// Split configuration with owner tracking for access control. Shares are in
+/// basis points and sum to 10000.
 #[derive(Clone)]
 #[contracttype]
 pub struct SplitConfig {
+    pub owner: Address,
+    pub spending_bps: u32,
+    pub savings_bps: u32,
+    pub bills_bps: u32,
+    pub insurance_bps: u32,
+    pub timestamp: u64,
+    pub initialized: bool,
+}
+
+/// What a split rule checks, reading the named contract at calculation time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum RuleCondition {
+    /// The owner's unpaid bills in the bill payments contract exceed the
+    /// amount.
+    UnpaidBillsAbove(Address, i128),
+    /// The goal in the savings goals contract has not reached its target.
+    GoalBelowTarget(Address, u32),
+}
+
+/// While `condition` holds, move `bps` of the remittance from one category
+/// to another (e.g. boost bills by 1000 bps out of spending).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitRule {
+    pub condition: RuleCondition,
+    pub from_category: Symbol,
+    pub to_category: Symbol,
+    pub bps: u32,
+}
+
+/// Whole-percent configuration written before basis points, either per owner
+/// or, before configurations were keyed by owner, as the contract's single
+/// instance `CONFIG`. Still read, and converted, so existing owners keep
+/// their split until they next update it.
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacySplitConfig {
     pub owner: Address,
     pub spending_percent: u32,
     pub savings_percent: u32,
@@ -74,14 +218,52 @@ pub struct SplitConfig {
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct SplitCalculatedEvent {
+    pub owner: Address,
     pub total_amount: i128,
-    pub spending_amount: i128,
-    pub savings_amount: i128,
-    pub bills_amount: i128,
-    pub insurance_amount: i128,
+    pub allocations: Vec<Allocation>,
+    /// Rounding dust assigned by the owner's remainder policy.
+    pub remainder: i128,
+    /// Rate the remittance was converted at into the home asset, scaled by
+    /// `FX_RATE_SCALE` (exactly `FX_RATE_SCALE` when nothing was converted).
+    pub fx_rate: i128,
+    /// Platform fee deducted from `total_amount` before splitting (zero for
+    /// calculations that transfer nothing).
+    pub fee: i128,
     pub timestamp: u64,
 }
 
+/// Platform fee taken from every executed split and sent to the treasury.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeConfig {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
+
+/// A household's home asset and how remittances in other assets are
+/// converted into it before splitting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FxConfig {
+    pub home_asset: Address,
+    pub oracle: Address,
+    /// Largest accepted gap between the oracle rate and the sender's quote.
+    pub max_slippage_bps: u32,
+}
+
+/// Which category receives the integer-division dust left after rounding
+/// every category down.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RemainderPolicy {
+    /// The last category (insurance by default) takes it all.
+    LastCategory,
+    /// The named category takes it all.
+    Category(Symbol),
+    /// One unit each to the categories with the largest fractional parts.
+    LargestRemainder,
+}
+
 /// Events emitted by the contract for audit trail
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -89,6 +271,160 @@ pub enum SplitEvent {
     Initialized,
     Updated,
     Calculated,
+    CategoriesSet,
+    Executed,
+    RemainderPolicySet,
+    ProfileSaved,
+    ProfileSwitched,
+    ChangeScheduled,
+    ChangeCancelled,
+    ConfigLocked,
+    TemplateApplied,
+    DestinationSet,
+    SpendingSharesSet,
+    FxConfigSet,
+    FeeConfigSet,
+    RulesSet,
+    Deposited,
+    CooldownSet,
+    ReallocationPending,
+    ReallocationApproved,
+    CorridorSet,
+    Override,
+}
+
+/// One change to an owner's split, kept for both sender and recipient to
+/// audit. Scheduled changes are stamped with their effective time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigChange {
+    pub operation: Symbol,
+    pub changed_by: Address,
+    pub old_categories: Vec<CategoryAlloc>,
+    pub new_categories: Vec<CategoryAlloc>,
+    pub timestamp: u64,
+}
+
+/// A split announced in advance. It becomes the live split once the ledger
+/// reaches `effective_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledSplitChange {
+    pub id: u32,
+    pub categories: Vec<CategoryAlloc>,
+    pub effective_at: u64,
+    pub created_at: u64,
+}
+
+/// An admin-managed preset split that any owner can adopt with
+/// `apply_template`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitTemplate {
+    pub id: u32,
+    pub name: Symbol,
+    pub categories: Vec<CategoryAlloc>,
+}
+
+/// Days a reduction to savings or insurance waits before taking effect.
+/// Lowering the cooldown itself only counts once the previous cooldown has
+/// run from `changed_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CooldownConfig {
+    pub days: u32,
+    pub previous_days: u32,
+    pub changed_at: u64,
+}
+
+/// Owner's large-reallocation approval settings.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitApprovalConfig {
+    /// Family wallet whose Owner/Admin members may approve changes.
+    pub family_wallet: Address,
+    /// Changes moving more than this many basis points need approval.
+    pub threshold_bps: u32,
+}
+
+/// Whether `owner`'s latest change is live or awaiting family approval.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigStatus {
+    Approved,
+    Pending,
+}
+
+/// A large change held until a family Owner or Admin approves it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingReallocation {
+    pub categories: Vec<CategoryAlloc>,
+    /// Basis points the change moves between categories.
+    pub shifted_bps: u32,
+    pub requested_at: u64,
+}
+
+/// A destination change held until a family Owner or Admin approves it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingDestination {
+    pub category: Symbol,
+    pub destination: Address,
+    /// Share of the split, in basis points, the change would redirect.
+    pub shifted_bps: u32,
+    pub requested_at: u64,
+}
+
+/// Amount routed to one category over a stats window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryTotal {
+    pub category: Symbol,
+    pub amount: i128,
+    /// Share of the window's total, in basis points.
+    pub share_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationStats {
+    pub from: u64,
+    pub to: u64,
+    pub total: i128,
+    pub categories: Vec<CategoryTotal>,
+}
+
+/// A proposed split and the downstream state behind it, for the app to
+/// offer as a one-tap `set_categories`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceSuggestion {
+    pub current: Vec<CategoryAlloc>,
+    pub suggested: Vec<CategoryAlloc>,
+    /// Unpaid bills total read from the bills destination.
+    pub unpaid_bills: i128,
+    /// Savings goals still below target, read from the savings destination.
+    pub active_goals: u32,
+    /// Amount routed over the last 30 days, used to size the bills share.
+    pub monthly_routed: i128,
+}
+
+/// A saved, named set of categories the owner can switch to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitProfile {
+    pub name: Symbol,
+    pub categories: Vec<CategoryAlloc>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileSwitchedEvent {
+    pub owner: Address,
+    pub previous: Option<Symbol>,
+    pub active: Symbol,
+    pub timestamp: u64,
 }
 
 /// Snapshot for data export/import (migration). Checksum is a simple numeric digest for on-chain verification.
@@ -137,7 +473,24 @@ pub enum ScheduleEvent {
     Cancelled,
 }
 
-const SNAPSHOT_VERSION: u32 = 1;
+const SNAPSHOT_VERSION: u32 = 2;
+const MAX_CATEGORIES: u32 = 10;
+const TOTAL_BPS: u32 = 10_000;
+const MAX_PROFILES: u32 = 10;
+const MAX_SCHEDULED_CHANGES: u32 = 10;
+const MAX_TEMPLATES: u32 = 20;
+const MAX_FEE_BPS: u32 = 1_000;
+const MAX_RULES: u32 = 5;
+const MAX_CORRIDORS: u32 = 10;
+const MAX_STATS_DAYS: u32 = 400;
+/// Longest window `get_allocation_stats` accepts, in whole days.
+const MAX_STATS_WINDOW_DAYS: u64 = 366;
+/// Most of the split `suggest_rebalance` will propose for bills.
+const MAX_SUGGESTED_BILLS_BPS: u32 = 5_000;
+/// Extra savings share proposed while goals are below target.
+const SAVINGS_NUDGE_BPS: u32 = 500;
+const SECONDS_PER_DAY: u64 = 86_400;
+const MAX_CONFIG_HISTORY: u32 = 100;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
 
@@ -169,12 +522,9 @@ impl RemittanceSplit {
         new_admin: Address,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        if contract_owner != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
         env.storage()
@@ -184,12 +534,9 @@ impl RemittanceSplit {
     }
     pub fn pause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        let admin = Self::get_pause_admin(&env).unwrap_or(contract_owner);
         if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -202,12 +549,9 @@ impl RemittanceSplit {
     }
     pub fn unpause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        let admin = Self::get_pause_admin(&env).unwrap_or(contract_owner);
         if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -236,12 +580,9 @@ impl RemittanceSplit {
         new_admin: Address,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        if contract_owner != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
         env.storage()
@@ -255,12 +596,9 @@ impl RemittanceSplit {
         new_version: u32,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_upgrade_admin(&env).unwrap_or(config.owner);
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        let admin = Self::get_upgrade_admin(&env).unwrap_or(contract_owner);
         if admin != caller {
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -275,7 +613,35 @@ impl RemittanceSplit {
         Ok(())
     }
 
-    /// Set or update the split percentages used to allocate remittances.
+    /// Set the contract owner, who administers pause, upgrades, templates,
+    /// destinations and fees. Call it in the same transaction as the deploy
+    /// so nobody can claim the contract first. A deployment from before
+    /// configurations were keyed by owner keeps the owner of its original
+    /// configuration and does not need this.
+    ///
+    /// # Errors
+    /// - `AlreadyInitialized` if the contract already has an owner
+    pub fn initialize_admin(env: Env, admin: Address) -> Result<(), RemittanceSplitError> {
+        admin.require_auth();
+        if Self::get_contract_owner(&env).is_some() {
+            return Err(RemittanceSplitError::AlreadyInitialized);
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("OWNER"), &admin);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("admin")), admin);
+        Ok(())
+    }
+
+    /// Set the split percentages used to allocate `owner`'s remittances.
+    ///
+    /// Each owner has an independent configuration, so several senders or
+    /// households can share one deployment. Configuring a split does not make
+    /// the owner an administrator; see `initialize_admin`. Percentages are
+    /// stored as basis points; use `initialize_split_bps` for finer shares
+    /// such as 12.5%.
     ///
     /// # Arguments
     /// * `owner` - Address of the split owner (must authorize)
@@ -292,7 +658,7 @@ impl RemittanceSplit {
     /// - If owner doesn't authorize the transaction
     /// - If nonce is invalid (replay)
     /// - If percentages don't sum to 100
-    /// - If split is already initialized (use update_split instead)
+    /// - If owner's split is already initialized (use update_split instead)
     pub fn initialize_split(
         env: Env,
         owner: Address,
@@ -301,19 +667,37 @@ impl RemittanceSplit {
         savings_percent: u32,
         bills_percent: u32,
         insurance_percent: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        let bps = Self::percents_to_bps([
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+        ]);
+        Self::initialize_split_bps(env, owner, nonce, bps[0], bps[1], bps[2], bps[3])
+    }
+
+    /// Same as `initialize_split`, with shares in basis points that must sum
+    /// to 10000.
+    pub fn initialize_split_bps(
+        env: Env,
+        owner: Address,
+        nonce: u64,
+        spending_bps: u32,
+        savings_bps: u32,
+        bills_bps: u32,
+        insurance_bps: u32,
     ) -> Result<bool, RemittanceSplitError> {
         owner.require_auth();
         Self::require_not_paused(&env)?;
         Self::require_nonce(&env, &owner, nonce)?;
 
-        let existing: Option<SplitConfig> = env.storage().instance().get(&symbol_short!("CONFIG"));
-        if existing.is_some() {
+        if Self::load_config(&env, &owner).is_some() {
             Self::append_audit(&env, symbol_short!("init"), &owner, false);
             return Err(RemittanceSplitError::AlreadyInitialized);
         }
 
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
+        if !Self::is_full_split(&[spending_bps, savings_bps, bills_bps, insurance_bps]) {
             Self::append_audit(&env, symbol_short!("init"), &owner, false);
             return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
         }
@@ -322,27 +706,18 @@ impl RemittanceSplit {
 
         let config = SplitConfig {
             owner: owner.clone(),
-            spending_percent,
-            savings_percent,
-            bills_percent,
-            insurance_percent,
+            spending_bps,
+            savings_bps,
+            bills_bps,
+            insurance_bps,
             timestamp: env.ledger().timestamp(),
             initialized: true,
         };
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
-        );
+        Self::promote_due_change(&env, &owner);
+        let old = Self::load_categories(&env, &owner);
+        Self::save_config(&env, &config);
+        Self::clear_categories(&env, &owner);
+        Self::record_config_change(&env, &owner, &owner, symbol_short!("init"), old);
 
         Self::increment_nonce(&env, &owner)?;
         Self::append_audit(&env, symbol_short!("init"), &owner, true);
@@ -360,54 +735,63 @@ impl RemittanceSplit {
         savings_percent: u32,
         bills_percent: u32,
         insurance_percent: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        let bps = Self::percents_to_bps([
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+        ]);
+        Self::update_split_bps(env, caller, nonce, bps[0], bps[1], bps[2], bps[3])
+    }
+
+    /// Same as `update_split`, with shares in basis points that must sum to
+    /// 10000.
+    pub fn update_split_bps(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        spending_bps: u32,
+        savings_bps: u32,
+        bills_bps: u32,
+        insurance_bps: u32,
     ) -> Result<bool, RemittanceSplitError> {
         caller.require_auth();
         Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &caller)?;
         Self::require_nonce(&env, &caller, nonce)?;
 
-        let mut config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-
-        if config.owner != caller {
-            Self::append_audit(&env, symbol_short!("update"), &caller, false);
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        let mut config =
+            Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
 
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
+        if !Self::is_full_split(&[spending_bps, savings_bps, bills_bps, insurance_bps]) {
             Self::append_audit(&env, symbol_short!("update"), &caller, false);
             return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
         }
 
         Self::extend_instance_ttl(&env);
+        Self::promote_due_change(&env, &caller);
+        let new =
+            Self::standard_categories(&env, spending_bps, savings_bps, bills_bps, insurance_bps);
+        if Self::defer_change(&env, &caller, &new)? {
+            return Ok(true);
+        }
+        let old = Self::load_categories(&env, &caller);
 
-        config.spending_percent = spending_percent;
-        config.savings_percent = savings_percent;
-        config.bills_percent = bills_percent;
-        config.insurance_percent = insurance_percent;
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
-        );
+        config.spending_bps = spending_bps;
+        config.savings_bps = savings_bps;
+        config.bills_bps = bills_bps;
+        config.insurance_bps = insurance_bps;
+        Self::save_config(&env, &config);
+        Self::clear_categories(&env, &caller);
+        Self::record_config_change(&env, &caller, &caller, symbol_short!("update"), old);
 
         let event = SplitInitializedEvent {
-            spending_percent,
-            savings_percent,
-            bills_percent,
-            insurance_percent,
+            owner: caller.clone(),
+            spending_bps,
+            savings_bps,
+            bills_bps,
+            insurance_bps,
             timestamp: env.ledger().timestamp(),
         };
         env.events().publish((SPLIT_INITIALIZED,), event);
@@ -417,318 +801,3113 @@ impl RemittanceSplit {
         Ok(true)
     }
 
-    pub fn get_split(env: &Env) -> Vec<u32> {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("SPLIT"))
-            .unwrap_or_else(|| vec![&env, 50, 30, 15, 5])
-    }
+    /// Replace `owner`'s split with an arbitrary list of named categories.
+    ///
+    /// Category names must be unique and the shares must sum to 10000 basis
+    /// points. Calculations then return one amount per category, in the order
+    /// given here. A later `update_split` reverts to the four standard
+    /// categories.
+    ///
+    /// # Errors
+    /// - `InvalidCategories` if the list is empty, longer than 10 entries, or
+    ///   repeats a name
+    /// - `PercentagesDoNotSumTo100` if the shares don't sum to 10000
+    pub fn set_categories(
+        env: Env,
+        owner: Address,
+        categories: Vec<CategoryAlloc>,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
 
-    pub fn get_config(env: Env) -> Option<SplitConfig> {
-        env.storage().instance().get(&symbol_short!("CONFIG"))
-    }
+        if let Err(e) = Self::validate_categories(&categories) {
+            Self::append_audit(&env, symbol_short!("cats"), &owner, false);
+            return Err(e);
+        }
 
-    pub fn calculate_split(
-        env: Env,
-        total_amount: i128,
-    ) -> Result<Vec<i128>, RemittanceSplitError> {
-        let amounts = Self::calculate_split_amounts(&env, total_amount, true)?;
-        Ok(vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]])
+        Self::extend_instance_ttl(&env);
+        Self::promote_due_change(&env, &owner);
+        if Self::defer_change(&env, &owner, &categories)? {
+            return Ok(true);
+        }
+        let old = Self::load_categories(&env, &owner);
+        Self::clear_categories(&env, &owner);
+        Self::store_categories(&env, &owner, &categories);
+        Self::record_config_change(&env, &owner, &owner, symbol_short!("cats"), old);
+
+        Self::append_audit(&env, symbol_short!("cats"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::CategoriesSet), owner);
+
+        Ok(true)
     }
 
-    pub fn distribute_usdc(
+    /// Add or replace the preset template `id`. Only the contract owner may
+    /// call this. Until the first call, `get_templates` returns the built-in
+    /// presets, which are then kept and can be edited or removed like any
+    /// other template.
+    ///
+    /// # Errors
+    /// - `Unauthorized` if the caller is not the contract owner
+    /// - `InvalidCategories` / `PercentagesDoNotSumTo100` as for `set_categories`
+    /// - `TooManyTemplates` if 20 templates already exist
+    pub fn set_template(
         env: Env,
-        usdc_contract: Address,
-        from: Address,
-        nonce: u64,
-        accounts: AccountGroup,
-        total_amount: i128,
+        caller: Address,
+        id: u32,
+        name: Symbol,
+        categories: Vec<CategoryAlloc>,
     ) -> Result<bool, RemittanceSplitError> {
-        if total_amount <= 0 {
-            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
-            return Err(RemittanceSplitError::InvalidAmount);
+        caller.require_auth();
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        if contract_owner != caller {
+            return Err(RemittanceSplitError::Unauthorized);
         }
+        Self::validate_categories(&categories)?;
 
-        from.require_auth();
-        Self::require_nonce(&env, &from, nonce)?;
-
-        let amounts = Self::calculate_split_amounts(&env, total_amount, false)?;
-        let token = TokenClient::new(&env, &usdc_contract);
-
-        if amounts[0] > 0 {
-            token.transfer(&from, &accounts.spending, &amounts[0]);
+        let mut templates = Self::load_templates(&env);
+        if !templates.contains_key(id) && templates.len() >= MAX_TEMPLATES {
+            return Err(RemittanceSplitError::TooManyTemplates);
         }
-        if amounts[1] > 0 {
-            token.transfer(&from, &accounts.savings, &amounts[1]);
-        }
-        if amounts[2] > 0 {
-            token.transfer(&from, &accounts.bills, &amounts[2]);
-        }
-        if amounts[3] > 0 {
-            token.transfer(&from, &accounts.insurance, &amounts[3]);
+        templates.set(
+            id,
+            SplitTemplate {
+                id,
+                name,
+                categories,
+            },
+        );
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("TEMPLATES"), &templates);
+        Self::append_audit(&env, symbol_short!("template"), &caller, true);
+        Ok(true)
+    }
+
+    /// Remove template `id`. Only the contract owner may call this. Owners
+    /// who already applied it keep their split.
+    ///
+    /// # Errors
+    /// - `Unauthorized` if the caller is not the contract owner
+    /// - `TemplateNotFound` if no template has that id
+    pub fn remove_template(
+        env: Env,
+        caller: Address,
+        id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        if contract_owner != caller {
+            return Err(RemittanceSplitError::Unauthorized);
         }
 
-        Self::increment_nonce(&env, &from)?;
-        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        let mut templates = Self::load_templates(&env);
+        if templates.remove(id).is_none() {
+            return Err(RemittanceSplitError::TemplateNotFound);
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("TEMPLATES"), &templates);
+        Self::append_audit(&env, symbol_short!("template"), &caller, true);
         Ok(true)
     }
 
-    pub fn get_usdc_balance(env: &Env, usdc_contract: Address, account: Address) -> i128 {
-        TokenClient::new(env, &usdc_contract).balance(&account)
+    /// All preset templates, ordered by id.
+    pub fn get_templates(env: Env) -> Vec<SplitTemplate> {
+        Self::load_templates(&env).values()
     }
 
-    pub fn get_split_allocations(
+    /// Replace `owner`'s split with template `template_id`, as if its
+    /// categories were passed to `set_categories`. Later changes to the
+    /// template do not affect owners who already applied it.
+    ///
+    /// # Errors
+    /// - `TemplateNotFound` if no template has that id
+    /// - `ConfigLocked` if the owner's split is locked
+    pub fn apply_template(
+        env: Env,
+        owner: Address,
+        template_id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        let template = Self::load_templates(&env)
+            .get(template_id)
+            .ok_or(RemittanceSplitError::TemplateNotFound)?;
+
+        Self::extend_instance_ttl(&env);
+        Self::promote_due_change(&env, &owner);
+        if Self::defer_change(&env, &owner, &template.categories)? {
+            return Ok(true);
+        }
+        let old = Self::load_categories(&env, &owner);
+        Self::clear_categories(&env, &owner);
+        Self::store_categories(&env, &owner, &template.categories);
+        Self::record_config_change(&env, &owner, &owner, symbol_short!("template"), old);
+
+        Self::append_audit(&env, symbol_short!("template"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::TemplateApplied),
+            (owner, template_id),
+        );
+        Ok(true)
+    }
+
+    /// Save a named split profile (e.g. "normal", "school fees", "emergency")
+    /// that can later be activated with `switch_profile`. Saving under an
+    /// existing name replaces that profile; saving the active profile does
+    /// not change the live split until it is switched to again.
+    ///
+    /// # Errors
+    /// - `InvalidCategories` / `PercentagesDoNotSumTo100` as for `set_categories`
+    /// - `TooManyProfiles` if the owner already has 10 other profiles
+    pub fn save_profile(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+        categories: Vec<CategoryAlloc>,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::validate_categories(&categories)?;
+
+        let mut profiles = Self::load_profiles(&env, &owner);
+        if !profiles.contains_key(name.clone()) && profiles.len() >= MAX_PROFILES {
+            return Err(RemittanceSplitError::TooManyProfiles);
+        }
+        profiles.set(name.clone(), categories);
+        Self::save_profiles(&env, &owner, &profiles);
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ProfileSaved),
+            (owner, name),
+        );
+        Ok(true)
+    }
+
+    pub fn delete_profile(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let mut profiles = Self::load_profiles(&env, &owner);
+        if profiles.remove(name.clone()).is_none() {
+            return Err(RemittanceSplitError::ProfileNotFound);
+        }
+        Self::save_profiles(&env, &owner, &profiles);
+        // The live split is kept; it just no longer has a profile name.
+        if Self::get_active_profile(env.clone(), owner.clone()) == Some(name) {
+            env.storage()
+                .persistent()
+                .remove(&(symbol_short!("ACT_PROF"), owner));
+        }
+        Ok(true)
+    }
+
+    /// Make the named profile `owner`'s live split.
+    ///
+    /// # Errors
+    /// - `ProfileNotFound` if no profile has that name
+    pub fn switch_profile(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        let categories = Self::load_profiles(&env, &owner)
+            .get(name.clone())
+            .ok_or(RemittanceSplitError::ProfileNotFound)?;
+        Self::promote_due_change(&env, &owner);
+        if Self::defer_change(&env, &owner, &categories)? {
+            return Ok(true);
+        }
+        let previous = Self::get_active_profile(env.clone(), owner.clone());
+        let old = Self::load_categories(&env, &owner);
+
+        Self::extend_instance_ttl(&env);
+        Self::store_categories(&env, &owner, &categories);
+        Self::record_config_change(&env, &owner, &owner, symbol_short!("profile"), old);
+        let key = (symbol_short!("ACT_PROF"), owner.clone());
+        env.storage().persistent().set(&key, &name);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        Self::append_audit(&env, symbol_short!("profile"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ProfileSwitched),
+            ProfileSwitchedEvent {
+                owner,
+                previous,
+                active: name,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(true)
+    }
+
+    /// Announce a split that takes effect at `effective_at`. Until then the
+    /// current split applies; from then on `calculate_split`, `execute_split`
+    /// and the getters use the new one. If several changes are due, the one
+    /// with the latest `effective_at` wins. A direct change made after a
+    /// scheduled change took effect replaces it.
+    ///
+    /// # Errors
+    /// - `InvalidDueDate` if `effective_at` is not in the future
+    /// - `InvalidCategories` / `PercentagesDoNotSumTo100` as for `set_categories`
+    /// - `TooManyScheduledChanges` if 10 changes are already pending
+    pub fn schedule_split_change(
+        env: Env,
+        owner: Address,
+        categories: Vec<CategoryAlloc>,
+        effective_at: u64,
+    ) -> Result<u32, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let now = env.ledger().timestamp();
+        if effective_at <= now {
+            return Err(RemittanceSplitError::InvalidDueDate);
+        }
+        if effective_at < Self::get_config_lock(env.clone(), owner.clone()) {
+            return Err(RemittanceSplitError::ConfigLocked);
+        }
+        Self::validate_categories(&categories)?;
+
+        Self::promote_due_change(&env, &owner);
+        if Self::needs_approval(&env, &owner, &categories).is_some() {
+            return Err(RemittanceSplitError::ApprovalRequired);
+        }
+        // A reduction cannot be scheduled sooner than the cooldown allows.
+        let effective_at =
+            if Self::reduces_protected(&Self::load_categories(&env, &owner), &categories) {
+                effective_at.max(now + Self::cooldown_seconds(&env, &owner))
+            } else {
+                effective_at
+            };
+        let id = Self::insert_scheduled_change(&env, &owner, categories, effective_at)?;
+        Self::append_audit(&env, symbol_short!("sched"), &owner, true);
+        Ok(id)
+    }
+
+    /// Require `days` to pass before a change that lowers the savings or
+    /// insurance share takes effect. Such changes made through
+    /// `update_split`, `set_categories`, `switch_profile`, `apply_template`
+    /// or `import_snapshot` are queued as scheduled changes instead of
+    /// applied; increases, and changes that leave both shares alone, stay
+    /// immediate. A deferred profile switch applies the profile's categories
+    /// without making it the active profile. Rules, limits and corridors
+    /// cannot be queued, so while a cooldown is set, `set_split_rules`,
+    /// `set_category_limits`, `set_corridor_categories` and `remove_corridor`
+    /// reject changes that would take from savings or insurance, and
+    /// `set_destination` cannot repoint savings, insurance or bills. Raising
+    /// the cooldown is immediate; lowering it only counts once the old
+    /// cooldown has run. Zero disables it.
+    pub fn set_cooldown(env: Env, owner: Address, days: u32) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let key = (symbol_short!("COOLDOWN"), owner.clone());
+        let current = Self::get_cooldown(env.clone(), owner.clone());
+        env.storage().persistent().set(
+            &key,
+            &CooldownConfig {
+                days,
+                previous_days: current,
+                changed_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        Self::append_audit(&env, symbol_short!("cooldown"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::CooldownSet),
+            (owner, days),
+        );
+        Ok(true)
+    }
+
+    /// Hold `owner`'s changes that move more than `threshold_bps` away from
+    /// the last approved split until an Owner or Admin member of
+    /// `family_wallet` approves them with `approve_split_change`. Until then
+    /// the previous split stays live and `get_config_status` reports
+    /// `Pending`; a newer change replaces the pending one. Repointing a
+    /// category whose share exceeds the threshold with `set_destination` is
+    /// held the same way. Pass a threshold of 0 to turn this off.
+    ///
+    /// The owner sets approval up, and may lower the threshold afterwards.
+    /// Turning it off, raising the threshold or moving it to another wallet
+    /// must be done by an approver of the current wallet.
+    ///
+    /// # Errors
+    /// - `Unauthorized` if `caller` may not make the change
+    /// - `ConfigLocked` if the owner's split is locked
+    pub fn configure_split_approval(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        family_wallet: Address,
+        threshold_bps: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        let current = Self::get_split_approval_config(env.clone(), owner.clone());
+        let by_approver = current
+            .as_ref()
+            .is_some_and(|config| Self::is_approver(&env, config, &owner, &caller));
+        let allowed = match &current {
+            None => caller == owner,
+            Some(config) => {
+                let tightens = threshold_bps > 0
+                    && threshold_bps <= config.threshold_bps
+                    && family_wallet == config.family_wallet;
+                by_approver || (tightens && caller == owner)
+            }
+        };
+        if !allowed {
+            Self::append_audit(&env, symbol_short!("appr_cfg"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        let key = (symbol_short!("APPR_CFG"), owner.clone());
+        let base_key = (symbol_short!("APPR_BASE"), owner.clone());
+        if threshold_bps == 0 {
+            env.storage().persistent().remove(&key);
+            env.storage().persistent().remove(&base_key);
+        } else {
+            env.storage().persistent().set(
+                &key,
+                &SplitApprovalConfig {
+                    family_wallet,
+                    threshold_bps,
+                },
+            );
+            env.storage().persistent().extend_ttl(
+                &key,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+            // Shifts are measured from the split in force when approval was
+            // set up or an approver last acted; the owner tightening the
+            // threshold must not reset that.
+            if current.is_none() || by_approver {
+                Self::save_approved_split(&env, &owner, &Self::load_categories(&env, &owner));
+            }
+        }
+        Self::append_audit(&env, symbol_short!("appr_cfg"), &caller, true);
+        Ok(true)
+    }
+
+    pub fn get_split_approval_config(env: Env, owner: Address) -> Option<SplitApprovalConfig> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("APPR_CFG"), owner))
+    }
+
+    pub fn get_pending_reallocation(env: Env, owner: Address) -> Option<PendingReallocation> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("PEND_CHG"), owner))
+    }
+
+    pub fn get_config_status(env: Env, owner: Address) -> ConfigStatus {
+        if Self::get_pending_reallocation(env.clone(), owner.clone()).is_some()
+            || Self::get_pending_destination(env, owner).is_some()
+        {
+            ConfigStatus::Pending
+        } else {
+            ConfigStatus::Approved
+        }
+    }
+
+    /// Approve `owner`'s pending changes, which then apply as if just made
+    /// (a savings or insurance cut still waits out the owner's cooldown). A
+    /// held split change and a held destination are approved together.
+    ///
+    /// # Errors
+    /// - `ApprovalNotPending` if no change is awaiting approval
+    /// - `Unauthorized` if `approver` is not an Owner or Admin of the
+    ///   owner's family wallet
+    /// - `ConfigLocked` if the owner's split has been locked since
+    pub fn approve_split_change(
+        env: Env,
+        approver: Address,
+        owner: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        approver.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        let pending = Self::get_pending_reallocation(env.clone(), owner.clone());
+        let destination = Self::get_pending_destination(env.clone(), owner.clone());
+        if pending.is_none() && destination.is_none() {
+            return Err(RemittanceSplitError::ApprovalNotPending);
+        }
+        let config = Self::get_split_approval_config(env.clone(), owner.clone())
+            .ok_or(RemittanceSplitError::Unauthorized)?;
+        if !Self::is_approver(&env, &config, &owner, &approver) {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::promote_due_change(&env, &owner);
+        if let Some(destination) = destination {
+            env.storage()
+                .persistent()
+                .remove(&(symbol_short!("PEND_DST"), owner.clone()));
+            Self::bind_destination(&env, &owner, destination.category, destination.destination);
+        }
+        if let Some(pending) = pending {
+            env.storage()
+                .persistent()
+                .remove(&(symbol_short!("PEND_CHG"), owner.clone()));
+            Self::save_approved_split(&env, &owner, &pending.categories);
+            if !Self::defer_reduction(&env, &owner, &pending.categories)? {
+                let old = Self::load_categories(&env, &owner);
+                Self::clear_categories(&env, &owner);
+                Self::store_categories(&env, &owner, &pending.categories);
+                Self::record_config_change(&env, &owner, &approver, symbol_short!("approved"), old);
+            }
+        }
+
+        Self::append_audit(&env, symbol_short!("approved"), &approver, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ReallocationApproved),
+            (owner, approver),
+        );
+        Ok(true)
+    }
+
+    /// The cooldown in days currently in force for `owner`.
+    pub fn get_cooldown(env: Env, owner: Address) -> u32 {
+        let config: Option<CooldownConfig> = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("COOLDOWN"), owner));
+        match config {
+            Some(config) if config.days < config.previous_days => {
+                let settles_at = config.changed_at + config.previous_days as u64 * SECONDS_PER_DAY;
+                if env.ledger().timestamp() < settles_at {
+                    config.previous_days
+                } else {
+                    config.days
+                }
+            }
+            Some(config) => config.days,
+            None => 0,
+        }
+    }
+
+    /// Withdraw a scheduled change that has not taken effect yet.
+    pub fn cancel_split_change(
+        env: Env,
+        owner: Address,
+        change_id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+
+        let now = env.ledger().timestamp();
+        let pending = Self::load_scheduled_changes(&env, &owner);
+        let mut remaining = Vec::new(&env);
+        let mut found = false;
+        for change in pending.iter() {
+            if change.id == change_id && change.effective_at > now {
+                found = true;
+            } else {
+                remaining.push_back(change);
+            }
+        }
+        if !found {
+            return Err(RemittanceSplitError::ChangeNotFound);
+        }
+        Self::save_scheduled_changes(&env, &owner, &remaining);
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ChangeCancelled),
+            (owner, change_id),
+        );
+        Ok(true)
+    }
+
+    /// Changes that have not taken effect yet, in effective order.
+    pub fn get_scheduled_changes(env: Env, owner: Address) -> Vec<ScheduledSplitChange> {
+        let now = env.ledger().timestamp();
+        let mut result = Vec::new(&env);
+        for change in Self::load_scheduled_changes(&env, &owner).iter() {
+            if change.effective_at > now {
+                result.push_back(change);
+            }
+        }
+        result
+    }
+
+    /// Lock `owner`'s split until `until_ts`, typically right after a sender
+    /// funds the household. While locked no one acting as the owner can
+    /// reconfigure it: every call that changes how remittances are split
+    /// (`update_split`, `set_categories`, profiles, templates, corridors,
+    /// spending shares, rules, limits, the remainder policy and snapshot
+    /// import) fails, and changes can only be scheduled from `until_ts` on.
+    /// A lock can be extended but never shortened.
+    ///
+    /// # Errors
+    /// - `InvalidDueDate` if `until_ts` is not in the future
+    /// - `ConfigLocked` if it would shorten an existing lock
+    /// - `ScheduledChangeConflict` if a pending change would take effect
+    ///   before `until_ts`
+    pub fn lock_config(
+        env: Env,
+        owner: Address,
+        until_ts: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+
+        if until_ts <= env.ledger().timestamp() {
+            return Err(RemittanceSplitError::InvalidDueDate);
+        }
+        if until_ts < Self::get_config_lock(env.clone(), owner.clone()) {
+            return Err(RemittanceSplitError::ConfigLocked);
+        }
+        Self::promote_due_change(&env, &owner);
+        for change in Self::load_scheduled_changes(&env, &owner).iter() {
+            if change.effective_at < until_ts {
+                return Err(RemittanceSplitError::ScheduledChangeConflict);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = (symbol_short!("LOCK"), owner.clone());
+        env.storage().persistent().set(&key, &until_ts);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        Self::append_audit(&env, symbol_short!("lock"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ConfigLocked),
+            (owner, until_ts),
+        );
+        Ok(true)
+    }
+
+    /// The time until which `owner`'s split is locked (0 if never locked).
+    pub fn get_config_lock(env: Env, owner: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("LOCK"), owner))
+            .unwrap_or(0)
+    }
+
+    /// Every change to `owner`'s split, oldest first: who made it, when, and
+    /// the categories before and after. Keeps the latest 100 changes.
+    pub fn get_config_history(
+        env: Env,
+        owner: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ConfigChange> {
+        let history: Vec<ConfigChange> = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("CFG_HIST"), owner))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut out = Vec::new(&env);
+        let len = history.len();
+        if offset >= len {
+            return out;
+        }
+        let end = offset
+            .saturating_add(limit.min(MAX_CONFIG_HISTORY))
+            .min(len);
+        for i in offset..end {
+            if let Some(change) = history.get(i) {
+                out.push_back(change);
+            }
+        }
+        out
+    }
+
+    pub fn get_profiles(env: Env, owner: Address) -> Vec<SplitProfile> {
+        let mut result = Vec::new(&env);
+        for (name, categories) in Self::load_profiles(&env, &owner).iter() {
+            result.push_back(SplitProfile { name, categories });
+        }
+        result
+    }
+
+    /// The profile last switched to, unless the split has since been changed
+    /// directly.
+    pub fn get_active_profile(env: Env, owner: Address) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("ACT_PROF"), owner))
+    }
+
+    /// Set absolute floors and caps on `owner`'s categories, replacing any
+    /// previous limits (an empty list removes them). Calculations satisfy
+    /// floors first, then caps, and share the difference among the other
+    /// categories in proportion to their shares; each allocation reports
+    /// the resulting adjustment.
+    ///
+    /// # Errors
+    /// - `InvalidLimits` if a bound is negative, a cap is below its floor, a
+    ///   category is repeated, or more than 10 limits are given
+    /// - `CooldownActive` if the owner has a cooldown and the limits would
+    ///   lower a savings or insurance floor, or tighten its cap
+    /// - `ApprovalRequired` if the owner's changes need family approval,
+    ///   which limits cannot be held for
+    pub fn set_category_limits(
+        env: Env,
+        owner: Address,
+        limits: Vec<CategoryLimit>,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        if limits.len() > MAX_CATEGORIES {
+            return Err(RemittanceSplitError::InvalidLimits);
+        }
+        let mut by_category: Map<Symbol, CategoryLimit> = Map::new(&env);
+        for limit in limits.iter() {
+            if limit.floor < 0
+                || limit.cap < 0
+                || (limit.cap > 0 && limit.cap < limit.floor)
+                || by_category.contains_key(limit.category.clone())
+            {
+                return Err(RemittanceSplitError::InvalidLimits);
+            }
+            by_category.set(limit.category.clone(), limit);
+        }
+        let current = Self::load_limits(&env, &owner);
+        if by_category != current
+            && Self::get_split_approval_config(env.clone(), owner.clone()).is_some()
+        {
+            return Err(RemittanceSplitError::ApprovalRequired);
+        }
+        if Self::cooldown_seconds(&env, &owner) > 0
+            && Self::limits_reduce_protected(&current, &by_category)
+        {
+            return Err(RemittanceSplitError::CooldownActive);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = (symbol_short!("LIMITS"), owner.clone());
+        if by_category.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &by_category);
+            env.storage().persistent().extend_ttl(
+                &key,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+        }
+
+        Self::append_audit(&env, symbol_short!("limits"), &owner, true);
+        Ok(true)
+    }
+
+    pub fn get_category_limits(env: Env, owner: Address) -> Vec<CategoryLimit> {
+        Self::load_limits(&env, &owner).values()
+    }
+
+    /// Choose which category receives `owner`'s rounding remainder.
+    ///
+    /// # Errors
+    /// - `InvalidCategories` if `Category(name)` names none of the owner's
+    ///   current categories
+    pub fn set_remainder_policy(
+        env: Env,
+        owner: Address,
+        policy: RemainderPolicy,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        if let RemainderPolicy::Category(name) = &policy {
+            let known = Self::load_categories(&env, &owner)
+                .iter()
+                .any(|category| category.name == *name);
+            if !known {
+                return Err(RemittanceSplitError::InvalidCategories);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = (symbol_short!("REM_POL"), owner.clone());
+        env.storage().persistent().set(&key, &policy);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::RemainderPolicySet),
+            (owner, policy),
+        );
+        Ok(true)
+    }
+
+    pub fn get_remainder_policy(env: Env, owner: Address) -> RemainderPolicy {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("REM_POL"), owner))
+            .unwrap_or(RemainderPolicy::LastCategory)
+    }
+
+    /// `owner`'s split categories: the custom list from `set_categories` if
+    /// any, otherwise the four standard categories from their configuration
+    /// (or the default 50/30/15/5).
+    pub fn get_categories(env: Env, owner: Address) -> Vec<CategoryAlloc> {
+        Self::load_categories(&env, &owner)
+    }
+
+    /// `owner`'s split in whole percentages, in category order. Fractional
+    /// shares are truncated; use `get_split_bps` for exact values.
+    pub fn get_split(env: &Env, owner: Address) -> Vec<u32> {
+        let mut split = Vec::new(env);
+        for category in Self::load_categories(env, &owner).iter() {
+            split.push_back(category.bps / 100);
+        }
+        split
+    }
+
+    /// `owner`'s split in basis points, in category order.
+    pub fn get_split_bps(env: Env, owner: Address) -> Vec<u32> {
+        let mut split = Vec::new(&env);
+        for category in Self::load_categories(&env, &owner).iter() {
+            split.push_back(category.bps);
+        }
+        split
+    }
+
+    pub fn get_config(env: Env, owner: Address) -> Option<SplitConfig> {
+        Self::load_config(&env, &owner)
+    }
+
+    /// The address that administers pause and upgrades, set by
+    /// `initialize_admin`.
+    pub fn get_owner(env: Env) -> Option<Address> {
+        Self::get_contract_owner(&env)
+    }
+
+    /// `owner`'s split of `total_amount`, published as a
+    /// `SplitCalculatedEvent`. Needs the owner's authorization, since the
+    /// event is recorded on their behalf; use `preview_split` to read the
+    /// amounts without it.
+    pub fn calculate_split(
+        env: Env,
+        owner: Address,
+        total_amount: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        owner.require_auth();
+        Self::promote_due_change(&env, &owner);
+        let allocations =
+            Self::calculate_split_amounts(&env, &owner, total_amount, true, FX_RATE_SCALE)?;
+        let mut amounts = Vec::new(&env);
+        for allocation in allocations.iter() {
+            amounts.push_back(allocation.amount);
+        }
+        Ok(amounts)
+    }
+
+    /// Dry run of `calculate_split`: the same amounts, but nothing is stored
+    /// and no event is emitted, so simulations and reports do not show up as
+    /// calculations.
+    pub fn preview_split(
+        env: Env,
+        owner: Address,
+        total_amount: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        let allocations =
+            Self::calculate_split_amounts(&env, &owner, total_amount, false, FX_RATE_SCALE)?;
+        let mut amounts = Vec::new(&env);
+        for allocation in allocations.iter() {
+            amounts.push_back(allocation.amount);
+        }
+        Ok(amounts)
+    }
+
+    /// Replace `owner`'s split rules. Rules are checked, in order, every
+    /// time the split is calculated; each one that holds moves its `bps` from
+    /// `from_category` to `to_category` (never more than `from_category`
+    /// has left) for that split only. Rules naming a category the split
+    /// doesn't have, or whose contract cannot be read, are skipped. An empty
+    /// list removes all rules.
+    ///
+    /// # Errors
+    /// - `InvalidRules` if there are more than 5 rules, or a rule moves zero
+    ///   or more than 10000 bps, or moves a category into itself
+    /// - `ConfigLocked` if the owner's split is locked
+    /// - `CooldownActive` if the owner has a cooldown and the rules would
+    ///   take more from savings or insurance
+    /// - `ApprovalRequired` if the owner's changes need family approval,
+    ///   which rules cannot be held for
+    pub fn set_split_rules(
+        env: Env,
+        owner: Address,
+        rules: Vec<SplitRule>,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        if rules.len() > MAX_RULES {
+            return Err(RemittanceSplitError::InvalidRules);
+        }
+        for rule in rules.iter() {
+            if rule.bps == 0 || rule.bps > TOTAL_BPS || rule.from_category == rule.to_category {
+                return Err(RemittanceSplitError::InvalidRules);
+            }
+        }
+        let current = Self::get_split_rules(env.clone(), owner.clone());
+        if rules != current && Self::get_split_approval_config(env.clone(), owner.clone()).is_some()
+        {
+            return Err(RemittanceSplitError::ApprovalRequired);
+        }
+        if Self::cooldown_seconds(&env, &owner) > 0
+            && Self::rules_reduce_protected(&current, &rules)
+        {
+            return Err(RemittanceSplitError::CooldownActive);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = (symbol_short!("RULES"), owner.clone());
+        if rules.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &rules);
+            env.storage().persistent().extend_ttl(
+                &key,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+        }
+
+        Self::append_audit(&env, symbol_short!("rules"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::RulesSet), owner);
+        Ok(true)
+    }
+
+    pub fn get_split_rules(env: Env, owner: Address) -> Vec<SplitRule> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("RULES"), owner))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set the platform fee deducted from every split before the owner's
+    /// percentages are applied, and the treasury it is paid to. A fee of
+    /// zero disables it. Only the contract owner may call this.
+    ///
+    /// # Errors
+    /// - `Unauthorized` if the caller is not the contract owner
+    /// - `InvalidFee` if `fee_bps` exceeds 1000 (10%)
+    pub fn set_fee_config(
+        env: Env,
+        caller: Address,
+        fee_bps: u32,
+        treasury: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        if contract_owner != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if fee_bps > MAX_FEE_BPS {
+            return Err(RemittanceSplitError::InvalidFee);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FEE_CFG"), &FeeConfig { fee_bps, treasury });
+        Self::append_audit(&env, symbol_short!("fee_cfg"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::FeeConfigSet), fee_bps);
+        Ok(true)
+    }
+
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        env.storage().instance().get(&symbol_short!("FEE_CFG"))
+    }
+
+    /// Set `owner`'s home asset and the oracle used to convert remittances
+    /// in other assets into it for `calculate_split_fx`, `execute_split`,
+    /// `execute_corridor_split` and `deposit`.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if `max_slippage_bps` exceeds 10000
+    pub fn set_fx_config(
+        env: Env,
+        owner: Address,
+        home_asset: Address,
+        oracle: Address,
+        max_slippage_bps: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        if max_slippage_bps > TOTAL_BPS {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        let key = (symbol_short!("FX_CFG"), owner.clone());
+        env.storage().persistent().set(
+            &key,
+            &FxConfig {
+                home_asset,
+                oracle,
+                max_slippage_bps,
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        Self::append_audit(&env, symbol_short!("fx_cfg"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::FxConfigSet), owner);
+        Ok(true)
+    }
+
+    pub fn get_fx_config(env: Env, owner: Address) -> Option<FxConfig> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("FX_CFG"), owner))
+    }
+
+    /// `calculate_split` for a remittance of `total_amount` in `asset`. If
+    /// `asset` is not `owner`'s home asset, the amount is first converted at
+    /// the oracle rate, which must be within the configured slippage of the
+    /// `quoted_rate` the sender saw. The rate used is recorded in the
+    /// `SplitCalculatedEvent`. Needs the owner's authorization.
+    ///
+    /// # Errors
+    /// - `FxNotConfigured` if `owner` has no FX configuration
+    /// - `FxRateUnavailable` if the oracle call failed or returned a rate <= 0
+    /// - `SlippageExceeded` if the oracle rate strays too far from the quote
+    pub fn calculate_split_fx(
+        env: Env,
+        owner: Address,
+        total_amount: i128,
+        asset: Address,
+        quoted_rate: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        owner.require_auth();
+        if total_amount <= 0 || quoted_rate <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let config = Self::get_fx_config(env.clone(), owner.clone())
+            .ok_or(RemittanceSplitError::FxNotConfigured)?;
+
+        let rate = Self::home_rate(&env, &config, &asset)?;
+        if asset != config.home_asset {
+            Self::check_slippage(&config, rate, quoted_rate)?;
+        }
+        let converted = total_amount
+            .checked_mul(rate)
+            .ok_or(RemittanceSplitError::Overflow)?
+            / FX_RATE_SCALE;
+
+        Self::promote_due_change(&env, &owner);
+        let allocations = Self::calculate_split_amounts(&env, &owner, converted, true, rate)?;
+        let mut amounts = Vec::new(&env);
+        for allocation in allocations.iter() {
+            amounts.push_back(allocation.amount);
+        }
+        Ok(amounts)
+    }
+
+    pub fn distribute_usdc(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        if total_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        from.require_auth();
+        Self::require_nonce(&env, &from, nonce)?;
+
+        // The account group only has the four standard destinations, so
+        // each allocation is matched to its account by category name.
+        Self::promote_due_change(&env, &from);
+        let categories = Self::load_categories(&env, &from);
+        let allocations = Self::split_categories(
+            &env,
+            &from,
+            categories,
+            total_amount,
+            false,
+            FX_RATE_SCALE,
+            true,
+        )?;
+        let mut transfers: Vec<(Address, i128)> = Vec::new(&env);
+        for allocation in allocations.iter() {
+            let Some(destination) = Self::standard_account(&accounts, &allocation.category) else {
+                Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+                return Err(RemittanceSplitError::InvalidCategories);
+            };
+            transfers.push_back((destination, allocation.amount));
+        }
+        let token = TokenClient::new(&env, &usdc_contract);
+
+        Self::pay_platform_fee(&env, &token, &from, total_amount)?;
+        for (destination, amount) in transfers.iter() {
+            if amount > 0 {
+                token.transfer(&from, &destination, &amount);
+            }
+        }
+
+        Self::increment_nonce(&env, &from)?;
+        Self::record_allocations(&env, &from, &allocations);
+        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        Ok(true)
+    }
+
+    /// Set the downstream contracts that `execute_split` deposits the four
+    /// standard categories into (spending wallet, savings, bills and
+    /// insurance contracts). Only the contract owner may call this.
+    pub fn set_destinations(
+        env: Env,
+        caller: Address,
+        accounts: AccountGroup,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        let contract_owner =
+            Self::get_contract_owner(&env).ok_or(RemittanceSplitError::NotInitialized)?;
+        if contract_owner != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DESTS"), &accounts);
+        Ok(())
+    }
+
+    pub fn get_destinations(env: Env) -> Option<AccountGroup> {
+        env.storage().instance().get(&symbol_short!("DESTS"))
+    }
+
+    /// Bind one of `owner`'s categories to the contract `execute_split`
+    /// deposits it into: a savings goal, bills or insurance contract, a
+    /// spending wallet, or any other address. Takes precedence over the
+    /// contract-wide destinations, and is the only way to route custom
+    /// categories. Binding a category again replaces its destination.
+    ///
+    /// With split approval configured, repointing a category whose share
+    /// exceeds the approval threshold is held like a large split change until
+    /// an approver calls `approve_split_change`; see `get_pending_destination`.
+    ///
+    /// # Errors
+    /// - `ConfigLocked` if the owner's split is locked
+    /// - `CooldownActive` if the owner has a cooldown and `category` is
+    ///   savings, insurance or bills
+    pub fn set_destination(
+        env: Env,
+        owner: Address,
+        category: Symbol,
+        contract_addr: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        let registry: Map<Symbol, Address> = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("DEST"), owner.clone()))
+            .unwrap_or_else(|| Map::new(&env));
+        let protected = [
+            symbol_short!("SAVINGS"),
+            symbol_short!("INSURANCE"),
+            symbol_short!("BILLS"),
+        ];
+        if Self::cooldown_seconds(&env, &owner) > 0
+            && protected.contains(&category)
+            && registry.get(category.clone()) != Some(contract_addr.clone())
+        {
+            return Err(RemittanceSplitError::CooldownActive);
+        }
+
+        Self::promote_due_change(&env, &owner);
+        let shifted_bps = Self::category_bps(&Self::load_categories(&env, &owner), &category);
+        let held = registry.get(category.clone()) != Some(contract_addr.clone())
+            && Self::get_split_approval_config(env.clone(), owner.clone())
+                .is_some_and(|config| shifted_bps > config.threshold_bps);
+        if held {
+            let key = (symbol_short!("PEND_DST"), owner.clone());
+            env.storage().persistent().set(
+                &key,
+                &PendingDestination {
+                    category,
+                    destination: contract_addr,
+                    shifted_bps,
+                    requested_at: env.ledger().timestamp(),
+                },
+            );
+            env.storage().persistent().extend_ttl(
+                &key,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+            Self::append_audit(&env, symbol_short!("pending"), &owner, true);
+            env.events().publish(
+                (symbol_short!("split"), SplitEvent::ReallocationPending),
+                (owner, shifted_bps),
+            );
+            return Ok(true);
+        }
+
+        Self::bind_destination(&env, &owner, category, contract_addr);
+        Ok(true)
+    }
+
+    pub fn get_pending_destination(env: Env, owner: Address) -> Option<PendingDestination> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("PEND_DST"), owner))
+    }
+
+    /// Pay parts of the spending portion straight to family members, e.g.
+    /// 3000 bps to one parent and 2000 to the eldest child. Whatever the
+    /// shares leave goes to the spending destination as before; if they sum
+    /// to 10000 the last member also receives the rounding dust and no
+    /// spending destination is needed. An empty list removes all shares.
+    ///
+    /// # Errors
+    /// - `InvalidShares` if a share is zero, a recipient repeats, the shares
+    ///   exceed 10000, or there are more than 10
+    /// - `ConfigLocked` if the owner's split is locked
+    pub fn set_spending_shares(
+        env: Env,
+        owner: Address,
+        shares: Vec<SpendingShare>,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        if shares.len() > MAX_CATEGORIES {
+            return Err(RemittanceSplitError::InvalidShares);
+        }
+        let mut total: u32 = 0;
+        for (i, share) in shares.iter().enumerate() {
+            for j in 0..i as u32 {
+                if shares.get(j).unwrap().recipient == share.recipient {
+                    return Err(RemittanceSplitError::InvalidShares);
+                }
+            }
+            if share.bps == 0 {
+                return Err(RemittanceSplitError::InvalidShares);
+            }
+            total = total.saturating_add(share.bps);
+        }
+        if total > TOTAL_BPS {
+            return Err(RemittanceSplitError::InvalidShares);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = (symbol_short!("SHARES"), owner.clone());
+        if shares.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &shares);
+            env.storage().persistent().extend_ttl(
+                &key,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+        }
+
+        Self::append_audit(&env, symbol_short!("shares"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::SpendingSharesSet),
+            owner,
+        );
+        Ok(true)
+    }
+
+    pub fn get_spending_shares(env: Env, owner: Address) -> Vec<SpendingShare> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("SHARES"), owner))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Where `execute_split` sends `owner`'s `category`: the owner's own
+    /// binding if set, else the contract-wide destination for the four
+    /// standard categories.
+    pub fn get_destination(env: Env, owner: Address, category: Symbol) -> Option<Address> {
+        Self::resolve_destination(&env, &owner, &category)
+    }
+
+    /// Calculate `owner`'s split of `amount` and transfer each portion of
+    /// `token` from the owner to its category's destination. Spending shares
+    /// set with `set_spending_shares` are paid to their members directly.
+    ///
+    /// All-or-nothing: every destination is resolved and the balance checked
+    /// before any transfer, and a failing transfer reverts the whole call.
+    ///
+    /// If the owner has an FX configuration and `token` is not their home
+    /// asset, the split is worked out on `amount` converted at the oracle
+    /// rate (so floors and caps apply in the home asset) and each portion is
+    /// then paid in `token` in the same proportions. The oracle rate must
+    /// then be within the configured slippage of `quoted_rate`, the rate the
+    /// sender saw; it is ignored when nothing is converted.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if amount is not positive
+    /// - `DestinationNotSet` if any category has no destination
+    /// - `InsufficientBalance` if the owner holds less than `amount`
+    /// - `FxRateUnavailable` if `token` needs converting and the oracle
+    ///   call failed
+    /// - `SlippageExceeded` if `token` needs converting and no quote was
+    ///   given or the oracle rate strays too far from it
+    pub fn execute_split(
+        env: Env,
+        owner: Address,
+        amount: i128,
+        token: Address,
+        quoted_rate: Option<i128>,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::promote_due_change(&env, &owner);
+        let categories = Self::load_categories(&env, &owner);
+        let allocations =
+            Self::split_in_token(&env, &owner, categories, amount, &token, quoted_rate)?;
+        if let Err(e) = Self::transfer_allocations(&env, &owner, &token, amount, &allocations) {
+            Self::append_audit(&env, symbol_short!("execute"), &owner, false);
+            return Err(e);
+        }
+
+        Self::record_allocations(&env, &owner, &allocations);
+        Self::append_audit(&env, symbol_short!("execute"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Executed),
+            (owner, token, amount),
+        );
+        Ok(allocations)
+    }
+
+    /// Give one remittance corridor (a currency or country code such as
+    /// `USDC` or `KE`) its own categories, used instead of the default split
+    /// by `calculate_corridor_split` and `execute_corridor_split`. Rules,
+    /// limits, the remainder policy and destinations are shared with the
+    /// default split. Setting a corridor again replaces it.
+    ///
+    /// # Errors
+    /// - `InvalidCategories` / `PercentagesDoNotSumTo100` as for `set_categories`
+    /// - `TooManyCorridors` if the owner already has 10 other corridors
+    /// - `ConfigLocked` if the owner's split is locked
+    /// - `ApprovalRequired` if the categories move further from the approved
+    ///   split than the owner's approval threshold, which corridors cannot
+    ///   be held for
+    /// - `CooldownActive` if the owner has a cooldown and the corridor would
+    ///   lower its savings or insurance share
+    pub fn set_corridor_categories(
+        env: Env,
+        owner: Address,
+        corridor: Symbol,
+        categories: Vec<CategoryAlloc>,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+        Self::validate_categories(&categories)?;
+
+        let mut corridors = Self::load_corridors(&env, &owner);
+        if !corridors.contains_key(corridor.clone()) && corridors.len() >= MAX_CORRIDORS {
+            return Err(RemittanceSplitError::TooManyCorridors);
+        }
+        Self::promote_due_change(&env, &owner);
+        if Self::needs_approval(&env, &owner, &categories).is_some() {
+            return Err(RemittanceSplitError::ApprovalRequired);
+        }
+        if Self::cooldown_seconds(&env, &owner) > 0
+            && Self::reduces_protected(
+                &Self::corridor_categories(&env, &owner, &corridor),
+                &categories,
+            )
+        {
+            return Err(RemittanceSplitError::CooldownActive);
+        }
+        corridors.set(corridor.clone(), categories);
+        Self::save_corridors(&env, &owner, &corridors);
+
+        Self::append_audit(&env, symbol_short!("corridor"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::CorridorSet),
+            (owner, corridor),
+        );
+        Ok(true)
+    }
+
+    /// Drop a corridor's categories; its remittances then use the default
+    /// split.
+    ///
+    /// # Errors
+    /// - `CorridorNotFound` if the corridor has no categories of its own
+    /// - `ConfigLocked` if the owner's split is locked
+    /// - `CooldownActive` if the owner has a cooldown and the default split
+    ///   gives savings or insurance less than the corridor did
+    pub fn remove_corridor(
+        env: Env,
+        owner: Address,
+        corridor: Symbol,
+    ) -> Result<bool, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+
+        let mut corridors = Self::load_corridors(&env, &owner);
+        let removed = corridors
+            .get(corridor.clone())
+            .ok_or(RemittanceSplitError::CorridorNotFound)?;
+        corridors.remove(corridor);
+        Self::promote_due_change(&env, &owner);
+        if Self::cooldown_seconds(&env, &owner) > 0
+            && Self::reduces_protected(&removed, &Self::load_categories(&env, &owner))
+        {
+            return Err(RemittanceSplitError::CooldownActive);
+        }
+        Self::save_corridors(&env, &owner, &corridors);
+        Ok(true)
+    }
+
+    pub fn get_corridors(env: Env, owner: Address) -> Map<Symbol, Vec<CategoryAlloc>> {
+        Self::load_corridors(&env, &owner)
+    }
+
+    /// `calculate_split` for a remittance through `corridor`. Corridors
+    /// without their own categories use the default split. Needs the
+    /// owner's authorization.
+    pub fn calculate_corridor_split(
+        env: Env,
+        owner: Address,
+        corridor: Symbol,
+        total_amount: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        owner.require_auth();
+        Self::promote_due_change(&env, &owner);
+        let categories = Self::corridor_categories(&env, &owner, &corridor);
+        let allocations = Self::split_categories(
+            &env,
+            &owner,
+            categories,
+            total_amount,
+            true,
+            FX_RATE_SCALE,
+            false,
+        )?;
+        let mut amounts = Vec::new(&env);
+        for allocation in allocations.iter() {
+            amounts.push_back(allocation.amount);
+        }
+        Ok(amounts)
+    }
+
+    /// `execute_split` for a remittance through `corridor`.
+    ///
+    /// # Errors
+    /// - As for `execute_split`
+    /// - `ApprovalRequired` if the corridor's categories move further from
+    ///   the approved split than the owner's approval threshold, e.g. a
+    ///   corridor set before approval was configured
+    pub fn execute_corridor_split(
+        env: Env,
+        owner: Address,
+        corridor: Symbol,
+        amount: i128,
+        token: Address,
+        quoted_rate: Option<i128>,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::promote_due_change(&env, &owner);
+        let categories = Self::corridor_categories(&env, &owner, &corridor);
+        if Self::needs_approval(&env, &owner, &categories).is_some() {
+            return Err(RemittanceSplitError::ApprovalRequired);
+        }
+        let allocations =
+            Self::split_in_token(&env, &owner, categories, amount, &token, quoted_rate)?;
+        Self::transfer_allocations(&env, &owner, &token, amount, &allocations)?;
+
+        Self::record_allocations(&env, &owner, &allocations);
+        Self::append_audit(&env, symbol_short!("execute"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Executed),
+            (owner, token, amount),
+        );
+        Ok(allocations)
+    }
+
+    /// Route one remittance entirely to `category` (e.g. a medical bills
+    /// emergency) without touching the stored configuration. Only the
+    /// platform fee is deducted; rules, limits and the remainder policy do
+    /// not apply. Publishes a distinct `Override` event instead of
+    /// `Executed`, so audits can tell overrides apart.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if amount is not positive
+    /// - `DestinationNotSet` if `category` has no destination
+    /// - `InsufficientBalance` if the owner holds less than `amount`
+    /// - `ConfigLocked` if the owner's split is locked
+    /// - `CooldownActive` if the owner has a cooldown and the override would
+    ///   skip savings or insurance
+    pub fn execute_override_split(
+        env: Env,
+        owner: Address,
+        amount: i128,
+        category: Symbol,
+        token: Address,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_unlocked(&env, &owner)?;
+        if amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let routed = vec![
+            &env,
+            CategoryAlloc {
+                name: category.clone(),
+                bps: TOTAL_BPS,
+            },
+        ];
+        if Self::cooldown_seconds(&env, &owner) > 0
+            && Self::reduces_protected(&Self::load_categories(&env, &owner), &routed)
+        {
+            Self::append_audit(&env, symbol_short!("override"), &owner, false);
+            return Err(RemittanceSplitError::CooldownActive);
+        }
+
+        let fee = Self::platform_fee(&env, amount)?;
+        let allocations = vec![
+            &env,
+            Allocation {
+                category: category.clone(),
+                amount: amount - fee,
+                adjustment: 0,
+            },
+        ];
+        if let Err(e) = Self::transfer_allocations(&env, &owner, &token, amount, &allocations) {
+            Self::append_audit(&env, symbol_short!("override"), &owner, false);
+            return Err(e);
+        }
+
+        Self::record_allocations(&env, &owner, &allocations);
+        Self::append_audit(&env, symbol_short!("override"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Override),
+            (owner, category, token, amount),
+        );
+        Ok(allocations)
+    }
+
+    /// Entry point senders can pay into directly: pulls `amount` of `token`
+    /// from `owner` into this contract, applies the owner's split, and
+    /// forwards each portion downstream exactly as `execute_split` would,
+    /// including any FX conversion checked against `quoted_rate`.
+    /// No separate orchestration call is needed for simple flows.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if amount is not positive
+    /// - `DestinationNotSet` if any category has no destination
+    /// - `InsufficientBalance` if the owner holds less than `amount`
+    /// - `FxRateUnavailable` if `token` needs converting and the oracle
+    ///   call failed
+    /// - `SlippageExceeded` if `token` needs converting and no quote was
+    ///   given or the oracle rate strays too far from it
+    pub fn deposit(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        quoted_rate: Option<i128>,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::promote_due_change(&env, &owner);
+        let categories = Self::load_categories(&env, &owner);
+        let allocations =
+            Self::split_in_token(&env, &owner, categories, amount, &token, quoted_rate)?;
+        let payouts = Self::plan_payouts(&env, &owner, &allocations)?;
+
+        let token_client = TokenClient::new(&env, &token);
+        if token_client.balance(&owner) < amount {
+            return Err(RemittanceSplitError::InsufficientBalance);
+        }
+        let this = env.current_contract_address();
+        token_client.transfer(&owner, &this, &amount);
+        Self::pay_platform_fee(&env, &token_client, &this, amount)?;
+        for (destination, payout) in payouts.iter() {
+            if payout > 0 {
+                token_client.transfer(&this, &destination, &payout);
+            }
+        }
+
+        Self::record_allocations(&env, &owner, &allocations);
+        Self::append_audit(&env, symbol_short!("deposit"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Deposited),
+            (owner, token, amount),
+        );
+        Ok(allocations)
+    }
+
+    /// Cumulative amounts `owner` routed to each category by executed splits
+    /// and deposits between `from` and `to`, with each category's share of
+    /// the total, ordered by category name. Totals are kept per UTC day, so
+    /// the window is widened to whole days; the last 400 days with activity
+    /// are kept.
+    ///
+    /// # Errors
+    /// - `InvalidWindow` if `to` is before `from`, or the window spans more
+    ///   than 366 days
+    pub fn get_allocation_stats(
+        env: Env,
+        owner: Address,
+        from: u64,
+        to: u64,
+    ) -> Result<AllocationStats, RemittanceSplitError> {
+        let first_day = from / SECONDS_PER_DAY;
+        let last_day = to / SECONDS_PER_DAY;
+        if to < from || last_day - first_day >= MAX_STATS_WINDOW_DAYS {
+            return Err(RemittanceSplitError::InvalidWindow);
+        }
+        let mut totals: Map<Symbol, i128> = Map::new(&env);
+        let mut total: i128 = 0;
+        for day in Self::load_stats_days(&env, &owner).iter() {
+            if day < first_day || day > last_day {
+                continue;
+            }
+            let bucket: Map<Symbol, i128> = env
+                .storage()
+                .persistent()
+                .get(&(symbol_short!("STATS"), owner.clone(), day))
+                .unwrap_or_else(|| Map::new(&env));
+            for (category, amount) in bucket.iter() {
+                totals.set(
+                    category.clone(),
+                    totals.get(category).unwrap_or(0).saturating_add(amount),
+                );
+                total = total.saturating_add(amount);
+            }
+        }
+
+        let mut categories = Vec::new(&env);
+        for (category, amount) in totals.iter() {
+            let share_bps = if total > 0 {
+                (amount.saturating_mul(TOTAL_BPS as i128) / total) as u32
+            } else {
+                0
+            };
+            categories.push_back(CategoryTotal {
+                category,
+                amount,
+                share_bps,
+            });
+        }
+        Ok(AllocationStats {
+            from,
+            to,
+            total,
+            categories,
+        })
+    }
+
+    /// Propose a split from the state of `owner`'s downstream contracts
+    /// (their bills and savings destinations). Read-only: nothing is stored
+    /// and no event is emitted.
+    ///
+    /// - With unpaid bills, the bills share is raised to what would clear
+    ///   them from the last 30 days' routed amount (at most 50%).
+    /// - With savings goals below target, savings gets 5% more.
+    ///
+    /// Both come out of spending, and never take more than it has. A
+    /// contract that is not set or cannot be read contributes nothing, and
+    /// `suggested` equals `current` when there is nothing to change.
+    pub fn suggest_rebalance(env: Env, owner: Address) -> RebalanceSuggestion {
+        let now = env.ledger().timestamp();
+        let current = Self::load_categories(&env, &owner);
+        let monthly_routed = Self::get_allocation_stats(
+            env.clone(),
+            owner.clone(),
+            now.saturating_sub(30 * SECONDS_PER_DAY),
+            now,
+        )
+        .map(|stats| stats.total)
+        .unwrap_or(0);
+
+        let unpaid_bills = Self::resolve_destination(&env, &owner, &symbol_short!("BILLS"))
+            .and_then(|bills| {
+                BillPaymentsClient::new(&env, &bills)
+                    .try_get_total_unpaid(&owner)
+                    .ok()
+                    .and_then(|res| res.ok())
+            })
+            .unwrap_or(0);
+        let active_goals = Self::resolve_destination(&env, &owner, &symbol_short!("SAVINGS"))
+            .and_then(|savings| {
+                SavingsGoalsClient::new(&env, &savings)
+                    .try_get_savings_stats(&owner, &now.saturating_sub(90 * SECONDS_PER_DAY), &now)
+                    .ok()
+                    .and_then(|res| res.ok())
+            })
+            .map(|stats| stats.active_goals)
+            .unwrap_or(0);
+
+        let mut suggested = current.clone();
+        if unpaid_bills > 0 && monthly_routed > 0 {
+            let needed = unpaid_bills
+                .saturating_mul(TOTAL_BPS as i128)
+                .checked_div(monthly_routed)
+                .unwrap_or(0)
+                .min(MAX_SUGGESTED_BILLS_BPS as i128) as u32;
+            let bills = Self::category_bps(&suggested, &symbol_short!("BILLS"));
+            if needed > bills {
+                Self::shift_from_spending(&mut suggested, symbol_short!("BILLS"), needed - bills);
+            }
+        }
+        if active_goals > 0 {
+            Self::shift_from_spending(&mut suggested, symbol_short!("SAVINGS"), SAVINGS_NUDGE_BPS);
+        }
+
+        RebalanceSuggestion {
+            current,
+            suggested,
+            unpaid_bills,
+            active_goals,
+            monthly_routed,
+        }
+    }
+
+    pub fn get_usdc_balance(env: &Env, usdc_contract: Address, account: Address) -> i128 {
+        TokenClient::new(env, &usdc_contract).balance(&account)
+    }
+
+    pub fn get_split_allocations(
+        env: &Env,
+        owner: Address,
+        total_amount: i128,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        Self::calculate_split_amounts(env, &owner, total_amount, false, FX_RATE_SCALE)
+    }
+
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        Self::get_nonce_value(&env, &address)
+    }
+
+    /// Nonces live in a persistent entry per address. Addresses that last
+    /// transacted before that are still read from the old shared map.
+    fn get_nonce_value(env: &Env, address: &Address) -> u64 {
+        if let Some(nonce) = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("NONCE"), address.clone()))
+        {
+            return nonce;
+        }
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces
+            .as_ref()
+            .and_then(|m: &Map<Address, u64>| m.get(address.clone()))
+            .unwrap_or(0)
+    }
+
+    pub fn export_snapshot(
+        env: Env,
+        caller: Address,
+    ) -> Result<Option<ExportSnapshot>, RemittanceSplitError> {
+        caller.require_auth();
+        let config =
+            Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+        let checksum = Self::compute_checksum(SNAPSHOT_VERSION, &config);
+        Ok(Some(ExportSnapshot {
+            version: SNAPSHOT_VERSION,
+            checksum,
+            config,
+        }))
+    }
+
+    pub fn import_snapshot(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        snapshot: ExportSnapshot,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce)?;
+        Self::require_unlocked(&env, &caller)?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(RemittanceSplitError::UnsupportedVersion);
+        }
+        let expected = Self::compute_checksum(snapshot.version, &snapshot.config);
+        if snapshot.checksum != expected {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(RemittanceSplitError::ChecksumMismatch);
+        }
+
+        Self::load_config(&env, &caller).ok_or(RemittanceSplitError::NotInitialized)?;
+        if snapshot.config.owner != caller {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        if !Self::is_full_split(&[
+            snapshot.config.spending_bps,
+            snapshot.config.savings_bps,
+            snapshot.config.bills_bps,
+            snapshot.config.insurance_bps,
+        ]) {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::promote_due_change(&env, &caller);
+        let new = Self::standard_categories(
+            &env,
+            snapshot.config.spending_bps,
+            snapshot.config.savings_bps,
+            snapshot.config.bills_bps,
+            snapshot.config.insurance_bps,
+        );
+        if Self::defer_change(&env, &caller, &new)? {
+            Self::increment_nonce(&env, &caller)?;
+            return Ok(true);
+        }
+        let old = Self::load_categories(&env, &caller);
+        Self::save_config(&env, &snapshot.config);
+        Self::clear_categories(&env, &caller);
+        Self::record_config_change(&env, &caller, &caller, symbol_short!("import"), old);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("import"), &caller, true);
+        Ok(true)
+    }
+
+    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
+        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
+        let log = log.unwrap_or_else(|| Vec::new(&env));
+        let len = log.len();
+        let cap = MAX_AUDIT_ENTRIES.min(limit);
+        let mut out = Vec::new(&env);
+        if from_index >= len {
+            return out;
+        }
+        let end = (from_index + cap).min(len);
+        for i in from_index..end {
+            if let Some(entry) = log.get(i) {
+                out.push_back(entry);
+            }
+        }
+        out
+    }
+
+    fn require_unlocked(env: &Env, owner: &Address) -> Result<(), RemittanceSplitError> {
+        if env.ledger().timestamp() < Self::get_config_lock(env.clone(), owner.clone()) {
+            return Err(RemittanceSplitError::ConfigLocked);
+        }
+        Ok(())
+    }
+
+    fn require_nonce(
+        env: &Env,
+        address: &Address,
+        expected: u64,
+    ) -> Result<(), RemittanceSplitError> {
+        let current = Self::get_nonce_value(env, address);
+        if expected != current {
+            return Err(RemittanceSplitError::InvalidNonce);
+        }
+        Ok(())
+    }
+
+    fn increment_nonce(env: &Env, address: &Address) -> Result<(), RemittanceSplitError> {
+        let current = Self::get_nonce_value(env, address);
+        let next = current
+            .checked_add(1)
+            .ok_or(RemittanceSplitError::Overflow)?;
+        let key = (symbol_short!("NONCE"), address.clone());
+        env.storage().persistent().set(&key, &next);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        let legacy: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        if let Some(mut nonces) = legacy.filter(|m| m.contains_key(address.clone())) {
+            nonces.remove(address.clone());
+            if nonces.is_empty() {
+                env.storage().instance().remove(&symbol_short!("NONCES"));
+            } else {
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("NONCES"), &nonces);
+            }
+        }
+        Ok(())
+    }
+
+    fn compute_checksum(version: u32, config: &SplitConfig) -> u64 {
+        let v = version as u64;
+        let s = config.spending_bps as u64;
+        let g = config.savings_bps as u64;
+        let b = config.bills_bps as u64;
+        let i = config.insurance_bps as u64;
+        v.wrapping_add(s)
+            .wrapping_add(g)
+            .wrapping_add(b)
+            .wrapping_add(i)
+            .wrapping_mul(31)
+    }
+
+    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
+        let timestamp = env.ledger().timestamp();
+        let mut log: Vec<AuditEntry> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("AUDIT"))
+            .unwrap_or_else(|| Vec::new(env));
+        if log.len() >= MAX_AUDIT_ENTRIES {
+            let mut new_log = Vec::new(env);
+            for i in 1..log.len() {
+                if let Some(entry) = log.get(i) {
+                    new_log.push_back(entry);
+                }
+            }
+            log = new_log;
+        }
+        log.push_back(AuditEntry {
+            operation,
+            caller: caller.clone(),
+            timestamp,
+            success,
+        });
+        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+    }
+
+    /// Split `total_amount` across `owner`'s categories. Every category is
+    /// rounded down and the owner's remainder policy places the dust, so the
+    /// amounts always sum to the total. No platform fee is taken, as nothing
+    /// is transferred.
+    fn calculate_split_amounts(
+        env: &Env,
+        owner: &Address,
+        total_amount: i128,
+        emit_events: bool,
+        fx_rate: i128,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        let categories = Self::load_categories(env, owner);
+        Self::split_categories(
+            env,
+            owner,
+            categories,
+            total_amount,
+            emit_events,
+            fx_rate,
+            false,
+        )
+    }
+
+    /// Split `total_amount` over `categories` with the owner's rules,
+    /// remainder policy and limits applied. With `charge_fee`, the platform
+    /// fee is taken off first; only callers that transfer it to the
+    /// treasury set it.
+    fn split_categories(
+        env: &Env,
+        owner: &Address,
+        categories: Vec<CategoryAlloc>,
+        total_amount: i128,
+        emit_events: bool,
+        fx_rate: i128,
+        charge_fee: bool,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        let fee = if charge_fee {
+            Self::platform_fee(env, total_amount)?
+        } else {
+            0
+        };
+        let split_amount = total_amount - fee;
+
+        let categories = Self::apply_rules(env, owner, categories);
+        let mut allocations = Vec::new(env);
+        let mut fractions: Vec<i128> = Vec::new(env);
+        let mut allocated: i128 = 0;
+        for category in categories.iter() {
+            let scaled = split_amount
+                .checked_mul(category.bps as i128)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            let amount = scaled / TOTAL_BPS as i128;
+            fractions.push_back(scaled % TOTAL_BPS as i128);
+            allocated = allocated
+                .checked_add(amount)
+                .ok_or(RemittanceSplitError::Overflow)?;
+            allocations.push_back(Allocation {
+                category: category.name,
+                amount,
+                adjustment: 0,
+            });
+        }
+        let remainder = split_amount - allocated;
+        Self::apply_remainder(env, owner, &mut allocations, fractions, remainder);
+        Self::apply_limits(env, owner, &categories, &mut allocations, split_amount)?;
+
+        if emit_events {
+            let event = SplitCalculatedEvent {
+                owner: owner.clone(),
+                total_amount,
+                allocations: allocations.clone(),
+                remainder,
+                fx_rate,
+                fee,
+                timestamp: env.ledger().timestamp(),
+            };
+            env.events().publish((SPLIT_CALCULATED,), event);
+            env.events().publish(
+                (symbol_short!("split"), SplitEvent::Calculated),
+                total_amount,
+            );
+        }
+
+        Ok(allocations)
+    }
+
+    /// Units of `owner`'s home asset per unit of `asset`, scaled by
+    /// `FX_RATE_SCALE`: exactly `FX_RATE_SCALE` for the home asset itself,
+    /// otherwise the oracle rate.
+    fn home_rate(
+        env: &Env,
+        config: &FxConfig,
+        asset: &Address,
+    ) -> Result<i128, RemittanceSplitError> {
+        if *asset == config.home_asset {
+            return Ok(FX_RATE_SCALE);
+        }
+        match FxOracleClient::new(env, &config.oracle).try_get_rate(asset, &config.home_asset) {
+            Ok(Ok(rate)) if rate > 0 => Ok(rate),
+            _ => Err(RemittanceSplitError::FxRateUnavailable),
+        }
+    }
+
+    /// Reject an oracle `rate` further than `config.max_slippage_bps` from
+    /// the `quoted_rate` the sender saw.
+    fn check_slippage(
+        config: &FxConfig,
+        rate: i128,
+        quoted_rate: i128,
+    ) -> Result<(), RemittanceSplitError> {
+        let gap = (rate - quoted_rate)
+            .abs()
+            .checked_mul(TOTAL_BPS as i128)
+            .ok_or(RemittanceSplitError::Overflow)?;
+        if gap > quoted_rate.saturating_mul(config.max_slippage_bps as i128) {
+            return Err(RemittanceSplitError::SlippageExceeded);
+        }
+        Ok(())
+    }
+
+    /// Split `amount` of `token` for transfer. When `token` has to be
+    /// converted into the owner's home asset, the split is calculated (and
+    /// its event published) on the converted amount, and each portion is
+    /// then scaled back to `token`, with the rounding dust going to the
+    /// largest portion. Converting needs a `quoted_rate` the oracle rate is
+    /// within the owner's slippage of.
+    fn split_in_token(
+        env: &Env,
+        owner: &Address,
+        categories: Vec<CategoryAlloc>,
+        amount: i128,
+        token: &Address,
+        quoted_rate: Option<i128>,
+    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
+        let rate = match Self::get_fx_config(env.clone(), owner.clone()) {
+            Some(config) if *token != config.home_asset => {
+                let rate = Self::home_rate(env, &config, token)?;
+                let quoted_rate = quoted_rate
+                    .filter(|q| *q > 0)
+                    .ok_or(RemittanceSplitError::SlippageExceeded)?;
+                Self::check_slippage(&config, rate, quoted_rate)?;
+                rate
+            }
+            _ => FX_RATE_SCALE,
+        };
+        if rate == FX_RATE_SCALE {
+            return Self::split_categories(env, owner, categories, amount, true, rate, true);
+        }
+
+        let converted = amount
+            .checked_mul(rate)
+            .ok_or(RemittanceSplitError::Overflow)?
+            / FX_RATE_SCALE;
+        let home = Self::split_categories(env, owner, categories, converted, true, rate, true)?;
+        let home_total: i128 = home.iter().map(|a| a.amount).sum();
+        if home_total <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        let split_amount = amount - Self::platform_fee(env, amount)?;
+
+        let mut allocations = Vec::new(env);
+        let mut allocated: i128 = 0;
+        let mut largest: u32 = 0;
+        for (i, allocation) in home.iter().enumerate() {
+            let scaled = allocation
+                .amount
+                .checked_mul(split_amount)
+                .ok_or(RemittanceSplitError::Overflow)?
+                / home_total;
+            let adjustment = allocation
+                .adjustment
+                .checked_mul(split_amount)
+                .ok_or(RemittanceSplitError::Overflow)?
+                / home_total;
+            if allocation.amount > home.get(largest).unwrap().amount {
+                largest = i as u32;
+            }
+            allocated += scaled;
+            allocations.push_back(Allocation {
+                category: allocation.category,
+                amount: scaled,
+                adjustment,
+            });
+        }
+        let mut dust = allocations.get(largest).unwrap();
+        dust.amount += split_amount - allocated;
+        allocations.set(largest, dust);
+        Ok(allocations)
+    }
+
+    /// Hold `categories` for family approval, or queue them behind the
+    /// cooldown. Returns whether the change was deferred either way.
+    fn defer_change(
+        env: &Env,
+        owner: &Address,
+        categories: &Vec<CategoryAlloc>,
+    ) -> Result<bool, RemittanceSplitError> {
+        let Some(shifted_bps) = Self::needs_approval(env, owner, categories) else {
+            return Self::defer_reduction(env, owner, categories);
+        };
+        let key = (symbol_short!("PEND_CHG"), owner.clone());
+        env.storage().persistent().set(
+            &key,
+            &PendingReallocation {
+                categories: categories.clone(),
+                shifted_bps,
+                requested_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+        Self::append_audit(env, symbol_short!("pending"), owner, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ReallocationPending),
+            (owner.clone(), shifted_bps),
+        );
+        Ok(true)
+    }
+
+    /// The basis points `categories` would move away from the last approved
+    /// split if that exceeds `owner`'s approval threshold. Measuring from
+    /// the approved split rather than the live one stops a run of small
+    /// changes adding up to a large one.
+    fn needs_approval(env: &Env, owner: &Address, categories: &Vec<CategoryAlloc>) -> Option<u32> {
+        let config = Self::get_split_approval_config(env.clone(), owner.clone())?;
+        let current = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("APPR_BASE"), owner.clone()))
+            .unwrap_or_else(|| Self::load_categories(env, owner));
+        let mut shifted: u32 = 0;
+        for category in categories.iter() {
+            let before = current
+                .iter()
+                .find(|c| c.name == category.name)
+                .map(|c| c.bps)
+                .unwrap_or(0);
+            shifted += category.bps.saturating_sub(before);
+        }
+        (shifted > config.threshold_bps).then_some(shifted)
+    }
+
+    /// Whether `member` is an Owner or Admin of the approval wallet, other
+    /// than `owner` themselves.
+    fn is_approver(
+        env: &Env,
+        config: &SplitApprovalConfig,
+        owner: &Address,
+        member: &Address,
+    ) -> bool {
+        member != owner
+            && FamilyWalletClient::new(env, &config.family_wallet)
+                .try_get_family_member(member)
+                .ok()
+                .and_then(|res| res.ok())
+                .flatten()
+                .is_some_and(|m| matches!(m.role, FamilyRole::Owner | FamilyRole::Admin))
+    }
+
+    fn bind_destination(env: &Env, owner: &Address, category: Symbol, contract_addr: Address) {
+        let key = (symbol_short!("DEST"), owner.clone());
+        let mut registry: Map<Symbol, Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(env));
+        registry.set(category.clone(), contract_addr.clone());
+        env.storage().persistent().set(&key, &registry);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::DestinationSet),
+            (owner.clone(), category, contract_addr),
+        );
+    }
+
+    fn save_approved_split(env: &Env, owner: &Address, categories: &Vec<CategoryAlloc>) {
+        let key = (symbol_short!("APPR_BASE"), owner.clone());
+        env.storage().persistent().set(&key, categories);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+    }
+
+    /// Queue `categories` to apply after `owner`'s cooldown if they lower the
+    /// savings or insurance share. Returns whether the change was deferred.
+    fn defer_reduction(
+        env: &Env,
+        owner: &Address,
+        categories: &Vec<CategoryAlloc>,
+    ) -> Result<bool, RemittanceSplitError> {
+        let cooldown = Self::cooldown_seconds(env, owner);
+        if cooldown == 0 || !Self::reduces_protected(&Self::load_categories(env, owner), categories)
+        {
+            return Ok(false);
+        }
+        let effective_at = env.ledger().timestamp() + cooldown;
+        Self::insert_scheduled_change(env, owner, categories.clone(), effective_at)?;
+        Self::append_audit(env, symbol_short!("deferred"), owner, true);
+        Ok(true)
+    }
+
+    fn cooldown_seconds(env: &Env, owner: &Address) -> u64 {
+        Self::get_cooldown(env.clone(), owner.clone()) as u64 * SECONDS_PER_DAY
+    }
+
+    fn category_bps(categories: &Vec<CategoryAlloc>, name: &Symbol) -> u32 {
+        categories
+            .iter()
+            .find(|c| c.name == *name)
+            .map(|c| c.bps)
+            .unwrap_or(0)
+    }
+
+    /// Move up to `bps` from spending to `target`, if both categories exist.
+    fn shift_from_spending(categories: &mut Vec<CategoryAlloc>, target: Symbol, bps: u32) {
+        let spending = categories
+            .iter()
+            .position(|c| c.name == symbol_short!("SPENDING"));
+        let to = categories.iter().position(|c| c.name == target);
+        let (Some(spending), Some(to)) = (spending, to) else {
+            return;
+        };
+        let mut source = categories.get(spending as u32).unwrap();
+        let mut dest = categories.get(to as u32).unwrap();
+        let moved = bps.min(source.bps);
+        source.bps -= moved;
+        dest.bps += moved;
+        categories.set(spending as u32, source);
+        categories.set(to as u32, dest);
+    }
+
+    /// Whether going from `old` to `new` lowers savings or insurance.
+    fn reduces_protected(old: &Vec<CategoryAlloc>, new: &Vec<CategoryAlloc>) -> bool {
+        [symbol_short!("SAVINGS"), symbol_short!("INSURANCE")]
+            .iter()
+            .any(|name| Self::category_bps(new, name) < Self::category_bps(old, name))
+    }
+
+    /// Whether replacing the rules `old` with `new` adds a rule that moves
+    /// savings or insurance away, or drops one that moved into them.
+    fn rules_reduce_protected(old: &Vec<SplitRule>, new: &Vec<SplitRule>) -> bool {
+        let protected = |name: &Symbol| {
+            *name == symbol_short!("SAVINGS") || *name == symbol_short!("INSURANCE")
+        };
+        new.iter()
+            .any(|rule| protected(&rule.from_category) && !old.contains(&rule))
+            || old
+                .iter()
+                .any(|rule| protected(&rule.to_category) && !new.contains(&rule))
+    }
+
+    /// Whether replacing the limits `old` with `new` lowers a savings or
+    /// insurance floor, or adds or lowers a cap on either.
+    fn limits_reduce_protected(
+        old: &Map<Symbol, CategoryLimit>,
+        new: &Map<Symbol, CategoryLimit>,
+    ) -> bool {
+        [symbol_short!("SAVINGS"), symbol_short!("INSURANCE")]
+            .iter()
+            .any(|name| {
+                let (old_floor, old_cap) = old
+                    .get(name.clone())
+                    .map_or((0, 0), |limit| (limit.floor, limit.cap));
+                let (new_floor, new_cap) = new
+                    .get(name.clone())
+                    .map_or((0, 0), |limit| (limit.floor, limit.cap));
+                new_floor < old_floor || (new_cap > 0 && (old_cap == 0 || new_cap < old_cap))
+            })
+    }
+
+    /// Add executed `allocations` to `owner`'s totals for today.
+    fn record_allocations(env: &Env, owner: &Address, allocations: &Vec<Allocation>) {
+        let day = env.ledger().timestamp() / SECONDS_PER_DAY;
+        let key = (symbol_short!("STATS"), owner.clone(), day);
+        let mut bucket: Map<Symbol, i128> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(env));
+        for allocation in allocations.iter() {
+            let running = bucket.get(allocation.category.clone()).unwrap_or(0);
+            bucket.set(
+                allocation.category,
+                running.saturating_add(allocation.amount),
+            );
+        }
+        env.storage().persistent().set(&key, &bucket);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+
+        let mut days = Self::load_stats_days(env, owner);
+        if days.last() != Some(day) {
+            days.push_back(day);
+            if days.len() > MAX_STATS_DAYS {
+                let oldest = days.pop_front().unwrap();
+                env.storage()
+                    .persistent()
+                    .remove(&(symbol_short!("STATS"), owner.clone(), oldest));
+            }
+            let index = (symbol_short!("STATS_IDX"), owner.clone());
+            env.storage().persistent().set(&index, &days);
+            env.storage().persistent().extend_ttl(
+                &index,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Days (since the epoch) on which `owner` has recorded allocations.
+    fn load_stats_days(env: &Env, owner: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("STATS_IDX"), owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Pay `allocations` of `amount` from `owner` in `token`: the platform
+    /// fee, spending shares, and each category's destination. Destinations
+    /// and the balance are checked before anything moves.
+    fn transfer_allocations(
+        env: &Env,
+        owner: &Address,
+        token: &Address,
+        amount: i128,
+        allocations: &Vec<Allocation>,
+    ) -> Result<(), RemittanceSplitError> {
+        let payouts = Self::plan_payouts(env, owner, allocations)?;
+        let token_client = TokenClient::new(env, token);
+        if token_client.balance(owner) < amount {
+            return Err(RemittanceSplitError::InsufficientBalance);
+        }
+        Self::pay_platform_fee(env, &token_client, owner, amount)?;
+        for (destination, payout) in payouts.iter() {
+            if payout > 0 {
+                token_client.transfer(owner, &destination, &payout);
+            }
+        }
+        Ok(())
+    }
+
+    /// The transfers that pay out `allocations`: spending shares to their
+    /// members, and every other portion to its category's destination.
+    fn plan_payouts(
+        env: &Env,
+        owner: &Address,
+        allocations: &Vec<Allocation>,
+    ) -> Result<Vec<(Address, i128)>, RemittanceSplitError> {
+        let shares = Self::get_spending_shares(env.clone(), owner.clone());
+        let shared_bps: u32 = shares.iter().map(|share| share.bps).sum();
+        let mut payouts: Vec<(Address, i128)> = Vec::new(env);
+        for allocation in allocations.iter() {
+            let mut unshared = allocation.amount;
+            if allocation.category == symbol_short!("SPENDING") {
+                for (i, share) in shares.iter().enumerate() {
+                    let paid = if shared_bps == TOTAL_BPS && i as u32 + 1 == shares.len() {
+                        unshared
+                    } else {
+                        allocation
+                            .amount
+                            .checked_mul(share.bps as i128)
+                            .ok_or(RemittanceSplitError::Overflow)?
+                            / TOTAL_BPS as i128
+                    };
+                    payouts.push_back((share.recipient, paid));
+                    unshared -= paid;
+                }
+                if shared_bps == TOTAL_BPS {
+                    continue;
+                }
+            }
+            match Self::resolve_destination(env, owner, &allocation.category) {
+                Some(destination) => payouts.push_back((destination, unshared)),
+                None => return Err(RemittanceSplitError::DestinationNotSet),
+            }
+        }
+        Ok(payouts)
+    }
+
+    /// `categories` with the shifts of every rule whose condition holds.
+    fn apply_rules(
+        env: &Env,
+        owner: &Address,
+        mut categories: Vec<CategoryAlloc>,
+    ) -> Vec<CategoryAlloc> {
+        for rule in Self::get_split_rules(env.clone(), owner.clone()).iter() {
+            let from = categories.iter().position(|c| c.name == rule.from_category);
+            let to = categories.iter().position(|c| c.name == rule.to_category);
+            let (Some(from), Some(to)) = (from, to) else {
+                continue;
+            };
+            if !Self::rule_holds(env, owner, &rule.condition) {
+                continue;
+            }
+            let mut source = categories.get(from as u32).unwrap();
+            let mut target = categories.get(to as u32).unwrap();
+            let moved = rule.bps.min(source.bps);
+            source.bps -= moved;
+            target.bps += moved;
+            categories.set(from as u32, source);
+            categories.set(to as u32, target);
+        }
+        categories
+    }
+
+    /// Whether a rule's condition holds. A contract that cannot be read
+    /// counts as not holding, so a broken source never blocks a split.
+    fn rule_holds(env: &Env, owner: &Address, condition: &RuleCondition) -> bool {
+        match condition {
+            RuleCondition::UnpaidBillsAbove(bills, threshold) => {
+                match BillPaymentsClient::new(env, bills).try_get_total_unpaid(owner) {
+                    Ok(Ok(unpaid)) => unpaid > *threshold,
+                    _ => false,
+                }
+            }
+            RuleCondition::GoalBelowTarget(savings, goal_id) => {
+                match SavingsGoalsClient::new(env, savings).try_is_goal_completed(goal_id) {
+                    Ok(Ok(completed)) => !completed,
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// The platform fee on `amount`, rounded down in the sender's favour.
+    fn platform_fee(env: &Env, amount: i128) -> Result<i128, RemittanceSplitError> {
+        match Self::get_fee_config(env.clone()) {
+            Some(config) => Ok(amount
+                .checked_mul(config.fee_bps as i128)
+                .ok_or(RemittanceSplitError::Overflow)?
+                / TOTAL_BPS as i128),
+            None => Ok(0),
+        }
+    }
+
+    /// Pay the platform fee on `amount` from `from` to the treasury.
+    fn pay_platform_fee(
+        env: &Env,
+        token: &TokenClient,
+        from: &Address,
+        amount: i128,
+    ) -> Result<(), RemittanceSplitError> {
+        let fee = Self::platform_fee(env, amount)?;
+        if fee > 0 {
+            let config = Self::get_fee_config(env.clone()).unwrap();
+            token.transfer(from, &config.treasury, &fee);
+        }
+        Ok(())
+    }
+
+    /// Raise categories below their floor and lower those above their cap,
+    /// then share the difference among the other categories in proportion to
+    /// their shares. Each pass pins at least one more category, so there are
+    /// at most as many passes as categories.
+    fn apply_limits(
         env: &Env,
+        owner: &Address,
+        categories: &Vec<CategoryAlloc>,
+        allocations: &mut Vec<Allocation>,
         total_amount: i128,
-    ) -> Result<Vec<Allocation>, RemittanceSplitError> {
-        let amounts = Self::calculate_split(env.clone(), total_amount)?;
-        let categories = [
-            symbol_short!("SPENDING"),
-            symbol_short!("SAVINGS"),
-            symbol_short!("BILLS"),
-            symbol_short!("INSURANCE"),
-        ];
+    ) -> Result<(), RemittanceSplitError> {
+        let limits = Self::load_limits(env, owner);
+        if limits.is_empty() {
+            return Ok(());
+        }
+
+        let count = allocations.len();
+        let base = allocations.clone();
+        let mut pinned: Vec<bool> = Vec::new(env);
+        for _ in 0..count {
+            pinned.push_back(false);
+        }
+
+        for _ in 0..=count {
+            let mut newly_pinned = false;
+            let mut pinned_total: i128 = 0;
+            for i in 0..count {
+                let mut allocation = allocations.get(i).unwrap();
+                if !pinned.get(i).unwrap() {
+                    if let Some(limit) = limits.get(allocation.category.clone()) {
+                        let bound = if allocation.amount < limit.floor {
+                            Some(limit.floor)
+                        } else if limit.cap > 0 && allocation.amount > limit.cap {
+                            Some(limit.cap)
+                        } else {
+                            None
+                        };
+                        if let Some(bound) = bound {
+                            allocation.amount = bound;
+                            allocations.set(i, allocation.clone());
+                            pinned.set(i, true);
+                            newly_pinned = true;
+                        }
+                    }
+                }
+                if pinned.get(i).unwrap() {
+                    pinned_total = pinned_total
+                        .checked_add(allocation.amount)
+                        .ok_or(RemittanceSplitError::Overflow)?;
+                }
+            }
+            if !newly_pinned {
+                break;
+            }
+
+            let residual = total_amount - pinned_total;
+            let mut free_bps: i128 = 0;
+            let mut last_free = None;
+            for i in 0..count {
+                if !pinned.get(i).unwrap() {
+                    free_bps += categories.get(i).unwrap().bps as i128;
+                    last_free = Some(i);
+                }
+            }
+            let Some(last_free) = last_free else {
+                if residual != 0 {
+                    return Err(RemittanceSplitError::LimitsUnsatisfiable);
+                }
+                break;
+            };
+            if residual < 0 {
+                return Err(RemittanceSplitError::LimitsUnsatisfiable);
+            }
+
+            let mut handed_out: i128 = 0;
+            for i in 0..count {
+                if pinned.get(i).unwrap() {
+                    continue;
+                }
+                let mut allocation = allocations.get(i).unwrap();
+                allocation.amount = if i == last_free {
+                    residual - handed_out
+                } else if free_bps == 0 {
+                    0
+                } else {
+                    residual
+                        .checked_mul(categories.get(i).unwrap().bps as i128)
+                        .ok_or(RemittanceSplitError::Overflow)?
+                        / free_bps
+                };
+                handed_out += allocation.amount;
+                allocations.set(i, allocation);
+            }
+        }
 
-        let mut result = Vec::new(env);
-        for (category, amount) in categories.into_iter().zip(amounts.into_iter()) {
-            result.push_back(Allocation { category, amount });
+        for i in 0..count {
+            let mut allocation = allocations.get(i).unwrap();
+            allocation.adjustment = allocation.amount - base.get(i).unwrap().amount;
+            allocations.set(i, allocation);
         }
-        Ok(result)
+        Ok(())
     }
 
-    pub fn get_nonce(env: Env, address: Address) -> u64 {
-        Self::get_nonce_value(&env, &address)
+    /// Hand out `remainder` (always fewer units than there are categories)
+    /// according to the owner's policy.
+    fn apply_remainder(
+        env: &Env,
+        owner: &Address,
+        allocations: &mut Vec<Allocation>,
+        mut fractions: Vec<i128>,
+        remainder: i128,
+    ) {
+        if remainder == 0 {
+            return;
+        }
+        let last = allocations.len() - 1;
+        match Self::get_remainder_policy(env.clone(), owner.clone()) {
+            RemainderPolicy::LastCategory => Self::add_to_allocation(allocations, last, remainder),
+            RemainderPolicy::Category(name) => {
+                let index = allocations
+                    .iter()
+                    .position(|allocation| allocation.category == name)
+                    .map(|i| i as u32)
+                    .unwrap_or(last);
+                Self::add_to_allocation(allocations, index, remainder);
+            }
+            RemainderPolicy::LargestRemainder => {
+                for _ in 0..remainder {
+                    let mut best = 0;
+                    for i in 1..fractions.len() {
+                        if fractions.get(i).unwrap() > fractions.get(best).unwrap() {
+                            best = i;
+                        }
+                    }
+                    Self::add_to_allocation(allocations, best, 1);
+                    fractions.set(best, -1);
+                }
+            }
+        }
     }
 
-    fn get_nonce_value(env: &Env, address: &Address) -> u64 {
-        let nonces: Option<Map<Address, u64>> =
-            env.storage().instance().get(&symbol_short!("NONCES"));
-        nonces
-            .as_ref()
-            .and_then(|m: &Map<Address, u64>| m.get(address.clone()))
-            .unwrap_or(0)
+    fn add_to_allocation(allocations: &mut Vec<Allocation>, index: u32, amount: i128) {
+        let mut allocation = allocations.get(index).unwrap();
+        allocation.amount += amount;
+        allocations.set(index, allocation);
     }
 
-    pub fn export_snapshot(
-        env: Env,
-        caller: Address,
-    ) -> Result<Option<ExportSnapshot>, RemittanceSplitError> {
-        caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
+    /// Extend the TTL of instance storage
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
             .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Whole percentages to basis points. Out-of-range inputs saturate so the
+    /// sum check rejects them instead of overflowing.
+    fn percents_to_bps(percents: [u32; 4]) -> [u32; 4] {
+        percents.map(|p| p.saturating_mul(100))
+    }
+
+    fn is_full_split(shares: &[u32]) -> bool {
+        let mut total: u32 = 0;
+        for share in shares {
+            match total.checked_add(*share) {
+                Some(sum) => total = sum,
+                None => return false,
+            }
         }
-        let checksum = Self::compute_checksum(SNAPSHOT_VERSION, &config);
-        Ok(Some(ExportSnapshot {
-            version: SNAPSHOT_VERSION,
-            checksum,
-            config,
-        }))
+        total == TOTAL_BPS
     }
 
-    pub fn import_snapshot(
-        env: Env,
-        caller: Address,
-        nonce: u64,
-        snapshot: ExportSnapshot,
-    ) -> Result<bool, RemittanceSplitError> {
-        caller.require_auth();
-        Self::require_nonce(&env, &caller, nonce)?;
+    /// The explicit admin, else the owner of a single-owner deployment's
+    /// configuration that has not moved to its own entry yet.
+    fn get_contract_owner(env: &Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("OWNER"))
+            .or_else(|| Self::load_instance_config(env).map(|config| config.owner))
+    }
 
-        if snapshot.version != SNAPSHOT_VERSION {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(RemittanceSplitError::UnsupportedVersion);
+    fn load_instance_config(env: &Env) -> Option<LegacySplitConfig> {
+        env.storage().instance().get(&symbol_short!("CONFIG"))
+    }
+
+    /// Each owner's configuration lives in its own persistent entry. Owners
+    /// who configured before basis points still have a whole-percent entry
+    /// under the old key, and the owner of a single-owner deployment still
+    /// has the instance `CONFIG`; both are converted on read.
+    fn load_config(env: &Env, owner: &Address) -> Option<SplitConfig> {
+        let key = (symbol_short!("CFG_BPS"), owner.clone());
+        if let Some(config) = env.storage().persistent().get::<_, SplitConfig>(&key) {
+            env.storage().persistent().extend_ttl(
+                &key,
+                INSTANCE_LIFETIME_THRESHOLD,
+                INSTANCE_BUMP_AMOUNT,
+            );
+            return Some(config);
         }
-        let expected = Self::compute_checksum(snapshot.version, &snapshot.config);
-        if snapshot.checksum != expected {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(RemittanceSplitError::ChecksumMismatch);
+
+        let legacy_key = (symbol_short!("CONFIG"), owner.clone());
+        let legacy: LegacySplitConfig = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .or_else(|| Self::load_instance_config(env).filter(|c| c.owner == *owner))?;
+        let bps = Self::percents_to_bps([
+            legacy.spending_percent,
+            legacy.savings_percent,
+            legacy.bills_percent,
+            legacy.insurance_percent,
+        ]);
+        Some(SplitConfig {
+            owner: legacy.owner,
+            spending_bps: bps[0],
+            savings_bps: bps[1],
+            bills_bps: bps[2],
+            insurance_bps: bps[3],
+            timestamp: legacy.timestamp,
+            initialized: legacy.initialized,
+        })
+    }
+
+    /// The split in force now: the latest scheduled change that is due, else
+    /// the stored split.
+    fn load_categories(env: &Env, owner: &Address) -> Vec<CategoryAlloc> {
+        let now = env.ledger().timestamp();
+        let mut due = None;
+        for change in Self::load_scheduled_changes(env, owner).iter() {
+            if change.effective_at <= now {
+                due = Some(change.categories);
+            }
+        }
+        due.unwrap_or_else(|| Self::load_stored_categories(env, owner))
+    }
+
+    /// The custom list if set, else the four standard categories.
+    fn load_stored_categories(env: &Env, owner: &Address) -> Vec<CategoryAlloc> {
+        let key = (symbol_short!("CATS"), owner.clone());
+        if let Some(categories) = env.storage().persistent().get(&key) {
+            return categories;
+        }
+        let (spending, savings, bills, insurance) = match Self::load_config(env, owner) {
+            Some(config) => (
+                config.spending_bps,
+                config.savings_bps,
+                config.bills_bps,
+                config.insurance_bps,
+            ),
+            None => (5000, 3000, 1500, 500),
+        };
+        Self::standard_categories(env, spending, savings, bills, insurance)
+    }
+
+    fn standard_categories(
+        env: &Env,
+        spending: u32,
+        savings: u32,
+        bills: u32,
+        insurance: u32,
+    ) -> Vec<CategoryAlloc> {
+        vec![
+            env,
+            CategoryAlloc {
+                name: symbol_short!("SPENDING"),
+                bps: spending,
+            },
+            CategoryAlloc {
+                name: symbol_short!("SAVINGS"),
+                bps: savings,
+            },
+            CategoryAlloc {
+                name: symbol_short!("BILLS"),
+                bps: bills,
+            },
+            CategoryAlloc {
+                name: symbol_short!("INSURANCE"),
+                bps: insurance,
+            },
+        ]
+    }
+
+    /// The admin's templates, or the built-in presets if none were ever set.
+    fn load_templates(env: &Env) -> Map<u32, SplitTemplate> {
+        if let Some(templates) = env.storage().instance().get(&symbol_short!("TEMPLATES")) {
+            return templates;
+        }
+        let mut templates = Map::new(env);
+        let presets = [
+            (1, symbol_short!("CONSERV"), (4000, 3000, 2000, 1000)),
+            (2, symbol_short!("DEBT"), (3000, 1000, 5000, 1000)),
+            (3, symbol_short!("SAVER"), (3000, 5000, 1500, 500)),
+        ];
+        for (id, name, (spending, savings, bills, insurance)) in presets {
+            templates.set(
+                id,
+                SplitTemplate {
+                    id,
+                    name,
+                    categories: Self::standard_categories(env, spending, savings, bills, insurance),
+                },
+            );
         }
+        templates
+    }
 
-        let existing: SplitConfig = env
+    /// The owner's binding for `category`, falling back to the contract-wide
+    /// destination for the four standard categories.
+    fn resolve_destination(env: &Env, owner: &Address, category: &Symbol) -> Option<Address> {
+        let registry: Option<Map<Symbol, Address>> = env
             .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if existing.owner != caller {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(RemittanceSplitError::Unauthorized);
+            .persistent()
+            .get(&(symbol_short!("DEST"), owner.clone()));
+        if let Some(destination) = registry.and_then(|r| r.get(category.clone())) {
+            return Some(destination);
         }
+        let accounts: AccountGroup = env.storage().instance().get(&symbol_short!("DESTS"))?;
+        Self::standard_account(&accounts, category)
+    }
 
-        let total = snapshot.config.spending_percent
-            + snapshot.config.savings_percent
-            + snapshot.config.bills_percent
-            + snapshot.config.insurance_percent;
-        if total != 100 {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+    /// The account in `accounts` for one of the four standard categories,
+    /// or `None` for any other name.
+    fn standard_account(accounts: &AccountGroup, category: &Symbol) -> Option<Address> {
+        if *category == symbol_short!("SPENDING") {
+            Some(accounts.spending.clone())
+        } else if *category == symbol_short!("SAVINGS") {
+            Some(accounts.savings.clone())
+        } else if *category == symbol_short!("BILLS") {
+            Some(accounts.bills.clone())
+        } else if *category == symbol_short!("INSURANCE") {
+            Some(accounts.insurance.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Non-empty, at most 10 entries, unique names, shares summing to 10000.
+    fn validate_categories(categories: &Vec<CategoryAlloc>) -> Result<(), RemittanceSplitError> {
+        if categories.is_empty() || categories.len() > MAX_CATEGORIES {
+            return Err(RemittanceSplitError::InvalidCategories);
+        }
+        let mut total: u32 = 0;
+        for (i, category) in categories.iter().enumerate() {
+            for j in 0..i as u32 {
+                if categories.get(j).unwrap().name == category.name {
+                    return Err(RemittanceSplitError::InvalidCategories);
+                }
+            }
+            total = total
+                .checked_add(category.bps)
+                .ok_or(RemittanceSplitError::PercentagesDoNotSumTo100)?;
+        }
+        if total != TOTAL_BPS {
             return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
         }
+        Ok(())
+    }
 
-        Self::extend_instance_ttl(&env);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &snapshot.config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                snapshot.config.spending_percent,
-                snapshot.config.savings_percent,
-                snapshot.config.bills_percent,
-                snapshot.config.insurance_percent,
-            ],
+    fn store_categories(env: &Env, owner: &Address, categories: &Vec<CategoryAlloc>) {
+        let key = (symbol_short!("CATS"), owner.clone());
+        env.storage().persistent().set(&key, categories);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
         );
+    }
 
-        Self::increment_nonce(&env, &caller)?;
-        Self::append_audit(&env, symbol_short!("import"), &caller, true);
-        Ok(true)
+    /// Drop the custom category list (and with it the active profile name),
+    /// reverting to the four standard categories.
+    fn clear_categories(env: &Env, owner: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("CATS"), owner.clone()));
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("ACT_PROF"), owner.clone()));
     }
 
-    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
-        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
-        let log = log.unwrap_or_else(|| Vec::new(&env));
-        let len = log.len();
-        let cap = MAX_AUDIT_ENTRIES.min(limit);
-        let mut out = Vec::new(&env);
-        if from_index >= len {
-            return out;
-        }
-        let end = (from_index + cap).min(len);
-        for i in from_index..end {
-            if let Some(entry) = log.get(i) {
-                out.push_back(entry);
-            }
+    fn load_scheduled_changes(env: &Env, owner: &Address) -> Vec<ScheduledSplitChange> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("SPLIT_CHG"), owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn load_corridors(env: &Env, owner: &Address) -> Map<Symbol, Vec<CategoryAlloc>> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("CORRIDOR"), owner.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_corridors(env: &Env, owner: &Address, corridors: &Map<Symbol, Vec<CategoryAlloc>>) {
+        let key = (symbol_short!("CORRIDOR"), owner.clone());
+        if corridors.is_empty() {
+            env.storage().persistent().remove(&key);
+            return;
         }
-        out
+        env.storage().persistent().set(&key, corridors);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
     }
 
-    fn require_nonce(
+    /// The corridor's own categories, else the default split.
+    fn corridor_categories(env: &Env, owner: &Address, corridor: &Symbol) -> Vec<CategoryAlloc> {
+        Self::load_corridors(env, owner)
+            .get(corridor.clone())
+            .unwrap_or_else(|| Self::load_categories(env, owner))
+    }
+
+    /// Add a change to `owner`'s queue, keeping it ordered by effective time.
+    fn insert_scheduled_change(
         env: &Env,
-        address: &Address,
-        expected: u64,
-    ) -> Result<(), RemittanceSplitError> {
-        let current = Self::get_nonce_value(env, address);
-        if expected != current {
-            return Err(RemittanceSplitError::InvalidNonce);
+        owner: &Address,
+        categories: Vec<CategoryAlloc>,
+        effective_at: u64,
+    ) -> Result<u32, RemittanceSplitError> {
+        let now = env.ledger().timestamp();
+        let pending = Self::load_scheduled_changes(env, owner);
+        if pending.len() >= MAX_SCHEDULED_CHANGES {
+            return Err(RemittanceSplitError::TooManyScheduledChanges);
         }
-        Ok(())
-    }
 
-    fn increment_nonce(env: &Env, address: &Address) -> Result<(), RemittanceSplitError> {
-        let current = Self::get_nonce_value(env, address);
-        let next = current
-            .checked_add(1)
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let mut nonces: Map<Address, u64> = env
+        Self::extend_instance_ttl(env);
+        let id = env
             .storage()
             .instance()
-            .get(&symbol_short!("NONCES"))
-            .unwrap_or_else(|| Map::new(env));
-        nonces.set(address.clone(), next);
+            .get(&symbol_short!("NEXT_CHG"))
+            .unwrap_or(0u32)
+            + 1;
         env.storage()
             .instance()
-            .set(&symbol_short!("NONCES"), &nonces);
-        Ok(())
+            .set(&symbol_short!("NEXT_CHG"), &id);
+
+        let change = ScheduledSplitChange {
+            id,
+            categories,
+            effective_at,
+            created_at: now,
+        };
+        let mut updated = Vec::new(env);
+        let mut inserted = false;
+        for existing in pending.iter() {
+            if !inserted && effective_at < existing.effective_at {
+                updated.push_back(change.clone());
+                inserted = true;
+            }
+            updated.push_back(existing);
+        }
+        if !inserted {
+            updated.push_back(change);
+        }
+        Self::save_scheduled_changes(env, owner, &updated);
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ChangeScheduled),
+            (owner.clone(), id, effective_at),
+        );
+        Ok(id)
     }
 
-    fn compute_checksum(version: u32, config: &SplitConfig) -> u64 {
-        let v = version as u64;
-        let s = config.spending_percent as u64;
-        let g = config.savings_percent as u64;
-        let b = config.bills_percent as u64;
-        let i = config.insurance_percent as u64;
-        v.wrapping_add(s)
-            .wrapping_add(g)
-            .wrapping_add(b)
-            .wrapping_add(i)
-            .wrapping_mul(31)
+    fn save_scheduled_changes(env: &Env, owner: &Address, changes: &Vec<ScheduledSplitChange>) {
+        let key = (symbol_short!("SPLIT_CHG"), owner.clone());
+        if changes.is_empty() {
+            env.storage().persistent().remove(&key);
+            return;
+        }
+        env.storage().persistent().set(&key, changes);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
     }
 
-    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
-        let timestamp = env.ledger().timestamp();
-        let mut log: Vec<AuditEntry> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("AUDIT"))
-            .unwrap_or_else(|| Vec::new(env));
-        if log.len() >= MAX_AUDIT_ENTRIES {
-            let mut new_log = Vec::new(env);
-            for i in 1..log.len() {
-                if let Some(entry) = log.get(i) {
-                    new_log.push_back(entry);
-                }
+    /// Store the scheduled change currently in force as the owner's split and
+    /// record it in the history under its effective time. Called by every
+    /// state-changing entrypoint so due changes are applied before anything
+    /// else touches the split.
+    fn promote_due_change(env: &Env, owner: &Address) {
+        let now = env.ledger().timestamp();
+        let pending = Self::load_scheduled_changes(env, owner);
+        let mut remaining = Vec::new(env);
+        let mut due = None;
+        for change in pending.iter() {
+            if change.effective_at <= now {
+                due = Some(change);
+            } else {
+                remaining.push_back(change);
             }
-            log = new_log;
         }
-        log.push_back(AuditEntry {
-            operation,
-            caller: caller.clone(),
-            timestamp,
-            success,
-        });
-        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+        let Some(change) = due else {
+            return;
+        };
+
+        let old = Self::load_stored_categories(env, owner);
+        Self::save_scheduled_changes(env, owner, &remaining);
+        Self::clear_categories(env, owner);
+        Self::store_categories(env, owner, &change.categories);
+        Self::push_config_history(
+            env,
+            owner,
+            ConfigChange {
+                operation: symbol_short!("sched"),
+                changed_by: owner.clone(),
+                old_categories: old,
+                new_categories: change.categories,
+                timestamp: change.effective_at,
+            },
+        );
     }
 
-    fn calculate_split_amounts(
+    /// Append the change from `old` to the split now in force.
+    fn record_config_change(
         env: &Env,
-        total_amount: i128,
-        emit_events: bool,
-    ) -> Result<[i128; 4], RemittanceSplitError> {
-        if total_amount <= 0 {
-            return Err(RemittanceSplitError::InvalidAmount);
-        }
+        owner: &Address,
+        changed_by: &Address,
+        operation: Symbol,
+        old: Vec<CategoryAlloc>,
+    ) {
+        let change = ConfigChange {
+            operation,
+            changed_by: changed_by.clone(),
+            old_categories: old,
+            new_categories: Self::load_categories(env, owner),
+            timestamp: env.ledger().timestamp(),
+        };
+        Self::push_config_history(env, owner, change);
+    }
 
-        let split = Self::get_split(env);
-        let s0 = split.get(0).unwrap() as i128;
-        let s1 = split.get(1).unwrap() as i128;
-        let s2 = split.get(2).unwrap() as i128;
+    fn push_config_history(env: &Env, owner: &Address, change: ConfigChange) {
+        let key = (symbol_short!("CFG_HIST"), owner.clone());
+        let mut history: Vec<ConfigChange> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        if history.len() >= MAX_CONFIG_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(change);
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+    }
 
-        let spending = total_amount
-            .checked_mul(s0)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let savings = total_amount
-            .checked_mul(s1)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let bills = total_amount
-            .checked_mul(s2)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let insurance = total_amount
-            .checked_sub(spending)
-            .and_then(|n| n.checked_sub(savings))
-            .and_then(|n| n.checked_sub(bills))
-            .ok_or(RemittanceSplitError::Overflow)?;
+    fn load_limits(env: &Env, owner: &Address) -> Map<Symbol, CategoryLimit> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("LIMITS"), owner.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
 
-        if emit_events {
-            let event = SplitCalculatedEvent {
-                total_amount,
-                spending_amount: spending,
-                savings_amount: savings,
-                bills_amount: bills,
-                insurance_amount: insurance,
-                timestamp: env.ledger().timestamp(),
-            };
-            env.events().publish((SPLIT_CALCULATED,), event);
-            env.events().publish(
-                (symbol_short!("split"), SplitEvent::Calculated),
-                total_amount,
-            );
-        }
+    fn load_profiles(env: &Env, owner: &Address) -> Map<Symbol, Vec<CategoryAlloc>> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("PROFILES"), owner.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
 
-        Ok([spending, savings, bills, insurance])
+    fn save_profiles(env: &Env, owner: &Address, profiles: &Map<Symbol, Vec<CategoryAlloc>>) {
+        let key = (symbol_short!("PROFILES"), owner.clone());
+        env.storage().persistent().set(&key, profiles);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
     }
 
-    /// Extend the TTL of instance storage
-    fn extend_instance_ttl(env: &Env) {
+    /// Saving always writes basis points and drops any legacy entry.
+    fn save_config(env: &Env, config: &SplitConfig) {
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .persistent()
+            .remove(&(symbol_short!("CONFIG"), config.owner.clone()));
+        if Self::load_instance_config(env).is_some_and(|c| c.owner == config.owner) {
+            // The single-owner layout's owner stays the contract owner.
+            if !env.storage().instance().has(&symbol_short!("OWNER")) {
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("OWNER"), &config.owner);
+            }
+            env.storage().instance().remove(&symbol_short!("CONFIG"));
+            env.storage().instance().remove(&symbol_short!("SPLIT"));
+        }
+        let key = (symbol_short!("CFG_BPS"), config.owner.clone());
+        env.storage().persistent().set(&key, config);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
     }
 
     pub fn create_remittance_schedule(
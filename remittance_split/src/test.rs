@@ -40,12 +40,12 @@ fn test_initialize_split() {
 
     assert_eq!(success, true);
 
-    let config = client.get_config().unwrap();
+    let config = client.get_config(&owner).unwrap();
     assert_eq!(config.owner, owner);
-    assert_eq!(config.spending_percent, 50);
-    assert_eq!(config.savings_percent, 30);
-    assert_eq!(config.bills_percent, 15);
-    assert_eq!(config.insurance_percent, 5);
+    assert_eq!(config.spending_bps, 5000);
+    assert_eq!(config.savings_bps, 3000);
+    assert_eq!(config.bills_bps, 1500);
+    assert_eq!(config.insurance_bps, 500);
 }
 
 #[test]
@@ -62,7 +62,10 @@ fn test_initialize_split_invalid_sum() {
         &50, &50, &10, // Sums to 110
         &0,
     );
-    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentages)));
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
 }
 
 #[test]
@@ -94,11 +97,11 @@ fn test_update_split() {
     let success = client.update_split(&owner, &1, &40, &40, &10, &10);
     assert_eq!(success, true);
 
-    let config = client.get_config().unwrap();
-    assert_eq!(config.spending_percent, 40);
-    assert_eq!(config.savings_percent, 40);
-    assert_eq!(config.bills_percent, 10);
-    assert_eq!(config.insurance_percent, 10);
+    let config = client.get_config(&owner).unwrap();
+    assert_eq!(config.spending_bps, 4000);
+    assert_eq!(config.savings_bps, 4000);
+    assert_eq!(config.bills_bps, 1000);
+    assert_eq!(config.insurance_bps, 1000);
 }
 
 #[test]
@@ -113,8 +116,54 @@ fn test_update_split_unauthorized() {
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
+    // Another address has no configuration of its own and cannot touch the owner's.
     let result = client.try_update_split(&other, &0, &40, &40, &10, &10);
-    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+    assert_eq!(client.get_config(&owner).unwrap().spending_bps, 5000);
+}
+
+#[test]
+fn test_split_configs_are_independent_per_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.initialize_split(&alice, &0, &50, &30, &15, &5);
+    client.initialize_split(&bob, &0, &25, &25, &25, &25);
+    client.update_split(&bob, &1, &10, &60, &20, &10);
+
+    assert_eq!(client.get_config(&alice).unwrap().spending_bps, 5000);
+    assert_eq!(client.get_config(&bob).unwrap().savings_bps, 6000);
+
+    let alice_amounts = client.calculate_split(&alice, &1000);
+    let bob_amounts = client.calculate_split(&bob, &1000);
+    assert_eq!(alice_amounts.get(0).unwrap(), 500);
+    assert_eq!(bob_amounts.get(0).unwrap(), 100);
+    assert_eq!(bob_amounts.get(1).unwrap(), 600);
+
+    // Configuring a split grants no admin rights; the admin is named
+    // explicitly, once.
+    assert_eq!(client.get_owner(), None);
+    assert_eq!(
+        client.try_pause(&alice),
+        Err(Ok(RemittanceSplitError::NotInitialized))
+    );
+    client.initialize_admin(&alice);
+    assert_eq!(
+        client.try_initialize_admin(&bob),
+        Err(Ok(RemittanceSplitError::AlreadyInitialized))
+    );
+    assert_eq!(client.get_owner(), Some(alice.clone()));
+    assert_eq!(
+        client.try_pause(&bob),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    client.pause(&alice);
+    assert!(client.is_paused());
 }
 
 #[test]
@@ -129,7 +178,7 @@ fn test_calculate_split() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     // Test with 1000 units
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
 
     // spending: 50% of 1000 = 500
     // savings: 30% of 1000 = 300
@@ -160,7 +209,7 @@ fn test_calculate_split_rounding() {
     // insurance = total - spending - savings - bills
     // 100 - 33 - 33 - 33 = 1. Correct.
 
-    let amounts = client.calculate_split(&100);
+    let amounts = client.calculate_split(&owner, &100);
     assert_eq!(amounts.get(0).unwrap(), 33);
     assert_eq!(amounts.get(1).unwrap(), 33);
     assert_eq!(amounts.get(2).unwrap(), 33);
@@ -177,7 +226,7 @@ fn test_calculate_split_zero_amount() {
     env.mock_all_auths();
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-    let result = client.try_calculate_split(&0);
+    let result = client.try_calculate_split(&owner, &0);
     assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
 }
 
@@ -198,7 +247,7 @@ fn test_calculate_complex_rounding() {
     // 23% = 230
     // 41% = 410
     // Sum = 1000. Perfect.
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 170);
     assert_eq!(amounts.get(1).unwrap(), 190);
     assert_eq!(amounts.get(2).unwrap(), 230);
@@ -209,7 +258,7 @@ fn test_calculate_complex_rounding() {
     // 19% of 3 = 0
     // 23% of 3 = 0
     // Remainder = 3 - 0 - 0 - 0 = 3. All goes to insurance.
-    let tiny_amounts = client.calculate_split(&3);
+    let tiny_amounts = client.calculate_split(&owner, &3);
     assert_eq!(tiny_amounts.get(0).unwrap(), 0);
     assert_eq!(tiny_amounts.get(3).unwrap(), 3);
 }
@@ -397,7 +446,7 @@ fn test_calculate_split_events() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     let total_amount = 1000i128;
-    client.calculate_split(&total_amount);
+    client.calculate_split(&owner, &total_amount);
 
     let events = env.events().all();
     // calculate_split publishes two events:
@@ -417,6 +466,55 @@ fn test_calculate_split_events() {
     assert_eq!(data, total_amount);
 }
 
+#[test]
+fn test_preview_split_emits_no_events() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let calculated = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|e| Symbol::try_from_val(env, &e.1.get(0).unwrap()) == Ok(SPLIT_CALCULATED))
+            .count()
+    };
+
+    let preview = client.preview_split(&owner, &1000);
+    assert_eq!(calculated(&env), 0);
+    client.get_split_allocations(&owner, &1000);
+    assert_eq!(calculated(&env), 0);
+
+    assert_eq!(preview, client.calculate_split(&owner, &1000));
+    assert_eq!(calculated(&env), 1);
+
+    assert_eq!(
+        client.try_preview_split(&owner, &0),
+        Err(Ok(RemittanceSplitError::InvalidAmount))
+    );
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
+fn test_calculate_split_requires_owner_auth() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    env.set_auths(&[]);
+
+    // Anyone can preview, but only the owner can publish a calculation.
+    client.preview_split(&owner, &1000);
+    client.calculate_split(&owner, &1000);
+}
+
 #[test]
 #[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
 fn test_update_split_non_owner_auth_failure() {
@@ -463,14 +561,14 @@ fn test_split_boundary_100_0_0_0() {
     assert!(ok);
 
     // get_split must return the exact percentages
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 100);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
     // calculate_split must allocate the entire amount to spending
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 1000);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -490,13 +588,13 @@ fn test_split_boundary_0_100_0_0() {
     let ok = client.initialize_split(&owner, &0, &0, &100, &0, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 100);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 1000);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -516,13 +614,13 @@ fn test_split_boundary_0_0_100_0() {
     let ok = client.initialize_split(&owner, &0, &0, &0, &100, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 100);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 1000);
@@ -542,14 +640,14 @@ fn test_split_boundary_0_0_0_100() {
     let ok = client.initialize_split(&owner, &0, &0, &0, &0, &100);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 100);
 
     // Insurance gets the remainder: 1000 - 0 - 0 - 0 = 1000
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -569,14 +667,14 @@ fn test_split_boundary_25_25_25_25() {
     let ok = client.initialize_split(&owner, &0, &25, &25, &25, &25);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 25);
     assert_eq!(split.get(1).unwrap(), 25);
     assert_eq!(split.get(2).unwrap(), 25);
     assert_eq!(split.get(3).unwrap(), 25);
 
     // 25 % of 1000 = 250 for each category
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 250);
     assert_eq!(amounts.get(1).unwrap(), 250);
     assert_eq!(amounts.get(2).unwrap(), 250);
@@ -601,13 +699,13 @@ fn test_update_split_boundary_percentages() {
     let ok = client.update_split(&owner, &1, &100, &0, &0, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 100);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 1000);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -617,13 +715,13 @@ fn test_update_split_boundary_percentages() {
     let ok = client.update_split(&owner, &1, &25, &25, &25, &25);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 25);
     assert_eq!(split.get(1).unwrap(), 25);
     assert_eq!(split.get(2).unwrap(), 25);
     assert_eq!(split.get(3).unwrap(), 25);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 250);
     assert_eq!(amounts.get(1).unwrap(), 250);
     assert_eq!(amounts.get(2).unwrap(), 250);
@@ -641,12 +739,2000 @@ fn test_update_split_not_initialized() {
     let result = client.try_update_split(&caller, &0, &25, &25, &25, &25);
     assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
 
-    let config = client.get_config();
+    let config = client.get_config(&caller);
     assert!(config.is_none());
 
-    let split = client.get_split();
+    let split = client.get_split(&caller);
     assert_eq!(split.get(0).unwrap(), 50);
     assert_eq!(split.get(1).unwrap(), 30);
     assert_eq!(split.get(2).unwrap(), 15);
     assert_eq!(split.get(3).unwrap(), 5);
 }
+
+// ──────────────────────────────────────────────────────────────────────────
+// Custom categories
+// ──────────────────────────────────────────────────────────────────────────
+
+fn category(env: &Env, name: &str, bps: u32) -> CategoryAlloc {
+    CategoryAlloc {
+        name: Symbol::new(env, name),
+        bps,
+    }
+}
+
+#[test]
+fn test_set_categories_calculates_per_category() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let categories = Vec::from_array(
+        &env,
+        [
+            category(&env, "rent", 3300),
+            category(&env, "school", 3300),
+            category(&env, "food", 3400),
+        ],
+    );
+    client.set_categories(&owner, &categories);
+    assert_eq!(client.get_categories(&owner), categories);
+
+    // Rounded-down amounts for all but the last, which takes the remainder.
+    let amounts = client.calculate_split(&owner, &1000);
+    assert_eq!(amounts.len(), 3);
+    assert_eq!(amounts.get(0).unwrap(), 330);
+    assert_eq!(amounts.get(1).unwrap(), 330);
+    assert_eq!(amounts.get(2).unwrap(), 340);
+
+    let allocations = client.get_split_allocations(&owner, &100);
+    assert_eq!(
+        allocations.get(2).unwrap().category,
+        Symbol::new(&env, "food")
+    );
+
+    // The four-bucket update replaces the custom list.
+    client.update_split(&owner, &1, &40, &40, &10, &10);
+    assert_eq!(client.get_categories(&owner).len(), 4);
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 400);
+}
+
+#[test]
+fn test_set_categories_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let empty: Vec<CategoryAlloc> = Vec::new(&env);
+    assert_eq!(
+        client.try_set_categories(&owner, &empty),
+        Err(Ok(RemittanceSplitError::InvalidCategories))
+    );
+
+    let duplicate = Vec::from_array(
+        &env,
+        [category(&env, "rent", 5000), category(&env, "rent", 5000)],
+    );
+    assert_eq!(
+        client.try_set_categories(&owner, &duplicate),
+        Err(Ok(RemittanceSplitError::InvalidCategories))
+    );
+
+    let short = Vec::from_array(
+        &env,
+        [category(&env, "rent", 5000), category(&env, "food", 4000)],
+    );
+    assert_eq!(
+        client.try_set_categories(&owner, &short),
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    // Nothing was stored: the defaults still apply.
+    assert_eq!(client.get_split(&owner).len(), 4);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Executed splits
+// ──────────────────────────────────────────────────────────────────────────
+
+fn setup_execute(env: &Env) -> (RemittanceSplitClient<'_>, Address, Address, AccountGroup) {
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(env, &contract_id);
+    let owner = Address::generate(env);
+
+    env.mock_all_auths();
+    client.initialize_admin(&owner);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let accounts = AccountGroup {
+        spending: Address::generate(env),
+        savings: Address::generate(env),
+        bills: Address::generate(env),
+        insurance: Address::generate(env),
+    };
+    client.set_destinations(&owner, &accounts);
+
+    let token_admin = Address::generate(env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(env, &token).mint(&owner, &1_000);
+
+    (client, owner, token, accounts)
+}
+
+#[test]
+fn test_execute_split_transfers_each_portion() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+
+    let allocations = client.execute_split(&owner, &1_000, &token, &None);
+    assert_eq!(allocations.len(), 4);
+
+    assert_eq!(token_client.balance(&owner), 0);
+    assert_eq!(token_client.balance(&accounts.spending), 500);
+    assert_eq!(token_client.balance(&accounts.savings), 300);
+    assert_eq!(token_client.balance(&accounts.bills), 150);
+    assert_eq!(token_client.balance(&accounts.insurance), 50);
+}
+
+#[test]
+fn test_execute_split_is_all_or_nothing() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+
+    assert_eq!(
+        client.try_execute_split(&owner, &2_000, &token, &None),
+        Err(Ok(RemittanceSplitError::InsufficientBalance))
+    );
+
+    // A custom category without a destination aborts before any transfer.
+    client.set_categories(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                category(&env, "SAVINGS", 6000),
+                category(&env, "school", 4000),
+            ],
+        ),
+    );
+    assert_eq!(
+        client.try_execute_split(&owner, &1_000, &token, &None),
+        Err(Ok(RemittanceSplitError::DestinationNotSet))
+    );
+
+    assert_eq!(token_client.balance(&owner), 1_000);
+    assert_eq!(token_client.balance(&accounts.savings), 0);
+}
+
+#[test]
+fn test_set_destinations_requires_contract_owner() {
+    let env = Env::default();
+    let (client, _owner, _token, accounts) = setup_execute(&env);
+    let other = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_destinations(&other, &accounts),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Basis-point precision
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_split_bps_supports_fractional_percentages() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // 47.5 / 30 / 10 / 12.5
+    client.initialize_split_bps(&owner, &0, &4750, &3000, &1000, &1250);
+    assert_eq!(
+        client.get_split_bps(&owner),
+        Vec::from_array(&env, [4750, 3000, 1000, 1250])
+    );
+    // Whole-percent view truncates.
+    assert_eq!(client.get_split(&owner).get(3).unwrap(), 12);
+
+    let amounts = client.calculate_split(&owner, &1000);
+    assert_eq!(amounts.get(0).unwrap(), 475);
+    assert_eq!(amounts.get(3).unwrap(), 125);
+
+    assert_eq!(
+        client.try_update_split_bps(&owner, &1, &4750, &3000, &1000, &1000),
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+}
+
+#[test]
+fn test_legacy_percent_config_is_read_as_bps() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // A configuration stored before basis points were introduced.
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &(symbol_short!("CONFIG"), owner.clone()),
+            &LegacySplitConfig {
+                owner: owner.clone(),
+                spending_percent: 40,
+                savings_percent: 30,
+                bills_percent: 20,
+                insurance_percent: 10,
+                timestamp: 0,
+                initialized: true,
+            },
+        );
+    });
+
+    let config = client.get_config(&owner).unwrap();
+    assert_eq!(config.spending_bps, 4000);
+    assert_eq!(config.insurance_bps, 1000);
+    assert_eq!(client.calculate_split(&owner, &1000).get(2).unwrap(), 200);
+
+    // Updating rewrites the entry in basis points.
+    client.update_split_bps(&owner, &0, &4000, &3000, &1750, &1250);
+    assert_eq!(client.get_config(&owner).unwrap().bills_bps, 1750);
+    env.as_contract(&contract_id, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("CONFIG"), owner.clone())));
+    });
+}
+
+#[test]
+fn test_single_owner_deployment_migrates_on_update() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    // The layout from before configurations were keyed by owner: one
+    // instance configuration and a shared nonce map.
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(
+            &symbol_short!("CONFIG"),
+            &LegacySplitConfig {
+                owner: owner.clone(),
+                spending_percent: 40,
+                savings_percent: 30,
+                bills_percent: 20,
+                insurance_percent: 10,
+                timestamp: 0,
+                initialized: true,
+            },
+        );
+        env.storage().instance().set(
+            &symbol_short!("SPLIT"),
+            &Vec::from_array(&env, [40u32, 30, 20, 10]),
+        );
+        let mut nonces = Map::<Address, u64>::new(&env);
+        nonces.set(owner.clone(), 3);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+    });
+
+    assert_eq!(client.get_config(&owner).unwrap().savings_bps, 3000);
+    assert!(client.get_config(&other).is_none());
+    assert_eq!(client.get_owner(), Some(owner.clone()));
+    assert_eq!(
+        client.try_initialize_admin(&other),
+        Err(Ok(RemittanceSplitError::AlreadyInitialized))
+    );
+    assert_eq!(client.get_nonce(&owner), 3);
+
+    client.update_split(&owner, &3, &50, &30, &15, &5);
+    assert_eq!(client.get_config(&owner).unwrap().spending_bps, 5000);
+    assert_eq!(client.get_owner(), Some(owner.clone()));
+
+    // The next nonce is written to the address's own entry.
+    let snapshot = client.export_snapshot(&owner).unwrap();
+    client.import_snapshot(&owner, &3, &snapshot);
+    assert_eq!(client.get_nonce(&owner), 4);
+    env.as_contract(&contract_id, || {
+        let instance = env.storage().instance();
+        assert!(!instance.has(&symbol_short!("CONFIG")));
+        assert!(!instance.has(&symbol_short!("SPLIT")));
+        assert!(!instance.has(&symbol_short!("NONCES")));
+    });
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Remainder policy
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_remainder_policy_places_rounding_dust() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.set_categories(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                category(&env, "rent", 3333),
+                category(&env, "school", 3333),
+                category(&env, "food", 3334),
+            ],
+        ),
+    );
+
+    // 101 splits to 33 / 33 / 33 with 2 left over.
+    assert_eq!(
+        client.get_remainder_policy(&owner),
+        RemainderPolicy::LastCategory
+    );
+    assert_eq!(
+        client.calculate_split(&owner, &101),
+        Vec::from_array(&env, [33, 33, 35])
+    );
+
+    client.set_remainder_policy(
+        &owner,
+        &RemainderPolicy::Category(Symbol::new(&env, "rent")),
+    );
+    assert_eq!(
+        client.calculate_split(&owner, &101),
+        Vec::from_array(&env, [35, 33, 33])
+    );
+
+    client.set_remainder_policy(&owner, &RemainderPolicy::LargestRemainder);
+    assert_eq!(
+        client.calculate_split(&owner, &101),
+        Vec::from_array(&env, [34, 33, 34])
+    );
+
+    // The event reports the remainder.
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .rev()
+        .find(|e| Symbol::try_from_val(&env, &e.1.get(0).unwrap()) == Ok(SPLIT_CALCULATED))
+        .unwrap();
+    let data = SplitCalculatedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.remainder, 2);
+}
+
+#[test]
+fn test_remainder_policy_rejects_unknown_category() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    let result = client.try_set_remainder_policy(
+        &owner,
+        &RemainderPolicy::Category(Symbol::new(&env, "school")),
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidCategories)));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Named profiles
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_switch_profile_changes_live_split() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let normal = Symbol::new(&env, "normal");
+    let school = Symbol::new(&env, "school");
+    client.save_profile(
+        &owner,
+        &normal,
+        &Vec::from_array(
+            &env,
+            [
+                category(&env, "SPENDING", 6000),
+                category(&env, "SAVINGS", 4000),
+            ],
+        ),
+    );
+    client.save_profile(
+        &owner,
+        &school,
+        &Vec::from_array(
+            &env,
+            [
+                category(&env, "SPENDING", 2000),
+                category(&env, "fees", 8000),
+            ],
+        ),
+    );
+    assert_eq!(client.get_profiles(&owner).len(), 2);
+    assert_eq!(client.get_active_profile(&owner), None);
+
+    // Saving alone does not change the live split.
+    assert_eq!(client.calculate_split(&owner, &1000).len(), 4);
+
+    client.switch_profile(&owner, &normal);
+    client.switch_profile(&owner, &school);
+    let event = env.events().all().last().unwrap();
+    let topic1 = SplitEvent::try_from_val(&env, &event.1.get(1).unwrap()).unwrap();
+    assert_eq!(topic1, SplitEvent::ProfileSwitched);
+    let data = ProfileSwitchedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.previous, Some(normal));
+    assert_eq!(data.active, school);
+
+    assert_eq!(client.get_active_profile(&owner), Some(school.clone()));
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [200, 800])
+    );
+
+    // Changing the split directly leaves no active profile.
+    client.update_split(&owner, &1, &40, &40, &10, &10);
+    assert_eq!(client.get_active_profile(&owner), None);
+}
+
+#[test]
+fn test_profile_errors() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    let missing = Symbol::new(&env, "missing");
+    assert_eq!(
+        client.try_switch_profile(&owner, &missing),
+        Err(Ok(RemittanceSplitError::ProfileNotFound))
+    );
+    assert_eq!(
+        client.try_delete_profile(&owner, &missing),
+        Err(Ok(RemittanceSplitError::ProfileNotFound))
+    );
+
+    let invalid = Vec::from_array(&env, [category(&env, "SPENDING", 9000)]);
+    assert_eq!(
+        client.try_save_profile(&owner, &missing, &invalid),
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
+
+    let valid = Vec::from_array(&env, [category(&env, "SPENDING", 10000)]);
+    for name in ["p0", "p1", "p2", "p3", "p4", "p5", "p6", "p7", "p8", "p9"] {
+        client.save_profile(&owner, &Symbol::new(&env, name), &valid);
+    }
+    assert_eq!(
+        client.try_save_profile(&owner, &missing, &valid),
+        Err(Ok(RemittanceSplitError::TooManyProfiles))
+    );
+    // Overwriting an existing profile is still allowed.
+    client.save_profile(&owner, &Symbol::new(&env, "p0"), &valid);
+
+    client.delete_profile(&owner, &Symbol::new(&env, "p0"));
+    assert_eq!(client.get_profiles(&owner).len(), 9);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Scheduled split changes
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_scheduled_split_change_takes_effect_at_time() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let next_month = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 2000),
+            category(&env, "fees", 8000),
+        ],
+    );
+    let id = client.schedule_split_change(&owner, &next_month, &5_000);
+    assert_eq!(client.get_scheduled_changes(&owner).len(), 1);
+
+    // Before the effective time the current split still applies.
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 500);
+
+    set_time(&env, 5_000);
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [200, 800])
+    );
+    assert_eq!(client.get_categories(&owner), next_month);
+    assert_eq!(client.get_scheduled_changes(&owner).len(), 0);
+
+    // A change that is already in force can no longer be cancelled.
+    assert_eq!(
+        client.try_cancel_split_change(&owner, &id),
+        Err(Ok(RemittanceSplitError::ChangeNotFound))
+    );
+
+    // A direct change made afterwards replaces it.
+    client.update_split(&owner, &1, &40, &40, &10, &10);
+    assert_eq!(client.calculate_split(&owner, &1000).len(), 4);
+}
+
+#[test]
+fn test_cancel_and_validate_scheduled_changes() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let split = Vec::from_array(&env, [category(&env, "SPENDING", 10000)]);
+    assert_eq!(
+        client.try_schedule_split_change(&owner, &split, &1_000),
+        Err(Ok(RemittanceSplitError::InvalidDueDate))
+    );
+
+    let later = client.schedule_split_change(&owner, &split, &9_000);
+    let sooner = client.schedule_split_change(&owner, &split, &3_000);
+    let pending = client.get_scheduled_changes(&owner);
+    assert_eq!(pending.get(0).unwrap().id, sooner);
+    assert_eq!(pending.get(1).unwrap().id, later);
+
+    client.cancel_split_change(&owner, &sooner);
+    set_time(&env, 3_000);
+    assert_eq!(client.calculate_split(&owner, &1000).len(), 4);
+    assert_eq!(client.get_scheduled_changes(&owner).len(), 1);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Configuration history
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_config_history_records_every_change() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    set_time(&env, 2_000);
+    client.update_split(&owner, &1, &40, &40, &10, &10);
+
+    let custom = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 2000),
+            category(&env, "fees", 8000),
+        ],
+    );
+    client.schedule_split_change(&owner, &custom, &3_000);
+    set_time(&env, 4_000);
+    // The next state-changing call applies the due change.
+    client.calculate_split(&owner, &100);
+
+    let history = client.get_config_history(&owner, &0, &10);
+    assert_eq!(history.len(), 3);
+
+    let init = history.get(0).unwrap();
+    assert_eq!(init.operation, symbol_short!("init"));
+    assert_eq!(init.changed_by, owner);
+    assert_eq!(init.timestamp, 1_000);
+
+    let update = history.get(1).unwrap();
+    assert_eq!(update.operation, symbol_short!("update"));
+    assert_eq!(update.old_categories.get(0).unwrap().bps, 5000);
+    assert_eq!(update.new_categories.get(0).unwrap().bps, 4000);
+
+    let scheduled = history.get(2).unwrap();
+    assert_eq!(scheduled.operation, symbol_short!("sched"));
+    assert_eq!(scheduled.timestamp, 3_000);
+    assert_eq!(scheduled.old_categories.len(), 4);
+    assert_eq!(scheduled.new_categories, custom);
+
+    // Paging
+    let page = client.get_config_history(&owner, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().operation, symbol_short!("update"));
+    assert_eq!(client.get_config_history(&owner, &5, &10).len(), 0);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Floors and caps
+// ──────────────────────────────────────────────────────────────────────────
+
+fn limit(env: &Env, name: &str, floor: i128, cap: i128) -> CategoryLimit {
+    CategoryLimit {
+        category: Symbol::new(env, name),
+        floor,
+        cap,
+    }
+}
+
+#[test]
+fn test_category_floor_redistributes_proportionally() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_category_limits(
+        &owner,
+        &Vec::from_array(&env, [limit(&env, "INSURANCE", 100, 0)]),
+    );
+
+    // Insurance is raised from 50 to 100; the other 900 is shared 50:30:15.
+    let allocations = client.get_split_allocations(&owner, &1000);
+    let amounts: Vec<i128> = Vec::from_array(
+        &env,
+        [
+            allocations.get(0).unwrap().amount,
+            allocations.get(1).unwrap().amount,
+            allocations.get(2).unwrap().amount,
+            allocations.get(3).unwrap().amount,
+        ],
+    );
+    assert_eq!(amounts, Vec::from_array(&env, [473, 284, 143, 100]));
+    assert_eq!(allocations.get(0).unwrap().adjustment, -27);
+    assert_eq!(allocations.get(3).unwrap().adjustment, 50);
+
+    // A floor that is already met changes nothing.
+    let large = client.get_split_allocations(&owner, &10_000);
+    assert_eq!(large.get(3).unwrap().amount, 500);
+    assert_eq!(large.get(3).unwrap().adjustment, 0);
+}
+
+#[test]
+fn test_category_cap_redistributes_surplus() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_category_limits(
+        &owner,
+        &Vec::from_array(&env, [limit(&env, "SPENDING", 0, 400)]),
+    );
+
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [400, 360, 180, 60])
+    );
+    assert_eq!(client.get_category_limits(&owner).len(), 1);
+}
+
+#[test]
+fn test_category_limits_validation_and_unsatisfiable() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    assert_eq!(
+        client.try_set_category_limits(
+            &owner,
+            &Vec::from_array(&env, [limit(&env, "SPENDING", 500, 100)])
+        ),
+        Err(Ok(RemittanceSplitError::InvalidLimits))
+    );
+
+    client.set_category_limits(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                limit(&env, "SPENDING", 800, 0),
+                limit(&env, "INSURANCE", 300, 0),
+            ],
+        ),
+    );
+    assert_eq!(
+        client.try_calculate_split(&owner, &1000),
+        Err(Ok(RemittanceSplitError::LimitsUnsatisfiable))
+    );
+
+    // Clearing the limits restores the plain split.
+    client.set_category_limits(&owner, &Vec::new(&env));
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 500);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Configuration lock
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_lock_config_blocks_changes_until_expiry() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.lock_config(&owner, &5_000);
+    assert_eq!(client.get_config_lock(&owner), 5_000);
+
+    assert_eq!(
+        client.try_update_split(&owner, &1, &40, &40, &10, &10),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+    let split = Vec::from_array(&env, [category(&env, "SPENDING", 10000)]);
+    assert_eq!(
+        client.try_set_categories(&owner, &split),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+    assert_eq!(
+        client.try_set_remainder_policy(&owner, &RemainderPolicy::LargestRemainder),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+    // Rerouting a category would divert it just as well as reweighting it.
+    assert_eq!(
+        client.try_set_destination(&owner, &symbol_short!("SAVINGS"), &Address::generate(&env)),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+    // Changes can be announced for after the lock, but not before it ends.
+    assert_eq!(
+        client.try_schedule_split_change(&owner, &split, &4_000),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+    client.schedule_split_change(&owner, &split, &6_000);
+
+    // Locks can only be extended.
+    assert_eq!(
+        client.try_lock_config(&owner, &3_000),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+
+    // Calculation is unaffected.
+    assert_eq!(client.calculate_split(&owner, &1000).get(0).unwrap(), 500);
+
+    set_time(&env, 5_000);
+    client.update_split(&owner, &1, &40, &40, &10, &10);
+    assert_eq!(client.get_config(&owner).unwrap().spending_bps, 4000);
+}
+
+#[test]
+fn test_lock_config_rejects_conflicting_scheduled_change() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let split = Vec::from_array(&env, [category(&env, "SPENDING", 10000)]);
+    client.schedule_split_change(&owner, &split, &2_000);
+
+    assert_eq!(
+        client.try_lock_config(&owner, &3_000),
+        Err(Ok(RemittanceSplitError::ScheduledChangeConflict))
+    );
+    assert_eq!(
+        client.try_lock_config(&owner, &1_000),
+        Err(Ok(RemittanceSplitError::InvalidDueDate))
+    );
+    client.lock_config(&owner, &2_000);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Templates
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_apply_builtin_template_for_new_user() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    let templates = client.get_templates();
+    assert_eq!(templates.len(), 3);
+    let conservative = templates.get(0).unwrap();
+    assert_eq!(conservative.name, symbol_short!("CONSERV"));
+
+    client.apply_template(&user, &conservative.id);
+    assert_eq!(client.get_categories(&user), conservative.categories);
+    let amounts = client.calculate_split(&user, &1000);
+    assert_eq!(amounts.get(0).unwrap(), 400);
+    assert_eq!(amounts.get(3).unwrap(), 100);
+
+    assert_eq!(
+        client.try_apply_template(&user, &99),
+        Err(Ok(RemittanceSplitError::TemplateNotFound))
+    );
+}
+
+#[test]
+fn test_admin_manages_templates() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_admin(&admin);
+    client.initialize_split(&admin, &0, &50, &30, &15, &5);
+
+    let remit_home = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 2000),
+            category(&env, "FAMILY", 8000),
+        ],
+    );
+    assert_eq!(
+        client.try_set_template(&user, &10, &symbol_short!("HOME"), &remit_home),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    client.set_template(&admin, &10, &symbol_short!("HOME"), &remit_home);
+    client.remove_template(&admin, &2);
+
+    let templates = client.get_templates();
+    assert_eq!(templates.len(), 3);
+    assert_eq!(templates.get(2).unwrap().id, 10);
+    assert_eq!(
+        client.try_remove_template(&admin, &2),
+        Err(Ok(RemittanceSplitError::TemplateNotFound))
+    );
+
+    client.apply_template(&user, &10);
+    // Editing the template later leaves the user's split alone.
+    client.set_template(
+        &admin,
+        &10,
+        &symbol_short!("HOME"),
+        &Vec::from_array(&env, [category(&env, "FAMILY", 10000)]),
+    );
+    assert_eq!(client.get_categories(&user), remit_home);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Destination registry
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_set_destination_routes_custom_and_overrides_default() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let school = Address::generate(&env);
+    let goal = Address::generate(&env);
+
+    client.set_categories(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                category(&env, "SAVINGS", 6000),
+                category(&env, "school", 4000),
+            ],
+        ),
+    );
+    client.set_destination(&owner, &Symbol::new(&env, "school"), &school);
+    client.set_destination(&owner, &symbol_short!("SAVINGS"), &goal);
+    assert_eq!(
+        client.get_destination(&owner, &Symbol::new(&env, "school")),
+        Some(school.clone())
+    );
+    assert_eq!(
+        client.get_destination(&owner, &symbol_short!("BILLS")),
+        Some(accounts.bills.clone())
+    );
+
+    client.execute_split(&owner, &1_000, &token, &None);
+    assert_eq!(token_client.balance(&goal), 600);
+    assert_eq!(token_client.balance(&school), 400);
+    assert_eq!(token_client.balance(&accounts.savings), 0);
+
+    // Bindings are per owner.
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.get_destination(&other, &symbol_short!("SAVINGS")),
+        Some(accounts.savings)
+    );
+}
+
+#[test]
+fn test_set_destination_respects_cooldown() {
+    let env = Env::default();
+    let (client, owner, _token, _accounts) = setup_execute(&env);
+    let goal = Address::generate(&env);
+    client.set_destination(&owner, &symbol_short!("SAVINGS"), &goal);
+    client.set_cooldown(&owner, &7);
+
+    for name in ["SAVINGS", "INSURANCE", "BILLS"] {
+        assert_eq!(
+            client.try_set_destination(&owner, &Symbol::new(&env, name), &Address::generate(&env)),
+            Err(Ok(RemittanceSplitError::CooldownActive))
+        );
+    }
+    // Rebinding to the same address, or repointing spending, is allowed.
+    client.set_destination(&owner, &symbol_short!("SAVINGS"), &goal);
+    client.set_destination(&owner, &symbol_short!("SPENDING"), &Address::generate(&env));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Spending shares
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_execute_split_pays_spending_shares_directly() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let mom = Address::generate(&env);
+    let eldest = Address::generate(&env);
+
+    client.set_spending_shares(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                SpendingShare {
+                    recipient: mom.clone(),
+                    bps: 3000,
+                },
+                SpendingShare {
+                    recipient: eldest.clone(),
+                    bps: 2000,
+                },
+            ],
+        ),
+    );
+    assert_eq!(client.get_spending_shares(&owner).len(), 2);
+
+    let allocations = client.execute_split(&owner, &1_000, &token, &None);
+    assert_eq!(allocations.get(0).unwrap().amount, 500);
+    assert_eq!(token_client.balance(&mom), 150);
+    assert_eq!(token_client.balance(&eldest), 100);
+    assert_eq!(token_client.balance(&accounts.spending), 250);
+    assert_eq!(token_client.balance(&accounts.savings), 300);
+    assert_eq!(token_client.balance(&owner), 0);
+}
+
+#[test]
+fn test_full_spending_shares_need_no_spending_destination() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let mom = Address::generate(&env);
+    let eldest = Address::generate(&env);
+
+    let share = |recipient: &Address, bps: u32| SpendingShare {
+        recipient: recipient.clone(),
+        bps,
+    };
+    assert_eq!(
+        client.try_set_spending_shares(
+            &owner,
+            &Vec::from_array(&env, [share(&mom, 6000), share(&eldest, 5000)])
+        ),
+        Err(Ok(RemittanceSplitError::InvalidShares))
+    );
+    assert_eq!(
+        client.try_set_spending_shares(
+            &owner,
+            &Vec::from_array(&env, [share(&mom, 5000), share(&mom, 5000)])
+        ),
+        Err(Ok(RemittanceSplitError::InvalidShares))
+    );
+    client.set_spending_shares(
+        &owner,
+        &Vec::from_array(&env, [share(&mom, 3333), share(&eldest, 6667)]),
+    );
+
+    // 500 spending: 166 to mom, the remaining 334 (including dust) to eldest.
+    client.execute_split(&owner, &1_000, &token, &None);
+    assert_eq!(token_client.balance(&mom), 166);
+    assert_eq!(token_client.balance(&eldest), 334);
+    assert_eq!(token_client.balance(&accounts.spending), 0);
+
+    client.set_spending_shares(&owner, &Vec::new(&env));
+    assert_eq!(client.get_spending_shares(&owner).len(), 0);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// FX conversion
+// ──────────────────────────────────────────────────────────────────────────
+
+#[soroban_sdk::contract]
+struct MockFxOracle;
+
+#[soroban_sdk::contractimpl]
+impl MockFxOracle {
+    pub fn set_rate(env: Env, base: Address, quote: Address, rate: i128) {
+        env.storage().instance().set(&(base, quote), &rate);
+    }
+
+    pub fn get_rate(env: Env, base: Address, quote: Address) -> i128 {
+        env.storage().instance().get(&(base, quote)).unwrap()
+    }
+}
+
+#[test]
+fn test_calculate_split_fx_converts_before_splitting() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let home = Address::generate(&env);
+    let oracle = env.register_contract(None, MockFxOracle);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    assert_eq!(
+        client.try_calculate_split_fx(&owner, &1000, &usdc, &FX_RATE_SCALE),
+        Err(Ok(RemittanceSplitError::FxNotConfigured))
+    );
+
+    // 1 USDC = 1.5 home units; accept 1% slippage against the quote.
+    MockFxOracleClient::new(&env, &oracle).set_rate(&usdc, &home, &15_000_000);
+    client.set_fx_config(&owner, &home, &oracle, &100);
+
+    let amounts = client.calculate_split_fx(&owner, &1000, &usdc, &14_900_000);
+    assert_eq!(amounts, Vec::from_array(&env, [750, 450, 225, 75]));
+
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| Symbol::try_from_val(&env, &e.1.get(0).unwrap()) == Ok(SPLIT_CALCULATED))
+        .unwrap();
+    let data = SplitCalculatedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.fx_rate, 15_000_000);
+    assert_eq!(data.total_amount, 1500);
+
+    assert_eq!(
+        client.try_calculate_split_fx(&owner, &1000, &usdc, &14_000_000),
+        Err(Ok(RemittanceSplitError::SlippageExceeded))
+    );
+
+    // Remittances already in the home asset are not converted.
+    assert_eq!(
+        client.calculate_split_fx(&owner, &1000, &home, &1),
+        Vec::from_array(&env, [500, 300, 150, 50])
+    );
+}
+
+#[test]
+fn test_execute_split_converts_before_splitting() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let home = Address::generate(&env);
+    let oracle = env.register_contract(None, MockFxOracle);
+
+    // 1 token = 1.5 home units; spending is capped at 600 home units.
+    MockFxOracleClient::new(&env, &oracle).set_rate(&token, &home, &15_000_000);
+    client.set_fx_config(&owner, &home, &oracle, &100);
+    client.set_category_limits(
+        &owner,
+        &Vec::from_array(&env, [limit(&env, "SPENDING", 0, 600)]),
+    );
+
+    // 1500 home units split [600, 540, 270, 90], paid in token as
+    // [400, 360, 180, 60]. Unconverted, the cap would not have applied.
+    let allocations = client.execute_split(&owner, &1_000, &token, &Some(15_000_000));
+    assert_eq!(allocations.get(0).unwrap().amount, 400);
+    assert_eq!(token_client.balance(&accounts.spending), 400);
+    assert_eq!(token_client.balance(&accounts.savings), 360);
+    assert_eq!(token_client.balance(&accounts.bills), 180);
+    assert_eq!(token_client.balance(&accounts.insurance), 60);
+
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| Symbol::try_from_val(&env, &e.1.get(0).unwrap()) == Ok(SPLIT_CALCULATED))
+        .unwrap();
+    let data = SplitCalculatedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.fx_rate, 15_000_000);
+    assert_eq!(data.total_amount, 1500);
+}
+
+#[test]
+fn test_executing_paths_enforce_slippage() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let home = Address::generate(&env);
+    let oracle = env.register_contract(None, MockFxOracle);
+    let oracle_client = MockFxOracleClient::new(&env, &oracle);
+    oracle_client.set_rate(&token, &home, &10_000_000);
+    client.set_fx_config(&owner, &home, &oracle, &100);
+
+    // The rate moves 2% after the sender was quoted; 1% is accepted.
+    oracle_client.set_rate(&token, &home, &10_200_000);
+    let quote = Some(10_000_000i128);
+    assert_eq!(
+        client.try_execute_split(&owner, &1_000, &token, &quote),
+        Err(Ok(RemittanceSplitError::SlippageExceeded))
+    );
+    assert_eq!(
+        client.try_deposit(&owner, &token, &1_000, &quote),
+        Err(Ok(RemittanceSplitError::SlippageExceeded))
+    );
+    assert_eq!(
+        client.try_execute_corridor_split(&owner, &symbol_short!("KES"), &1_000, &token, &quote),
+        Err(Ok(RemittanceSplitError::SlippageExceeded))
+    );
+    // Converting without a quote is refused too.
+    assert_eq!(
+        client.try_execute_split(&owner, &1_000, &token, &None),
+        Err(Ok(RemittanceSplitError::SlippageExceeded))
+    );
+    assert_eq!(token_client.balance(&owner), 1_000);
+
+    oracle_client.set_rate(&token, &home, &10_100_000);
+    client.execute_split(&owner, &1_000, &token, &quote);
+    assert_eq!(token_client.balance(&accounts.savings), 300);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Platform fee
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_platform_fee_is_deducted_before_split() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let treasury = Address::generate(&env);
+    assert_eq!(client.get_fee_config(), None);
+
+    assert_eq!(
+        client.try_set_fee_config(&Address::generate(&env), &100, &treasury),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_fee_config(&owner, &1_001, &treasury),
+        Err(Ok(RemittanceSplitError::InvalidFee))
+    );
+    client.set_fee_config(&owner, &200, &treasury);
+    assert_eq!(
+        client.get_fee_config(),
+        Some(FeeConfig {
+            fee_bps: 200,
+            treasury: treasury.clone(),
+        })
+    );
+
+    // Calculations pay no one, so they take no fee.
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [500, 300, 150, 50])
+    );
+
+    // 2% of 1000 is taken first; 980 is split 50/30/15/5.
+    client.execute_split(&owner, &1_000, &token, &None);
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .rev()
+        .find(|e| Symbol::try_from_val(&env, &e.1.get(0).unwrap()) == Ok(SPLIT_CALCULATED))
+        .unwrap();
+    let data = SplitCalculatedEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.fee, 20);
+    assert_eq!(data.total_amount, 1000);
+    assert_eq!(data.allocations.get(0).unwrap().amount, 490);
+
+    assert_eq!(token_client.balance(&treasury), 20);
+    assert_eq!(token_client.balance(&accounts.spending), 490);
+    assert_eq!(token_client.balance(&accounts.insurance), 49);
+    assert_eq!(token_client.balance(&owner), 0);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Split rules
+// ──────────────────────────────────────────────────────────────────────────
+
+#[soroban_sdk::contract]
+struct MockBills;
+
+#[soroban_sdk::contractimpl]
+impl MockBills {
+    pub fn set_unpaid(env: Env, owner: Address, amount: i128) {
+        env.storage().instance().set(&owner, &amount);
+    }
+
+    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
+        env.storage().instance().get(&owner).unwrap_or(0)
+    }
+}
+
+#[soroban_sdk::contract]
+struct MockGoals;
+
+#[soroban_sdk::contractimpl]
+impl MockGoals {
+    pub fn set_completed(env: Env, goal_id: u32, completed: bool) {
+        env.storage().instance().set(&goal_id, &completed);
+    }
+
+    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
+        env.storage().instance().get(&goal_id).unwrap_or(false)
+    }
+
+    pub fn set_active_goals(env: Env, count: u32) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("active"), &count);
+    }
+
+    pub fn get_savings_stats(env: Env, _owner: Address, _from: u64, _to: u64) -> SavingsStats {
+        SavingsStats {
+            total_contributed: 0,
+            total_withdrawn: 0,
+            net_saved: 0,
+            active_goals: env
+                .storage()
+                .instance()
+                .get(&symbol_short!("active"))
+                .unwrap_or(0),
+            average_monthly_savings: 0,
+        }
+    }
+}
+
+#[test]
+fn test_split_rules_shift_allocations_while_conditions_hold() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let bills = env.register_contract(None, MockBills);
+    let goals = env.register_contract(None, MockGoals);
+    let bills_client = MockBillsClient::new(&env, &bills);
+    let goals_client = MockGoalsClient::new(&env, &goals);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_split_rules(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                SplitRule {
+                    condition: RuleCondition::UnpaidBillsAbove(bills.clone(), 500),
+                    from_category: symbol_short!("SPENDING"),
+                    to_category: symbol_short!("BILLS"),
+                    bps: 1000,
+                },
+                SplitRule {
+                    condition: RuleCondition::GoalBelowTarget(goals.clone(), 7),
+                    from_category: symbol_short!("SPENDING"),
+                    to_category: symbol_short!("SAVINGS"),
+                    bps: 500,
+                },
+            ],
+        ),
+    );
+    goals_client.set_completed(&7, &true);
+
+    // Neither condition holds.
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [500, 300, 150, 50])
+    );
+
+    bills_client.set_unpaid(&owner, &800);
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [400, 300, 250, 50])
+    );
+
+    goals_client.set_completed(&7, &false);
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [350, 350, 250, 50])
+    );
+
+    // The stored split itself is untouched.
+    assert_eq!(
+        client.get_split_bps(&owner),
+        Vec::from_array(&env, [5000, 3000, 1500, 500])
+    );
+}
+
+#[test]
+fn test_split_rules_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let bills = Address::generate(&env);
+
+    env.mock_all_auths();
+    let rule = |bps: u32, to: Symbol| SplitRule {
+        condition: RuleCondition::UnpaidBillsAbove(bills.clone(), 0),
+        from_category: symbol_short!("SPENDING"),
+        to_category: to,
+        bps,
+    };
+    assert_eq!(
+        client.try_set_split_rules(
+            &owner,
+            &Vec::from_array(&env, [rule(0, symbol_short!("BILLS"))])
+        ),
+        Err(Ok(RemittanceSplitError::InvalidRules))
+    );
+    assert_eq!(
+        client.try_set_split_rules(
+            &owner,
+            &Vec::from_array(&env, [rule(100, symbol_short!("SPENDING"))])
+        ),
+        Err(Ok(RemittanceSplitError::InvalidRules))
+    );
+
+    // A source that is not a bills contract is skipped rather than failing.
+    client.set_split_rules(
+        &owner,
+        &Vec::from_array(&env, [rule(100, symbol_short!("BILLS"))]),
+    );
+    assert_eq!(client.get_split_rules(&owner).len(), 1);
+    assert_eq!(
+        client.calculate_split(&owner, &1000),
+        Vec::from_array(&env, [500, 300, 150, 50])
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Deposit hook
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_deposit_pulls_and_forwards_portions() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let treasury = Address::generate(&env);
+    client.set_fee_config(&owner, &100, &treasury);
+
+    let allocations = client.deposit(&owner, &token, &1_000, &None);
+    assert_eq!(allocations.len(), 4);
+    assert_eq!(token_client.balance(&owner), 0);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(token_client.balance(&treasury), 10);
+    assert_eq!(token_client.balance(&accounts.spending), 495);
+    assert_eq!(token_client.balance(&accounts.savings), 297);
+    assert_eq!(token_client.balance(&accounts.bills), 148);
+    assert_eq!(token_client.balance(&accounts.insurance), 50);
+
+    assert_eq!(
+        client.try_deposit(&owner, &token, &1, &None),
+        Err(Ok(RemittanceSplitError::InsufficientBalance))
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Cooldown
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_cooldown_defers_savings_reduction_only() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_cooldown(&owner, &7);
+    assert_eq!(client.get_cooldown(&owner), 7);
+
+    // More savings: immediate.
+    client.update_split(&owner, &1, &40, &40, &15, &5);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [40, 40, 15, 5])
+    );
+    assert_eq!(client.get_scheduled_changes(&owner).len(), 0);
+
+    // Less savings: queued for seven days.
+    client.update_split(&owner, &1, &60, &20, &15, &5);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [40, 40, 15, 5])
+    );
+    let pending = client.get_scheduled_changes(&owner);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().effective_at, 1_000 + 7 * 86_400);
+
+    // Scheduling a reduction sooner is pushed back to the cooldown.
+    let cut = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 9500),
+            category(&env, "INSURANCE", 500),
+        ],
+    );
+    client.schedule_split_change(&owner, &cut, &2_000);
+    assert_eq!(
+        client
+            .get_scheduled_changes(&owner)
+            .get(1)
+            .unwrap()
+            .effective_at,
+        1_000 + 7 * 86_400
+    );
+
+    set_time(&env, 1_000 + 7 * 86_400);
+    assert_eq!(client.get_split_bps(&owner).len(), 2);
+}
+
+#[test]
+fn test_lowering_cooldown_waits_for_old_cooldown() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    client.set_cooldown(&owner, &3);
+    client.set_cooldown(&owner, &0);
+    assert_eq!(client.get_cooldown(&owner), 3);
+
+    let cut = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 9000),
+            category(&env, "SAVINGS", 1000),
+        ],
+    );
+    client.set_categories(&owner, &cut);
+    assert_eq!(client.get_scheduled_changes(&owner).len(), 1);
+
+    set_time(&env, 1_000 + 3 * 86_400);
+    assert_eq!(client.get_cooldown(&owner), 0);
+    assert_eq!(client.get_categories(&owner), cut);
+}
+
+#[test]
+fn test_cooldown_covers_import_rules_and_limits() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let snapshot = client.export_snapshot(&owner).unwrap();
+    client.set_cooldown(&owner, &7);
+    client.update_split(&owner, &1, &40, &40, &15, &5);
+
+    // Importing the older, lower-savings split is queued like an update.
+    client.import_snapshot(&owner, &client.get_nonce(&owner), &snapshot);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [40, 40, 15, 5])
+    );
+    assert_eq!(client.get_scheduled_changes(&owner).len(), 1);
+
+    // Rules cannot take from savings, or stop feeding it.
+    let rule = |from: &str, to: &str| SplitRule {
+        condition: RuleCondition::UnpaidBillsAbove(Address::generate(&env), 0),
+        from_category: Symbol::new(&env, from),
+        to_category: Symbol::new(&env, to),
+        bps: 1000,
+    };
+    assert_eq!(
+        client.try_set_split_rules(
+            &owner,
+            &Vec::from_array(&env, [rule("SAVINGS", "SPENDING")])
+        ),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+    client.set_split_rules(
+        &owner,
+        &Vec::from_array(&env, [rule("SPENDING", "SAVINGS")]),
+    );
+    assert_eq!(
+        client.try_set_split_rules(&owner, &Vec::new(&env)),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+
+    // Limits cannot cap savings or lower its floor.
+    assert_eq!(
+        client.try_set_category_limits(
+            &owner,
+            &Vec::from_array(&env, [limit(&env, "SAVINGS", 0, 100)])
+        ),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+    client.set_category_limits(
+        &owner,
+        &Vec::from_array(
+            &env,
+            [
+                limit(&env, "SAVINGS", 100, 0),
+                limit(&env, "SPENDING", 0, 400),
+            ],
+        ),
+    );
+    assert_eq!(
+        client.try_set_category_limits(&owner, &Vec::new(&env)),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Large reallocation approval
+// ──────────────────────────────────────────────────────────────────────────
+
+#[soroban_sdk::contract]
+struct MockFamilyWallet;
+
+#[soroban_sdk::contractimpl]
+impl MockFamilyWallet {
+    pub fn add_member(env: Env, member: Address, role: remitwise_common::FamilyRole) {
+        let record = FamilyMember {
+            address: member.clone(),
+            role,
+            spending_limit: 0,
+            added_at: 0,
+        };
+        env.storage().instance().set(&member, &record);
+    }
+
+    pub fn get_family_member(env: Env, member: Address) -> Option<FamilyMember> {
+        env.storage().instance().get(&member)
+    }
+}
+
+#[test]
+fn test_large_reallocation_waits_for_family_approval() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let elder = Address::generate(&env);
+    let child = Address::generate(&env);
+    let wallet = env.register_contract(None, MockFamilyWallet);
+    let wallet_client = MockFamilyWalletClient::new(&env, &wallet);
+    wallet_client.add_member(&elder, &remitwise_common::FamilyRole::Admin);
+    wallet_client.add_member(&child, &remitwise_common::FamilyRole::Member);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.configure_split_approval(&owner, &owner, &wallet, &1000);
+
+    // Moving 10% is within the threshold and applies at once.
+    client.update_split(&owner, &1, &40, &40, &15, &5);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Approved);
+
+    // Moving 20% from the approved 50/30/15/5 is held.
+    client.update_split(&owner, &1, &70, &10, &15, &5);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Pending);
+    assert_eq!(
+        client.get_pending_reallocation(&owner).unwrap().shifted_bps,
+        2000
+    );
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [40, 40, 15, 5])
+    );
+    assert_eq!(
+        client.try_schedule_split_change(
+            &owner,
+            &client.get_pending_reallocation(&owner).unwrap().categories,
+            &100
+        ),
+        Err(Ok(RemittanceSplitError::ApprovalRequired))
+    );
+
+    assert_eq!(
+        client.try_approve_split_change(&child, &owner),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    client.approve_split_change(&elder, &owner);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Approved);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [70, 10, 15, 5])
+    );
+    let history = client.get_config_history(&owner, &0, &10);
+    let last = history.get(history.len() - 1).unwrap();
+    assert_eq!(last.changed_by, elder);
+
+    assert_eq!(
+        client.try_approve_split_change(&elder, &owner),
+        Err(Ok(RemittanceSplitError::ApprovalNotPending))
+    );
+}
+
+#[test]
+fn test_split_approval_measures_from_approved_split() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let elder = Address::generate(&env);
+    let wallet = env.register_contract(None, MockFamilyWallet);
+    let wallet_client = MockFamilyWalletClient::new(&env, &wallet);
+    wallet_client.add_member(&elder, &remitwise_common::FamilyRole::Admin);
+    wallet_client.add_member(&owner, &remitwise_common::FamilyRole::Owner);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.configure_split_approval(&owner, &owner, &wallet, &1000);
+
+    // Two 10% steps add up to 20% away from what was approved.
+    client.update_split(&owner, &1, &60, &20, &15, &5);
+    client.update_split(&owner, &1, &70, &10, &15, &5);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Pending);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [60, 20, 15, 5])
+    );
+
+    // The owner cannot approve their own change, or loosen the policy.
+    assert_eq!(
+        client.try_approve_split_change(&owner, &owner),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    for (wallet, threshold) in [(&wallet, 0), (&wallet, 5000), (&owner, 1000)] {
+        assert_eq!(
+            client.try_configure_split_approval(&owner, &owner, wallet, &threshold),
+            Err(Ok(RemittanceSplitError::Unauthorized))
+        );
+    }
+    let snapshot = client.export_snapshot(&owner).unwrap();
+    client.approve_split_change(&elder, &owner);
+    client.configure_split_approval(&owner, &owner, &wallet, &500);
+
+    // Imports are held like updates; rules and limits cannot be held.
+    client.import_snapshot(&owner, &client.get_nonce(&owner), &snapshot);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Pending);
+    assert_eq!(
+        client.get_split(&owner),
+        Vec::from_array(&env, [70, 10, 15, 5])
+    );
+    assert_eq!(
+        client.try_set_category_limits(
+            &owner,
+            &Vec::from_array(&env, [limit(&env, "SAVINGS", 0, 100)])
+        ),
+        Err(Ok(RemittanceSplitError::ApprovalRequired))
+    );
+
+    // An approver can turn approval off.
+    client.configure_split_approval(&elder, &owner, &wallet, &0);
+    assert_eq!(client.get_split_approval_config(&owner), None);
+}
+
+#[test]
+fn test_destination_change_needs_approval() {
+    let env = Env::default();
+    let (client, owner, _token, accounts) = setup_execute(&env);
+    let elder = Address::generate(&env);
+    let wallet = env.register_contract(None, MockFamilyWallet);
+    MockFamilyWalletClient::new(&env, &wallet)
+        .add_member(&elder, &remitwise_common::FamilyRole::Admin);
+    client.configure_split_approval(&owner, &owner, &wallet, &1000);
+
+    // Insurance carries 500 bps, under the threshold, so it moves at once.
+    let insurer = Address::generate(&env);
+    client.set_destination(&owner, &symbol_short!("INSURANCE"), &insurer);
+    assert_eq!(
+        client.get_destination(&owner, &symbol_short!("INSURANCE")),
+        Some(insurer)
+    );
+
+    // Savings carries 3000 bps and waits for an approver.
+    let elsewhere = Address::generate(&env);
+    client.set_destination(&owner, &symbol_short!("SAVINGS"), &elsewhere);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Pending);
+    assert_eq!(
+        client.get_pending_destination(&owner).unwrap().shifted_bps,
+        3000
+    );
+    assert_eq!(
+        client.get_destination(&owner, &symbol_short!("SAVINGS")),
+        Some(accounts.savings)
+    );
+    assert_eq!(
+        client.try_approve_split_change(&owner, &owner),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+
+    client.approve_split_change(&elder, &owner);
+    assert_eq!(client.get_config_status(&owner), ConfigStatus::Approved);
+    assert_eq!(client.get_pending_destination(&owner), None);
+    assert_eq!(
+        client.get_destination(&owner, &symbol_short!("SAVINGS")),
+        Some(elsewhere)
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Corridors
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_corridor_split_uses_its_own_categories() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let local = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 8000),
+            category(&env, "BILLS", 2000),
+        ],
+    );
+    client.set_corridor_categories(&owner, &symbol_short!("KES"), &local);
+    assert_eq!(client.get_corridors(&owner).len(), 1);
+
+    assert_eq!(
+        client.calculate_corridor_split(&owner, &symbol_short!("KES"), &1000),
+        Vec::from_array(&env, [800, 200])
+    );
+    // Unknown corridors fall back to the default split.
+    assert_eq!(
+        client.calculate_corridor_split(&owner, &symbol_short!("USDC"), &1000),
+        Vec::from_array(&env, [500, 300, 150, 50])
+    );
+
+    client.execute_corridor_split(&owner, &symbol_short!("KES"), &1_000, &token, &None);
+    assert_eq!(token_client.balance(&accounts.spending), 800);
+    assert_eq!(token_client.balance(&accounts.bills), 200);
+    assert_eq!(token_client.balance(&accounts.savings), 0);
+
+    client.remove_corridor(&owner, &symbol_short!("KES"));
+    assert_eq!(
+        client.try_remove_corridor(&owner, &symbol_short!("KES")),
+        Err(Ok(RemittanceSplitError::CorridorNotFound))
+    );
+}
+
+#[test]
+fn test_corridors_respect_cooldown_and_approval() {
+    let env = Env::default();
+    let (client, owner, token, _accounts) = setup_execute(&env);
+    let corridor = |spending: u32, savings: u32| {
+        Vec::from_array(
+            &env,
+            [
+                category(&env, "SPENDING", spending),
+                category(&env, "SAVINGS", savings),
+                category(&env, "BILLS", 1500),
+                category(&env, "INSURANCE", 500),
+            ],
+        )
+    };
+    client.set_corridor_categories(&owner, &symbol_short!("KES"), &corridor(8000, 0));
+    client.set_corridor_categories(&owner, &symbol_short!("NGN"), &corridor(4000, 4000));
+
+    // During a cooldown a corridor cannot give savings less, either
+    // directly or by falling back to the default split.
+    client.set_cooldown(&owner, &7);
+    assert_eq!(
+        client.try_set_corridor_categories(&owner, &symbol_short!("NGN"), &corridor(5000, 3000)),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+    assert_eq!(
+        client.try_remove_corridor(&owner, &symbol_short!("NGN")),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+    client.set_corridor_categories(&owner, &symbol_short!("NGN"), &corridor(3500, 4500));
+
+    // Once approval is configured, a corridor cannot move further from the
+    // approved split than the threshold, nor can one set before it be used.
+    client.configure_split_approval(&owner, &owner, &Address::generate(&env), &1000);
+    assert_eq!(
+        client.try_set_corridor_categories(&owner, &symbol_short!("GHS"), &corridor(8000, 0)),
+        Err(Ok(RemittanceSplitError::ApprovalRequired))
+    );
+    assert_eq!(
+        client.try_execute_corridor_split(&owner, &symbol_short!("KES"), &1_000, &token, &None),
+        Err(Ok(RemittanceSplitError::ApprovalRequired))
+    );
+    client.set_corridor_categories(&owner, &symbol_short!("GHS"), &corridor(4500, 3500));
+    client.execute_corridor_split(&owner, &symbol_short!("GHS"), &1_000, &token, &None);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Allocation statistics
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_allocation_stats_accumulate_over_window() {
+    let env = Env::default();
+    let (client, owner, token, _accounts) = setup_execute(&env);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &3_000);
+
+    set_time(&env, 10 * 86_400);
+    client.execute_split(&owner, &1_000, &token, &None);
+    set_time(&env, 40 * 86_400);
+    client.deposit(&owner, &token, &2_000, &None);
+    set_time(&env, 100 * 86_400);
+    client.execute_split(&owner, &1_000, &token, &None);
+
+    let stats = client.get_allocation_stats(&owner, &0, &(50 * 86_400));
+    assert_eq!(stats.total, 3_000);
+    // Ordered by name: BILLS, INSURANCE, SAVINGS, SPENDING.
+    let savings = stats.categories.get(2).unwrap();
+    assert_eq!(savings.category, symbol_short!("SAVINGS"));
+    assert_eq!(savings.amount, 900);
+    assert_eq!(savings.share_bps, 3000);
+
+    // The window covers whole days.
+    let day = client.get_allocation_stats(&owner, &(100 * 86_400 + 5), &(100 * 86_400 + 6));
+    assert_eq!(day.total, 1_000);
+
+    let empty = client.get_allocation_stats(&owner, &(60 * 86_400), &(90 * 86_400));
+    assert_eq!(empty.total, 0);
+    assert_eq!(empty.categories.len(), 0);
+}
+
+#[test]
+fn test_allocation_stats_window_is_bounded() {
+    let env = Env::default();
+    let (client, owner, token, _accounts) = setup_execute(&env);
+    set_time(&env, 10 * 86_400);
+    client.execute_split(&owner, &1_000, &token, &None);
+
+    // Days 10 through 375 are exactly 366 whole days.
+    let year = client.get_allocation_stats(&owner, &(10 * 86_400), &(376 * 86_400 - 1));
+    assert_eq!(year.total, 1_000);
+    assert_eq!(
+        client.try_get_allocation_stats(&owner, &(10 * 86_400), &(376 * 86_400)),
+        Err(Ok(RemittanceSplitError::InvalidWindow))
+    );
+    assert_eq!(
+        client.try_get_allocation_stats(&owner, &(20 * 86_400), &(10 * 86_400)),
+        Err(Ok(RemittanceSplitError::InvalidWindow))
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Rebalancing suggestions
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_suggest_rebalance_reads_downstream_state() {
+    let env = Env::default();
+    let (client, owner, token, _accounts) = setup_execute(&env);
+    let bills = env.register_contract(None, MockBills);
+    let goals = env.register_contract(None, MockGoals);
+    client.set_destination(&owner, &symbol_short!("BILLS"), &bills);
+    client.set_destination(&owner, &symbol_short!("SAVINGS"), &goals);
+
+    // Nothing to fix yet.
+    let idle = client.suggest_rebalance(&owner);
+    assert_eq!(idle.suggested, idle.current);
+
+    set_time(&env, 100 * 86_400);
+    client.execute_split(&owner, &1_000, &token, &None);
+    // 250 unpaid against 1000 a month: bills should be 25%.
+    MockBillsClient::new(&env, &bills).set_unpaid(&owner, &250);
+    MockGoalsClient::new(&env, &goals).set_active_goals(&2);
+
+    let suggestion = client.suggest_rebalance(&owner);
+    assert_eq!(suggestion.unpaid_bills, 250);
+    assert_eq!(suggestion.active_goals, 2);
+    assert_eq!(suggestion.monthly_routed, 1_000);
+    assert_eq!(
+        suggestion.suggested,
+        Vec::from_array(
+            &env,
+            [
+                category(&env, "SPENDING", 3500),
+                category(&env, "SAVINGS", 3500),
+                category(&env, "BILLS", 2500),
+                category(&env, "INSURANCE", 500),
+            ]
+        )
+    );
+    // Read-only: the live split is unchanged.
+    assert_eq!(client.get_categories(&owner), suggestion.current);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Emergency override
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_override_split_routes_everything_to_one_category() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    let before = client.get_categories(&owner);
+
+    let allocations =
+        client.execute_override_split(&owner, &1_000, &symbol_short!("BILLS"), &token);
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(token_client.balance(&accounts.bills), 1_000);
+    assert_eq!(token_client.balance(&accounts.spending), 0);
+
+    let event = env.events().all().last().unwrap();
+    let topic: SplitEvent = SplitEvent::try_from_val(&env, &event.1.get(1).unwrap()).unwrap();
+    assert_eq!(topic, SplitEvent::Override);
+
+    assert_eq!(client.get_categories(&owner), before);
+    assert_eq!(
+        client.try_execute_override_split(&owner, &1, &Symbol::new(&env, "medical"), &token),
+        Err(Ok(RemittanceSplitError::DestinationNotSet))
+    );
+}
+
+#[test]
+fn test_override_split_respects_lock_and_cooldown() {
+    let env = Env::default();
+    let (client, owner, token, accounts) = setup_execute(&env);
+    let token_client = soroban_sdk::token::Client::new(&env, &token);
+    set_time(&env, 1_000);
+
+    let savings_only = Vec::from_array(
+        &env,
+        [
+            category(&env, "SPENDING", 5000),
+            category(&env, "SAVINGS", 5000),
+        ],
+    );
+    client.set_categories(&owner, &savings_only);
+
+    // With a cooldown, savings cannot be skipped, only routed to.
+    client.set_cooldown(&owner, &7);
+    assert_eq!(
+        client.try_execute_override_split(&owner, &500, &symbol_short!("SPENDING"), &token),
+        Err(Ok(RemittanceSplitError::CooldownActive))
+    );
+    client.execute_override_split(&owner, &500, &symbol_short!("SAVINGS"), &token);
+    assert_eq!(token_client.balance(&accounts.savings), 500);
+
+    // A lock blocks overrides altogether.
+    client.lock_config(&owner, &5_000);
+    assert_eq!(
+        client.try_execute_override_split(&owner, &500, &symbol_short!("SAVINGS"), &token),
+        Err(Ok(RemittanceSplitError::ConfigLocked))
+    );
+}
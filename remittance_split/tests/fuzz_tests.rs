@@ -55,13 +55,13 @@ fn fuzz_calculate_split_sum_preservation() {
         }
 
         // Calculate split
-        let result = client.try_calculate_split(&total_amount);
+        let result = client.try_calculate_split(&owner, &total_amount);
 
         if result.is_err() {
             continue; // Skip if calculation fails
         }
 
-        let amounts = client.calculate_split(&total_amount);
+        let amounts = client.calculate_split(&owner, &total_amount);
 
         let spending = amounts.get(0).unwrap();
         let savings = amounts.get(1).unwrap();
@@ -107,7 +107,7 @@ fn fuzz_calculate_split_small_amounts() {
 
     // Test amounts 1-100
     for amount in 1..=100 {
-        let amounts = client.calculate_split(&amount);
+        let amounts = client.calculate_split(&owner, &amount);
 
         let spending = amounts.get(0).unwrap();
         let savings = amounts.get(1).unwrap();
@@ -174,7 +174,7 @@ fn fuzz_rounding_behavior() {
 
         // Test various amounts
         for amount in &[100, 1000, 9999, 123456] {
-            let amounts = client.calculate_split(amount);
+            let amounts = client.calculate_split(&owner, amount);
 
             let spending = amounts.get(0).unwrap();
             let savings = amounts.get(1).unwrap();
@@ -214,7 +214,7 @@ fn fuzz_invalid_amounts() {
 
     // Test invalid amounts
     for amount in &[0, -1, -100, -1000, i128::MIN] {
-        let result = client.try_calculate_split(amount);
+        let result = client.try_calculate_split(&owner, amount);
         assert!(result.is_err(), "Expected error for amount {}", amount);
     }
 }
@@ -277,11 +277,11 @@ fn fuzz_large_amounts() {
     ];
 
     for amount in large_amounts {
-        let result = client.try_calculate_split(&amount);
+        let result = client.try_calculate_split(&owner, &amount);
 
         // Should either succeed with correct sum, or fail with overflow
         if result.is_ok() {
-            let amounts = client.calculate_split(&amount);
+            let amounts = client.calculate_split(&owner, &amount);
             let spending = amounts.get(0).unwrap();
             let savings = amounts.get(1).unwrap();
             let bills = amounts.get(2).unwrap();
@@ -324,7 +324,7 @@ fn fuzz_single_category_splits() {
             &insurance_pct,
         );
 
-        let amounts = client.calculate_split(&1000);
+        let amounts = client.calculate_split(&owner, &1000);
 
         let spending = amounts.get(0).unwrap();
         let savings = amounts.get(1).unwrap();
@@ -32,7 +32,7 @@ fn test_calculate_split_with_large_amount() {
     // Test with i128::MAX / 200 to ensure multiplication by percentages doesn't overflow
     let large_amount = i128::MAX / 200;
 
-    let result = client.calculate_split(&large_amount);
+    let result = client.calculate_split(&owner, &large_amount);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
@@ -57,7 +57,7 @@ fn test_calculate_split_near_max_safe_value() {
     // Maximum safe value for multiplication by 100 (largest percentage)
     let max_safe = i128::MAX / 100 - 1;
 
-    let result = client.calculate_split(&max_safe);
+    let result = client.calculate_split(&owner, &max_safe);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
@@ -81,7 +81,7 @@ fn test_calculate_split_overflow_detection() {
     // Value that will overflow when multiplied by percentage
     let overflow_amount = i128::MAX / 50; // Will overflow when multiplied by 50
 
-    let result = client.try_calculate_split(&overflow_amount);
+    let result = client.try_calculate_split(&owner, &overflow_amount);
 
     // Should return Overflow error, not panic
     assert_eq!(result, Err(Ok(RemittanceSplitError::Overflow)));
@@ -102,7 +102,7 @@ fn test_calculate_split_with_minimal_percentages() {
     // With 1% multiplier, we can handle much larger values
     let large_amount = i128::MAX / 150;
 
-    let result = client.calculate_split(&large_amount);
+    let result = client.calculate_split(&owner, &large_amount);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
@@ -123,7 +123,7 @@ fn test_get_split_allocations_with_large_amount() {
 
     let large_amount = i128::MAX / 200;
 
-    let result = client.get_split_allocations(&large_amount);
+    let result = client.get_split_allocations(&owner, &large_amount);
     assert!(result.is_ok());
 
     let allocations = result.unwrap();
@@ -149,7 +149,7 @@ fn test_multiple_splits_with_large_amounts() {
 
     // Perform multiple splits to ensure no state corruption
     for _ in 0..5 {
-        let result = client.calculate_split(&large_amount);
+        let result = client.calculate_split(&owner, &large_amount);
         assert!(result.is_ok());
 
         let amounts = result.unwrap();
@@ -172,7 +172,7 @@ fn test_edge_case_i128_max_divided_by_100() {
     // Exact edge case: i128::MAX / 100
     let edge_amount = i128::MAX / 100;
 
-    let result = client.calculate_split(&edge_amount);
+    let result = client.calculate_split(&owner, &edge_amount);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
@@ -193,7 +193,7 @@ fn test_split_with_100_percent_to_one_category() {
 
     let large_amount = i128::MAX / 150;
 
-    let result = client.calculate_split(&large_amount);
+    let result = client.calculate_split(&owner, &large_amount);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
@@ -219,7 +219,7 @@ fn test_rounding_behavior_with_large_amounts() {
 
     let large_amount = i128::MAX / 200;
 
-    let result = client.calculate_split(&large_amount);
+    let result = client.calculate_split(&owner, &large_amount);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
@@ -250,7 +250,7 @@ fn test_sequential_large_calculations() {
     ];
 
     for amount in amounts_to_test {
-        let result = client.calculate_split(&amount);
+        let result = client.calculate_split(&owner, &amount);
         assert!(result.is_ok(), "Failed for amount: {}", amount);
 
         let splits = result.unwrap();
@@ -278,7 +278,7 @@ fn test_checked_arithmetic_prevents_silent_overflow() {
     ];
 
     for amount in dangerous_amounts {
-        let result = client.try_calculate_split(&amount);
+        let result = client.try_calculate_split(&owner, &amount);
         // Should return error, not panic or wrap around
         assert!(
             result.is_err(),
@@ -302,7 +302,7 @@ fn test_insurance_remainder_calculation_with_large_values() {
 
     let large_amount = i128::MAX / 200;
 
-    let result = client.calculate_split(&large_amount);
+    let result = client.calculate_split(&owner, &large_amount);
     assert!(result.is_ok());
 
     let amounts = result.unwrap();
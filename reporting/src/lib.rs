@@ -1,6 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+    Vec,
 };
 
 use remitwise_common::Category;
@@ -208,8 +209,8 @@ pub struct StorageStats {
 
 #[contractclient(name = "RemittanceSplitClient")]
 pub trait RemittanceSplitTrait {
-    fn get_split(env: &Env) -> Vec<u32>;
-    fn calculate_split(env: Env, total_amount: i128) -> Vec<i128>;
+    fn get_categories(env: Env, owner: Address) -> Vec<CategoryAlloc>;
+    fn get_split_allocations(env: Env, owner: Address, total_amount: i128) -> Vec<Allocation>;
 }
 
 #[contractclient(name = "SavingsGoalsClient")]
@@ -233,6 +234,21 @@ pub trait InsuranceTrait {
 
 // Data structures from other contracts (needed for client traits)
 
+#[contracttype]
+#[derive(Clone)]
+pub struct CategoryAlloc {
+    pub name: Symbol,
+    pub bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Allocation {
+    pub category: Symbol,
+    pub amount: i128,
+    pub adjustment: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsGoal {
@@ -384,7 +400,7 @@ impl ReportingContract {
     /// Generate remittance summary report
     pub fn get_remittance_summary(
         env: Env,
-        _user: Address,
+        user: Address,
         total_amount: i128,
         period_start: u64,
         period_end: u64,
@@ -396,28 +412,42 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let split_client = RemittanceSplitClient::new(&env, &addresses.remittance_split);
-        let split_percentages = split_client.get_split();
-        let split_amounts = split_client.calculate_split(&total_amount);
+        let split_categories = split_client.get_categories(&user);
+        let split_amounts = split_client.get_split_allocations(&user, &total_amount);
 
+        // The split may list categories in any order and include custom
+        // ones, so each standard category is looked up by name.
         let mut breakdown = Vec::new(&env);
+        let mut total_allocated: i128 = 0;
         let categories = [
-            Category::Spending,
-            Category::Savings,
-            Category::Bills,
-            Category::Insurance,
+            (Category::Spending, symbol_short!("SPENDING")),
+            (Category::Savings, symbol_short!("SAVINGS")),
+            (Category::Bills, symbol_short!("BILLS")),
+            (Category::Insurance, symbol_short!("INSURANCE")),
         ];
 
-        for (i, &category) in categories.iter().enumerate() {
+        for (category, name) in categories.iter() {
+            let amount = split_amounts
+                .iter()
+                .find(|a| a.category == *name)
+                .map(|a| a.amount)
+                .unwrap_or(0);
+            let percentage = split_categories
+                .iter()
+                .find(|c| c.name == *name)
+                .map(|c| c.bps / 100)
+                .unwrap_or(0);
+            total_allocated += amount;
             breakdown.push_back(CategoryBreakdown {
-                category,
-                amount: split_amounts.get(i as u32).unwrap_or(0),
-                percentage: split_percentages.get(i as u32).unwrap_or(0),
+                category: *category,
+                amount,
+                percentage,
             });
         }
 
         RemittanceSummary {
             total_received: total_amount,
-            total_allocated: total_amount,
+            total_allocated,
             category_breakdown: breakdown,
             period_start,
             period_end,
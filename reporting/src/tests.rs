@@ -7,29 +7,46 @@ use soroban_sdk::{
 
 // Mock contracts for testing
 mod remittance_split {
-    use soroban_sdk::{contract, contractimpl, Env, Vec};
+    use crate::{Allocation, CategoryAlloc};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol, Vec};
 
     #[contract]
     pub struct RemittanceSplit;
 
+    // Listed out of the standard order, as an owner's split may be.
+    fn split() -> [(Symbol, u32); 4] {
+        [
+            (symbol_short!("INSURANCE"), 500),
+            (symbol_short!("SPENDING"), 5000),
+            (symbol_short!("BILLS"), 1500),
+            (symbol_short!("SAVINGS"), 3000),
+        ]
+    }
+
     #[contractimpl]
     impl RemittanceSplit {
-        pub fn get_split(env: &Env) -> Vec<u32> {
-            let mut split = Vec::new(env);
-            split.push_back(50);
-            split.push_back(30);
-            split.push_back(15);
-            split.push_back(5);
-            split
+        pub fn get_categories(env: Env, _owner: Address) -> Vec<CategoryAlloc> {
+            let mut categories = Vec::new(&env);
+            for (name, bps) in split() {
+                categories.push_back(CategoryAlloc { name, bps });
+            }
+            categories
         }
 
-        pub fn calculate_split(env: Env, total_amount: i128) -> Vec<i128> {
-            let mut amounts = Vec::new(&env);
-            amounts.push_back(total_amount * 50 / 100);
-            amounts.push_back(total_amount * 30 / 100);
-            amounts.push_back(total_amount * 15 / 100);
-            amounts.push_back(total_amount * 5 / 100);
-            amounts
+        pub fn get_split_allocations(
+            env: Env,
+            _owner: Address,
+            total_amount: i128,
+        ) -> Vec<Allocation> {
+            let mut allocations = Vec::new(&env);
+            for (category, bps) in split() {
+                allocations.push_back(Allocation {
+                    category,
+                    amount: total_amount * bps as i128 / 10000,
+                    adjustment: 0,
+                });
+            }
+            allocations
         }
     }
 }
@@ -333,6 +350,10 @@ fn test_get_remittance_summary() {
     assert_eq!(spending.category, Category::Spending);
     assert_eq!(spending.amount, 5000);
     assert_eq!(spending.percentage, 50);
+    let insurance = summary.category_breakdown.get(3).unwrap();
+    assert_eq!(insurance.category, Category::Insurance);
+    assert_eq!(insurance.amount, 500);
+    assert_eq!(insurance.percentage, 5);
 }
 
 #[test]
@@ -1,12 +1,20 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, vec, Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
 // Event topics
 const GOAL_CREATED: Symbol = symbol_short!("created");
 const FUNDS_ADDED: Symbol = symbol_short!("added");
 const GOAL_COMPLETED: Symbol = symbol_short!("completed");
+const WITHDRAWAL_MADE: Symbol = symbol_short!("withdrawn");
+const EARLY_WITHDRAWAL: Symbol = symbol_short!("early_wd");
+const GOAL_TRANSFER: Symbol = symbol_short!("transfer");
+const GOAL_PAID_OUT: Symbol = symbol_short!("paid_out");
+const CIRCLE_PAYOUT: Symbol = symbol_short!("cir_pay");
+const TARGET_CHANGED: Symbol = symbol_short!("tgt_chg");
+const SWEEP: Symbol = symbol_short!("sweep");
 
 #[derive(Clone)]
 #[contracttype]
@@ -27,6 +35,83 @@ pub struct FundsAddedEvent {
     pub timestamp: u64,
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct WithdrawalMadeEvent {
+    pub goal_id: u32,
+    pub amount: i128,
+    pub remaining: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct EarlyWithdrawalEvent {
+    pub goal_id: u32,
+    pub amount: i128,
+    /// What the owner actually receives: `amount - penalty`.
+    pub net_amount: i128,
+    pub penalty: i128,
+    pub penalty_goal_id: u32,
+    pub remaining: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct GoalTransferEvent {
+    pub from_goal_id: u32,
+    pub to_goal_id: u32,
+    pub amount: i128,
+    pub from_remaining: i128,
+    pub to_total: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct GoalPaidOutEvent {
+    pub goal_id: u32,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct SweepEvent {
+    pub goal_id: u32,
+    pub owner: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Balance left in the owner's account, equal to the threshold.
+    pub threshold: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct TargetChangedEvent {
+    pub goal_id: u32,
+    pub old_target: i128,
+    pub new_target: i128,
+    pub current_amount: i128,
+    pub timestamp: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct CirclePayoutEvent {
+    pub circle_id: u32,
+    pub round: u32,
+    pub recipient: Address,
+    pub amount: i128,
+    /// Members who did not pay into this round.
+    pub missed_count: u32,
+    pub timestamp: u64,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct GoalCompletedEvent {
@@ -46,6 +131,103 @@ pub const MAX_PAGE_LIMIT: u32 = 50;
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsGoal {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub target_date: u64,
+    pub lock_policy: LockPolicy,
+    pub unlock_date: Option<u64>,
+    pub tags: Vec<String>,
+    /// Accepts contributions from addresses other than the owner.
+    pub shared: bool,
+    /// Relative share of `deposit_allocation` deposits; 0 opts out.
+    pub weight: u32,
+    pub category: GoalCategory,
+    /// Token the goal is denominated in; `None` for goals created before
+    /// denomination or never denominated.
+    pub asset: Option<Address>,
+    /// Co-signer for `emergency_unlock`. While set, the goal cannot be
+    /// unlocked before `target_date` unless it is complete.
+    pub guardian: Option<Address>,
+    /// Ledger time interest was last settled up to.
+    pub last_accrual: u64,
+    pub created_at: u64,
+}
+
+/// Recurring pull from the owner's token allowance into a goal, run by
+/// `process_due_contributions`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionSchedule {
+    pub goal_id: u32,
+    pub owner: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Seconds between contributions.
+    pub interval: u64,
+    pub next_due: u64,
+}
+
+/// A sponsor's promise to match contributions to one goal, pulled from the
+/// sponsor's token allowance as contributions arrive.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SponsorMatch {
+    pub sponsor: Address,
+    pub token: Address,
+    /// Share of each contribution matched, in basis points
+    pub match_bps: u32,
+    /// Most the sponsor will contribute in total
+    pub cap: i128,
+    /// Matched so far
+    pub matched: i128,
+}
+
+/// Where a goal's balance is sent once it reaches its target.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutTarget {
+    /// Family wallet or any other address that receives the payout
+    pub recipient: Address,
+    /// Token the contract pays out in
+    pub token: Address,
+}
+
+/// Who may claim a goal's balance once the owner has been inactive for
+/// `inactivity_period` seconds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Beneficiary {
+    pub beneficiary: Address,
+    pub inactivity_period: u64,
+}
+
+/// One contribution counted against a rolling contribution cap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapEntry {
+    pub at: u64,
+    pub amount: i128,
+}
+
+/// A goal's funds in the yield vault.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldPosition {
+    pub token: Address,
+    /// Deployed and not yet recalled.
+    pub principal: i128,
+    /// Net yield recalls have credited to the goal; negative after a
+    /// shortfall.
+    pub earned: i128,
+}
+
+/// Goal record as kept in the instance `GOALS` map by earlier versions.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacySavingsGoal {
     pub id: u32,
     pub owner: Address,
     pub name: String,
@@ -57,6 +239,60 @@ pub struct SavingsGoal {
     pub tags: Vec<String>,
 }
 
+/// A goal removed from active storage by `close_goal`.
+#[contracttype]
+#[derive(Clone)]
+pub struct ClosedGoal {
+    /// The goal as it stood when closed; `current_amount` is zero.
+    pub goal: SavingsGoal,
+    /// Balance released to the owner on closing, plus anything paid out to
+    /// the goal's payout target before.
+    pub released: i128,
+    pub closed_at: u64,
+}
+
+/// How a goal guards its balance against withdrawal. Goals start
+/// `Soft`; `lock_goal` and `unlock_goal` switch between `Soft` and
+/// `Unlocked`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LockPolicy {
+    /// Withdraw freely
+    Unlocked,
+    /// Only `withdraw_early`, paying the owner's early-withdrawal penalty
+    Soft,
+    /// Nothing leaves before `target_date`
+    Hard,
+    /// Withdrawals need the guardian's co-signature
+    Guardian,
+}
+
+/// What a goal is saving for. Goals created with `create_goal` start as
+/// `General`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GoalCategory {
+    General,
+    Education,
+    Medical,
+    Housing,
+    Emergency,
+    Business,
+}
+
+/// Preset used by `create_goal_from_template` for one-tap goal creation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalTemplate {
+    pub name: String,
+    pub category: GoalCategory,
+    pub target_amount: i128,
+    /// Seconds from creation until the target date.
+    pub duration: u64,
+    /// Seconds the goal stays time-locked after creation; 0 for none.
+    pub lock_period: u64,
+}
+
 /// Paginated result for savings goal queries
 #[contracttype]
 #[derive(Clone)]
@@ -69,6 +305,43 @@ pub struct GoalPage {
     pub count: u32,
 }
 
+/// Progress summary from `get_goal_projection`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalProjection {
+    pub goal_id: u32,
+    /// Share of the target reached, capped at 100
+    pub percent_complete: u32,
+    /// Average contributed per 30 days since the goal was created
+    pub avg_per_30_days: i128,
+    /// Estimated completion time at that pace; 0 if there is no pace yet
+    pub projected_completion: u64,
+    /// Complete, or projected to complete by `target_date`
+    pub on_track: bool,
+}
+
+/// Combined balance of one owner's goals in one asset. `asset` is `None`
+/// for goals without a denomination.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetTotal {
+    pub asset: Option<Address>,
+    pub total: i128,
+    pub goal_count: u32,
+}
+
+/// Offset-based page over one owner's goals
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalListPage {
+    /// Goals for this page
+    pub items: Vec<SavingsGoal>,
+    /// Number of goals matching the query across all pages
+    pub total: u32,
+    /// Pass as `offset` for the next page. 0 = no more pages.
+    pub next_offset: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsSchedule {
@@ -85,8 +358,98 @@ pub struct SavingsSchedule {
     pub missed_count: u32,
 }
 
+/// Owner-chosen cost of breaking a lock early. The penalty stays in the
+/// household, credited to `destination_goal` (e.g. the emergency fund).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyWithdrawalPolicy {
+    pub penalty_bps: u32,
+    pub destination_goal: u32,
+}
+
+/// One interest credit, kept per goal for `get_accrual_history`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestAccrual {
+    pub amount: i128,
+    pub rate_bps: u32,
+    pub from: u64,
+    pub to: u64,
+}
+
+/// A point at which the interest rate changed. `index` is the accrual index
+/// (rate in basis points times seconds, summed over all earlier rates) at
+/// `at`, so interest over any period is charged at the rates in force then.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateChange {
+    pub at: u64,
+    pub rate_bps: u32,
+    pub index: i128,
+}
+
+/// Aggregate savings activity for an owner over a time range, returned by
+/// `get_savings_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsStats {
+    pub total_contributed: i128,
+    pub total_withdrawn: i128,
+    /// `total_contributed - total_withdrawn`.
+    pub net_saved: i128,
+    /// Open goals that have not reached their target yet.
+    pub active_goals: u32,
+    /// `net_saved` spread over the 30-day periods in the range.
+    pub average_monthly_savings: i128,
+}
+
+/// Approval rule for a shared goal: once more than `threshold` has left the
+/// goal within a rolling 30-day window, further withdrawals need
+/// `required_approvals` of the named `approvers` to sign off within
+/// `expiry` seconds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalPolicy {
+    pub threshold: i128,
+    pub approvers: Vec<Address>,
+    pub required_approvals: u32,
+    pub expiry: u64,
+}
+
+/// A withdrawal waiting for contributor approvals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalRequest {
+    pub id: u32,
+    pub goal_id: u32,
+    pub amount: i128,
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// Compressed record of an owner's archived closed goals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedSavings {
+    pub goal_count: u32,
+    /// Sum of the balances released when the archived goals were closed.
+    pub total_released: i128,
+    pub last_closed_at: u64,
+}
+
+/// One change to a goal's target, kept for `get_target_history`.
 #[contracttype]
-#[derive(Clone, Copy)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TargetChange {
+    pub old_target: i128,
+    pub new_target: i128,
+    pub timestamp: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
 pub enum SavingsGoalsError {
     InvalidAmount = 1,
     GoalNotFound = 2,
@@ -94,49 +457,26 @@ pub enum SavingsGoalsError {
     GoalLocked = 4,
     InsufficientBalance = 5,
     Overflow = 6,
-}
-
-impl From<SavingsGoalsError> for soroban_sdk::Error {
-    fn from(err: SavingsGoalsError) -> Self {
-        match err {
-            SavingsGoalsError::InvalidAmount => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidInput,
-            )),
-            SavingsGoalsError::GoalNotFound => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::MissingValue,
-            )),
-            SavingsGoalsError::Unauthorized => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidAction,
-            )),
-            SavingsGoalsError::GoalLocked => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidAction,
-            )),
-            SavingsGoalsError::InsufficientBalance => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidInput,
-            )),
-            SavingsGoalsError::Overflow => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidInput,
-            )),
-        }
-    }
-}
-
-impl From<&SavingsGoalsError> for soroban_sdk::Error {
-    fn from(err: &SavingsGoalsError) -> Self {
-        (*err).into()
-    }
-}
-
-impl From<soroban_sdk::Error> for SavingsGoalsError {
-    fn from(_err: soroban_sdk::Error) -> Self {
-        SavingsGoalsError::Unauthorized
-    }
+    NoActiveGoals = 7,
+    PenaltyNotConfigured = 8,
+    TemplateNotFound = 9,
+    RoundupGoalNotSet = 10,
+    ContributionCapExceeded = 11,
+    OwnerStillActive = 12,
+    GuardianNotSet = 13,
+    AssetMismatch = 14,
+    YieldAdapterNotSet = 15,
+    CircleNotFound = 16,
+    NotCircleMember = 17,
+    AlreadyContributed = 18,
+    RoundStillOpen = 19,
+    CircleCompleted = 20,
+    GoalFrozen = 21,
+    ApprovalRequired = 22,
+    RequestNotFound = 23,
+    RequestExpired = 24,
+    AlreadyApproved = 25,
+    GoalNotShared = 26,
 }
 
 #[contracttype]
@@ -177,6 +517,15 @@ const SNAPSHOT_VERSION: u32 = 1;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+const MAX_PENALTY_BPS: u32 = 10_000;
+const MAX_MATCH_BPS: u32 = 10_000;
+const MAX_INTEREST_RATE_BPS: u32 = 10_000;
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+const MAX_ACCRUAL_HISTORY: u32 = 50;
+const MAX_TARGET_HISTORY: u32 = 50;
+const PROJECTION_PERIOD: u64 = 30 * 86_400;
+const CAP_WINDOW: u64 = 30 * 86_400;
+const STATS_PERIOD: u64 = 30 * 86_400;
 
 pub mod pause_functions {
     use soroban_sdk::{symbol_short, Symbol};
@@ -185,23 +534,137 @@ pub mod pause_functions {
     pub const WITHDRAW: Symbol = symbol_short!("withdraw");
     pub const LOCK: Symbol = symbol_short!("lock");
     pub const UNLOCK: Symbol = symbol_short!("unlock");
+    pub const CIRCLE: Symbol = symbol_short!("circle");
 }
 
+/// One entry in a goal's contribution history.
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionRecord {
+    pub contributor: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+    /// Goal balance right after this contribution
+    pub new_total: i128,
+}
+
+/// What one address has put into a goal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub contributor: Address,
+    /// Cumulative amount added; withdrawals do not reduce it.
+    pub total: i128,
+    pub last_contributed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ContributionItem {
     pub goal_id: u32,
     pub amount: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalItem {
+    pub goal_id: u32,
+    pub amount: i128,
+}
+
+/// Outcome of one `batch_withdraw` item
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalResult {
+    pub goal_id: u32,
+    pub amount: i128,
+    pub remaining: i128,
+}
+
+/// A rotating savings circle (ROSCA). Every round each member pays
+/// `contribution` of `token`, and the pot goes to `members[current_round]`,
+/// less the contributions that member still owes for later rounds, which
+/// are held as collateral until the circle completes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsCircle {
+    pub id: u32,
+    pub creator: Address,
+    pub token: Address,
+    /// Members in payout order.
+    pub members: Vec<Address>,
+    pub contribution: i128,
+    pub round_interval: u64,
+    pub current_round: u32,
+    pub round_deadline: u64,
+    /// Members who have paid into the current round.
+    pub paid: Vec<Address>,
+    /// Number of rounds each member has missed.
+    pub missed: Map<Address, u32>,
+    /// Pot held back from members already paid out, covering rounds they
+    /// still owe.
+    pub collateral: Map<Address, i128>,
+    pub completed: bool,
+}
+
+/// Interface an external yield vault exposes to this contract.
+#[contractclient(name = "YieldAdapterClient")]
+pub trait YieldAdapterTrait {
+    /// Credit `from` with `amount` of `token`, already transferred to the
+    /// adapter.
+    fn deposit(env: Env, from: Address, token: Address, amount: i128);
+    /// Send `amount` of `token` back to `to`, plus any yield it has earned.
+    /// Returns the amount actually sent.
+    fn withdraw(env: Env, to: Address, token: Address, amount: i128) -> i128;
+}
+
 #[contract]
 pub struct SavingsGoalContract;
 
 #[contractimpl]
 impl SavingsGoalContract {
     const STORAGE_NEXT_ID: Symbol = symbol_short!("NEXT_ID");
-    const STORAGE_GOALS: Symbol = symbol_short!("GOALS");
+    const STORAGE_GOAL: Symbol = symbol_short!("GOAL");
     const STORAGE_OWNER_GOAL_IDS: Symbol = symbol_short!("OWN_GOAL");
+    const STORAGE_CONTRIBUTIONS: Symbol = symbol_short!("CONTRIB");
+    const STORAGE_CONTRIBUTION_LOG: Symbol = symbol_short!("CONT_LOG");
+    const STORAGE_CONTRIBUTION_COUNT: Symbol = symbol_short!("CONT_CNT");
+    const STORAGE_EARLY_POLICIES: Symbol = symbol_short!("EW_POL");
+    const STORAGE_INTEREST_RATE: Symbol = symbol_short!("INT_RATE");
+    const STORAGE_ACCRUALS: Symbol = symbol_short!("INT_HIST");
+    const STORAGE_RATE_HISTORY: Symbol = symbol_short!("RATE_HIST");
+    const STORAGE_TEMPLATES: Symbol = symbol_short!("TEMPLATES");
+    const STORAGE_CLOSED: Symbol = symbol_short!("CLOSED");
+    const STORAGE_OWNER_CLOSED_IDS: Symbol = symbol_short!("OWN_CLSD");
+    const STORAGE_CLOSED_QUEUE: Symbol = symbol_short!("CLSD_Q");
+    const STORAGE_PAID_OUT: Symbol = symbol_short!("PAID_OUT");
+    const STORAGE_PAID_QUEUE: Symbol = symbol_short!("PAID_Q");
+    const STORAGE_CONTRIBUTION_SCHEDULES: Symbol = symbol_short!("CONT_SCH");
+    const STORAGE_SCHEDULED_GOALS: Symbol = symbol_short!("SCHED_IDS");
+    const STORAGE_ROUNDUP_GOALS: Symbol = symbol_short!("ROUNDUP");
+    const STORAGE_SPONSOR_MATCHES: Symbol = symbol_short!("MATCHES");
+    const STORAGE_GOAL_CAPS: Symbol = symbol_short!("GOAL_CAP");
+    const STORAGE_OWNER_CAPS: Symbol = symbol_short!("OWNER_CAP");
+    const STORAGE_GOAL_CAP_LOG: Symbol = symbol_short!("GCAP_LOG");
+    const STORAGE_OWNER_CAP_LOG: Symbol = symbol_short!("OCAP_LOG");
+    const STORAGE_PAYOUT_TARGETS: Symbol = symbol_short!("PAYOUTS");
+    const STORAGE_BENEFICIARIES: Symbol = symbol_short!("BENEFIC");
+    const STORAGE_LAST_ACTIVE: Symbol = symbol_short!("LAST_ACT");
+    const STORAGE_YIELD_ADAPTER: Symbol = symbol_short!("YLD_ADPT");
+    const STORAGE_YIELD_POSITIONS: Symbol = symbol_short!("YLD_POS");
+    const STORAGE_HELD: Symbol = symbol_short!("HELD");
+    const STORAGE_CIRCLE: Symbol = symbol_short!("CIRCLE");
+    const STORAGE_NEXT_CIRCLE_ID: Symbol = symbol_short!("NEXT_CIR");
+    const STORAGE_TARGET_LOG: Symbol = symbol_short!("TGT_LOG");
+    const STORAGE_ARCHIVED: Symbol = symbol_short!("ARCHIVED");
+    const STORAGE_FROZEN: Symbol = symbol_short!("FROZEN");
+    const STORAGE_APPROVAL_POLICIES: Symbol = symbol_short!("APPR_POL");
+    const STORAGE_WITHDRAWAL_LOG: Symbol = symbol_short!("WD_LOG");
+    const STORAGE_WITHDRAWAL_REQUEST: Symbol = symbol_short!("WD_REQ");
+    const STORAGE_PENDING_REQUESTS: Symbol = symbol_short!("WD_PEND");
+    const STORAGE_NEXT_REQUEST_ID: Symbol = symbol_short!("NEXT_WDR");
+    const STORAGE_FLOWS: Symbol = symbol_short!("FLOWS");
+    const STORAGE_LEGACY_GOALS: Symbol = symbol_short!("GOALS");
 
     // -----------------------------------------------------------------------
     // Internal helpers
@@ -247,22 +710,16 @@ impl SavingsGoalContract {
     // Pause / upgrade
     // -----------------------------------------------------------------------
 
-    /// Bootstrap storage: set NEXT_ID to 1 and GOALS to an empty map only when
-    /// those keys are missing. Intended to be idempotent: calling init() more
-    /// than once (e.g. from different entrypoints or upgrade paths) must not
-    /// overwrite existing goals or reset NEXT_ID, to avoid ID collisions and
-    /// data loss.
+    /// Bootstrap storage: set NEXT_ID to 1 only when the key is missing.
+    /// Goals need no bootstrapping since each one lives under its own key.
+    /// Intended to be idempotent: calling init() more than once (e.g. from
+    /// different entrypoints or upgrade paths) must not reset NEXT_ID, to
+    /// avoid ID collisions and data loss.
     pub fn init(env: Env) {
         let storage = env.storage().persistent();
         if storage.get::<_, u32>(&Self::STORAGE_NEXT_ID).is_none() {
             storage.set(&Self::STORAGE_NEXT_ID, &1u32);
         }
-        if storage
-            .get::<_, Map<u32, SavingsGoal>>(&Self::STORAGE_GOALS)
-            .is_none()
-        {
-            storage.set(&Self::STORAGE_GOALS, &Map::<u32, SavingsGoal>::new(&env));
-        }
     }
 
     pub fn set_pause_admin(env: Env, caller: Address, new_admin: Address) {
@@ -397,6 +854,136 @@ impl SavingsGoalContract {
         );
     }
 
+    /// Move up to `limit` entries left in instance storage by earlier
+    /// versions into per-goal persistent storage: the `GOALS` map and its
+    /// `OWN_GOAL` index, then the per-goal and per-owner side tables.
+    ///
+    /// Upgrade admin only. A migrated goal keeps its ID and balance; a
+    /// `locked` goal becomes `Soft`-locked, and interest starts accruing
+    /// from the migration. Call repeatedly until it returns 0.
+    ///
+    /// # Returns
+    /// Number of legacy entries still waiting to be migrated
+    pub fn migrate_legacy_storage(
+        env: Env,
+        caller: Address,
+        limit: u32,
+    ) -> Result<u32, SavingsGoalsError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+        let mut budget = Self::clamp_limit(limit);
+        let mut pending = 0u32;
+        let now = env.ledger().timestamp();
+
+        let goals: Vec<(u32, LegacySavingsGoal)> =
+            Self::drain_legacy(&env, &Self::STORAGE_LEGACY_GOALS, &mut budget, &mut pending);
+        for (_, old) in goals.iter() {
+            let goal = SavingsGoal {
+                id: old.id,
+                owner: old.owner.clone(),
+                name: old.name,
+                target_amount: old.target_amount,
+                current_amount: old.current_amount,
+                target_date: old.target_date,
+                lock_policy: if old.locked {
+                    LockPolicy::Soft
+                } else {
+                    LockPolicy::Unlocked
+                },
+                unlock_date: old.unlock_date,
+                tags: old.tags,
+                shared: false,
+                weight: 1,
+                category: GoalCategory::General,
+                asset: None,
+                guardian: None,
+                last_accrual: now,
+                created_at: now,
+            };
+            Self::save_goal(&env, &goal);
+            Self::append_owner_goal_id(&env, &old.owner, old.id);
+        }
+        if !env.storage().instance().has(&Self::STORAGE_LEGACY_GOALS) {
+            env.storage()
+                .instance()
+                .remove(&Self::STORAGE_OWNER_GOAL_IDS);
+        }
+
+        for table in [
+            Self::STORAGE_CONTRIBUTIONS,
+            Self::STORAGE_ACCRUALS,
+            Self::STORAGE_PAYOUT_TARGETS,
+            Self::STORAGE_FROZEN,
+            Self::STORAGE_GOAL_CAP_LOG,
+        ] {
+            let entries: Vec<(u32, Val)> =
+                Self::drain_legacy(&env, &table, &mut budget, &mut pending);
+            for (goal_id, value) in entries.iter() {
+                Self::save_entry(&env, &(table.clone(), goal_id), &value);
+            }
+        }
+        for table in [Self::STORAGE_EARLY_POLICIES, Self::STORAGE_OWNER_CAP_LOG] {
+            let entries: Vec<(Address, Val)> =
+                Self::drain_legacy(&env, &table, &mut budget, &mut pending);
+            for (owner, value) in entries.iter() {
+                Self::save_entry(&env, &(table.clone(), owner), &value);
+            }
+        }
+
+        // Legacy positions were bare amounts in the goal's asset.
+        let positions: Vec<(u32, i128)> = Self::drain_legacy(
+            &env,
+            &Self::STORAGE_YIELD_POSITIONS,
+            &mut budget,
+            &mut pending,
+        );
+        for (goal_id, principal) in positions.iter() {
+            if let Some(token) = Self::load_goal(&env, goal_id).and_then(|goal| goal.asset) {
+                Self::save_entry(
+                    &env,
+                    &(Self::STORAGE_YIELD_POSITIONS, goal_id),
+                    &YieldPosition {
+                        token,
+                        principal,
+                        earned: 0,
+                    },
+                );
+            }
+        }
+
+        let schedules: Vec<(u32, ContributionSchedule)> = Self::drain_legacy(
+            &env,
+            &Self::STORAGE_CONTRIBUTION_SCHEDULES,
+            &mut budget,
+            &mut pending,
+        );
+        for (_, schedule) in schedules.iter() {
+            Self::save_schedule(&env, &schedule);
+        }
+
+        let closed: Vec<(Address, Vec<ClosedGoal>)> =
+            Self::drain_legacy(&env, &Self::STORAGE_CLOSED, &mut budget, &mut pending);
+        for (_, entries) in closed.iter() {
+            for entry in entries.iter() {
+                Self::store_closed_goal(&env, &entry);
+            }
+        }
+
+        if pending == 0 {
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("migrated")),
+                env.storage()
+                    .instance()
+                    .get::<_, u32>(&symbol_short!("NEXT_ID"))
+                    .unwrap_or(0),
+            );
+        }
+        Ok(pending)
+    }
+
     // -----------------------------------------------------------------------
     // Tag management
     // -----------------------------------------------------------------------
@@ -422,13 +1009,7 @@ impl SavingsGoalContract {
         Self::validate_tags(&tags);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = Self::load_goal(&env, goal_id).expect("Goal not found");
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("add_tags"), &caller, false);
@@ -439,10 +1020,7 @@ impl SavingsGoalContract {
             goal.tags.push_back(tag);
         }
 
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         env.events().publish(
             (symbol_short!("savings"), symbol_short!("tags_add")),
@@ -462,13 +1040,7 @@ impl SavingsGoalContract {
         Self::validate_tags(&tags);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = Self::load_goal(&env, goal_id).expect("Goal not found");
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("rem_tags"), &caller, false);
@@ -490,10 +1062,7 @@ impl SavingsGoalContract {
         }
 
         goal.tags = new_tags;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         env.events().publish(
             (symbol_short!("savings"), symbol_short!("tags_rem")),
@@ -524,61 +1093,267 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let next_id = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32)
-            + 1;
-
-        let goal = SavingsGoal {
-            id: next_id,
-            owner: owner.clone(),
-            name: name.clone(),
+        Ok(Self::insert_goal(
+            &env,
+            &owner,
+            name,
             target_amount,
-            current_amount: 0,
             target_date,
-            locked: true,
-            unlock_date: None,
-            tags: Vec::new(&env),
-        };
-
-        goals.set(next_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &next_id);
-        Self::append_owner_goal_id(&env, &owner, next_id);
+            GoalCategory::General,
+            None,
+        ))
+    }
 
-        let event = GoalCreatedEvent {
-            goal_id: next_id,
-            name: goal.name.clone(),
-            target_amount,
-            target_date,
-            timestamp: env.ledger().timestamp(),
+    /// Create a goal in one step from an admin-published template. The goal
+    /// takes the template's name and category, its target is due
+    /// `duration` seconds from now, and it is time-locked for `lock_period`
+    /// seconds when that is non-zero.
+    ///
+    /// # Errors
+    /// * `TemplateNotFound` - If template_id has not been published
+    pub fn create_goal_from_template(
+        env: Env,
+        owner: Address,
+        template_id: u32,
+    ) -> Result<u32, SavingsGoalsError> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_GOAL);
+
+        let template = match Self::get_goal_template(env.clone(), template_id) {
+            Some(t) => t,
+            None => {
+                Self::append_audit(&env, symbol_short!("create"), &owner, false);
+                return Err(SavingsGoalsError::TemplateNotFound);
+            }
         };
-        env.events().publish((GOAL_CREATED,), event);
+
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let unlock_date = if template.lock_period > 0 {
+            Some(now.saturating_add(template.lock_period))
+        } else {
+            None
+        };
+        Ok(Self::insert_goal(
+            &env,
+            &owner,
+            template.name,
+            template.target_amount,
+            now.saturating_add(template.duration),
+            template.category,
+            unlock_date,
+        ))
+    }
+
+    /// Publish or replace a goal template. Restricted to the upgrade admin.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidAmount` - If the template target is ≤ 0
+    pub fn set_goal_template(
+        env: Env,
+        caller: Address,
+        template_id: u32,
+        template: GoalTemplate,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if template.target_amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut templates: Map<u32, GoalTemplate> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_TEMPLATES)
+            .unwrap_or_else(|| Map::new(&env));
+        templates.set(template_id, template);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_TEMPLATES, &templates);
+        Ok(())
+    }
+
+    pub fn get_goal_template(env: Env, template_id: u32) -> Option<GoalTemplate> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, GoalTemplate>>(&Self::STORAGE_TEMPLATES)
+            .and_then(|templates| templates.get(template_id))
+    }
+
+    /// Change the category of an existing goal.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_goal_category(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        category: GoalCategory,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("category"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        goal.category = category;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("category"), &caller, true);
+        Ok(())
+    }
+
+    /// Denominate a goal in `asset` (e.g. USDC, XLM or a local stable
+    /// asset). Contribution schedules, sponsor matches and payout targets
+    /// on the goal must then use that token, and transfers only move funds
+    /// between goals in the same asset. Only an empty goal can change
+    /// denomination.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `AssetMismatch` - If the goal holds funds, or an existing schedule,
+    ///   sponsor match or payout target uses another token
+    pub fn set_goal_asset(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        asset: Address,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("asset"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let conflicting = (goal.current_amount > 0 && goal.asset.as_ref() != Some(&asset))
+            || Self::get_contribution_schedule(env.clone(), goal_id)
+                .is_some_and(|schedule| schedule.token != asset)
+            || Self::get_sponsor_match(env.clone(), goal_id).is_some_and(|m| m.token != asset)
+            || Self::get_payout_target(env.clone(), goal_id).is_some_and(|t| t.token != asset);
+        if conflicting {
+            Self::append_audit(&env, symbol_short!("asset"), &caller, false);
+            return Err(SavingsGoalsError::AssetMismatch);
+        }
+
+        goal.asset = Some(asset.clone());
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("asset"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalCreated),
-            (next_id, owner),
+            (symbol_short!("savings"), symbol_short!("asset")),
+            (goal_id, asset),
+        );
+        Ok(())
+    }
+
+    /// Change a goal's target amount. The previous target is appended to
+    /// the goal's change log so earlier progress can still be read against
+    /// the target that applied at the time. Lowering the target to or below
+    /// the current balance completes the goal.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - `new_target` is not positive
+    /// * `GoalNotFound`
+    /// * `Unauthorized` - `caller` is not the goal owner
+    pub fn update_goal_target(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        new_target: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if new_target <= 0 {
+            Self::append_audit(&env, symbol_short!("target"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("target"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let old_target = goal.target_amount;
+        let previously_completed = goal.current_amount >= old_target;
+        let timestamp = env.ledger().timestamp();
+
+        let key = (Self::STORAGE_TARGET_LOG, goal_id);
+        let mut log: Vec<TargetChange> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if log.len() >= MAX_TARGET_HISTORY {
+            log.pop_front();
+        }
+        log.push_back(TargetChange {
+            old_target,
+            new_target,
+            timestamp,
+        });
+        env.storage().persistent().set(&key, &log);
+        Self::extend_persistent_ttl(&env, &key);
+
+        goal.target_amount = new_target;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("target"), &caller, true);
+        env.events().publish(
+            (TARGET_CHANGED,),
+            TargetChangedEvent {
+                goal_id,
+                old_target,
+                new_target,
+                current_amount: goal.current_amount,
+                timestamp,
+            },
         );
+        if !previously_completed && goal.current_amount >= new_target {
+            env.events().publish(
+                (GOAL_COMPLETED,),
+                GoalCompletedEvent {
+                    goal_id,
+                    name: goal.name.clone(),
+                    final_amount: goal.current_amount,
+                    timestamp,
+                },
+            );
+            Self::pay_out_if_complete(&env, &mut goal);
+        }
+        Ok(())
+    }
 
-        Ok(next_id)
+    /// Target changes for a goal, oldest first. Only the most recent
+    /// `MAX_TARGET_HISTORY` entries are kept.
+    pub fn get_target_history(env: Env, goal_id: u32) -> Vec<TargetChange> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_TARGET_LOG, goal_id))
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
     /// Adds funds to an existing savings goal.
     ///
     /// # Arguments
-    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `caller` - Address of the goal owner, or any contributor when the
+    ///   goal is shared (must authorize)
     /// * `goal_id` - ID of the goal to add funds to
+    /// * `token` - Token to transfer `amount` of from `caller` and hold for
+    ///   the goal; `None` only records the amount and is refused for a
+    ///   denominated goal
     /// * `amount` - Amount to add in stroops (must be > 0)
     ///
     /// # Returns
@@ -587,7 +1362,8 @@ impl SavingsGoalContract {
     /// # Errors
     /// * `InvalidAmount` - If amount ≤ 0
     /// * `GoalNotFound` - If goal_id does not exist
-    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `Unauthorized` - If caller is not the goal owner and the goal is not shared
+    /// * `AssetMismatch` - If `token` is not the goal's asset
     /// * `Overflow` - If adding amount would overflow i128
     ///
     /// # Panics
@@ -596,6 +1372,7 @@ impl SavingsGoalContract {
         env: Env,
         caller: Address,
         goal_id: u32,
+        token: Option<Address>,
         amount: i128,
     ) -> Result<i128, SavingsGoalsError> {
         caller.require_auth();
@@ -608,13 +1385,7 @@ impl SavingsGoalContract {
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("add"), &caller, false);
@@ -622,23 +1393,41 @@ impl SavingsGoalContract {
             }
         };
 
-        if goal.owner != caller {
+        if goal.owner != caller && !goal.shared {
             Self::append_audit(&env, symbol_short!("add"), &caller, false);
             return Err(SavingsGoalsError::Unauthorized);
         }
+        if let Err(e) = Self::check_token(&goal, &token).and(Self::check_not_frozen(&env, goal_id))
+        {
+            Self::append_audit(&env, symbol_short!("add"), &caller, false);
+            return Err(e);
+        }
 
+        if let Err(e) = Self::apply_contribution_cap(&env, &goal, amount) {
+            Self::append_audit(&env, symbol_short!("add"), &caller, false);
+            return Err(e);
+        }
+
+        Self::settle_interest(&env, &mut goal)?;
+        let previously_completed = goal.current_amount >= goal.target_amount;
         goal.current_amount = goal
             .current_amount
             .checked_add(amount)
             .ok_or(SavingsGoalsError::Overflow)?;
+        if let Some(token) = &token {
+            TokenClient::new(&env, token).transfer(
+                &caller,
+                &env.current_contract_address(),
+                &amount,
+            );
+            Self::adjust_held(&env, goal_id, token, amount);
+        }
+        Self::record_contribution(&env, &goal, &caller, amount);
+        Self::apply_sponsor_match(&env, &mut goal, amount);
         let new_total = goal.current_amount;
         let was_completed = new_total >= goal.target_amount;
-        let previously_completed = (new_total - amount) >= goal.target_amount;
 
-        goals.set(goal_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         let funds_event = FundsAddedEvent {
             goal_id,
@@ -670,6 +1459,7 @@ impl SavingsGoalContract {
                 (goal_id, caller),
             );
         }
+        Self::pay_out_if_complete(&env, &mut goal);
 
         Ok(new_total)
     }
@@ -684,40 +1474,42 @@ impl SavingsGoalContract {
         if contributions.len() > MAX_BATCH_SIZE {
             panic!("Batch too large");
         }
-        let goals_map: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
         for item in contributions.iter() {
             if item.amount <= 0 {
                 panic!("Amount must be positive");
             }
-            let goal = goals_map.get(item.goal_id).expect("Goal not found");
+            let goal = Self::load_goal(&env, item.goal_id).expect("Goal not found");
             if goal.owner != caller {
                 panic!("Not owner of all goals");
             }
+            if Self::is_frozen(&env, item.goal_id) {
+                panic!("Goal is frozen");
+            }
+            if goal.asset.is_some() {
+                panic!("Denominated goal needs a token");
+            }
         }
         Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
         let mut count = 0u32;
         for item in contributions.iter() {
-            let mut goal = goals.get(item.goal_id).expect("Goal not found");
+            let mut goal = Self::load_goal(&env, item.goal_id).expect("Goal not found");
             if goal.owner != caller {
                 panic!("Batch validation failed");
             }
+            if Self::apply_contribution_cap(&env, &goal, item.amount).is_err() {
+                panic!("Contribution cap exceeded");
+            }
+            Self::settle_interest(&env, &mut goal).expect("Interest overflow");
+            let previously_completed = goal.current_amount >= goal.target_amount;
             goal.current_amount = goal
                 .current_amount
                 .checked_add(item.amount)
                 .expect("overflow");
+            Self::record_contribution(&env, &goal, &caller, item.amount);
+            Self::apply_sponsor_match(&env, &mut goal, item.amount);
             let new_total = goal.current_amount;
             let was_completed = new_total >= goal.target_amount;
-            let previously_completed = (new_total - item.amount) >= goal.target_amount;
-            goals.set(item.goal_id, goal.clone());
+            Self::save_goal(&env, &goal);
             let funds_event = FundsAddedEvent {
                 goal_id: item.goal_id,
                 amount: item.amount,
@@ -744,11 +1536,10 @@ impl SavingsGoalContract {
                     (item.goal_id, caller.clone()),
                 );
             }
+            Self::pay_out_if_complete(&env, &mut goal);
             count += 1;
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::record_activity(&env, &caller);
         env.events().publish(
             (symbol_short!("savings"), symbol_short!("batch_add")),
             (count, caller),
@@ -756,795 +1547,6451 @@ impl SavingsGoalContract {
         count
     }
 
-    /// Withdraws funds from an existing savings goal.
+    /// Distribute the savings portion of a remittance across the owner's
+    /// active goals. Intended to be called by remittance_split or the
+    /// orchestrator.
     ///
-    /// # Arguments
-    /// * `caller` - Address of the goal owner (must authorize)
-    /// * `goal_id` - ID of the goal to withdraw from
-    /// * `amount` - Amount to withdraw in stroops (must be > 0)
+    /// Every goal that has not reached its target and has a non-zero
+    /// `weight` receives `amount * weight / total_weight`; the rounding
+    /// remainder goes to the last of them. Each credited goal emits the
+    /// same events as `add_to_goal`. No tokens move, so goals denominated
+    /// in an asset are skipped.
     ///
     /// # Returns
-    /// `Ok(remaining_amount)` - The remaining amount in the goal after withdrawal
+    /// The amount credited to each goal, in goal ID order.
     ///
     /// # Errors
     /// * `InvalidAmount` - If amount ≤ 0
-    /// * `GoalNotFound` - If goal_id does not exist
-    /// * `Unauthorized` - If caller is not the goal owner
-    /// * `GoalLocked` - If goal is locked or time-locked
-    /// * `InsufficientBalance` - If amount > current_amount
-    /// * `Overflow` - If subtraction would underflow i128
-    ///
-    /// # Panics
-    /// * If `caller` does not authorize the transaction
-    pub fn withdraw_from_goal(
+    /// * `NoActiveGoals` - If the owner has no unfinished goal with a weight
+    /// * `Overflow` - If a goal balance would overflow i128
+    pub fn deposit_allocation(
         env: Env,
-        caller: Address,
-        goal_id: u32,
+        owner: Address,
         amount: i128,
-    ) -> Result<i128, SavingsGoalsError> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+    ) -> Result<Vec<ContributionItem>, SavingsGoalsError> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
 
         if amount <= 0 {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            Self::append_audit(&env, symbol_short!("alloc"), &owner, false);
             return Err(SavingsGoalsError::InvalidAmount);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-                return Err(SavingsGoalsError::GoalNotFound);
+        let mut active = Vec::new(&env);
+        let mut total_weight: i128 = 0;
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            let Some(goal) = Self::load_goal(&env, id) else {
+                continue;
+            };
+            if goal.weight > 0
+                && goal.current_amount < goal.target_amount
+                && goal.asset.is_none()
+                && !Self::is_frozen(&env, id)
+            {
+                active.push_back(id);
+                total_weight += goal.weight as i128;
             }
-        };
-
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::Unauthorized);
         }
-
-        if goal.locked {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::GoalLocked);
+        if active.is_empty() {
+            Self::append_audit(&env, symbol_short!("alloc"), &owner, false);
+            return Err(SavingsGoalsError::NoActiveGoals);
         }
 
-        if let Some(unlock_date) = goal.unlock_date {
-            let current_time = env.ledger().timestamp();
-            if current_time < unlock_date {
-                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-                return Err(SavingsGoalsError::GoalLocked);
+        let mut credited = Vec::new(&env);
+        let mut remaining = amount;
+        for (i, goal_id) in active.iter().enumerate() {
+            let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+            let share = if i as u32 == active.len() - 1 {
+                remaining
+            } else {
+                amount
+                    .checked_mul(goal.weight as i128)
+                    .ok_or(SavingsGoalsError::Overflow)?
+                    / total_weight
+            };
+            remaining -= share;
+            if share == 0 {
+                continue;
             }
-        }
 
-        if amount > goal.current_amount {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::InsufficientBalance);
+            Self::credit_goal(&env, &mut goal, &owner, share)?;
+            Self::save_goal(&env, &goal);
+            credited.push_back(ContributionItem {
+                goal_id,
+                amount: share,
+            });
         }
 
-        goal.current_amount = goal
-            .current_amount
-            .checked_sub(amount)
-            .ok_or(SavingsGoalsError::Overflow)?;
-        let new_amount = goal.current_amount;
-
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
-
-        Self::append_audit(&env, symbol_short!("withdraw"), &caller, true);
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
-            (goal_id, caller, amount),
-        );
-
-        Ok(new_amount)
+        Self::append_audit(&env, symbol_short!("alloc"), &owner, true);
+        Ok(credited)
     }
 
-    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+    /// Set a goal's share of `deposit_allocation` deposits relative to the
+    /// owner's other goals. New goals start at 1; 0 leaves the goal out.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_goal_weight(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        weight: u32,
+    ) -> Result<(), SavingsGoalsError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::LOCK);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-                panic!("Goal not found");
-            }
-        };
-
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
         if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-            panic!("Only the goal owner can lock this goal");
+            Self::append_audit(&env, symbol_short!("weight"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
         }
 
-        goal.locked = true;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
-
-        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalLocked),
-            (goal_id, caller),
-        );
+        goal.weight = weight;
+        Self::save_goal(&env, &goal);
 
-        true
+        Self::append_audit(&env, symbol_short!("weight"), &caller, true);
+        Ok(())
     }
 
-    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+    /// Choose which of the caller's goals receives `contribute_roundup`
+    /// deposits.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_roundup_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<(), SavingsGoalsError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::UNLOCK);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("roundup"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let mut roundup_goals: Map<Address, u32> = env
             .storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
+            .get(&Self::STORAGE_ROUNDUP_GOALS)
             .unwrap_or_else(|| Map::new(&env));
+        roundup_goals.set(caller.clone(), goal_id);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_ROUNDUP_GOALS, &roundup_goals);
 
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-                panic!("Goal not found");
-            }
-        };
-
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-            panic!("Only the goal owner can unlock this goal");
-        }
-
-        goal.locked = false;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
-
-        Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalUnlocked),
-            (goal_id, caller),
-        );
-
-        true
+        Self::append_audit(&env, symbol_short!("roundup"), &caller, true);
+        Ok(())
     }
 
-    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
+    pub fn get_roundup_goal(env: Env, owner: Address) -> Option<u32> {
+        env.storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        goals.get(goal_id)
+            .get::<_, Map<Address, u32>>(&Self::STORAGE_ROUNDUP_GOALS)
+            .and_then(|roundup_goals| roundup_goals.get(owner))
     }
 
-    // -----------------------------------------------------------------------
-    // PAGINATED LIST QUERIES
-    // -----------------------------------------------------------------------
-
-    /// Get a page of savings goals for `owner`.
-    ///
-    /// # Arguments
-    /// * `owner`  – whose goals to return
-    /// * `cursor` – start after this goal ID (pass 0 for the first page)
-    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// Save the spare change from a purchase. Called from the spending path
+    /// (family wallet or orchestrator) with the amount spent; the difference
+    /// up to the next multiple of `round_to` is added to the owner's
+    /// round-up goal.
     ///
     /// # Returns
-    /// `GoalPage { items, next_cursor, count }`.
-    /// `next_cursor == 0` means no more pages.
-    pub fn get_goals(env: Env, owner: Address, cursor: u32, limit: u32) -> GoalPage {
-        let limit = Self::clamp_limit(limit);
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut result = Vec::new(&env);
-        let mut next_cursor: u32 = 0;
-        let mut collected: u32 = 0;
+    /// `Ok(roundup)` - The amount saved (0 if spend_amount was already round)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If spend_amount or round_to ≤ 0
+    /// * `RoundupGoalNotSet` - If the owner has no round-up goal, or it was closed
+    /// * `Overflow` - If the goal balance would overflow i128
+    pub fn contribute_roundup(
+        env: Env,
+        owner: Address,
+        spend_amount: i128,
+        round_to: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
 
-        for (id, goal) in goals.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if goal.owner != owner {
-                continue;
-            }
-            if collected < limit {
-                result.push_back(goal);
-                collected += 1;
-                next_cursor = id; // track last returned ID
-            } else {
-                break;
-            }
+        if spend_amount <= 0 || round_to <= 0 {
+            Self::append_audit(&env, symbol_short!("roundup"), &owner, false);
+            return Err(SavingsGoalsError::InvalidAmount);
         }
 
-        // If we didn't fill the page, there are no more items
-        if collected < limit {
-            next_cursor = 0;
+        let roundup = (round_to - spend_amount % round_to) % round_to;
+        if roundup == 0 {
+            return Ok(0);
         }
 
-        GoalPage {
-            items: result,
-            next_cursor,
-            count: collected,
-        }
+        Self::extend_instance_ttl(&env);
+
+        let goal_id = Self::get_roundup_goal(env.clone(), owner.clone())
+            .ok_or(SavingsGoalsError::RoundupGoalNotSet)?;
+        let mut goal =
+            Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::RoundupGoalNotSet)?;
+
+        Self::credit_goal(&env, &mut goal, &owner, roundup)?;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("roundup"), &owner, true);
+        Ok(roundup)
     }
 
-    /// Backward-compatible: returns ALL goals for owner in one Vec.
-    /// Prefer the paginated `get_goals` for production use.
-    pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut result = Vec::new(&env);
-        for (_, goal) in goals.iter() {
-            if goal.owner == owner {
-                result.push_back(goal);
-            }
+    /// Move whatever `owner` holds of the `source` token above `threshold`
+    /// into `goal_id`. Meant to be submitted by a keeper at the end of a
+    /// spending period with the owner's authorization, so leftover spending
+    /// money is saved instead of sitting idle.
+    ///
+    /// # Returns
+    /// `Ok(swept)` - The amount moved (0 if the balance is at or below the
+    /// threshold)
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If threshold < 0
+    /// * `GoalNotFound`
+    /// * `Unauthorized` - If owner does not own the goal
+    /// * `AssetMismatch` - If the goal is denominated in another token
+    /// * `GoalFrozen`, `ContributionCapExceeded`, `Overflow` - As for any
+    ///   contribution
+    pub fn sweep_to_goal(
+        env: Env,
+        owner: Address,
+        source: Address,
+        threshold: i128,
+        goal_id: u32,
+    ) -> Result<i128, SavingsGoalsError> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+
+        if threshold < 0 {
+            Self::append_audit(&env, symbol_short!("sweep"), &owner, false);
+            return Err(SavingsGoalsError::InvalidAmount);
         }
-        result
-    }
+        Self::extend_instance_ttl(&env);
 
-    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
-        let storage = env.storage().instance();
-        let goals: Map<u32, SavingsGoal> = storage
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or(Map::new(&env));
-        if let Some(goal) = goals.get(goal_id) {
-            goal.current_amount >= goal.target_amount
-        } else {
-            false
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != owner {
+            Self::append_audit(&env, symbol_short!("sweep"), &owner, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if let Err(e) = Self::check_asset(&goal, &source) {
+            Self::append_audit(&env, symbol_short!("sweep"), &owner, false);
+            return Err(e);
         }
-    }
 
-    // -----------------------------------------------------------------------
-    // Snapshot, audit, schedule
-    // -----------------------------------------------------------------------
+        let token = TokenClient::new(&env, &source);
+        let amount = token.balance(&owner) - threshold;
+        if amount <= 0 {
+            return Ok(0);
+        }
 
-    pub fn get_nonce(env: Env, address: Address) -> u64 {
-        let nonces: Option<Map<Address, u64>> =
-            env.storage().instance().get(&symbol_short!("NONCES"));
-        nonces
-            .as_ref()
-            .and_then(|m: &Map<Address, u64>| m.get(address))
-            .unwrap_or(0)
+        token.transfer(&owner, &env.current_contract_address(), &amount);
+        Self::adjust_held(&env, goal_id, &source, amount);
+        Self::credit_goal(&env, &mut goal, &owner, amount)?;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("sweep"), &owner, true);
+        env.events().publish(
+            (SWEEP,),
+            SweepEvent {
+                goal_id,
+                owner,
+                token: source,
+                amount,
+                threshold,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(amount)
     }
 
-    pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
+    /// Withdraw from several goals at once, e.g. to consolidate funds for
+    /// a large expense. Every item follows the `withdraw_from_goal` rules;
+    /// if any item fails, nothing is withdrawn.
+    ///
+    /// # Returns
+    /// One `WithdrawalResult` per item, in order
+    ///
+    /// # Errors
+    /// The first failing item's error, as for `withdraw_from_goal`
+    pub fn batch_withdraw(
+        env: Env,
+        caller: Address,
+        items: Vec<WithdrawalItem>,
+    ) -> Result<Vec<WithdrawalResult>, SavingsGoalsError> {
         caller.require_auth();
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let next_id = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
-        let mut list = Vec::new(&env);
-        for i in 1..=next_id {
-            if let Some(g) = goals.get(i) {
-                list.push_back(g);
-            }
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        if items.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
         }
-        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
-        GoalsExportSnapshot {
-            version: SNAPSHOT_VERSION,
-            checksum,
-            next_id,
-            goals: list,
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+
+        let mut results = Vec::new(&env);
+        for item in items.iter() {
+            let checked = match Self::load_goal(&env, item.goal_id) {
+                _ if item.amount <= 0 => Err(SavingsGoalsError::InvalidAmount),
+                None => Err(SavingsGoalsError::GoalNotFound),
+                Some(goal) if goal.owner != caller => Err(SavingsGoalsError::Unauthorized),
+                Some(mut goal) => Self::settle_interest(&env, &mut goal)
+                    .and_then(|_| Self::check_withdrawal_lock(&env, &goal))
+                    .and(Self::check_withdrawal_approval(
+                        &env,
+                        item.goal_id,
+                        item.amount,
+                    ))
+                    .and_then(|_| {
+                        if item.amount > Self::available_balance(&env, &goal) {
+                            Err(SavingsGoalsError::InsufficientBalance)
+                        } else {
+                            Ok(goal)
+                        }
+                    }),
+            };
+            let mut goal = match checked {
+                Ok(goal) => goal,
+                Err(e) => {
+                    Self::append_audit(&env, symbol_short!("batch_wd"), &caller, false);
+                    return Err(e);
+                }
+            };
+            goal.current_amount -= item.amount;
+            results.push_back(WithdrawalResult {
+                goal_id: item.goal_id,
+                amount: item.amount,
+                remaining: goal.current_amount,
+            });
+            Self::save_goal(&env, &goal);
+            Self::record_flow(&env, &caller, 0, item.amount);
+            Self::record_withdrawal(&env, item.goal_id, item.amount);
+        }
+
+        for result in results.iter() {
+            env.events().publish(
+                (WITHDRAWAL_MADE,),
+                WithdrawalMadeEvent {
+                    goal_id: result.goal_id,
+                    amount: result.amount,
+                    remaining: result.remaining,
+                    timestamp: now,
+                },
+            );
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
+                (result.goal_id, caller.clone(), result.amount),
+            );
         }
+        Self::append_audit(&env, symbol_short!("batch_wd"), &caller, true);
+        Ok(results)
     }
 
-    pub fn import_snapshot(
+    /// Withdraws funds from an existing savings goal.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `goal_id` - ID of the goal to withdraw from
+    /// * `token` - Token to send the withdrawal in. Up to `amount` of the
+    ///   tokens held for the goal are transferred to `caller`; the rest is
+    ///   released in the books only. `None` moves no tokens and is refused
+    ///   for a denominated goal
+    /// * `amount` - Amount to withdraw in stroops (must be > 0)
+    ///
+    /// # Returns
+    /// `Ok(remaining_amount)` - The remaining amount in the goal after withdrawal
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `AssetMismatch` - If `token` is not the goal's asset
+    /// * `GoalLocked` - If goal is locked or time-locked
+    /// * `InsufficientBalance` - If amount > current_amount
+    /// * `Overflow` - If subtraction would underflow i128
+    ///
+    /// # Panics
+    /// * If `caller` does not authorize the transaction
+    pub fn withdraw_from_goal(
         env: Env,
         caller: Address,
-        nonce: u64,
-        snapshot: GoalsExportSnapshot,
-    ) -> bool {
+        goal_id: u32,
+        token: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
         caller.require_auth();
-        Self::require_nonce(&env, &caller, nonce);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
 
-        if snapshot.version != SNAPSHOT_VERSION {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            panic!("Unsupported snapshot version");
-        }
-        let expected =
-            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
-        if snapshot.checksum != expected {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            panic!("Snapshot checksum mismatch");
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
         }
 
         Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = Map::new(&env);
-        for g in snapshot.goals.iter() {
-            goals.set(g.id, g.clone());
-            let mut ids = owner_goal_ids
-                .get(g.owner.clone())
-                .unwrap_or_else(|| Vec::new(&env));
-            ids.push_back(g.id);
-            owner_goal_ids.set(g.owner.clone(), ids);
-        }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
-        env.storage()
-            .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
-
-        Self::increment_nonce(&env, &caller);
-        Self::append_audit(&env, symbol_short!("import"), &caller, true);
-        true
-    }
 
-    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
-        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
-        let log = log.unwrap_or_else(|| Vec::new(&env));
-        let len = log.len();
-        let cap = MAX_AUDIT_ENTRIES.min(limit);
-        let mut out = Vec::new(&env);
-        if from_index >= len {
-            return out;
-        }
-        let end = (from_index + cap).min(len);
-        for i in from_index..end {
-            if let Some(entry) = log.get(i) {
-                out.push_back(entry);
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+                return Err(SavingsGoalsError::GoalNotFound);
             }
-        }
-        out
-    }
+        };
 
-    fn require_nonce(env: &Env, address: &Address, expected: u64) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        if expected != current {
-            panic!("Invalid nonce: expected {}, got {}", current, expected);
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
         }
-    }
 
-    fn increment_nonce(env: &Env, address: &Address) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        let next = current.checked_add(1).expect("nonce overflow");
-        let mut nonces: Map<Address, u64> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NONCES"))
-            .unwrap_or_else(|| Map::new(env));
-        nonces.set(address.clone(), next);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NONCES"), &nonces);
-    }
+        if let Err(e) = Self::check_token(&goal, &token)
+            .and(Self::check_withdrawal_lock(&env, &goal))
+            .and(Self::check_withdrawal_approval(&env, goal_id, amount))
+        {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(e);
+        }
 
-    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
-        let mut c = version as u64 + next_id as u64;
-        for i in 0..goals.len() {
-            if let Some(g) = goals.get(i) {
-                c = c
-                    .wrapping_add(g.id as u64)
-                    .wrapping_add(g.target_amount as u64)
-                    .wrapping_add(g.current_amount as u64);
-            }
+        Self::settle_interest(&env, &mut goal)?;
+        if amount > Self::available_balance(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(SavingsGoalsError::InsufficientBalance);
         }
-        c.wrapping_mul(31)
-    }
 
-    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
-        let timestamp = env.ledger().timestamp();
-        let mut log: Vec<AuditEntry> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("AUDIT"))
-            .unwrap_or_else(|| Vec::new(env));
-        if log.len() >= MAX_AUDIT_ENTRIES {
-            let mut new_log = Vec::new(env);
-            for i in 1..log.len() {
-                if let Some(entry) = log.get(i) {
-                    new_log.push_back(entry);
-                }
+        goal.current_amount = goal
+            .current_amount
+            .checked_sub(amount)
+            .ok_or(SavingsGoalsError::Overflow)?;
+        let new_amount = goal.current_amount;
+
+        Self::save_goal(&env, &goal);
+        Self::record_flow(&env, &caller, 0, amount);
+        Self::record_withdrawal(&env, goal_id, amount);
+        if let Some(token) = &token {
+            let sent = amount.min(Self::held_balance(&env, goal_id, token));
+            if sent > 0 {
+                TokenClient::new(&env, token).transfer(
+                    &env.current_contract_address(),
+                    &caller,
+                    &sent,
+                );
+                Self::adjust_held(&env, goal_id, token, -sent);
             }
-            log = new_log;
         }
-        log.push_back(AuditEntry {
-            operation,
-            caller: caller.clone(),
-            timestamp,
-            success,
-        });
-        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
-    }
 
-    #[allow(dead_code)]
-    fn get_owner_goal_ids_map(env: &Env) -> Option<Map<Address, Vec<u32>>> {
-        env.storage().instance().get(&Self::STORAGE_OWNER_GOAL_IDS)
-    }
+        let withdrawal_event = WithdrawalMadeEvent {
+            goal_id,
+            amount,
+            remaining: new_amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((WITHDRAWAL_MADE,), withdrawal_event);
 
-    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
-            .storage()
-            .instance()
-            .get(&Self::STORAGE_OWNER_GOAL_IDS)
-            .unwrap_or_else(|| Map::new(env));
-        let mut ids = owner_goal_ids
-            .get(owner.clone())
-            .unwrap_or_else(|| Vec::new(env));
-        ids.push_back(goal_id);
-        owner_goal_ids.set(owner.clone(), ids);
-        env.storage()
-            .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
-    }
+        Self::append_audit(&env, symbol_short!("withdraw"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
+            (goal_id, caller, amount),
+        );
 
-    /// Extend the TTL of instance storage
-    fn extend_instance_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Ok(new_amount)
     }
 
-    /// Set time-lock on a goal
-    pub fn set_time_lock(env: Env, caller: Address, goal_id: u32, unlock_date: u64) -> bool {
+    /// Move funds from one of the caller's goals to another, e.g. surplus
+    /// from a completed goal into the medical fund. An unlocked source can
+    /// give any amount; a locked or time-locked source only what it holds
+    /// above its target. Tokens held for the source in the goals' asset move
+    /// with the amount. Emits a single `GoalTransferEvent`.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0 or both IDs are the same goal
+    /// * `GoalNotFound` - If either goal does not exist
+    /// * `Unauthorized` - If caller does not own both goals
+    /// * `GoalLocked` - If the source is locked and amount exceeds its surplus
+    /// * `InsufficientBalance` - If amount > the source's current_amount
+    /// * `Overflow` - If the destination balance would overflow i128
+    pub fn transfer_between_goals(
+        env: Env,
+        caller: Address,
+        from_id: u32,
+        to_id: u32,
+        amount: i128,
+    ) -> Result<(), SavingsGoalsError> {
         caller.require_auth();
-        Self::extend_instance_ttl(&env);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        if amount <= 0 || from_id == to_id {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
 
-        let mut goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => {
-                Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-                panic!("Goal not found");
-            }
-        };
+        Self::extend_instance_ttl(&env);
 
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            panic!("Only the goal owner can set time-lock");
+        let mut from = Self::load_goal(&env, from_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let mut to = Self::load_goal(&env, to_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if from.owner != caller || to.owner != caller {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if from.asset != to.asset {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::AssetMismatch);
+        }
+        if let Err(e) = Self::check_not_frozen(&env, from_id)
+            .and(Self::check_not_frozen(&env, to_id))
+            .and(Self::check_withdrawal_approval(&env, from_id, amount))
+        {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(e);
+        }
+        Self::settle_interest(&env, &mut from)?;
+        Self::settle_interest(&env, &mut to)?;
+        if amount > Self::available_balance(&env, &from) {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::InsufficientBalance);
         }
 
-        let current_time = env.ledger().timestamp();
-        if unlock_date <= current_time {
-            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            panic!("Unlock date must be in the future");
+        if Self::is_locked(&env, &from) && amount > from.current_amount - from.target_amount {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            return Err(SavingsGoalsError::GoalLocked);
         }
 
-        goal.unlock_date = Some(unlock_date);
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        from.current_amount -= amount;
+        to.current_amount = to
+            .current_amount
+            .checked_add(amount)
+            .ok_or(SavingsGoalsError::Overflow)?;
+        let from_remaining = from.current_amount;
+        let to_total = to.current_amount;
+        Self::save_goal(&env, &from);
+        Self::save_goal(&env, &to);
+        Self::record_withdrawal(&env, from_id, amount);
+        if let Some(asset) = &from.asset {
+            let moved = amount.min(Self::held_balance(&env, from_id, asset));
+            Self::adjust_held(&env, from_id, asset, -moved);
+            Self::adjust_held(&env, to_id, asset, moved);
+        }
 
-        Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
-        true
+        env.events().publish(
+            (GOAL_TRANSFER,),
+            GoalTransferEvent {
+                from_goal_id: from_id,
+                to_goal_id: to_id,
+                amount,
+                from_remaining,
+                to_total,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::append_audit(&env, symbol_short!("transfer"), &caller, true);
+        Ok(())
     }
 
-    pub fn create_savings_schedule(
-        env: Env,
-        owner: Address,
-        goal_id: u32,
-        amount: i128,
-        next_due: u64,
-        interval: u64,
-    ) -> u32 {
-        owner.require_auth();
+    /// Close a goal: release its remaining balance to the owner, transferring
+    /// any tokens held for it, drop it from active storage and queries, and
+    /// archive it for `get_closed_goals`. A locked or time-locked goal can
+    /// only be closed once it is empty.
+    ///
+    /// # Returns
+    /// `Ok(released)` - The balance released to the owner
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `GoalLocked` - If the goal still holds funds and is locked, or has
+    ///   funds deployed to yield
+    pub fn close_goal(env: Env, caller: Address, goal_id: u32) -> Result<i128, SavingsGoalsError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        Self::extend_instance_ttl(&env);
 
-        if amount <= 0 {
-            panic!("Amount must be positive");
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("close"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        Self::settle_interest(&env, &mut goal)?;
+        let locked = goal.current_amount > 0 && Self::is_locked(&env, &goal);
+        if locked || Self::deployed_amount(&env, goal_id) > 0 || Self::is_frozen(&env, goal_id) {
+            Self::append_audit(&env, symbol_short!("close"), &caller, false);
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        if let Err(e) = Self::check_withdrawal_approval(&env, goal_id, goal.current_amount) {
+            Self::append_audit(&env, symbol_short!("close"), &caller, false);
+            return Err(e);
         }
 
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let released = goal.current_amount;
+        goal.current_amount = 0;
+        Self::delete_goal(&env, &goal);
+        Self::record_flow(&env, &caller, 0, released);
+        Self::release_held(&env, goal_id, &caller, i128::MAX);
+
+        let paid_out = Self::take_paid_out(&env, goal_id);
+        Self::store_closed_goal(
+            &env,
+            &ClosedGoal {
+                goal,
+                released: released.saturating_add(paid_out),
+                closed_at: env.ledger().timestamp(),
+            },
+        );
 
-        let goal = goals.get(goal_id).expect("Goal not found");
+        Self::append_audit(&env, symbol_short!("close"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("closed")),
+            (goal_id, caller, released),
+        );
+        Ok(released)
+    }
 
-        if goal.owner != owner {
-            panic!("Only the goal owner can create schedules");
-        }
+    /// Goals `owner` has closed, oldest first.
+    pub fn get_closed_goals(env: Env, owner: Address, offset: u32, limit: u32) -> Vec<ClosedGoal> {
+        let limit = Self::clamp_limit(limit);
+        let ids = Self::owner_closed_ids(&env, &owner);
 
-        let current_time = env.ledger().timestamp();
-        if next_due <= current_time {
-            panic!("Next due date must be in the future");
+        let mut result = Vec::new(&env);
+        let end = ids.len().min(offset.saturating_add(limit));
+        for i in offset..end {
+            if let Some(entry) = ids.get(i).and_then(|id| Self::load_closed_goal(&env, id)) {
+                result.push_back(entry);
+            }
         }
+        result
+    }
 
+    /// Keeper entrypoint: fold up to `limit` goals that were closed, or
+    /// emptied by their payout, before `before_ts` into their owners'
+    /// `ArchivedSavings` summaries and drop the full records. A paid-out goal
+    /// that has taken new funds since is left alone; goals still holding a
+    /// balance are never touched. Returns the number of goals archived.
+    pub fn archive_completed_goals(env: Env, before_ts: u64, limit: u32) -> u32 {
+        let limit = Self::clamp_limit(limit);
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        // The queue is in closing order, so the goals old enough to archive
+        // form a prefix.
+        let mut queue = Self::closed_queue(&env);
+        let mut summaries: Map<Address, (ArchivedSavings, u32)> = Map::new(&env);
+        let mut archived_total = 0u32;
+        while archived_total < limit {
+            let Some((closed_at, goal_id)) = queue.first() else {
+                break;
+            };
+            if closed_at >= before_ts {
+                break;
+            }
+            queue.pop_front();
+            let Some(entry) = Self::load_closed_goal(&env, goal_id) else {
+                continue;
+            };
+            let owner = entry.goal.owner.clone();
+            Self::fold_archived(&env, &mut summaries, &owner, entry.released, closed_at);
+            env.storage()
+                .persistent()
+                .remove(&(Self::STORAGE_CLOSED, goal_id));
+            Self::remove_owner_closed_id(&env, &owner, goal_id);
+            archived_total += 1;
+        }
 
-        let next_schedule_id = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_SSCH"))
-            .unwrap_or(0u32)
-            + 1;
+        // Goals emptied by their payout are retired here rather than closed.
+        let mut paid_queue = Self::paid_queue(&env);
+        while archived_total < limit {
+            let Some((paid_at, goal_id)) = paid_queue.first() else {
+                break;
+            };
+            if paid_at >= before_ts {
+                break;
+            }
+            paid_queue.pop_front();
+            let Some(goal) = Self::load_goal(&env, goal_id).filter(|g| {
+                g.current_amount == 0
+                    && Self::deployed_amount(&env, g.id) == 0
+                    && !Self::is_frozen(&env, g.id)
+            }) else {
+                continue;
+            };
+            Self::delete_goal(&env, &goal);
+            Self::release_held(&env, goal_id, &goal.owner, i128::MAX);
+            let paid_out = Self::take_paid_out(&env, goal_id);
+            Self::fold_archived(&env, &mut summaries, &goal.owner, paid_out, paid_at);
+            archived_total += 1;
+        }
 
-        let schedule = SavingsSchedule {
-            id: next_schedule_id,
-            owner: owner.clone(),
-            goal_id,
-            amount,
-            next_due,
-            interval,
-            recurring: interval > 0,
-            active: true,
-            created_at: current_time,
-            last_executed: None,
-            missed_count: 0,
-        };
+        for (owner, (summary, archived)) in summaries.iter() {
+            let key = (Self::STORAGE_ARCHIVED, owner.clone());
+            env.storage().persistent().set(&key, &summary);
+            Self::extend_persistent_ttl(&env, &key);
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("archived")),
+                (owner, archived, summary.total_released),
+            );
+        }
+        Self::save_closed_queue(&env, &queue);
+        Self::save_paid_queue(&env, &paid_queue);
+        archived_total
+    }
 
-        schedules.set(next_schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+    /// Summary of `owner`'s archived goals; all zero if none were archived.
+    pub fn get_archived_savings(env: Env, owner: Address) -> ArchivedSavings {
         env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
-
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
-            (next_schedule_id, owner),
-        );
-
-        next_schedule_id
+            .persistent()
+            .get(&(Self::STORAGE_ARCHIVED, owner))
+            .unwrap_or(ArchivedSavings {
+                goal_count: 0,
+                total_released: 0,
+                last_closed_at: 0,
+            })
     }
 
-    pub fn modify_savings_schedule(
+    /// Contributions into and withdrawals out of `owner`'s goals between
+    /// `from` and `to`, plus their current number of active goals. Flows are
+    /// kept in 30-day periods, so a period counts if it overlaps the range.
+    /// Interest, yield and transfers between the owner's own goals are not
+    /// counted as contributions or withdrawals.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - `from` is after `to`
+    pub fn get_savings_stats(
         env: Env,
-        caller: Address,
-        schedule_id: u32,
-        amount: i128,
-        next_due: u64,
-        interval: u64,
-    ) -> bool {
-        caller.require_auth();
+        owner: Address,
+        from: u64,
+        to: u64,
+    ) -> Result<SavingsStats, SavingsGoalsError> {
+        if from > to {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        let first = from / STATS_PERIOD;
+        let last = to / STATS_PERIOD;
 
-        if amount <= 0 {
-            panic!("Amount must be positive");
+        let mut total_contributed = 0i128;
+        let mut total_withdrawn = 0i128;
+        if let Some(flows) = env
+            .storage()
+            .persistent()
+            .get::<_, Map<u64, (i128, i128)>>(&(Self::STORAGE_FLOWS, owner.clone()))
+        {
+            for (period, (contributed, withdrawn)) in flows.iter() {
+                if period >= first && period <= last {
+                    total_contributed = total_contributed.saturating_add(contributed);
+                    total_withdrawn = total_withdrawn.saturating_add(withdrawn);
+                }
+            }
         }
 
-        let current_time = env.ledger().timestamp();
-        if next_due <= current_time {
-            panic!("Next due date must be in the future");
+        let mut active_goals = 0u32;
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            if let Some(goal) = Self::load_goal(&env, id) {
+                if goal.current_amount < goal.target_amount {
+                    active_goals += 1;
+                }
+            }
         }
 
-        Self::extend_instance_ttl(&env);
+        let net_saved = total_contributed.saturating_sub(total_withdrawn);
+        Ok(SavingsStats {
+            total_contributed,
+            total_withdrawn,
+            net_saved,
+            active_goals,
+            average_monthly_savings: net_saved / (last - first + 1) as i128,
+        })
+    }
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
+    /// Everything `owner` has saved: balances of their open goals plus what
+    /// was paid out of them or released from closed goals, archived or not.
+    pub fn get_lifetime_savings(env: Env, owner: Address) -> i128 {
+        let mut total = Self::get_archived_savings(env.clone(), owner.clone()).total_released;
+        for id in Self::owner_closed_ids(&env, &owner).iter() {
+            if let Some(entry) = Self::load_closed_goal(&env, id) {
+                total = total.saturating_add(entry.released);
+            }
+        }
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            if let Some(goal) = Self::load_goal(&env, id) {
+                total = total.saturating_add(goal.current_amount);
+            }
+            let paid_out: Option<i128> = Self::load_entry(&env, &(Self::STORAGE_PAID_OUT, id));
+            total = total.saturating_add(paid_out.unwrap_or(0));
+        }
+        total
+    }
+
+    /// Set the penalty charged when `caller` withdraws from one of their
+    /// locked goals early, and the goal that receives it.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If penalty_bps exceeds 10 000
+    /// * `GoalNotFound` - If destination_goal does not exist
+    /// * `Unauthorized` - If caller does not own destination_goal
+    pub fn set_early_withdrawal_policy(
+        env: Env,
+        caller: Address,
+        penalty_bps: u32,
+        destination_goal: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if penalty_bps > MAX_PENALTY_BPS {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let destination =
+            Self::load_goal(&env, destination_goal).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if destination.owner != caller {
+            Self::append_audit(&env, symbol_short!("ew_pol"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        Self::save_entry(
+            &env,
+            &(Self::STORAGE_EARLY_POLICIES, caller.clone()),
+            &EarlyWithdrawalPolicy {
+                penalty_bps,
+                destination_goal,
+            },
+        );
+
+        Self::append_audit(&env, symbol_short!("ew_pol"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_early_withdrawal_policy(env: Env, owner: Address) -> Option<EarlyWithdrawalPolicy> {
+        Self::load_entry(&env, &(Self::STORAGE_EARLY_POLICIES, owner))
+    }
+
+    /// Withdraw from a goal that is still locked or time-locked, paying the
+    /// owner's early-withdrawal penalty into their penalty destination goal.
+    /// Once the goal is unlocked no penalty is charged.
+    ///
+    /// # Returns
+    /// `Ok(net_amount)` - The amount released after the penalty
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id or the penalty destination does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `PenaltyNotConfigured` - If the owner has no early-withdrawal policy
+    /// * `InsufficientBalance` - If amount > current_amount
+    /// * `Overflow` - If a balance would overflow i128
+    pub fn withdraw_early(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if let Err(e) = Self::check_not_frozen(&env, goal_id)
+            .and(Self::check_withdrawal_approval(&env, goal_id, amount))
+        {
+            Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+            return Err(e);
+        }
+        Self::settle_interest(&env, &mut goal)?;
+        if amount > Self::available_balance(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+            return Err(SavingsGoalsError::InsufficientBalance);
+        }
+
+        let hard_locked =
+            goal.lock_policy == LockPolicy::Hard && env.ledger().timestamp() < goal.target_date;
+        if hard_locked || Self::target_date_locked(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        if goal.lock_policy == LockPolicy::Guardian {
+            match &goal.guardian {
+                Some(guardian) => guardian.require_auth(),
+                None => {
+                    Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+                    return Err(SavingsGoalsError::GuardianNotSet);
+                }
+            }
+        }
+
+        let time_locked = goal
+            .unlock_date
+            .is_some_and(|unlock_date| env.ledger().timestamp() < unlock_date);
+        let (penalty, penalty_goal_id) = if goal.lock_policy == LockPolicy::Soft || time_locked {
+            let policy = match Self::get_early_withdrawal_policy(env.clone(), caller.clone()) {
+                Some(p) => p,
+                None => {
+                    Self::append_audit(&env, symbol_short!("early_wd"), &caller, false);
+                    return Err(SavingsGoalsError::PenaltyNotConfigured);
+                }
+            };
+            let penalty = amount
+                .checked_mul(policy.penalty_bps as i128)
+                .ok_or(SavingsGoalsError::Overflow)?
+                / MAX_PENALTY_BPS as i128;
+            (penalty, policy.destination_goal)
+        } else {
+            (0, goal_id)
+        };
+        let net_amount = amount - penalty;
+
+        goal.current_amount -= amount;
+        Self::save_goal(&env, &goal);
+        Self::record_flow(&env, &caller, 0, net_amount);
+        Self::record_withdrawal(&env, goal_id, amount);
+
+        if penalty > 0 {
+            let mut destination =
+                Self::load_goal(&env, penalty_goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+            Self::settle_interest(&env, &mut destination)?;
+            destination.current_amount = destination
+                .current_amount
+                .checked_add(penalty)
+                .ok_or(SavingsGoalsError::Overflow)?;
+            Self::save_goal(&env, &destination);
+        }
+
+        let remaining = Self::load_goal(&env, goal_id)
+            .map(|g| g.current_amount)
+            .unwrap_or(0);
+
+        env.events().publish(
+            (EARLY_WITHDRAWAL,),
+            EarlyWithdrawalEvent {
+                goal_id,
+                amount,
+                net_amount,
+                penalty,
+                penalty_goal_id,
+                remaining,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::append_audit(&env, symbol_short!("early_wd"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
+            (goal_id, caller, net_amount),
+        );
+
+        Ok(net_amount)
+    }
+
+    /// Set the annual interest rate, in basis points, paid on locked goals.
+    /// Restricted to the upgrade admin. The new rate applies from now on;
+    /// time already locked keeps earning at the rate in force then.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidAmount` - If rate_bps exceeds 10 000
+    pub fn set_interest_rate(
+        env: Env,
+        caller: Address,
+        rate_bps: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if rate_bps > MAX_INTEREST_RATE_BPS {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+        let now = env.ledger().timestamp();
+        let mut history = Self::rate_history(&env);
+        let index = Self::interest_index(&history, now);
+        history.push_back(RateChange {
+            at: now,
+            rate_bps,
+            index,
+        });
+        env.storage()
             .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+            .set(&Self::STORAGE_RATE_HISTORY, &history);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_INTEREST_RATE, &rate_bps);
+        Ok(())
+    }
 
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+    pub fn get_interest_rate(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Self::STORAGE_INTEREST_RATE)
+            .unwrap_or(0)
+    }
+
+    /// Set the external vault that `deploy_to_yield` sends idle funds to.
+    /// Restricted to the upgrade admin.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    pub fn set_yield_adapter(
+        env: Env,
+        caller: Address,
+        adapter: Address,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_YIELD_ADAPTER, &adapter);
+        Ok(())
+    }
+
+    pub fn get_yield_adapter(env: Env) -> Option<Address> {
+        env.storage().instance().get(&Self::STORAGE_YIELD_ADAPTER)
+    }
+
+    /// The goal's vault position: what is deployed and what it has earned.
+    pub fn get_yield_position(env: Env, goal_id: u32) -> Option<YieldPosition> {
+        Self::load_entry(&env, &(Self::STORAGE_YIELD_POSITIONS, goal_id))
+    }
+
+    /// Move part of a goal's balance into the yield vault so long-horizon
+    /// savings earn while they wait. The funds stay counted in the goal's
+    /// `current_amount` but cannot be withdrawn, transferred or paid out
+    /// until recalled. The goal must be denominated, and only tokens of its
+    /// asset the contract holds for the goal can be deployed.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `YieldAdapterNotSet` - If no adapter is configured
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `AssetMismatch` - If the goal has no asset
+    /// * `InsufficientBalance` - If amount exceeds the goal's undeployed
+    ///   balance or the tokens held for it
+    pub fn deploy_to_yield(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let adapter =
+            Self::get_yield_adapter(env.clone()).ok_or(SavingsGoalsError::YieldAdapterNotSet)?;
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("yld_dep"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let Some(token) = goal.asset.clone() else {
+            Self::append_audit(&env, symbol_short!("yld_dep"), &caller, false);
+            return Err(SavingsGoalsError::AssetMismatch);
+        };
+        if amount > Self::available_balance(&env, &goal)
+            || amount > Self::held_balance(&env, goal_id, &token)
+        {
+            Self::append_audit(&env, symbol_short!("yld_dep"), &caller, false);
+            return Err(SavingsGoalsError::InsufficientBalance);
+        }
+
+        let contract = env.current_contract_address();
+        TokenClient::new(&env, &token).transfer(&contract, &adapter, &amount);
+        YieldAdapterClient::new(&env, &adapter).deposit(&contract, &token, &amount);
+        Self::adjust_held(&env, goal_id, &token, -amount);
+        let mut position =
+            Self::get_yield_position(env.clone(), goal_id).unwrap_or(YieldPosition {
+                token,
+                principal: 0,
+                earned: 0,
+            });
+        position.principal += amount;
+        let deployed = position.principal;
+        Self::save_entry(&env, &(Self::STORAGE_YIELD_POSITIONS, goal_id), &position);
+
+        Self::append_audit(&env, symbol_short!("yld_dep"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("yld_dep")),
+            (goal_id, amount, deployed),
+        );
+        Ok(deployed)
+    }
+
+    /// Bring deployed funds back from the vault. Anything the vault returns
+    /// beyond `amount` is credited to the goal as yield; a shortfall is
+    /// written off the goal's balance. Either way the difference is added to
+    /// the position's `earned`, and the returned tokens are held for the
+    /// goal again.
+    ///
+    /// # Returns
+    /// `Ok(returned)` - What the vault sent back, including yield
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `YieldAdapterNotSet` - If no adapter is configured
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `InsufficientBalance` - If amount exceeds the goal's deployed funds
+    /// * `Overflow` - If the goal balance would overflow i128
+    pub fn recall_from_yield(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        caller.require_auth();
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let adapter =
+            Self::get_yield_adapter(env.clone()).ok_or(SavingsGoalsError::YieldAdapterNotSet)?;
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("yld_rec"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let mut position = match Self::get_yield_position(env.clone(), goal_id) {
+            Some(position) if amount <= position.principal => position,
+            _ => {
+                Self::append_audit(&env, symbol_short!("yld_rec"), &caller, false);
+                return Err(SavingsGoalsError::InsufficientBalance);
+            }
+        };
+
+        let returned = YieldAdapterClient::new(&env, &adapter).withdraw(
+            &env.current_contract_address(),
+            &position.token,
+            &amount,
+        );
+        Self::settle_interest(&env, &mut goal)?;
+        goal.current_amount = goal
+            .current_amount
+            .checked_add(returned - amount)
+            .ok_or(SavingsGoalsError::Overflow)?
+            .max(0);
+        Self::save_goal(&env, &goal);
+        Self::adjust_held(&env, goal_id, &position.token, returned);
+        position.principal -= amount;
+        position.earned = position.earned.saturating_add(returned - amount);
+        Self::save_entry(&env, &(Self::STORAGE_YIELD_POSITIONS, goal_id), &position);
+
+        Self::append_audit(&env, symbol_short!("yld_rec"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("yld_rec")),
+            (goal_id, amount, returned),
+        );
+        Ok(returned)
+    }
+
+    /// Credit simple interest at the current rate for the time the goal has
+    /// been locked since its last accrual. Anyone may call it; unlocked time
+    /// earns nothing.
+    ///
+    /// # Returns
+    /// `Ok(interest)` - The amount credited (0 if nothing was due)
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Overflow` - If the interest or new balance would overflow i128
+    pub fn accrue_interest(env: Env, goal_id: u32) -> Result<i128, SavingsGoalsError> {
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let interest = Self::settle_interest(&env, &mut goal)?;
+        Self::save_goal(&env, &goal);
+        Ok(interest)
+    }
+
+    /// Run `accrue_interest` over up to `MAX_BATCH_SIZE` goals.
+    ///
+    /// # Returns
+    /// `Ok(total)` - The interest credited across all goals
+    pub fn batch_accrue_interest(env: Env, goal_ids: Vec<u32>) -> Result<i128, SavingsGoalsError> {
+        if goal_ids.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut total: i128 = 0;
+        for goal_id in goal_ids.iter() {
+            let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+            let interest = Self::settle_interest(&env, &mut goal)?;
+            Self::save_goal(&env, &goal);
+            total = total
+                .checked_add(interest)
+                .ok_or(SavingsGoalsError::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    /// Interest credited to a goal, oldest first. Only the most recent
+    /// `MAX_ACCRUAL_HISTORY` entries are kept.
+    pub fn get_accrual_history(env: Env, goal_id: u32) -> Vec<InterestAccrual> {
+        Self::load_entry(&env, &(Self::STORAGE_ACCRUALS, goal_id)).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::LOCK);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+            panic!("Only the goal owner can lock this goal");
+        }
+
+        Self::settle_interest(&env, &mut goal).expect("Interest overflow");
+        if goal.lock_policy == LockPolicy::Unlocked {
+            goal.lock_policy = LockPolicy::Soft;
+        }
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalLocked),
+            (goal_id, caller),
+        );
+
+        true
+    }
+
+    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::UNLOCK);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            panic!("Only the goal owner can unlock this goal");
+        }
+
+        let hard_locked =
+            goal.lock_policy == LockPolicy::Hard && env.ledger().timestamp() < goal.target_date;
+        if hard_locked || Self::target_date_locked(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            panic!("Goal is locked until its target date");
+        }
+        if goal.lock_policy == LockPolicy::Guardian {
+            if let Some(guardian) = &goal.guardian {
+                guardian.require_auth();
+            }
+        }
+
+        Self::settle_interest(&env, &mut goal).expect("Interest overflow");
+        goal.lock_policy = LockPolicy::Unlocked;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalUnlocked),
+            (goal_id, caller),
+        );
+
+        true
+    }
+
+    /// Choose how the goal guards its balance. Leaving a `Hard` lock before
+    /// the target date is refused, and leaving a `Guardian` lock needs the
+    /// guardian's signature. `Guardian` needs a guardian to be set.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `GoalLocked` - If the goal is hard-locked until its target date
+    /// * `GuardianNotSet` - If `Guardian` is chosen without a guardian
+    pub fn set_lock_policy(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        policy: LockPolicy,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::LOCK);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("lock_pol"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if policy == goal.lock_policy {
+            return Ok(());
+        }
+        let hard_locked =
+            goal.lock_policy == LockPolicy::Hard && env.ledger().timestamp() < goal.target_date;
+        let unlocking = policy == LockPolicy::Unlocked && Self::target_date_locked(&env, &goal);
+        if hard_locked || unlocking {
+            Self::append_audit(&env, symbol_short!("lock_pol"), &caller, false);
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        match (policy, &goal.guardian) {
+            (LockPolicy::Guardian, None) => {
+                Self::append_audit(&env, symbol_short!("lock_pol"), &caller, false);
+                return Err(SavingsGoalsError::GuardianNotSet);
+            }
+            (_, Some(guardian)) if goal.lock_policy == LockPolicy::Guardian => {
+                guardian.require_auth();
+            }
+            _ => {}
+        }
+
+        Self::settle_interest(&env, &mut goal)?;
+        goal.lock_policy = policy;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("lock_pol"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("lock_pol")),
+            (goal_id, policy),
+        );
+        Ok(())
+    }
+
+    /// Assign, replace or remove the guardian who must co-sign
+    /// `emergency_unlock`. A goal with a guardian cannot be unlocked before
+    /// its target date unless complete. Once a guardian is set, changing it
+    /// needs the current guardian's signature as well.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_guardian(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        guardian: Option<Address>,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("guardian"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if let Some(current) = &goal.guardian {
+            current.require_auth();
+        }
+
+        goal.guardian = guardian.clone();
+        if guardian.is_none() && goal.lock_policy == LockPolicy::Guardian {
+            goal.lock_policy = LockPolicy::Soft;
+        }
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("guardian"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("guardian")),
+            (goal_id, caller, guardian),
+        );
+        Ok(())
+    }
+
+    /// Unlock a guarded goal before its target date in a genuine
+    /// emergency. Needs both the owner's and the guardian's signature and
+    /// also clears any time-lock.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the owner or the goal has no guardian
+    pub fn emergency_unlock(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let guardian = match (&goal.guardian, goal.owner == caller) {
+            (Some(guardian), true) => guardian.clone(),
+            _ => {
+                Self::append_audit(&env, symbol_short!("emrg_unl"), &caller, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+        };
+        guardian.require_auth();
+
+        Self::settle_interest(&env, &mut goal)?;
+        goal.lock_policy = LockPolicy::Unlocked;
+        goal.unlock_date = None;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("emrg_unl"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("emrg_unl")),
+            (goal_id, caller, guardian),
+        );
+        Ok(())
+    }
+
+    /// Freeze a goal the owner fears is compromised. Until `unfreeze_goal`,
+    /// the goal takes no contributions of any kind (including schedules
+    /// and allocations) and releases no funds through withdrawals,
+    /// transfers, closing, payouts or beneficiary claims. Other goals are
+    /// unaffected.
+    ///
+    /// # Errors
+    /// * `GoalNotFound`
+    /// * `Unauthorized` - `caller` is not the goal owner
+    pub fn freeze_goal(env: Env, caller: Address, goal_id: u32) -> Result<(), SavingsGoalsError> {
+        Self::set_goal_frozen(&env, &caller, goal_id, true)
+    }
+
+    /// Lift a freeze set by `freeze_goal`.
+    ///
+    /// # Errors
+    /// * `GoalNotFound`
+    /// * `Unauthorized` - `caller` is not the goal owner
+    pub fn unfreeze_goal(env: Env, caller: Address, goal_id: u32) -> Result<(), SavingsGoalsError> {
+        Self::set_goal_frozen(&env, &caller, goal_id, false)
+    }
+
+    pub fn is_goal_frozen(env: Env, goal_id: u32) -> bool {
+        Self::is_frozen(&env, goal_id)
+    }
+
+    fn set_goal_frozen(
+        env: &Env,
+        caller: &Address,
+        goal_id: u32,
+        frozen: bool,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(env);
+        let op = if frozen {
+            symbol_short!("freeze")
+        } else {
+            symbol_short!("unfreeze")
+        };
+
+        let goal = Self::load_goal(env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != *caller {
+            Self::append_audit(env, op, caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let key = (Self::STORAGE_FROZEN, goal_id);
+        if frozen {
+            Self::save_entry(env, &key, &env.ledger().timestamp());
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        Self::append_audit(env, op.clone(), caller, true);
+        env.events()
+            .publish((symbol_short!("savings"), op), (goal_id, caller.clone()));
+        Ok(())
+    }
+
+    /// Open a goal to contributions from other addresses, e.g. several
+    /// relatives abroad funding one education goal, or close it again.
+    /// Only the owner can withdraw from a shared goal.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_goal_shared(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        shared: bool,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("shared"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        goal.shared = shared;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("shared"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("shared")),
+            (goal_id, caller, shared),
+        );
+        Ok(())
+    }
+
+    /// Require approval from named `approvers` for large withdrawals from a
+    /// shared goal. Withdrawals, transfers out, closings and payouts that
+    /// would take more than `threshold` out of the goal within a rolling
+    /// 30-day window then fail with `ApprovalRequired` and go through
+    /// `request_withdrawal` instead. Once set, a policy can only be
+    /// tightened (lower threshold, more approvals, fewer approvers), so the
+    /// owner cannot quietly switch it off.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - negative threshold, zero approvals or zero expiry,
+    ///   an approver listed twice or who is the owner, more approvals than
+    ///   approvers, or a change that loosens the current policy
+    /// * `GoalNotFound`
+    /// * `Unauthorized` - `caller` is not the goal owner
+    /// * `GoalNotShared`
+    pub fn set_withdrawal_approvals(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        threshold: i128,
+        approvers: Vec<Address>,
+        required_approvals: u32,
+        expiry_days: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("appr_pol"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if !goal.shared {
+            Self::append_audit(&env, symbol_short!("appr_pol"), &caller, false);
+            return Err(SavingsGoalsError::GoalNotShared);
+        }
+
+        let mut policies: Map<u32, ApprovalPolicy> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_APPROVAL_POLICIES)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut distinct = Vec::new(&env);
+        for approver in approvers.iter() {
+            if approver == caller || distinct.contains(&approver) {
+                Self::append_audit(&env, symbol_short!("appr_pol"), &caller, false);
+                return Err(SavingsGoalsError::InvalidAmount);
+            }
+            distinct.push_back(approver);
+        }
+        let loosens = policies.get(goal_id).is_some_and(|current| {
+            threshold > current.threshold
+                || required_approvals < current.required_approvals
+                || approvers.iter().any(|a| !current.approvers.contains(&a))
+        });
+        if threshold < 0
+            || required_approvals == 0
+            || expiry_days == 0
+            || required_approvals > approvers.len()
+            || loosens
+        {
+            Self::append_audit(&env, symbol_short!("appr_pol"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        policies.set(
+            goal_id,
+            ApprovalPolicy {
+                threshold,
+                approvers,
+                required_approvals,
+                expiry: expiry_days as u64 * 86_400,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_APPROVAL_POLICIES, &policies);
+
+        Self::append_audit(&env, symbol_short!("appr_pol"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("appr_pol")),
+            (goal_id, threshold, required_approvals),
+        );
+        Ok(())
+    }
+
+    pub fn get_withdrawal_approvals(env: Env, goal_id: u32) -> Option<ApprovalPolicy> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, ApprovalPolicy>>(&Self::STORAGE_APPROVAL_POLICIES)
+            .and_then(|policies| policies.get(goal_id))
+    }
+
+    /// Open a request to withdraw `amount` from a goal whose approval policy
+    /// covers it. Returns the request ID that the policy's approvers approve.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - `amount` still fits within the policy threshold,
+    ///   or the goal has no approval policy
+    /// * `GoalNotFound`
+    /// * `Unauthorized` - `caller` is not the goal owner
+    /// * `InsufficientBalance`
+    pub fn request_withdrawal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<u32, SavingsGoalsError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("wd_req"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let needs_approval = Self::check_withdrawal_approval(&env, goal_id, amount).is_err();
+        let policy = match Self::get_withdrawal_approvals(env.clone(), goal_id) {
+            Some(policy) if needs_approval => policy,
+            _ => {
+                Self::append_audit(&env, symbol_short!("wd_req"), &caller, false);
+                return Err(SavingsGoalsError::InvalidAmount);
+            }
+        };
+        if amount > Self::available_balance(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("wd_req"), &caller, false);
+            return Err(SavingsGoalsError::InsufficientBalance);
+        }
+
+        let request_id: u32 = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_NEXT_REQUEST_ID)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_NEXT_REQUEST_ID, &(request_id + 1));
+
+        let now = env.ledger().timestamp();
+        let request = WithdrawalRequest {
+            id: request_id,
+            goal_id,
+            amount,
+            approvals: Vec::new(&env),
+            created_at: now,
+            expires_at: now + policy.expiry,
+        };
+        let key = (Self::STORAGE_WITHDRAWAL_REQUEST, request_id);
+        env.storage().persistent().set(&key, &request);
+        Self::extend_persistent_ttl(&env, &key);
+
+        // Drop expired requests from the goal's pending list as we go.
+        let mut pending = Self::pending_request_ids(&env);
+        let mut ids = Vec::new(&env);
+        for id in pending
+            .get(goal_id)
+            .unwrap_or_else(|| Vec::new(&env))
+            .iter()
+        {
+            match Self::load_withdrawal_request(&env, id) {
+                Some(r) if r.expires_at >= now => ids.push_back(id),
+                _ => env
+                    .storage()
+                    .persistent()
+                    .remove(&(Self::STORAGE_WITHDRAWAL_REQUEST, id)),
+            }
+        }
+        ids.push_back(request_id);
+        pending.set(goal_id, ids);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_PENDING_REQUESTS, &pending);
+
+        Self::append_audit(&env, symbol_short!("wd_req"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("wd_req")),
+            (request_id, goal_id, amount),
+        );
+        Ok(request_id)
+    }
+
+    /// Approve a pending withdrawal as one of the policy's approvers. The
+    /// approval that reaches the policy's `required_approvals` executes the
+    /// withdrawal; returns whether this call did so. Approved withdrawals
+    /// do not count against the threshold window.
+    ///
+    /// # Errors
+    /// * `RequestNotFound` - unknown or already executed request
+    /// * `RequestExpired`
+    /// * `Unauthorized` - `caller` is not one of the policy's approvers
+    /// * `AlreadyApproved`
+    /// * `GoalLocked`, `GoalFrozen`, `InsufficientBalance` - the withdrawal
+    ///   cannot execute right now; the approval is not recorded
+    pub fn approve_withdrawal(
+        env: Env,
+        caller: Address,
+        request_id: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        Self::extend_instance_ttl(&env);
+
+        let mut request = Self::load_withdrawal_request(&env, request_id)
+            .ok_or(SavingsGoalsError::RequestNotFound)?;
+        if env.ledger().timestamp() > request.expires_at {
+            return Err(SavingsGoalsError::RequestExpired);
+        }
+        let mut goal =
+            Self::load_goal(&env, request.goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let Some(policy) = Self::get_withdrawal_approvals(env.clone(), request.goal_id)
+            .filter(|policy| policy.approvers.contains(&caller))
+        else {
+            Self::append_audit(&env, symbol_short!("wd_appr"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        };
+        if request.approvals.contains(&caller) {
+            Self::append_audit(&env, symbol_short!("wd_appr"), &caller, false);
+            return Err(SavingsGoalsError::AlreadyApproved);
+        }
+        request.approvals.push_back(caller.clone());
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("wd_appr")),
+            (request_id, caller.clone()),
+        );
+
+        let key = (Self::STORAGE_WITHDRAWAL_REQUEST, request_id);
+        if request.approvals.len() < policy.required_approvals {
+            env.storage().persistent().set(&key, &request);
+            Self::extend_persistent_ttl(&env, &key);
+            Self::append_audit(&env, symbol_short!("wd_appr"), &caller, true);
+            return Ok(false);
+        }
+
+        Self::check_withdrawal_lock(&env, &goal)?;
+        Self::settle_interest(&env, &mut goal)?;
+        if request.amount > Self::available_balance(&env, &goal) {
+            return Err(SavingsGoalsError::InsufficientBalance);
+        }
+        goal.current_amount -= request.amount;
+        Self::save_goal(&env, &goal);
+        Self::record_flow(&env, &goal.owner, 0, request.amount);
+
+        env.storage().persistent().remove(&key);
+        let mut pending = Self::pending_request_ids(&env);
+        if let Some(ids) = pending.get(request.goal_id) {
+            let mut remaining = Vec::new(&env);
+            for id in ids.iter().filter(|id| *id != request_id) {
+                remaining.push_back(id);
+            }
+            pending.set(request.goal_id, remaining);
+            env.storage()
+                .instance()
+                .set(&Self::STORAGE_PENDING_REQUESTS, &pending);
+        }
+
+        Self::append_audit(&env, symbol_short!("wd_appr"), &caller, true);
+        env.events().publish(
+            (WITHDRAWAL_MADE,),
+            WithdrawalMadeEvent {
+                goal_id: request.goal_id,
+                amount: request.amount,
+                remaining: goal.current_amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
+            (request.goal_id, goal.owner, request.amount),
+        );
+        Ok(true)
+    }
+
+    /// Unexpired withdrawal requests still waiting for approvals on a goal,
+    /// oldest first.
+    pub fn get_pending_withdrawals(env: Env, goal_id: u32) -> Vec<WithdrawalRequest> {
+        let now = env.ledger().timestamp();
+        let mut result = Vec::new(&env);
+        for id in Self::pending_request_ids(&env)
+            .get(goal_id)
+            .unwrap_or_else(|| Vec::new(&env))
+            .iter()
+        {
+            if let Some(request) = Self::load_withdrawal_request(&env, id) {
+                if request.expires_at >= now {
+                    result.push_back(request);
+                }
+            }
+        }
+        result
+    }
+
+    fn load_withdrawal_request(env: &Env, request_id: u32) -> Option<WithdrawalRequest> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_WITHDRAWAL_REQUEST, request_id))
+    }
+
+    fn pending_request_ids(env: &Env) -> Map<u32, Vec<u32>> {
+        env.storage()
+            .instance()
+            .get(&Self::STORAGE_PENDING_REQUESTS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Limit how much may be contributed to one goal in any rolling 30-day
+    /// window. A cap of 0 removes it. Only contributions made while a cap is
+    /// set count towards it.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If cap < 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_goal_contribution_cap(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        cap: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if cap < 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("cap"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let mut caps: Map<u32, i128> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_GOAL_CAPS)
+            .unwrap_or_else(|| Map::new(&env));
+        if cap == 0 {
+            caps.remove(goal_id);
+        } else {
+            caps.set(goal_id, cap);
+        }
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_GOAL_CAPS, &caps);
+
+        Self::append_audit(&env, symbol_short!("cap"), &caller, true);
+        Ok(())
+    }
+
+    /// Limit how much may be contributed across all of the caller's goals
+    /// in any rolling 30-day window. A cap of 0 removes it.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If cap < 0
+    pub fn set_owner_contribution_cap(
+        env: Env,
+        caller: Address,
+        cap: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if cap < 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut caps: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_OWNER_CAPS)
+            .unwrap_or_else(|| Map::new(&env));
+        if cap == 0 {
+            caps.remove(caller.clone());
+        } else {
+            caps.set(caller.clone(), cap);
+        }
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_OWNER_CAPS, &caps);
+
+        Self::append_audit(&env, symbol_short!("cap"), &caller, true);
+        Ok(())
+    }
+
+    /// Goal cap and how much of it is left in the current window, or
+    /// `None` if the goal has no cap.
+    pub fn get_goal_contribution_cap(env: Env, goal_id: u32) -> Option<(i128, i128)> {
+        let cap = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, i128>>(&Self::STORAGE_GOAL_CAPS)
+            .and_then(|caps| caps.get(goal_id))?;
+        let used = Self::cap_window_total(
+            &env,
+            Self::load_entry(&env, &(Self::STORAGE_GOAL_CAP_LOG, goal_id)),
+        );
+        Some((cap, (cap - used).max(0)))
+    }
+
+    /// Owner cap and how much of it is left in the current window, or
+    /// `None` if the owner has no cap.
+    pub fn get_owner_contribution_cap(env: Env, owner: Address) -> Option<(i128, i128)> {
+        let cap = env
+            .storage()
+            .instance()
+            .get::<_, Map<Address, i128>>(&Self::STORAGE_OWNER_CAPS)
+            .and_then(|caps| caps.get(owner.clone()))?;
+        let used = Self::cap_window_total(
+            &env,
+            Self::load_entry(&env, &(Self::STORAGE_OWNER_CAP_LOG, owner)),
+        );
+        Some((cap, (cap - used).max(0)))
+    }
+
+    /// Commit `sponsor` to matching `match_bps` of every contribution to a
+    /// goal, up to `cap` in total. The match is pulled from the allowance
+    /// the sponsor has approved for this contract. Calling again replaces
+    /// the terms but keeps the matched total; a goal has one sponsor.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If match_bps is 0 or above 10 000, or cap ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If another sponsor already matches this goal
+    pub fn set_sponsor_match(
+        env: Env,
+        sponsor: Address,
+        goal_id: u32,
+        token: Address,
+        match_bps: u32,
+        cap: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        sponsor.require_auth();
+        if match_bps == 0 || match_bps > MAX_MATCH_BPS || cap <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        Self::check_asset(&goal, &token)?;
+
+        let mut matches: Map<u32, SponsorMatch> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_SPONSOR_MATCHES)
+            .unwrap_or_else(|| Map::new(&env));
+        let matched = match matches.get(goal_id) {
+            Some(existing) if existing.sponsor != sponsor => {
+                Self::append_audit(&env, symbol_short!("sponsor"), &sponsor, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+            Some(existing) => existing.matched,
+            None => 0,
+        };
+        matches.set(
+            goal_id,
+            SponsorMatch {
+                sponsor: sponsor.clone(),
+                token,
+                match_bps,
+                cap,
+                matched,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_SPONSOR_MATCHES, &matches);
+
+        Self::append_audit(&env, symbol_short!("sponsor"), &sponsor, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("sponsor")),
+            (goal_id, sponsor, match_bps, cap),
+        );
+        Ok(())
+    }
+
+    /// Withdraw a sponsor's matching commitment from a goal.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If `sponsor` is not the goal's sponsor
+    pub fn remove_sponsor_match(
+        env: Env,
+        sponsor: Address,
+        goal_id: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        sponsor.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut matches: Map<u32, SponsorMatch> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_SPONSOR_MATCHES)
+            .unwrap_or_else(|| Map::new(&env));
+        match matches.get(goal_id) {
+            Some(existing) if existing.sponsor == sponsor => {}
+            _ => {
+                Self::append_audit(&env, symbol_short!("sponsor"), &sponsor, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+        }
+        matches.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_SPONSOR_MATCHES, &matches);
+
+        Self::append_audit(&env, symbol_short!("sponsor"), &sponsor, true);
+        Ok(())
+    }
+
+    /// The goal's matching commitment, including the total matched so far.
+    pub fn get_sponsor_match(env: Env, goal_id: u32) -> Option<SponsorMatch> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, SponsorMatch>>(&Self::STORAGE_SPONSOR_MATCHES)
+            .and_then(|matches| matches.get(goal_id))
+    }
+
+    /// Send the goal's balance to `recipient` in `token` as soon as the goal
+    /// reaches its target, instead of leaving the funds parked. The payout
+    /// runs on the contribution that completes the goal, or on the next one
+    /// if the goal is already complete. Only the part of the balance the
+    /// contract holds in `token` for this goal (see `get_held_balance`) is
+    /// sent. Calling again replaces the target.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_payout_target(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        token: Address,
+        recipient: Address,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("payout"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if let Err(e) = Self::check_asset(&goal, &token) {
+            Self::append_audit(&env, symbol_short!("payout"), &caller, false);
+            return Err(e);
+        }
+
+        Self::save_entry(
+            &env,
+            &(Self::STORAGE_PAYOUT_TARGETS, goal_id),
+            &PayoutTarget {
+                recipient: recipient.clone(),
+                token,
+            },
+        );
+
+        Self::append_audit(&env, symbol_short!("payout"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("payout")),
+            (goal_id, recipient),
+        );
+        Ok(())
+    }
+
+    /// Stop paying the goal out on completion.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn clear_payout_target(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("payout"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&(Self::STORAGE_PAYOUT_TARGETS, goal_id));
+
+        Self::append_audit(&env, symbol_short!("payout"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_payout_target(env: Env, goal_id: u32) -> Option<PayoutTarget> {
+        Self::load_entry(&env, &(Self::STORAGE_PAYOUT_TARGETS, goal_id))
+    }
+
+    /// Tokens of `token` the contract holds for the goal. Only these can be
+    /// paid out; contributions recorded without a token transfer are not
+    /// included.
+    pub fn get_held_balance(env: Env, goal_id: u32, token: Address) -> i128 {
+        Self::held_balance(&env, goal_id, &token)
+    }
+
+    /// Name who may claim the goal's balance if the owner shows no
+    /// activity for `inactivity_days`, protecting the savings if the
+    /// owner loses access. Any successful owner-signed operation, or
+    /// `check_in`, counts as activity. Calling again replaces the
+    /// designation.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If inactivity_days is 0 or beneficiary is the owner
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_beneficiary(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        beneficiary: Address,
+        inactivity_days: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if inactivity_days == 0 || beneficiary == caller {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let mut beneficiaries: Map<u32, Beneficiary> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_BENEFICIARIES)
+            .unwrap_or_else(|| Map::new(&env));
+        beneficiaries.set(
+            goal_id,
+            Beneficiary {
+                beneficiary: beneficiary.clone(),
+                inactivity_period: inactivity_days as u64 * 86400,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_BENEFICIARIES, &beneficiaries);
+
+        Self::append_audit(&env, symbol_short!("benefic"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("benefic")),
+            (goal_id, beneficiary, inactivity_days),
+        );
+        Ok(())
+    }
+
+    /// Remove the goal's beneficiary.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn remove_beneficiary(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let mut beneficiaries: Map<u32, Beneficiary> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_BENEFICIARIES)
+            .unwrap_or_else(|| Map::new(&env));
+        beneficiaries.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_BENEFICIARIES, &beneficiaries);
+
+        Self::append_audit(&env, symbol_short!("benefic"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_beneficiary(env: Env, goal_id: u32) -> Option<Beneficiary> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Beneficiary>>(&Self::STORAGE_BENEFICIARIES)
+            .and_then(|beneficiaries| beneficiaries.get(goal_id))
+    }
+
+    /// Record activity for `owner` without changing any goal, resetting the
+    /// inactivity clock on their beneficiary designations.
+    pub fn check_in(env: Env, owner: Address) {
+        owner.require_auth();
+        Self::record_activity(&env, &owner);
+    }
+
+    /// Ledger time of the last activity recorded for `owner`.
+    pub fn get_last_active(env: Env, owner: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_LAST_ACTIVE, owner))
+    }
+
+    /// Claim the goal's balance as its beneficiary once the owner has been
+    /// inactive for the designated period. Locks do not apply, but funds
+    /// deployed to yield stay with the goal. The goal stays with the owner.
+    ///
+    /// Tokens the contract holds for the goal are transferred to the
+    /// beneficiary. Balance recorded without a token transfer is released
+    /// off the goal as with `withdraw_from_goal`.
+    ///
+    /// # Returns
+    /// `Ok(claimed)` - The balance released to the beneficiary
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal's beneficiary
+    /// * `OwnerStillActive` - If the inactivity period has not yet passed
+    pub fn claim_as_beneficiary(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<i128, SavingsGoalsError> {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let designation = match Self::get_beneficiary(env.clone(), goal_id) {
+            Some(d) if d.beneficiary == caller => d,
+            _ => {
+                Self::append_audit(&env, symbol_short!("ben_claim"), &caller, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+        };
+
+        let last_active =
+            Self::get_last_active(env.clone(), goal.owner.clone()).unwrap_or(goal.created_at);
+        if env.ledger().timestamp() < last_active.saturating_add(designation.inactivity_period) {
+            Self::append_audit(&env, symbol_short!("ben_claim"), &caller, false);
+            return Err(SavingsGoalsError::OwnerStillActive);
+        }
+        if let Err(e) = Self::check_not_frozen(&env, goal_id) {
+            Self::append_audit(&env, symbol_short!("ben_claim"), &caller, false);
+            return Err(e);
+        }
+
+        Self::settle_interest(&env, &mut goal)?;
+        let claimed = Self::available_balance(&env, &goal);
+        goal.current_amount -= claimed;
+        Self::save_goal(&env, &goal);
+        Self::record_flow(&env, &goal.owner, 0, claimed);
+        Self::release_held(&env, goal_id, &caller, claimed);
+
+        Self::append_audit(&env, symbol_short!("ben_claim"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("ben_claim")),
+            (goal_id, caller, claimed),
+        );
+        Ok(claimed)
+    }
+
+    /// Total `contributor` has added to a goal. Withdrawals by the owner do
+    /// not reduce it.
+    pub fn get_contributions_by(env: Env, goal_id: u32, contributor: Address) -> i128 {
+        Self::get_goal_contributions(&env, goal_id)
+            .get(contributor)
+            .map(|c| c.total)
+            .unwrap_or(0)
+    }
+
+    /// Everyone who has contributed to a goal, largest total first, with
+    /// the time of their latest contribution.
+    pub fn get_contributors(env: Env, goal_id: u32) -> Vec<Contribution> {
+        let mut sorted: Vec<Contribution> = Vec::new(&env);
+        for (_, contribution) in Self::get_goal_contributions(&env, goal_id).iter() {
+            let mut pos = sorted.len();
+            for (i, other) in sorted.iter().enumerate() {
+                if contribution.total > other.total {
+                    pos = i as u32;
+                    break;
+                }
+            }
+            sorted.insert(pos, contribution);
+        }
+        sorted
+    }
+
+    /// Every contribution to the goal, oldest first, with the balance each
+    /// one produced. Sponsor matches appear as the sponsor's contributions.
+    pub fn get_contribution_history(
+        env: Env,
+        goal_id: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ContributionRecord> {
+        let limit = Self::clamp_limit(limit);
+        let end = Self::contribution_history_len(&env, goal_id).min(offset.saturating_add(limit));
+        let mut records = Vec::new(&env);
+        for index in offset..end {
+            let key = (Self::STORAGE_CONTRIBUTION_LOG, goal_id, index);
+            if let Some(record) = env.storage().persistent().get(&key) {
+                Self::extend_persistent_ttl(&env, &key);
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
+        Self::load_goal(&env, goal_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // PAGINATED LIST QUERIES
+    // -----------------------------------------------------------------------
+
+    /// Get a page of savings goals for `owner`.
+    ///
+    /// # Arguments
+    /// * `owner`  – whose goals to return
+    /// * `cursor` – start after this goal ID (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `GoalPage { items, next_cursor, count }`.
+    /// `next_cursor == 0` means no more pages.
+    pub fn get_goals(env: Env, owner: Address, cursor: u32, limit: u32) -> GoalPage {
+        let limit = Self::clamp_limit(limit);
+        let mut result = Vec::new(&env);
+        let mut next_cursor: u32 = 0;
+        let mut collected: u32 = 0;
+
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            if id <= cursor {
+                continue;
+            }
+            let Some(goal) = Self::load_goal(&env, id) else {
+                continue;
+            };
+            if collected < limit {
+                result.push_back(goal);
+                collected += 1;
+                next_cursor = id; // track last returned ID
+            } else {
+                break;
+            }
+        }
+
+        // If we didn't fill the page, there are no more items
+        if collected < limit {
+            next_cursor = 0;
+        }
+
+        GoalPage {
+            items: result,
+            next_cursor,
+            count: collected,
+        }
+    }
+
+    /// Page through `owner`'s goals using the owner index, so only their
+    /// own goals are read.
+    pub fn get_goals_page(env: Env, owner: Address, offset: u32, limit: u32) -> GoalListPage {
+        Self::owner_goals_page(&env, &owner, offset, limit, false)
+    }
+
+    /// Like `get_goals_page`, but only goals that have not reached their
+    /// target.
+    pub fn get_active_goals_page(
+        env: Env,
+        owner: Address,
+        offset: u32,
+        limit: u32,
+    ) -> GoalListPage {
+        Self::owner_goals_page(&env, &owner, offset, limit, true)
+    }
+
+    /// Backward-compatible: returns ALL goals for owner in one Vec.
+    /// Prefer the paginated `get_goals` for production use.
+    pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
+        let mut result = Vec::new(&env);
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            if let Some(goal) = Self::load_goal(&env, id) {
+                result.push_back(goal);
+            }
+        }
+        result
+    }
+
+    /// The owner's goal balances summed per asset, in order of first
+    /// appearance among their goals.
+    pub fn get_totals_by_asset(env: Env, owner: Address) -> Vec<AssetTotal> {
+        let mut totals: Vec<AssetTotal> = Vec::new(&env);
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            let Some(goal) = Self::load_goal(&env, id) else {
+                continue;
+            };
+            let index = totals.iter().position(|t| t.asset == goal.asset);
+            match index {
+                Some(i) => {
+                    let mut entry = totals.get(i as u32).unwrap();
+                    entry.total = entry.total.saturating_add(goal.current_amount);
+                    entry.goal_count += 1;
+                    totals.set(i as u32, entry);
+                }
+                None => totals.push_back(AssetTotal {
+                    asset: goal.asset,
+                    total: goal.current_amount,
+                    goal_count: 1,
+                }),
+            }
+        }
+        totals
+    }
+
+    /// Project when a goal will be reached from its contribution history,
+    /// so the app can show "on track / behind". The pace is the total
+    /// contributed (interest and transfers excluded) averaged per 30 days
+    /// since creation; a goal younger than 30 days counts as one period.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    pub fn get_goal_projection(
+        env: Env,
+        goal_id: u32,
+    ) -> Result<GoalProjection, SavingsGoalsError> {
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let now = env.ledger().timestamp();
+
+        let percent_complete = if goal.current_amount >= goal.target_amount {
+            100
+        } else {
+            (goal.current_amount.max(0) * 100 / goal.target_amount) as u32
+        };
+
+        let mut contributed: i128 = 0;
+        for (_, contribution) in Self::get_goal_contributions(&env, goal_id).iter() {
+            contributed = contributed.saturating_add(contribution.total);
+        }
+        let elapsed = now.saturating_sub(goal.created_at).max(PROJECTION_PERIOD);
+        let avg_per_30_days =
+            contributed.saturating_mul(PROJECTION_PERIOD as i128) / elapsed as i128;
+
+        let remaining = goal.target_amount - goal.current_amount;
+        let projected_completion = if remaining <= 0 {
+            now
+        } else if avg_per_30_days <= 0 {
+            0
+        } else {
+            let periods = (remaining + avg_per_30_days - 1) / avg_per_30_days;
+            let secs = u64::try_from(periods)
+                .unwrap_or(u64::MAX)
+                .saturating_mul(PROJECTION_PERIOD);
+            now.saturating_add(secs)
+        };
+        let on_track = remaining <= 0
+            || (projected_completion != 0 && projected_completion <= goal.target_date);
+
+        Ok(GoalProjection {
+            goal_id,
+            percent_complete,
+            avg_per_30_days,
+            projected_completion,
+            on_track,
+        })
+    }
+
+    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
+        if let Some(goal) = Self::load_goal(&env, goal_id) {
+            goal.current_amount >= goal.target_amount
+        } else {
+            false
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Snapshot, audit, schedule
+    // -----------------------------------------------------------------------
+
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces
+            .as_ref()
+            .and_then(|m: &Map<Address, u64>| m.get(address))
+            .unwrap_or(0)
+    }
+
+    pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
+        caller.require_auth();
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut list = Vec::new(&env);
+        for i in 1..=next_id {
+            if let Some(g) = Self::load_goal(&env, i) {
+                list.push_back(g);
+            }
+        }
+        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
+        GoalsExportSnapshot {
+            version: SNAPSHOT_VERSION,
+            checksum,
+            next_id,
+            goals: list,
+        }
+    }
+
+    pub fn import_snapshot(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        snapshot: GoalsExportSnapshot,
+    ) -> bool {
+        caller.require_auth();
+        Self::require_nonce(&env, &caller, nonce);
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            panic!("Unsupported snapshot version");
+        }
+        let expected =
+            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
+        if snapshot.checksum != expected {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            panic!("Snapshot checksum mismatch");
+        }
+
+        Self::extend_instance_ttl(&env);
+        let current_next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        for id in 1..=current_next_id {
+            if let Some(existing) = Self::load_goal(&env, id) {
+                Self::delete_goal(&env, &existing);
+            }
+        }
+        for g in snapshot.goals.iter() {
+            Self::save_goal(&env, &g);
+            Self::append_owner_goal_id(&env, &g.owner, g.id);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
+
+        Self::increment_nonce(&env, &caller);
+        Self::append_audit(&env, symbol_short!("import"), &caller, true);
+        true
+    }
+
+    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
+        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
+        let log = log.unwrap_or_else(|| Vec::new(&env));
+        let len = log.len();
+        let cap = MAX_AUDIT_ENTRIES.min(limit);
+        let mut out = Vec::new(&env);
+        if from_index >= len {
+            return out;
+        }
+        let end = (from_index + cap).min(len);
+        for i in from_index..end {
+            if let Some(entry) = log.get(i) {
+                out.push_back(entry);
+            }
+        }
+        out
+    }
+
+    fn require_nonce(env: &Env, address: &Address, expected: u64) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        if expected != current {
+            panic!("Invalid nonce: expected {}, got {}", current, expected);
+        }
+    }
+
+    fn increment_nonce(env: &Env, address: &Address) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        let next = current.checked_add(1).expect("nonce overflow");
+        let mut nonces: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NONCES"))
+            .unwrap_or_else(|| Map::new(env));
+        nonces.set(address.clone(), next);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+    }
+
+    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
+        let mut c = version as u64 + next_id as u64;
+        for i in 0..goals.len() {
+            if let Some(g) = goals.get(i) {
+                c = c
+                    .wrapping_add(g.id as u64)
+                    .wrapping_add(g.target_amount as u64)
+                    .wrapping_add(g.current_amount as u64);
+            }
+        }
+        c.wrapping_mul(31)
+    }
+
+    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
+        let timestamp = env.ledger().timestamp();
+        let mut log: Vec<AuditEntry> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("AUDIT"))
+            .unwrap_or_else(|| Vec::new(env));
+        if log.len() >= MAX_AUDIT_ENTRIES {
+            let mut new_log = Vec::new(env);
+            for i in 1..log.len() {
+                if let Some(entry) = log.get(i) {
+                    new_log.push_back(entry);
+                }
+            }
+            log = new_log;
+        }
+        log.push_back(AuditEntry {
+            operation,
+            caller: caller.clone(),
+            timestamp,
+            success,
+        });
+        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+        if success {
+            Self::record_activity(env, caller);
+        }
+    }
+
+    /// Stamp `address` as active now. Called for every successful signed
+    /// operation; `claim_as_beneficiary` measures owner inactivity from it.
+    fn record_activity(env: &Env, address: &Address) {
+        let key = (Self::STORAGE_LAST_ACTIVE, address.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &env.ledger().timestamp());
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    // -----------------------------------------------------------------------
+    // Goal storage
+    //
+    // Each goal lives in its own persistent entry keyed by ID, so a
+    // contribution only rewrites the goal it touches. Every owner has an
+    // ascending ID index for owner queries. Scans across all owners walk
+    // the ID range up to `NEXT_ID`. Side tables (contributions, interest
+    // history, payout targets, schedules, ...) are keyed the same way, by
+    // goal ID or by owner.
+    // -----------------------------------------------------------------------
+
+    fn load_goal(env: &Env, goal_id: u32) -> Option<SavingsGoal> {
+        let key = (Self::STORAGE_GOAL, goal_id);
+        let goal: Option<SavingsGoal> = env.storage().persistent().get(&key);
+        if goal.is_some() {
+            Self::extend_persistent_ttl(env, &key);
+        }
+        goal
+    }
+
+    fn save_goal(env: &Env, goal: &SavingsGoal) {
+        let key = (Self::STORAGE_GOAL, goal.id);
+        env.storage().persistent().set(&key, goal);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Remove `goal`'s entry and drop its ID from the owner's index.
+    fn delete_goal(env: &Env, goal: &SavingsGoal) {
+        env.storage()
+            .persistent()
+            .remove(&(Self::STORAGE_GOAL, goal.id));
+        Self::remove_owner_goal_id(env, &goal.owner, goal.id);
+    }
+
+    fn owner_goal_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        let key = (Self::STORAGE_OWNER_GOAL_IDS, owner.clone());
+        let ids: Option<Vec<u32>> = env.storage().persistent().get(&key);
+        match ids {
+            Some(ids) => {
+                Self::extend_persistent_ttl(env, &key);
+                ids
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        let mut ids = Self::owner_goal_ids(env, owner);
+        // New IDs always sort last; migrated ones may land anywhere.
+        let Err(index) = ids.binary_search(goal_id) else {
+            return;
+        };
+        ids.insert(index, goal_id);
+        let key = (Self::STORAGE_OWNER_GOAL_IDS, owner.clone());
+        env.storage().persistent().set(&key, &ids);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn remove_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        let key = (Self::STORAGE_OWNER_GOAL_IDS, owner.clone());
+        let mut ids = Self::owner_goal_ids(env, owner);
+        let Some(index) = ids.first_index_of(goal_id) else {
+            return;
+        };
+        ids.remove(index);
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &ids);
+        }
+    }
+
+    fn extend_persistent_ttl<K>(env: &Env, key: &K)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        env.storage().persistent().extend_ttl(
+            key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+    }
+
+    /// Read a per-goal or per-owner side-table entry, extending its TTL.
+    fn load_entry<K, V>(env: &Env, key: &K) -> Option<V>
+    where
+        K: IntoVal<Env, Val>,
+        V: TryFromVal<Env, Val>,
+    {
+        let value: Option<V> = env.storage().persistent().get(key);
+        if value.is_some() {
+            Self::extend_persistent_ttl(env, key);
+        }
+        value
+    }
+
+    fn save_entry<K, V>(env: &Env, key: &K, value: &V)
+    where
+        K: IntoVal<Env, Val>,
+        V: IntoVal<Env, Val>,
+    {
+        env.storage().persistent().set(key, value);
+        Self::extend_persistent_ttl(env, key);
+    }
+
+    /// Take up to `budget` entries out of the legacy instance map at
+    /// `table`, dropping the map once it is empty. Entries left behind are
+    /// added to `pending`.
+    fn drain_legacy<K, V>(
+        env: &Env,
+        table: &Symbol,
+        budget: &mut u32,
+        pending: &mut u32,
+    ) -> Vec<(K, V)>
+    where
+        K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+        V: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+        Val: TryFromVal<Env, K> + TryFromVal<Env, V>,
+    {
+        let mut drained = Vec::new(env);
+        let Some(mut legacy) = env.storage().instance().get::<_, Map<K, V>>(table) else {
+            return drained;
+        };
+        for (key, value) in legacy.clone().iter().take(*budget as usize) {
+            legacy.remove(key.clone());
+            drained.push_back((key, value));
+        }
+        *budget -= drained.len();
+        *pending += legacy.len();
+        if legacy.is_empty() {
+            env.storage().instance().remove(table);
+        } else {
+            env.storage().instance().set(table, &legacy);
+        }
+        drained
+    }
+
+    // Closed goals live under their own keys, with an index per owner and a
+    // queue in closing order for `archive_completed_goals`.
+
+    fn store_closed_goal(env: &Env, entry: &ClosedGoal) {
+        let goal_id = entry.goal.id;
+        Self::save_entry(env, &(Self::STORAGE_CLOSED, goal_id), entry);
+
+        let key = (Self::STORAGE_OWNER_CLOSED_IDS, entry.goal.owner.clone());
+        let mut ids = Self::owner_closed_ids(env, &entry.goal.owner);
+        ids.push_back(goal_id);
+        Self::save_entry(env, &key, &ids);
+
+        let mut queue = Self::closed_queue(env);
+        let item = (entry.closed_at, goal_id);
+        let index = match queue.binary_search(item) {
+            Ok(index) | Err(index) => index,
+        };
+        queue.insert(index, item);
+        Self::save_closed_queue(env, &queue);
+    }
+
+    fn load_closed_goal(env: &Env, goal_id: u32) -> Option<ClosedGoal> {
+        Self::load_entry(env, &(Self::STORAGE_CLOSED, goal_id))
+    }
+
+    /// IDs of `owner`'s closed, not yet archived goals in closing order.
+    fn owner_closed_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        Self::load_entry(env, &(Self::STORAGE_OWNER_CLOSED_IDS, owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn remove_owner_closed_id(env: &Env, owner: &Address, goal_id: u32) {
+        let key = (Self::STORAGE_OWNER_CLOSED_IDS, owner.clone());
+        let mut ids = Self::owner_closed_ids(env, owner);
+        let Some(index) = ids.first_index_of(goal_id) else {
+            return;
+        };
+        ids.remove(index);
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &ids);
+        }
+    }
+
+    fn closed_queue(env: &Env) -> Vec<(u64, u32)> {
+        Self::load_entry(env, &Self::STORAGE_CLOSED_QUEUE).unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn save_closed_queue(env: &Env, queue: &Vec<(u64, u32)>) {
+        if queue.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&Self::STORAGE_CLOSED_QUEUE);
+        } else {
+            Self::save_entry(env, &Self::STORAGE_CLOSED_QUEUE, queue);
+        }
+    }
+
+    /// Add one archived goal to `owner`'s summary in `summaries`, starting
+    /// from the stored summary.
+    fn fold_archived(
+        env: &Env,
+        summaries: &mut Map<Address, (ArchivedSavings, u32)>,
+        owner: &Address,
+        released: i128,
+        closed_at: u64,
+    ) {
+        let (mut summary, archived) = summaries
+            .get(owner.clone())
+            .unwrap_or_else(|| (Self::get_archived_savings(env.clone(), owner.clone()), 0));
+        summary.goal_count += 1;
+        summary.total_released = summary.total_released.saturating_add(released);
+        summary.last_closed_at = closed_at;
+        summaries.set(owner.clone(), (summary, archived + 1));
+    }
+
+    // Payouts are totalled per goal, and a goal its payout empties is queued
+    // for `archive_completed_goals`.
+
+    fn take_paid_out(env: &Env, goal_id: u32) -> i128 {
+        let key = (Self::STORAGE_PAID_OUT, goal_id);
+        let paid_out: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().remove(&key);
+        paid_out
+    }
+
+    fn paid_queue(env: &Env) -> Vec<(u64, u32)> {
+        Self::load_entry(env, &Self::STORAGE_PAID_QUEUE).unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn save_paid_queue(env: &Env, queue: &Vec<(u64, u32)>) {
+        if queue.is_empty() {
+            env.storage().persistent().remove(&Self::STORAGE_PAID_QUEUE);
+        } else {
+            Self::save_entry(env, &Self::STORAGE_PAID_QUEUE, queue);
+        }
+    }
+
+    // Contribution schedules live under their goal's ID, with an ascending
+    // ID index for `process_due_contributions`.
+
+    fn scheduled_goal_ids(env: &Env) -> Vec<u32> {
+        Self::load_entry(env, &Self::STORAGE_SCHEDULED_GOALS).unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn save_schedule(env: &Env, schedule: &ContributionSchedule) {
+        let goal_id = schedule.goal_id;
+        Self::save_entry(
+            env,
+            &(Self::STORAGE_CONTRIBUTION_SCHEDULES, goal_id),
+            schedule,
+        );
+        let mut ids = Self::scheduled_goal_ids(env);
+        if let Err(index) = ids.binary_search(goal_id) {
+            ids.insert(index, goal_id);
+            Self::save_entry(env, &Self::STORAGE_SCHEDULED_GOALS, &ids);
+        }
+    }
+
+    fn remove_schedule(env: &Env, goal_id: u32) {
+        env.storage()
+            .persistent()
+            .remove(&(Self::STORAGE_CONTRIBUTION_SCHEDULES, goal_id));
+        let mut ids = Self::scheduled_goal_ids(env);
+        if let Ok(index) = ids.binary_search(goal_id) {
+            ids.remove(index);
+            Self::save_entry(env, &Self::STORAGE_SCHEDULED_GOALS, &ids);
+        }
+    }
+
+    /// Add `amount` to `goal` on behalf of `contributor`: record the
+    /// contribution and emit the same events as `add_to_goal`. The caller
+    /// is responsible for writing the goal back to storage.
+    fn credit_goal(
+        env: &Env,
+        goal: &mut SavingsGoal,
+        contributor: &Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::check_not_frozen(env, goal.id)?;
+        Self::apply_contribution_cap(env, goal, amount)?;
+        Self::settle_interest(env, goal)?;
+        let previously_completed = goal.current_amount >= goal.target_amount;
+        goal.current_amount = goal
+            .current_amount
+            .checked_add(amount)
+            .ok_or(SavingsGoalsError::Overflow)?;
+        Self::record_contribution(env, goal, contributor, amount);
+        Self::apply_sponsor_match(env, goal, amount);
+        let new_total = goal.current_amount;
+
+        env.events().publish(
+            (FUNDS_ADDED,),
+            FundsAddedEvent {
+                goal_id: goal.id,
+                amount,
+                new_total,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsAdded),
+            (goal.id, contributor.clone(), amount),
+        );
+        if new_total >= goal.target_amount && !previously_completed {
+            env.events().publish(
+                (GOAL_COMPLETED,),
+                GoalCompletedEvent {
+                    goal_id: goal.id,
+                    name: goal.name.clone(),
+                    final_amount: new_total,
+                    timestamp: env.ledger().timestamp(),
+                },
+            );
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                (goal.id, goal.owner.clone()),
+            );
+        }
+        Self::pay_out_if_complete(env, goal);
+        Ok(new_total)
+    }
+
+    /// If `goal` has reached its target and has a payout target, transfer
+    /// the balance there and write the goal back. Only tokens the contract
+    /// holds for the goal in the target's token are sent; the rest of the
+    /// balance stays on the goal. A payout the goal's approval policy does
+    /// not allow is held back with `pay_held`, and a failed transfer leaves
+    /// the funds on the goal and emits `pay_fail`. Returns the amount paid out.
+    fn pay_out_if_complete(env: &Env, goal: &mut SavingsGoal) -> i128 {
+        if goal.current_amount <= 0
+            || goal.current_amount < goal.target_amount
+            || Self::deployed_amount(env, goal.id) > 0
+            || Self::is_frozen(env, goal.id)
+        {
+            return 0;
+        }
+        let Some(target) =
+            Self::load_entry::<_, PayoutTarget>(env, &(Self::STORAGE_PAYOUT_TARGETS, goal.id))
+        else {
+            return 0;
+        };
+
+        let amount = goal
+            .current_amount
+            .min(Self::held_balance(env, goal.id, &target.token));
+        if amount <= 0 {
+            return 0;
+        }
+        if Self::check_withdrawal_approval(env, goal.id, amount).is_err() {
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("pay_held")),
+                (goal.id, target.recipient, amount),
+            );
+            return 0;
+        }
+        let sent = TokenClient::new(env, &target.token).try_transfer(
+            &env.current_contract_address(),
+            &target.recipient,
+            &amount,
+        );
+        if !matches!(sent, Ok(Ok(()))) {
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("pay_fail")),
+                (goal.id, target.recipient, amount),
+            );
+            return 0;
+        }
+
+        goal.current_amount -= amount;
+        Self::adjust_held(env, goal.id, &target.token, -amount);
+        Self::save_goal(env, goal);
+        Self::record_flow(env, &goal.owner, 0, amount);
+        Self::record_withdrawal(env, goal.id, amount);
+
+        let key = (Self::STORAGE_PAID_OUT, goal.id);
+        let paid_out: i128 = Self::load_entry(env, &key).unwrap_or(0);
+        Self::save_entry(env, &key, &paid_out.saturating_add(amount));
+        if goal.current_amount == 0 {
+            // Payouts happen in ledger order, so the queue stays sorted.
+            let mut queue = Self::paid_queue(env);
+            queue.push_back((env.ledger().timestamp(), goal.id));
+            Self::save_paid_queue(env, &queue);
+        }
+        env.events().publish(
+            (GOAL_PAID_OUT,),
+            GoalPaidOutEvent {
+                goal_id: goal.id,
+                recipient: target.recipient,
+                token: target.token,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        amount
+    }
+
+    /// Sum of the entries still inside the rolling window.
+    fn cap_window_total(env: &Env, entries: Option<Vec<CapEntry>>) -> i128 {
+        let now = env.ledger().timestamp();
+        let mut total: i128 = 0;
+        for entry in entries.unwrap_or_else(|| Vec::new(env)).iter() {
+            if entry.at.saturating_add(CAP_WINDOW) > now {
+                total = total.saturating_add(entry.amount);
+            }
+        }
+        total
+    }
+
+    /// Keep the entries still inside the rolling window, plus a new one.
+    fn push_cap_entry(env: &Env, entries: Option<Vec<CapEntry>>, amount: i128) -> Vec<CapEntry> {
+        let now = env.ledger().timestamp();
+        let mut kept = Vec::new(env);
+        for entry in entries.unwrap_or_else(|| Vec::new(env)).iter() {
+            if entry.at.saturating_add(CAP_WINDOW) > now {
+                kept.push_back(entry);
+            }
+        }
+        kept.push_back(CapEntry { at: now, amount });
+        kept
+    }
+
+    /// Whether `amount` more fits under the goal's and the owner's caps.
+    fn check_contribution_cap(
+        env: &Env,
+        goal: &SavingsGoal,
+        amount: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        if let Some((_, remaining)) = Self::get_goal_contribution_cap(env.clone(), goal.id) {
+            if amount > remaining {
+                return Err(SavingsGoalsError::ContributionCapExceeded);
+            }
+        }
+        if let Some((_, remaining)) =
+            Self::get_owner_contribution_cap(env.clone(), goal.owner.clone())
+        {
+            if amount > remaining {
+                return Err(SavingsGoalsError::ContributionCapExceeded);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check `amount` against the caps and, if it fits, count it towards
+    /// every cap that applies to the goal.
+    fn apply_contribution_cap(
+        env: &Env,
+        goal: &SavingsGoal,
+        amount: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        Self::check_contribution_cap(env, goal, amount)?;
+        let storage = env.storage().instance();
+
+        let goal_caps: Option<Map<u32, i128>> = storage.get(&Self::STORAGE_GOAL_CAPS);
+        if goal_caps.is_some_and(|caps| caps.contains_key(goal.id)) {
+            let key = (Self::STORAGE_GOAL_CAP_LOG, goal.id);
+            let log = Self::push_cap_entry(env, Self::load_entry(env, &key), amount);
+            Self::save_entry(env, &key, &log);
+        }
+
+        let owner_caps: Option<Map<Address, i128>> = storage.get(&Self::STORAGE_OWNER_CAPS);
+        if owner_caps.is_some_and(|caps| caps.contains_key(goal.owner.clone())) {
+            let key = (Self::STORAGE_OWNER_CAP_LOG, goal.owner.clone());
+            let log = Self::push_cap_entry(env, Self::load_entry(env, &key), amount);
+            Self::save_entry(env, &key, &log);
+        }
+        Ok(())
+    }
+
+    /// Pull the sponsor's match for a `contribution` to `goal`, if it has
+    /// one, and add it to the goal. A failed pull (allowance or balance too
+    /// low) only emits `match_err`; the contribution itself still stands.
+    /// The caller is responsible for writing the goal back to storage.
+    fn apply_sponsor_match(env: &Env, goal: &mut SavingsGoal, contribution: i128) -> i128 {
+        let mut matches: Map<u32, SponsorMatch> =
+            match env.storage().instance().get(&Self::STORAGE_SPONSOR_MATCHES) {
+                Some(m) => m,
+                None => return 0,
+            };
+        let Some(mut sponsor_match) = matches.get(goal.id) else {
+            return 0;
+        };
+
+        let amount = (contribution.saturating_mul(sponsor_match.match_bps as i128)
+            / MAX_MATCH_BPS as i128)
+            .min(sponsor_match.cap - sponsor_match.matched);
+        if amount <= 0 {
+            return 0;
+        }
+        let Some(new_total) = goal.current_amount.checked_add(amount) else {
+            return 0;
+        };
+
+        let contract = env.current_contract_address();
+        let pulled = TokenClient::new(env, &sponsor_match.token).try_transfer_from(
+            &contract,
+            &sponsor_match.sponsor,
+            &contract,
+            &amount,
+        );
+        if !matches!(pulled, Ok(Ok(()))) {
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("match_err")),
+                (goal.id, sponsor_match.sponsor, amount),
+            );
+            return 0;
+        }
+
+        goal.current_amount = new_total;
+        Self::adjust_held(env, goal.id, &sponsor_match.token, amount);
+        sponsor_match.matched += amount;
+        Self::record_contribution(env, goal, &sponsor_match.sponsor, amount);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("matched")),
+            (
+                goal.id,
+                sponsor_match.sponsor.clone(),
+                amount,
+                sponsor_match.matched,
+            ),
+        );
+        matches.set(goal.id, sponsor_match);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_SPONSOR_MATCHES, &matches);
+        amount
+    }
+
+    /// Tokens of `token` the contract actually holds for the goal, as
+    /// opposed to balance that was only recorded.
+    fn held_balance(env: &Env, goal_id: u32, token: &Address) -> i128 {
+        Self::load_entry::<_, Map<Address, i128>>(env, &(Self::STORAGE_HELD, goal_id))
+            .and_then(|held| held.get(token.clone()))
+            .unwrap_or(0)
+    }
+
+    fn adjust_held(env: &Env, goal_id: u32, token: &Address, delta: i128) {
+        let key = (Self::STORAGE_HELD, goal_id);
+        let mut held: Map<Address, i128> =
+            Self::load_entry(env, &key).unwrap_or_else(|| Map::new(env));
+        let balance = held.get(token.clone()).unwrap_or(0).saturating_add(delta);
+        if balance > 0 {
+            held.set(token.clone(), balance);
+        } else {
+            held.remove(token.clone());
+        }
+        if held.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::save_entry(env, &key, &held);
+        }
+    }
+
+    /// Transfer up to `amount` of the tokens held for the goal to `to`,
+    /// token by token. Returns the total sent.
+    fn release_held(env: &Env, goal_id: u32, to: &Address, amount: i128) -> i128 {
+        let held: Map<Address, i128> =
+            Self::load_entry(env, &(Self::STORAGE_HELD, goal_id)).unwrap_or_else(|| Map::new(env));
+        let contract = env.current_contract_address();
+        let mut sent = 0i128;
+        for (token, balance) in held.iter() {
+            let part = balance.min(amount - sent);
+            if part <= 0 {
+                break;
+            }
+            TokenClient::new(env, &token).transfer(&contract, to, &part);
+            Self::adjust_held(env, goal_id, &token, -part);
+            sent += part;
+        }
+        sent
+    }
+
+    fn deployed_amount(env: &Env, goal_id: u32) -> i128 {
+        Self::load_entry::<_, YieldPosition>(env, &(Self::STORAGE_YIELD_POSITIONS, goal_id))
+            .map(|position| position.principal)
+            .unwrap_or(0)
+    }
+
+    /// The part of the goal's balance not deployed to the yield vault.
+    fn available_balance(env: &Env, goal: &SavingsGoal) -> i128 {
+        goal.current_amount - Self::deployed_amount(env, goal.id)
+    }
+
+    /// As `check_asset`, but a denominated goal also refuses flows that move
+    /// no tokens.
+    fn check_token(goal: &SavingsGoal, token: &Option<Address>) -> Result<(), SavingsGoalsError> {
+        match token {
+            Some(token) => Self::check_asset(goal, token),
+            None if goal.asset.is_some() => Err(SavingsGoalsError::AssetMismatch),
+            None => Ok(()),
+        }
+    }
+
+    /// A denominated goal only accepts token flows in its own asset.
+    fn check_asset(goal: &SavingsGoal, token: &Address) -> Result<(), SavingsGoalsError> {
+        match &goal.asset {
+            Some(asset) if asset != token => Err(SavingsGoalsError::AssetMismatch),
+            _ => Ok(()),
+        }
+    }
+
+    /// A guarded goal stays locked until its target date or completion.
+    fn target_date_locked(env: &Env, goal: &SavingsGoal) -> bool {
+        goal.guardian.is_some()
+            && env.ledger().timestamp() < goal.target_date
+            && goal.current_amount < goal.target_amount
+    }
+
+    /// Whether the goal's lock policy or time-lock currently holds its
+    /// balance. A `Hard` lock lifts at the target date.
+    fn is_locked(env: &Env, goal: &SavingsGoal) -> bool {
+        let now = env.ledger().timestamp();
+        let time_locked = goal.unlock_date.is_some_and(|d| now < d);
+        time_locked
+            || match goal.lock_policy {
+                LockPolicy::Unlocked => false,
+                LockPolicy::Hard => now < goal.target_date,
+                LockPolicy::Soft | LockPolicy::Guardian => true,
+            }
+    }
+
+    fn is_frozen(env: &Env, goal_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&(Self::STORAGE_FROZEN, goal_id))
+    }
+
+    fn check_not_frozen(env: &Env, goal_id: u32) -> Result<(), SavingsGoalsError> {
+        if Self::is_frozen(env, goal_id) {
+            Err(SavingsGoalsError::GoalFrozen)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fail with `ApprovalRequired` if taking `amount` out of the goal would
+    /// push its unapproved withdrawals in the window past the threshold.
+    fn check_withdrawal_approval(
+        env: &Env,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<(), SavingsGoalsError> {
+        let Some(policy) = Self::get_withdrawal_approvals(env.clone(), goal_id) else {
+            return Ok(());
+        };
+        let withdrawn = Self::cap_window_total(
+            env,
+            Self::load_entry(env, &(Self::STORAGE_WITHDRAWAL_LOG, goal_id)),
+        );
+        if withdrawn.saturating_add(amount) > policy.threshold {
+            Err(SavingsGoalsError::ApprovalRequired)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Count an unapproved withdrawal against the goal's threshold window.
+    fn record_withdrawal(env: &Env, goal_id: u32, amount: i128) {
+        if Self::get_withdrawal_approvals(env.clone(), goal_id).is_none() {
+            return;
+        }
+        let key = (Self::STORAGE_WITHDRAWAL_LOG, goal_id);
+        let entries = Self::push_cap_entry(env, Self::load_entry(env, &key), amount);
+        Self::save_entry(env, &key, &entries);
+    }
+
+    /// Enforce the goal's time-lock and lock policy on a plain withdrawal.
+    /// A `Guardian`-locked goal requires the guardian's signature.
+    fn check_withdrawal_lock(env: &Env, goal: &SavingsGoal) -> Result<(), SavingsGoalsError> {
+        Self::check_not_frozen(env, goal.id)?;
+        let now = env.ledger().timestamp();
+        if goal.unlock_date.is_some_and(|d| now < d) {
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        match (goal.lock_policy, &goal.guardian) {
+            (LockPolicy::Unlocked, _) => Ok(()),
+            (LockPolicy::Soft, _) => Err(SavingsGoalsError::GoalLocked),
+            (LockPolicy::Hard, _) if now < goal.target_date => Err(SavingsGoalsError::GoalLocked),
+            (LockPolicy::Hard, _) => Ok(()),
+            (LockPolicy::Guardian, Some(guardian)) => {
+                guardian.require_auth();
+                Ok(())
+            }
+            (LockPolicy::Guardian, None) => Err(SavingsGoalsError::GuardianNotSet),
+        }
+    }
+
+    fn owner_goals_page(
+        env: &Env,
+        owner: &Address,
+        offset: u32,
+        limit: u32,
+        active_only: bool,
+    ) -> GoalListPage {
+        let limit = Self::clamp_limit(limit);
+        let ids = Self::owner_goal_ids(env, owner);
+        let mut items = Vec::new(env);
+        let mut total = 0u32;
+        for id in ids.iter() {
+            let Some(goal) = Self::load_goal(env, id) else {
+                continue;
+            };
+            if active_only && goal.current_amount >= goal.target_amount {
+                continue;
+            }
+            if total >= offset && items.len() < limit {
+                items.push_back(goal);
+            }
+            total += 1;
+        }
+
+        let next_offset = if offset.saturating_add(items.len()) < total {
+            offset + items.len()
+        } else {
+            0
+        };
+        GoalListPage {
+            items,
+            total,
+            next_offset,
+        }
+    }
+
+    fn get_goal_contributions(env: &Env, goal_id: u32) -> Map<Address, Contribution> {
+        Self::load_entry(env, &(Self::STORAGE_CONTRIBUTIONS, goal_id))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Rate changes so far. A rate set before the history was kept is treated
+    /// as having applied from the start.
+    fn rate_history(env: &Env) -> Vec<RateChange> {
+        env.storage()
+            .instance()
+            .get(&Self::STORAGE_RATE_HISTORY)
+            .unwrap_or_else(|| {
+                let rate_bps: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&Self::STORAGE_INTEREST_RATE)
+                    .unwrap_or(0);
+                vec![
+                    env,
+                    RateChange {
+                        at: 0,
+                        rate_bps,
+                        index: 0,
+                    },
+                ]
+            })
+    }
+
+    /// Accrual index at `at`: the sum of rate × seconds up to that time.
+    fn interest_index(history: &Vec<RateChange>, at: u64) -> i128 {
+        history
+            .iter()
+            .rev()
+            .find(|change| change.at <= at)
+            .map(|change| change.index + change.rate_bps as i128 * (at - change.at) as i128)
+            .unwrap_or(0)
+    }
+
+    /// Bring `goal` up to date with interest owed for its locked time since
+    /// `last_accrual`. A time-locked goal earns until its unlock date and a
+    /// `Hard`-locked goal until its target date. The caller is responsible for
+    /// writing the goal back to storage.
+    fn settle_interest(env: &Env, goal: &mut SavingsGoal) -> Result<i128, SavingsGoalsError> {
+        let now = env.ledger().timestamp();
+        let policy_until = match goal.lock_policy {
+            LockPolicy::Unlocked => 0,
+            LockPolicy::Hard => goal.target_date,
+            LockPolicy::Soft | LockPolicy::Guardian => now,
+        };
+        let locked_until = policy_until.max(goal.unlock_date.unwrap_or(0)).min(now);
+        let from = goal.last_accrual;
+        let elapsed = locked_until.saturating_sub(from);
+        goal.last_accrual = now;
+        if elapsed == 0 {
+            return Ok(0);
+        }
+
+        let history = Self::rate_history(env);
+        let accrued =
+            Self::interest_index(&history, locked_until) - Self::interest_index(&history, from);
+        let rate_bps = (accrued / elapsed as i128) as u32;
+        let interest = goal
+            .current_amount
+            .checked_mul(accrued)
+            .ok_or(SavingsGoalsError::Overflow)?
+            / (10_000 * SECONDS_PER_YEAR as i128);
+        if interest <= 0 {
+            return Ok(0);
+        }
+
+        let previously_completed = goal.current_amount >= goal.target_amount;
+        goal.current_amount = goal
+            .current_amount
+            .checked_add(interest)
+            .ok_or(SavingsGoalsError::Overflow)?;
+
+        let key = (Self::STORAGE_ACCRUALS, goal.id);
+        let mut entries: Vec<InterestAccrual> =
+            Self::load_entry(env, &key).unwrap_or_else(|| Vec::new(env));
+        if entries.len() >= MAX_ACCRUAL_HISTORY {
+            entries.pop_front();
+        }
+        entries.push_back(InterestAccrual {
+            amount: interest,
+            rate_bps,
+            from,
+            to: locked_until,
+        });
+        Self::save_entry(env, &key, &entries);
+
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("interest")),
+            (goal.id, interest, goal.current_amount),
+        );
+        if goal.current_amount >= goal.target_amount && !previously_completed {
+            env.events().publish(
+                (GOAL_COMPLETED,),
+                GoalCompletedEvent {
+                    goal_id: goal.id,
+                    name: goal.name.clone(),
+                    final_amount: goal.current_amount,
+                    timestamp: now,
+                },
+            );
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                (goal.id, goal.owner.clone()),
+            );
+        }
+        Ok(interest)
+    }
+
+    /// Store a new goal and emit the creation events. Validation is left to
+    /// the public entrypoints.
+    fn insert_goal(
+        env: &Env,
+        owner: &Address,
+        name: String,
+        target_amount: i128,
+        target_date: u64,
+        category: GoalCategory,
+        unlock_date: Option<u64>,
+    ) -> u32 {
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32)
+            + 1;
+
+        let goal = SavingsGoal {
+            id: next_id,
+            owner: owner.clone(),
+            name: name.clone(),
+            target_amount,
+            current_amount: 0,
+            target_date,
+            lock_policy: LockPolicy::Soft,
+            unlock_date,
+            tags: Vec::new(env),
+            shared: false,
+            weight: 1,
+            category,
+            asset: None,
+            guardian: None,
+            last_accrual: env.ledger().timestamp(),
+            created_at: env.ledger().timestamp(),
+        };
+
+        Self::save_goal(env, &goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+        Self::append_owner_goal_id(env, owner, next_id);
+        Self::record_activity(env, owner);
+
+        let event = GoalCreatedEvent {
+            goal_id: next_id,
+            name: goal.name.clone(),
+            target_amount,
+            target_date,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((GOAL_CREATED,), event);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalCreated),
+            (next_id, owner.clone()),
+        );
+
+        next_id
+    }
+
+    /// Add `amount` to `contributor`'s running total on the goal and append
+    /// it to the goal's contribution history. `new_total` is the goal
+    /// balance right after this contribution.
+    /// Record a contribution `goal` has just been credited with. Call after
+    /// updating `goal.current_amount`.
+    fn record_contribution(env: &Env, goal: &SavingsGoal, contributor: &Address, amount: i128) {
+        let goal_id = goal.id;
+        let new_total = goal.current_amount;
+        let mut by_contributor = Self::get_goal_contributions(env, goal_id);
+        let total = by_contributor
+            .get(contributor.clone())
+            .map(|c| c.total)
+            .unwrap_or(0)
+            .saturating_add(amount);
+        by_contributor.set(
+            contributor.clone(),
+            Contribution {
+                contributor: contributor.clone(),
+                total,
+                last_contributed_at: env.ledger().timestamp(),
+            },
+        );
+        Self::save_entry(
+            env,
+            &(Self::STORAGE_CONTRIBUTIONS, goal_id),
+            &by_contributor,
+        );
+
+        let count = Self::contribution_history_len(env, goal_id);
+        let entry_key = (Self::STORAGE_CONTRIBUTION_LOG, goal_id, count);
+        env.storage().persistent().set(
+            &entry_key,
+            &ContributionRecord {
+                contributor: contributor.clone(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+                new_total,
+            },
+        );
+        Self::extend_persistent_ttl(env, &entry_key);
+        let count_key = (Self::STORAGE_CONTRIBUTION_COUNT, goal_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_persistent_ttl(env, &count_key);
+
+        Self::record_flow(env, &goal.owner, amount, 0);
+    }
+
+    /// Add to `owner`'s contribution and withdrawal totals for the current
+    /// `STATS_PERIOD`, read back by `get_savings_stats`.
+    fn record_flow(env: &Env, owner: &Address, contributed: i128, withdrawn: i128) {
+        let key = (Self::STORAGE_FLOWS, owner.clone());
+        let mut flows: Map<u64, (i128, i128)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(env));
+        let period = env.ledger().timestamp() / STATS_PERIOD;
+        let (c, w) = flows.get(period).unwrap_or((0, 0));
+        flows.set(
+            period,
+            (c.saturating_add(contributed), w.saturating_add(withdrawn)),
+        );
+        env.storage().persistent().set(&key, &flows);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    fn contribution_history_len(env: &Env, goal_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(Self::STORAGE_CONTRIBUTION_COUNT, goal_id))
+            .unwrap_or(0)
+    }
+
+    /// Extend the TTL of instance storage
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Set time-lock on a goal
+    pub fn set_time_lock(env: Env, caller: Address, goal_id: u32, unlock_date: u64) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            panic!("Only the goal owner can set time-lock");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if unlock_date <= current_time {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            panic!("Unlock date must be in the future");
+        }
+
+        Self::settle_interest(&env, &mut goal).expect("Interest overflow");
+        goal.unlock_date = Some(unlock_date);
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
+        true
+    }
+
+    /// Contribute `amount` of `token` to a goal every `interval_days`,
+    /// pulled from the allowance the owner has approved for this contract.
+    /// The first contribution is due immediately. Passing an amount of 0
+    /// removes the goal's schedule.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount < 0 or interval_days is 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_contribution_schedule(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        token: Address,
+        amount: i128,
+        interval_days: u32,
+    ) -> Result<(), SavingsGoalsError> {
+        caller.require_auth();
+        if amount < 0 || (amount > 0 && interval_days == 0) {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("cont_sch"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if amount > 0 {
+            if let Err(e) = Self::check_asset(&goal, &token) {
+                Self::append_audit(&env, symbol_short!("cont_sch"), &caller, false);
+                return Err(e);
+            }
+        }
+
+        if amount == 0 {
+            Self::remove_schedule(&env, goal_id);
+        } else {
+            Self::save_schedule(
+                &env,
+                &ContributionSchedule {
+                    goal_id,
+                    owner: caller.clone(),
+                    token,
+                    amount,
+                    interval: interval_days as u64 * 86_400,
+                    next_due: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        Self::append_audit(&env, symbol_short!("cont_sch"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("cont_sch")),
+            (goal_id, caller, amount, interval_days),
+        );
+        Ok(())
+    }
+
+    pub fn get_contribution_schedule(env: Env, goal_id: u32) -> Option<ContributionSchedule> {
+        Self::load_entry(&env, &(Self::STORAGE_CONTRIBUTION_SCHEDULES, goal_id))
+    }
+
+    /// Keeper entrypoint: run up to `limit` due contribution schedules.
+    ///
+    /// Each due schedule pulls its amount from the owner's allowance into
+    /// this contract and credits the goal. A failed pull (allowance or
+    /// balance too low, or a contribution cap reached) is reported with a
+    /// `cont_fail` event and skipped until the next interval. Schedules
+    /// whose goal has been closed are dropped.
+    ///
+    /// # Returns
+    /// Number of contributions made
+    pub fn process_due_contributions(env: Env, limit: u32) -> u32 {
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+        Self::extend_instance_ttl(&env);
+        let limit = Self::clamp_limit(limit);
+        let now = env.ledger().timestamp();
+        let contract = env.current_contract_address();
+
+        let mut processed = 0u32;
+        let mut contributed = 0u32;
+        for goal_id in Self::scheduled_goal_ids(&env).iter() {
+            if processed >= limit {
+                break;
+            }
+            let Some(mut schedule) = Self::get_contribution_schedule(env.clone(), goal_id) else {
+                continue;
+            };
+            if schedule.next_due > now {
+                continue;
+            }
+            processed += 1;
+
+            let Some(mut goal) = Self::load_goal(&env, goal_id) else {
+                Self::remove_schedule(&env, goal_id);
+                continue;
+            };
+
+            // Check the cap before pulling so a capped goal never takes funds.
+            let pulled = !Self::is_frozen(&env, goal_id)
+                && Self::check_contribution_cap(&env, &goal, schedule.amount).is_ok()
+                && matches!(
+                    TokenClient::new(&env, &schedule.token).try_transfer_from(
+                        &contract,
+                        &schedule.owner,
+                        &contract,
+                        &schedule.amount,
+                    ),
+                    Ok(Ok(()))
+                );
+            if pulled {
+                Self::adjust_held(&env, goal_id, &schedule.token, schedule.amount);
+            }
+            let credited = pulled
+                && Self::credit_goal(&env, &mut goal, &schedule.owner, schedule.amount).is_ok();
+            if credited {
+                Self::save_goal(&env, &goal);
+                contributed += 1;
+            } else {
+                env.events().publish(
+                    (symbol_short!("savings"), symbol_short!("cont_fail")),
+                    (goal_id, schedule.owner.clone(), schedule.amount),
+                );
+            }
+
+            while schedule.next_due <= now {
+                schedule.next_due = schedule.next_due.saturating_add(schedule.interval);
+            }
+            Self::save_schedule(&env, &schedule);
+        }
+        contributed
+    }
+
+    pub fn create_savings_schedule(
+        env: Env,
+        owner: Address,
+        goal_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> u32 {
+        owner.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let goal = Self::load_goal(&env, goal_id).expect("Goal not found");
+
+        if goal.owner != owner {
+            panic!("Only the goal owner can create schedules");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if next_due <= current_time {
+            panic!("Next due date must be in the future");
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let next_schedule_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_SSCH"))
+            .unwrap_or(0u32)
+            + 1;
+
+        let schedule = SavingsSchedule {
+            id: next_schedule_id,
+            owner: owner.clone(),
+            goal_id,
+            amount,
+            next_due,
+            interval,
+            recurring: interval > 0,
+            active: true,
+            created_at: current_time,
+            last_executed: None,
+            missed_count: 0,
+        };
+
+        schedules.set(next_schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
+            (next_schedule_id, owner),
+        );
+
+        next_schedule_id
+    }
+
+    pub fn modify_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> bool {
+        caller.require_auth();
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if next_due <= current_time {
+            panic!("Next due date must be in the future");
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+
+        if schedule.owner != caller {
+            panic!("Only the schedule owner can modify it");
+        }
+
+        schedule.amount = amount;
+        schedule.next_due = next_due;
+        schedule.interval = interval;
+        schedule.recurring = interval > 0;
+
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleModified),
+            (schedule_id, caller),
+        );
+
+        true
+    }
+
+    pub fn cancel_savings_schedule(env: Env, caller: Address, schedule_id: u32) -> bool {
+        caller.require_auth();
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+
+        if schedule.owner != caller {
+            panic!("Only the schedule owner can cancel it");
+        }
+
+        schedule.active = false;
+
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
+            (schedule_id, caller),
+        );
+
+        true
+    }
+
+    pub fn execute_due_savings_schedules(env: Env) -> Vec<u32> {
+        Self::extend_instance_ttl(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut executed = Vec::new(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        for (schedule_id, mut schedule) in schedules.iter() {
+            if !schedule.active || schedule.next_due > current_time {
+                continue;
+            }
+
+            if let Some(mut goal) = Self::load_goal(&env, schedule.goal_id)
+                .filter(|g| !Self::is_frozen(&env, g.id))
+                .filter(|g| Self::apply_contribution_cap(&env, g, schedule.amount).is_ok())
+            {
+                Self::settle_interest(&env, &mut goal).expect("Interest overflow");
+                goal.current_amount = goal
+                    .current_amount
+                    .checked_add(schedule.amount)
+                    .expect("overflow");
+                Self::record_contribution(&env, &goal, &schedule.owner, schedule.amount);
+                Self::apply_sponsor_match(&env, &mut goal, schedule.amount);
+
+                let is_completed = goal.current_amount >= goal.target_amount;
+                Self::save_goal(&env, &goal);
+
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
+                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
+                );
+
+                if is_completed {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                        (schedule.goal_id, goal.owner.clone()),
+                    );
+                }
+                Self::pay_out_if_complete(&env, &mut goal);
+            }
+
+            schedule.last_executed = Some(current_time);
+
+            if schedule.recurring && schedule.interval > 0 {
+                let mut missed = 0u32;
+                let mut next = schedule.next_due + schedule.interval;
+                while next <= current_time {
+                    missed += 1;
+                    next += schedule.interval;
+                }
+                schedule.missed_count += missed;
+                schedule.next_due = next;
+
+                if missed > 0 {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
+                        (schedule_id, missed),
+                    );
+                }
+            } else {
+                schedule.active = false;
+            }
+
+            schedules.set(schedule_id, schedule);
+            executed.push_back(schedule_id);
+
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
+                schedule_id,
+            );
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        executed
+    }
+
+    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
+        let schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        for (_, schedule) in schedules.iter() {
+            if schedule.owner == owner {
+                result.push_back(schedule);
+            }
+        }
+        result
+    }
+
+    pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
+        let schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+        schedules.get(schedule_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Savings circles
+    //
+    // Circle contributions are real token transfers into this contract, and
+    // each round's pot is transferred straight out to that round's recipient.
+    // Circles never touch goal balances.
+    // -----------------------------------------------------------------------
+
+    /// Start a savings circle. `members` is also the payout order, so the
+    /// circle runs for `members.len()` rounds of `round_days` each.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - non-positive contribution, zero round length,
+    ///   fewer than two members or a duplicated member
+    /// * `Overflow` - the full pot would not fit in an i128
+    pub fn create_circle(
+        env: Env,
+        creator: Address,
+        token: Address,
+        members: Vec<Address>,
+        contribution: i128,
+        round_days: u32,
+    ) -> Result<u32, SavingsGoalsError> {
+        creator.require_auth();
+        Self::require_not_paused(&env, pause_functions::CIRCLE);
+
+        let mut unique = Map::<Address, u32>::new(&env);
+        for member in members.iter() {
+            unique.set(member, 0);
+        }
+        if contribution <= 0
+            || round_days == 0
+            || members.len() < 2
+            || unique.len() != members.len()
+        {
+            Self::append_audit(&env, symbol_short!("circle"), &creator, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        contribution
+            .checked_mul(members.len() as i128)
+            .ok_or(SavingsGoalsError::Overflow)?;
+
+        Self::extend_instance_ttl(&env);
+        let circle_id: u32 = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_NEXT_CIRCLE_ID)
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_NEXT_CIRCLE_ID, &(circle_id + 1));
+
+        let round_interval = round_days as u64 * 86_400;
+        let circle = SavingsCircle {
+            id: circle_id,
+            creator: creator.clone(),
+            token,
+            members: members.clone(),
+            contribution,
+            round_interval,
+            current_round: 0,
+            round_deadline: env.ledger().timestamp() + round_interval,
+            paid: Vec::new(&env),
+            missed: unique,
+            collateral: Map::new(&env),
+            completed: false,
+        };
+        Self::save_circle(&env, &circle);
+
+        Self::append_audit(&env, symbol_short!("circle"), &creator, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("circ_new")),
+            (circle_id, creator, members.len(), contribution),
+        );
+        Ok(circle_id)
+    }
+
+    /// Pay `member`'s share into the current round. Late payments are
+    /// accepted until the round is settled. When the last member pays, the
+    /// round settles immediately and the pot is returned; otherwise 0.
+    ///
+    /// # Errors
+    /// * `CircleNotFound`, `CircleCompleted`
+    /// * `NotCircleMember` - `member` is not in the circle
+    /// * `AlreadyContributed` - `member` already paid this round
+    pub fn contribute_to_circle(
+        env: Env,
+        member: Address,
+        circle_id: u32,
+    ) -> Result<i128, SavingsGoalsError> {
+        member.require_auth();
+        Self::require_not_paused(&env, pause_functions::CIRCLE);
+        Self::extend_instance_ttl(&env);
+
+        let mut circle =
+            Self::load_circle(&env, circle_id).ok_or(SavingsGoalsError::CircleNotFound)?;
+        if circle.completed {
+            return Err(SavingsGoalsError::CircleCompleted);
+        }
+        if !circle.members.contains(&member) {
+            Self::append_audit(&env, symbol_short!("circ_pay"), &member, false);
+            return Err(SavingsGoalsError::NotCircleMember);
+        }
+        if circle.paid.contains(&member) {
+            Self::append_audit(&env, symbol_short!("circ_pay"), &member, false);
+            return Err(SavingsGoalsError::AlreadyContributed);
+        }
+
+        TokenClient::new(&env, &circle.token).transfer(
+            &member,
+            &env.current_contract_address(),
+            &circle.contribution,
+        );
+        circle.paid.push_back(member.clone());
+
+        Self::append_audit(&env, symbol_short!("circ_pay"), &member, true);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("circ_paid")),
+            (circle_id, circle.current_round, member, circle.contribution),
+        );
+
+        let pot = if circle.paid.len() == circle.members.len() {
+            Self::settle_circle_round(&env, &mut circle)
+        } else {
+            0
+        };
+        Self::save_circle(&env, &circle);
+        Ok(pot)
+    }
+
+    /// Keeper entrypoint: settle the current round once its deadline has
+    /// passed. Members who have not paid are marked as having missed the
+    /// round, and the recipient receives whatever was collected. Returns the
+    /// pot paid out.
+    ///
+    /// # Errors
+    /// * `CircleNotFound`, `CircleCompleted`
+    /// * `RoundStillOpen` - the round deadline has not passed yet
+    pub fn close_circle_round(env: Env, circle_id: u32) -> Result<i128, SavingsGoalsError> {
+        Self::require_not_paused(&env, pause_functions::CIRCLE);
+        Self::extend_instance_ttl(&env);
+
+        let mut circle =
+            Self::load_circle(&env, circle_id).ok_or(SavingsGoalsError::CircleNotFound)?;
+        if circle.completed {
+            return Err(SavingsGoalsError::CircleCompleted);
+        }
+        if env.ledger().timestamp() < circle.round_deadline {
+            return Err(SavingsGoalsError::RoundStillOpen);
+        }
+
+        let pot = Self::settle_circle_round(&env, &mut circle);
+        Self::save_circle(&env, &circle);
+        Ok(pot)
+    }
+
+    pub fn get_circle(env: Env, circle_id: u32) -> Option<SavingsCircle> {
+        Self::load_circle(&env, circle_id)
+    }
+
+    /// Rounds `member` has missed in `circle_id`, or 0 for unknown circles
+    /// and non-members.
+    pub fn get_circle_missed(env: Env, circle_id: u32, member: Address) -> u32 {
+        Self::load_circle(&env, circle_id)
+            .and_then(|circle| circle.missed.get(member))
+            .unwrap_or(0)
+    }
+
+    fn load_circle(env: &Env, circle_id: u32) -> Option<SavingsCircle> {
+        let key = (Self::STORAGE_CIRCLE, circle_id);
+        let circle: Option<SavingsCircle> = env.storage().persistent().get(&key);
+        if circle.is_some() {
+            Self::extend_persistent_ttl(env, &key);
+        }
+        circle
+    }
+
+    fn save_circle(env: &Env, circle: &SavingsCircle) {
+        let key = (Self::STORAGE_CIRCLE, circle.id);
+        env.storage().persistent().set(&key, circle);
+        Self::extend_persistent_ttl(env, &key);
+    }
+
+    /// Pay the current round's pot to its recipient, record missed
+    /// payments and open the next round. A member who was already paid out
+    /// and skips the round pays from their collateral instead, and the
+    /// recipient's share of later rounds is held back from their pot. On the
+    /// last round the remaining collateral is returned. The caller saves the
+    /// circle. Returns the amount sent to the recipient.
+    fn settle_circle_round(env: &Env, circle: &mut SavingsCircle) -> i128 {
+        let token = TokenClient::new(env, &circle.token);
+        let round = circle.current_round;
+        let mut missed_count = 0u32;
+        let mut covered = 0u32;
+        for member in circle.members.iter() {
+            if circle.paid.contains(&member) {
+                continue;
+            }
+            let held = circle.collateral.get(member.clone()).unwrap_or(0);
+            if held >= circle.contribution {
+                covered += 1;
+                circle
+                    .collateral
+                    .set(member.clone(), held - circle.contribution);
+                env.events().publish(
+                    (symbol_short!("savings"), symbol_short!("circ_cov")),
+                    (circle.id, round, member),
+                );
+            } else {
+                missed_count += 1;
+                let missed = circle.missed.get(member.clone()).unwrap_or(0);
+                circle.missed.set(member.clone(), missed + 1);
+                env.events().publish(
+                    (symbol_short!("savings"), symbol_short!("circ_miss")),
+                    (circle.id, round, member),
+                );
+            }
+        }
+
+        let recipient = circle.members.get(round).unwrap();
+        let pot = circle.contribution * (circle.paid.len() + covered) as i128;
+        let rounds_left = circle.members.len() - round - 1;
+        let held = pot.min(circle.contribution * rounds_left as i128);
+        if held > 0 {
+            circle.collateral.set(recipient.clone(), held);
+        }
+        let payout = pot - held;
+        if payout > 0 {
+            token.transfer(&env.current_contract_address(), &recipient, &payout);
+        }
+        env.events().publish(
+            (CIRCLE_PAYOUT,),
+            CirclePayoutEvent {
+                circle_id: circle.id,
+                round,
+                recipient,
+                amount: payout,
+                missed_count,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        circle.current_round = round + 1;
+        circle.paid = Vec::new(env);
+        circle.round_deadline = env.ledger().timestamp() + circle.round_interval;
+        if circle.current_round >= circle.members.len() {
+            for (member, held) in circle.collateral.iter() {
+                if held > 0 {
+                    token.transfer(&env.current_contract_address(), &member, &held);
+                }
+            }
+            circle.collateral = Map::new(env);
+            circle.completed = true;
+            env.events().publish(
+                (symbol_short!("savings"), symbol_short!("circ_done")),
+                circle.id,
+            );
+        }
+        payout
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Events, Ledger},
+        token::StellarAssetClient,
+        Env, IntoVal, String, TryFromVal,
+    };
+
+    fn make_env() -> Env {
+        Env::default()
+    }
+
+    fn create_funded_goal(
+        env: &Env,
+        client: &SavingsGoalContractClient,
+        owner: &Address,
+        amount: i128,
+    ) -> u32 {
+        let goal_id = client.create_goal(
+            owner,
+            &String::from_str(env, "Goal"),
+            &10_000,
+            &(env.ledger().timestamp() + 86400 * 365),
+        );
+        client.add_to_goal(owner, &goal_id, &None, &amount);
+        goal_id
+    }
+
+    fn setup_goals(env: &Env, client: &SavingsGoalContractClient, owner: &Address, count: u32) {
+        for i in 0..count {
+            client.create_goal(
+                owner,
+                &String::from_str(env, "Goal"),
+                &(1000i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+            );
+        }
+    }
+
+    // --- withdraw_from_goal ---
+
+    #[test]
+    fn test_withdraw_from_unlocked_goal_emits_withdrawal_made() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        client.unlock_goal(&owner, &goal_id);
+        assert_eq!(
+            client.withdraw_from_goal(&owner, &goal_id, &None, &400),
+            600
+        );
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 600);
+
+        let withdrawn = env.events().all().iter().any(|(_, topics, data)| {
+            topics == (WITHDRAWAL_MADE,).into_val(&env)
+                && WithdrawalMadeEvent::try_from_val(&env, &data)
+                    .is_ok_and(|e| e.goal_id == goal_id && e.amount == 400 && e.remaining == 600)
+        });
+        assert!(withdrawn);
+    }
+
+    #[test]
+    fn test_withdraw_from_locked_goal_is_rejected() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+
+        client.unlock_goal(&owner, &goal_id);
+        client.set_time_lock(&owner, &goal_id, &(env.ledger().timestamp() + 100));
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 1_000);
+    }
+
+    #[test]
+    fn test_withdraw_more_than_balance_is_rejected() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        client.unlock_goal(&owner, &goal_id);
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &1_001),
+            Err(Ok(SavingsGoalsError::InsufficientBalance))
+        );
+    }
+
+    // --- shared goals ---
+
+    #[test]
+    fn test_shared_goal_tracks_each_contributor() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let aunt = Address::generate(&env);
+        let uncle = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 500);
+        client.set_goal_shared(&owner, &goal_id, &true);
+        client.add_to_goal(&aunt, &goal_id, &None, &300);
+        client.add_to_goal(&aunt, &goal_id, &None, &200);
+        assert_eq!(client.add_to_goal(&uncle, &goal_id, &None, &1_000), 2_000);
+
+        assert_eq!(client.get_contributions_by(&goal_id, &owner), 500);
+        assert_eq!(client.get_contributions_by(&goal_id, &aunt), 500);
+        assert_eq!(client.get_contributions_by(&goal_id, &uncle), 1_000);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 2_000);
+
+        // Contributors cannot take money out of a goal they do not own.
+        client.unlock_goal(&owner, &goal_id);
+        assert_eq!(
+            client.try_withdraw_from_goal(&aunt, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_unshared_goal_rejects_other_contributors() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let relative = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 500);
+        assert_eq!(
+            client.try_add_to_goal(&relative, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        assert_eq!(
+            client.try_set_goal_shared(&relative, &goal_id, &true),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+
+        client.set_goal_shared(&owner, &goal_id, &true);
+        client.add_to_goal(&relative, &goal_id, &None, &100);
+        client.set_goal_shared(&owner, &goal_id, &false);
+        assert_eq!(
+            client.try_add_to_goal(&relative, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        assert_eq!(client.get_contributions_by(&goal_id, &relative), 100);
+    }
+
+    #[test]
+    fn test_get_contributors_orders_by_total() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let aunt = Address::generate(&env);
+        let uncle = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 200);
+        client.set_goal_shared(&owner, &goal_id, &true);
+        env.ledger().set_timestamp(1_000);
+        client.add_to_goal(&aunt, &goal_id, &None, &300);
+        env.ledger().set_timestamp(2_000);
+        client.add_to_goal(&uncle, &goal_id, &None, &250);
+        env.ledger().set_timestamp(3_000);
+        client.add_to_goal(&uncle, &goal_id, &None, &250);
+
+        let contributors = client.get_contributors(&goal_id);
+        assert_eq!(contributors.len(), 3);
+        let top = contributors.get(0).unwrap();
+        assert_eq!(top.contributor, uncle);
+        assert_eq!(top.total, 500);
+        assert_eq!(top.last_contributed_at, 3_000);
+        let second = contributors.get(1).unwrap();
+        assert_eq!(second.contributor, aunt);
+        assert_eq!(second.last_contributed_at, 1_000);
+        assert_eq!(contributors.get(2).unwrap().contributor, owner);
+
+        assert_eq!(client.get_contributors(&999).len(), 0);
+    }
+
+    #[test]
+    fn test_contribution_history_pages_in_order() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let aunt = Address::generate(&env);
+
+        env.ledger().set_timestamp(1_000);
+        let goal_id = create_funded_goal(&env, &client, &owner, 200);
+        client.set_goal_shared(&owner, &goal_id, &true);
+        env.ledger().set_timestamp(2_000);
+        client.add_to_goal(&aunt, &goal_id, &None, &300);
+        client.unlock_goal(&owner, &goal_id);
+        client.withdraw_from_goal(&owner, &goal_id, &None, &100);
+        client.batch_add_to_goals(
+            &owner,
+            &Vec::from_array(
+                &env,
+                [ContributionItem {
+                    goal_id,
+                    amount: 50,
+                }],
+            ),
+        );
+
+        let history = client.get_contribution_history(&goal_id, &0, &0);
+        assert_eq!(history.len(), 3);
+        let first = history.get(0).unwrap();
+        assert_eq!(first.contributor, owner);
+        assert_eq!(first.amount, 200);
+        assert_eq!(first.timestamp, 1_000);
+        assert_eq!(first.new_total, 200);
+        assert_eq!(history.get(1).unwrap().new_total, 500);
+        // The withdrawal between them shows in the resulting total.
+        assert_eq!(history.get(2).unwrap().new_total, 450);
+
+        let page = client.get_contribution_history(&goal_id, &1, &1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap().contributor, aunt);
+        assert_eq!(client.get_contribution_history(&goal_id, &3, &10).len(), 0);
+    }
+
+    // --- withdrawal approvals ---
+
+    #[test]
+    fn test_large_shared_withdrawal_needs_named_approvals() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        client.unlock_goal(&owner, &goal_id);
+        let approvers = Vec::from_array(&env, [alice.clone(), bob.clone()]);
+
+        assert_eq!(
+            client.try_set_withdrawal_approvals(&owner, &goal_id, &100, &approvers, &1, &3),
+            Err(Ok(SavingsGoalsError::GoalNotShared))
+        );
+        client.set_goal_shared(&owner, &goal_id, &true);
+        client.add_to_goal(&alice, &goal_id, &None, &500);
+        client.add_to_goal(&carol, &goal_id, &None, &500);
+        // Only two approvers are named, and the owner cannot be one.
+        assert_eq!(
+            client.try_set_withdrawal_approvals(&owner, &goal_id, &100, &approvers, &3, &3),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        let with_owner = Vec::from_array(&env, [alice.clone(), owner.clone()]);
+        assert_eq!(
+            client.try_set_withdrawal_approvals(&owner, &goal_id, &100, &with_owner, &1, &3),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        client.set_withdrawal_approvals(&owner, &goal_id, &100, &approvers, &2, &3);
+        assert_eq!(
+            client.try_set_withdrawal_approvals(&owner, &goal_id, &500, &approvers, &2, &3),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        let more = Vec::from_array(&env, [alice.clone(), bob.clone(), carol.clone()]);
+        assert_eq!(
+            client.try_set_withdrawal_approvals(&owner, &goal_id, &100, &more, &2, &3),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+
+        // The threshold covers everything withdrawn in the window, not
+        // each call on its own.
+        assert_eq!(
+            client.withdraw_from_goal(&owner, &goal_id, &None, &60),
+            1_940
+        );
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &50),
+            Err(Ok(SavingsGoalsError::ApprovalRequired))
+        );
+        assert_eq!(
+            client.withdraw_from_goal(&owner, &goal_id, &None, &40),
+            1_900
+        );
+
+        let request_id = client.request_withdrawal(&owner, &goal_id, &600);
+        assert_eq!(client.get_pending_withdrawals(&goal_id).len(), 1);
+        // Contributing does not make carol an approver.
+        for outsider in [owner.clone(), carol.clone()] {
+            assert_eq!(
+                client.try_approve_withdrawal(&outsider, &request_id),
+                Err(Ok(SavingsGoalsError::Unauthorized))
+            );
+        }
+        assert!(!client.approve_withdrawal(&alice, &request_id));
+        assert_eq!(
+            client.try_approve_withdrawal(&alice, &request_id),
+            Err(Ok(SavingsGoalsError::AlreadyApproved))
+        );
+        assert!(client.approve_withdrawal(&bob, &request_id));
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 1_300);
+        assert_eq!(client.get_pending_withdrawals(&goal_id).len(), 0);
+        assert_eq!(
+            client.try_approve_withdrawal(&bob, &request_id),
+            Err(Ok(SavingsGoalsError::RequestNotFound))
+        );
+
+        // Requests lapse after the policy's expiry.
+        let request_id = client.request_withdrawal(&owner, &goal_id, &600);
+        env.ledger().with_mut(|li| li.timestamp += 3 * 86_400 + 1);
+        assert_eq!(client.get_pending_withdrawals(&goal_id).len(), 0);
+        assert_eq!(
+            client.try_approve_withdrawal(&alice, &request_id),
+            Err(Ok(SavingsGoalsError::RequestExpired))
+        );
+    }
+
+    #[test]
+    fn test_approval_window_holds_back_payouts() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+
+        let goal_id = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Goal"),
+            &1_000,
+            &(env.ledger().timestamp() + 86400 * 365),
+        );
+        client.unlock_goal(&owner, &goal_id);
+        client.set_goal_shared(&owner, &goal_id, &true);
+        let approvers = Vec::from_array(&env, [alice.clone()]);
+        client.set_withdrawal_approvals(&owner, &goal_id, &100, &approvers, &1, &3);
+
+        // Pointing the payout at the owner does not get around the policy.
+        client.set_payout_target(&owner, &goal_id, &token, &owner);
+        client.sweep_to_goal(&owner, &token, &0, &goal_id);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 1_000);
+        assert_eq!(client.get_held_balance(&goal_id, &token), 1_000);
+
+        // The window rolls over after 30 days.
+        client.withdraw_from_goal(&owner, &goal_id, &None, &100);
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &1),
+            Err(Ok(SavingsGoalsError::ApprovalRequired))
+        );
+        env.ledger().with_mut(|li| li.timestamp += CAP_WINDOW);
+        client.withdraw_from_goal(&owner, &goal_id, &None, &100);
+    }
+
+    // --- deposit_allocation ---
+
+    #[test]
+    fn test_deposit_allocation_splits_by_weight() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 3);
+        client.set_goal_weight(&owner, &1, &3);
+        client.set_goal_weight(&owner, &2, &1);
+        client.set_goal_weight(&owner, &3, &0);
+
+        let credited = client.deposit_allocation(&owner, &1_001);
+        assert_eq!(credited.len(), 2);
+        assert_eq!(credited.get(0).unwrap().amount, 750);
+        assert_eq!(credited.get(1).unwrap().amount, 251);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 750);
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 251);
+        assert_eq!(client.get_goal(&3).unwrap().current_amount, 0);
+        assert_eq!(client.get_contributions_by(&1, &owner), 750);
+
+        let funds_added = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| *topics == (FUNDS_ADDED,).into_val(&env))
+            .count();
+        assert_eq!(funds_added, 2);
+    }
+
+    #[test]
+    fn test_deposit_allocation_skips_completed_goals() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 2);
+        client.add_to_goal(&owner, &1, &None, &1_000);
+        client.deposit_allocation(&owner, &500);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 1_000);
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 500);
+
+        let other = Address::generate(&env);
+        assert_eq!(
+            client.try_deposit_allocation(&other, &500),
+            Err(Ok(SavingsGoalsError::NoActiveGoals))
+        );
+        assert_eq!(
+            client.try_deposit_allocation(&owner, &0),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+    }
+
+    #[test]
+    fn test_set_goal_weight_reconfigures_next_deposit() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 3);
+        // Default weights split evenly; the remainder lands on the last goal.
+        let credited = client.deposit_allocation(&owner, &100);
+        assert_eq!(credited.get(0).unwrap().amount, 33);
+        assert_eq!(credited.get(1).unwrap().amount, 33);
+        assert_eq!(credited.get(2).unwrap().amount, 34);
+
+        client.set_goal_weight(&owner, &1, &0);
+        client.set_goal_weight(&owner, &3, &4);
+        let credited = client.deposit_allocation(&owner, &100);
+        assert_eq!(credited.len(), 2);
+        assert_eq!(credited.get(0).unwrap().goal_id, 2);
+        assert_eq!(credited.get(0).unwrap().amount, 20);
+        assert_eq!(credited.get(1).unwrap().amount, 80);
+        assert_eq!(client.get_goal(&1).unwrap().weight, 0);
+
+        assert_eq!(
+            client.try_set_goal_weight(&Address::generate(&env), &2, &5),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+    }
+
+    // --- withdraw_early ---
+
+    #[test]
+    fn test_withdraw_early_routes_penalty_to_destination_goal() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 2_000);
+        let emergency = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Emergency"),
+            &5_000,
+            &(env.ledger().timestamp() + 86_400),
+        );
+        assert_eq!(
+            client.try_withdraw_early(&owner, &goal_id, &1_000),
+            Err(Ok(SavingsGoalsError::PenaltyNotConfigured))
+        );
+
+        client.set_early_withdrawal_policy(&owner, &500, &emergency);
+        assert_eq!(client.withdraw_early(&owner, &goal_id, &1_000), 950);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 1_000);
+        assert_eq!(client.get_goal(&emergency).unwrap().current_amount, 50);
+
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| *topics == (EARLY_WITHDRAWAL,).into_val(&env))
+            .unwrap();
+        let event = EarlyWithdrawalEvent::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.net_amount, 950);
+        assert_eq!(event.penalty, 50);
+        assert_eq!(event.penalty_goal_id, emergency);
+        assert_eq!(event.remaining, 1_000);
+    }
+
+    #[test]
+    fn test_early_withdrawal_policy_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        assert_eq!(
+            client.try_set_early_withdrawal_policy(&owner, &10_001, &goal_id),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_set_early_withdrawal_policy(&other, &100, &goal_id),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.set_early_withdrawal_policy(&owner, &100, &goal_id);
+        assert_eq!(
+            client.try_withdraw_early(&owner, &goal_id, &101),
+            Err(Ok(SavingsGoalsError::InsufficientBalance))
+        );
+
+        client.unlock_goal(&owner, &goal_id);
+        assert_eq!(client.withdraw_early(&owner, &goal_id, &100), 100);
+    }
+
+    // --- transfer_between_goals ---
+
+    #[test]
+    fn test_transfer_surplus_from_locked_completed_goal() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let done = create_funded_goal(&env, &client, &owner, 12_000);
+        let medical = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Medical"),
+            &5_000,
+            &(env.ledger().timestamp() + 86_400),
+        );
+        assert_eq!(
+            client.try_transfer_between_goals(&owner, &done, &medical, &2_001),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+
+        client.transfer_between_goals(&owner, &done, &medical, &2_000);
+        assert_eq!(client.get_goal(&done).unwrap().current_amount, 10_000);
+        assert_eq!(client.get_goal(&medical).unwrap().current_amount, 2_000);
+
+        let events = env.events().all();
+        let mut transfers = events
+            .iter()
+            .filter(|(_, topics, _)| *topics == (GOAL_TRANSFER,).into_val(&env));
+        let (_, _, data) = transfers.next().unwrap();
+        assert!(transfers.next().is_none());
+        let event = GoalTransferEvent::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.amount, 2_000);
+        assert_eq!(event.from_remaining, 10_000);
+        assert_eq!(event.to_total, 2_000);
+    }
+
+    #[test]
+    fn test_transfer_between_goals_checks_owner_and_balance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let from = create_funded_goal(&env, &client, &owner, 500);
+        let to = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Medical"),
+            &5_000,
+            &(env.ledger().timestamp() + 86_400),
+        );
+        let foreign = client.create_goal(
+            &other,
+            &String::from_str(&env, "Other"),
+            &5_000,
+            &(env.ledger().timestamp() + 86_400),
+        );
+        client.unlock_goal(&owner, &from);
+
+        assert_eq!(
+            client.try_transfer_between_goals(&owner, &from, &foreign, &100),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        assert_eq!(
+            client.try_transfer_between_goals(&owner, &from, &to, &501),
+            Err(Ok(SavingsGoalsError::InsufficientBalance))
+        );
+        assert_eq!(
+            client.try_transfer_between_goals(&owner, &from, &from, &100),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        client.transfer_between_goals(&owner, &from, &to, &500);
+        assert_eq!(client.get_goal(&to).unwrap().current_amount, 500);
+    }
+
+    // --- close_goal ---
+
+    #[test]
+    fn test_close_goal_releases_and_archives() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 700);
+        assert_eq!(
+            client.try_close_goal(&owner, &goal_id),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+
+        client.unlock_goal(&owner, &goal_id);
+        assert_eq!(client.close_goal(&owner, &goal_id), 700);
+        assert!(client.get_goal(&goal_id).is_none());
+        assert_eq!(client.get_all_goals(&owner).len(), 0);
+
+        let closed = client.get_closed_goals(&owner, &0, &10);
+        assert_eq!(closed.len(), 1);
+        let entry = closed.get(0).unwrap();
+        assert_eq!(entry.goal.id, goal_id);
+        assert_eq!(entry.goal.current_amount, 0);
+        assert_eq!(entry.released, 700);
+        assert_eq!(client.get_closed_goals(&owner, &1, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_close_goal_requires_owner() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        assert_eq!(
+            client.try_close_goal(&Address::generate(&env), &1),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        // An empty goal can be closed even while locked.
+        assert_eq!(client.close_goal(&owner, &1), 0);
+        assert_eq!(
+            client.try_close_goal(&owner, &1),
+            Err(Ok(SavingsGoalsError::GoalNotFound))
+        );
+    }
+
+    #[test]
+    fn test_archive_closed_goals_keeps_lifetime_savings() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let mut closed_ids = Vec::new(&env);
+        for amount in [100i128, 200, 300] {
+            let goal_id = create_funded_goal(&env, &client, &owner, amount);
+            client.unlock_goal(&owner, &goal_id);
+            closed_ids.push_back(goal_id);
+        }
+        client.close_goal(&owner, &closed_ids.get(0).unwrap());
+        client.close_goal(&owner, &closed_ids.get(1).unwrap());
+        let cutoff = env.ledger().timestamp() + 1;
+        env.ledger().with_mut(|li| li.timestamp += 10);
+        client.close_goal(&owner, &closed_ids.get(2).unwrap());
+        create_funded_goal(&env, &client, &owner, 50);
+        assert_eq!(client.get_lifetime_savings(&owner), 650);
+
+        // Only goals closed before the cutoff are archived, `limit` at a time.
+        assert_eq!(client.archive_completed_goals(&cutoff, &1), 1);
+        assert_eq!(client.archive_completed_goals(&cutoff, &10), 1);
+        assert_eq!(client.archive_completed_goals(&cutoff, &10), 0);
+
+        let summary = client.get_archived_savings(&owner);
+        assert_eq!(summary.goal_count, 2);
+        assert_eq!(summary.total_released, 300);
+        assert_eq!(client.get_closed_goals(&owner, &0, &10).len(), 1);
+        assert_eq!(client.get_lifetime_savings(&owner), 650);
+    }
+
+    #[test]
+    fn test_archive_paid_out_goals() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let family = Address::generate(&env);
+        let token = setup_token(&env, &owner, 2_000);
+
+        let mut goal_ids = Vec::new(&env);
+        for threshold in [1_000i128, 0] {
+            let goal_id = client.create_goal(
+                &owner,
+                &String::from_str(&env, "Goal"),
+                &1_000,
+                &(env.ledger().timestamp() + 86400 * 365),
+            );
+            client.set_payout_target(&owner, &goal_id, &token, &family);
+            assert_eq!(
+                client.sweep_to_goal(&owner, &token, &threshold, &goal_id),
+                1_000
+            );
+            goal_ids.push_back(goal_id);
+        }
+        // The second goal takes new funds after its payout, so it stays.
+        let (paid, refilled) = (goal_ids.get(0).unwrap(), goal_ids.get(1).unwrap());
+        client.add_to_goal(&owner, &refilled, &None, &50);
+        assert_eq!(client.get_lifetime_savings(&owner), 2_050);
+
+        let cutoff = env.ledger().timestamp() + 1;
+        env.ledger().with_mut(|li| li.timestamp += 10);
+        assert_eq!(client.archive_completed_goals(&cutoff, &10), 1);
+        assert_eq!(client.archive_completed_goals(&cutoff, &10), 0);
+
+        assert!(client.get_goal(&paid).is_none());
+        assert!(client.get_goal(&refilled).is_some());
+        let summary = client.get_archived_savings(&owner);
+        assert_eq!(summary.goal_count, 1);
+        assert_eq!(summary.total_released, 1_000);
+        assert_eq!(client.get_lifetime_savings(&owner), 2_050);
+    }
+
+    #[test]
+    fn test_migrate_legacy_storage() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+
+        let legacy_goal = |goal_id: u32, current_amount: i128, locked: bool| LegacySavingsGoal {
+            id: goal_id,
+            owner: owner.clone(),
+            name: String::from_str(&env, "Legacy"),
+            target_amount: 1_000,
+            current_amount,
+            target_date: 86_400,
+            locked,
+            unlock_date: None,
+            tags: Vec::new(&env),
+        };
+        let closed_goal = SavingsGoal {
+            id: 3,
+            owner: owner.clone(),
+            name: String::from_str(&env, "Closed"),
+            target_amount: 1_000,
+            current_amount: 0,
+            target_date: 86_400,
+            lock_policy: LockPolicy::Unlocked,
+            unlock_date: None,
+            tags: Vec::new(&env),
+            shared: false,
+            weight: 1,
+            category: GoalCategory::General,
+            asset: None,
+            guardian: None,
+            last_accrual: 0,
+            created_at: 0,
+        };
+        env.as_contract(&id, || {
+            let storage = env.storage().instance();
+            let mut goals = Map::new(&env);
+            goals.set(1u32, legacy_goal(1, 250, true));
+            goals.set(2u32, legacy_goal(2, 0, false));
+            storage.set(&symbol_short!("GOALS"), &goals);
+            let mut own = Map::new(&env);
+            own.set(owner.clone(), Vec::from_array(&env, [1u32, 2]));
+            storage.set(&symbol_short!("OWN_GOAL"), &own);
+            storage.set(&symbol_short!("NEXT_ID"), &3u32);
+
+            let mut frozen = Map::new(&env);
+            frozen.set(1u32, 5u64);
+            storage.set(&symbol_short!("FROZEN"), &frozen);
+            let mut closed = Map::new(&env);
+            closed.set(
+                owner.clone(),
+                Vec::from_array(
+                    &env,
+                    [ClosedGoal {
+                        goal: closed_goal,
+                        released: 400,
+                        closed_at: 7,
+                    }],
+                ),
+            );
+            storage.set(&symbol_short!("CLOSED"), &closed);
+        });
+
+        assert_eq!(
+            client.try_migrate_legacy_storage(&owner, &10),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        assert_eq!(client.migrate_legacy_storage(&admin, &2), 2);
+        assert_eq!(client.migrate_legacy_storage(&admin, &10), 0);
+
+        let goal = client.get_goal(&1).unwrap();
+        assert_eq!(goal.current_amount, 250);
+        assert_eq!(goal.lock_policy, LockPolicy::Soft);
+        assert_eq!(
+            client.get_goal(&2).unwrap().lock_policy,
+            LockPolicy::Unlocked
+        );
+        assert_eq!(client.get_all_goals(&owner).len(), 2);
+        assert!(client.try_add_to_goal(&owner, &1, &None, &10).is_err());
+        assert_eq!(client.get_closed_goals(&owner, &0, &10).len(), 1);
+        assert_eq!(client.get_lifetime_savings(&owner), 650);
+        env.as_contract(&id, || {
+            assert!(!env.storage().instance().has(&symbol_short!("GOALS")));
+            assert!(!env.storage().instance().has(&symbol_short!("FROZEN")));
+        });
+
+        // New goals continue after the legacy IDs.
+        let goal_id = client.create_goal(&owner, &String::from_str(&env, "New"), &1_000, &86_400);
+        assert_eq!(goal_id, 4);
+        assert_eq!(client.archive_completed_goals(&10, &10), 1);
+        assert_eq!(client.get_archived_savings(&owner).total_released, 400);
+    }
+
+    // --- get_savings_stats ---
+
+    #[test]
+    fn test_savings_stats_over_a_range() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let start = env.ledger().timestamp();
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 3_000);
+        client.unlock_goal(&owner, &goal_id);
+        client.withdraw_from_goal(&owner, &goal_id, &None, &1_000);
+        create_funded_goal(&env, &client, &owner, 10_000);
+
+        env.ledger().with_mut(|li| li.timestamp += 30 * 86_400);
+        client.add_to_goal(&owner, &goal_id, &None, &2_000);
+        let end = env.ledger().timestamp();
+
+        let stats = client.get_savings_stats(&owner, &start, &end);
+        assert_eq!(stats.total_contributed, 15_000);
+        assert_eq!(stats.total_withdrawn, 1_000);
+        assert_eq!(stats.net_saved, 14_000);
+        assert_eq!(stats.active_goals, 1);
+        assert_eq!(stats.average_monthly_savings, 7_000);
+
+        let later = client.get_savings_stats(&owner, &end, &end);
+        assert_eq!(later.total_contributed, 2_000);
+        assert_eq!(later.total_withdrawn, 0);
+        assert_eq!(
+            client.try_get_savings_stats(&owner, &end, &start),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+    }
+
+    // --- guardian / emergency_unlock ---
+
+    #[test]
+    #[should_panic(expected = "Goal is locked until its target date")]
+    fn test_guarded_goal_cannot_unlock_before_target_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        client.set_guardian(&owner, &goal_id, &Some(guardian));
+        client.unlock_goal(&owner, &goal_id);
+    }
+
+    #[test]
+    fn test_emergency_unlock_with_guardian() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        assert_eq!(
+            client.try_emergency_unlock(&owner, &goal_id),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+
+        client.set_guardian(&owner, &goal_id, &Some(guardian.clone()));
+        assert_eq!(
+            client.try_withdraw_early(&owner, &goal_id, &100),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+
+        client.emergency_unlock(&owner, &goal_id);
+        assert!(env.auths().iter().any(|(addr, _)| *addr == guardian));
+        assert_eq!(
+            client.get_goal(&goal_id).unwrap().lock_policy,
+            LockPolicy::Unlocked
+        );
+        assert_eq!(
+            client.withdraw_from_goal(&owner, &goal_id, &None, &1_000),
+            0
+        );
+    }
+
+    #[test]
+    fn test_guarded_goal_unlocks_after_target_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        client.set_guardian(&owner, &goal_id, &Some(Address::generate(&env)));
+        let target_date = client.get_goal(&goal_id).unwrap().target_date;
+        env.ledger().set_timestamp(target_date);
+        assert!(client.unlock_goal(&owner, &goal_id));
+    }
+
+    // --- freeze_goal ---
+
+    #[test]
+    fn test_frozen_goal_blocks_contributions_and_withdrawals() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let frozen = create_funded_goal(&env, &client, &owner, 1_000);
+        let other = create_funded_goal(&env, &client, &owner, 1_000);
+        client.unlock_goal(&owner, &frozen);
+        client.unlock_goal(&owner, &other);
+
+        assert_eq!(
+            client.try_freeze_goal(&Address::generate(&env), &frozen),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.freeze_goal(&owner, &frozen);
+        assert!(client.is_goal_frozen(&frozen));
+
+        assert_eq!(
+            client.try_add_to_goal(&owner, &frozen, &None, &100),
+            Err(Ok(SavingsGoalsError::GoalFrozen))
+        );
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &frozen, &None, &100),
+            Err(Ok(SavingsGoalsError::GoalFrozen))
+        );
+        assert_eq!(
+            client.try_transfer_between_goals(&owner, &other, &frozen, &100),
+            Err(Ok(SavingsGoalsError::GoalFrozen))
+        );
+        assert_eq!(
+            client.try_close_goal(&owner, &frozen),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+
+        // Other goals keep working.
+        client.add_to_goal(&owner, &other, &None, &100);
+        client.withdraw_from_goal(&owner, &other, &None, &100);
+
+        client.unfreeze_goal(&owner, &frozen);
+        assert!(!client.is_goal_frozen(&frozen));
+        assert_eq!(client.withdraw_from_goal(&owner, &frozen, &None, &100), 900);
+    }
+
+    // --- lock policies ---
+
+    #[test]
+    fn test_hard_lock_holds_until_target_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+        client.set_lock_policy(&owner, &goal_id, &LockPolicy::Hard);
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+        assert_eq!(
+            client.try_withdraw_early(&owner, &goal_id, &100),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+        assert_eq!(
+            client.try_set_lock_policy(&owner, &goal_id, &LockPolicy::Unlocked),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+
+        let target_date = client.get_goal(&goal_id).unwrap().target_date;
+        env.ledger().set_timestamp(target_date);
+        assert_eq!(
+            client.withdraw_from_goal(&owner, &goal_id, &None, &100),
+            900
+        );
+    }
+
+    #[test]
+    fn test_guardian_lock_requires_cosign() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 10_000);
+        assert_eq!(
+            client.try_set_lock_policy(&owner, &goal_id, &LockPolicy::Guardian),
+            Err(Ok(SavingsGoalsError::GuardianNotSet))
+        );
+        client.set_guardian(&owner, &goal_id, &Some(guardian.clone()));
+        client.set_lock_policy(&owner, &goal_id, &LockPolicy::Guardian);
+
+        assert_eq!(
+            client.withdraw_from_goal(&owner, &goal_id, &None, &400),
+            9_600
+        );
+        assert!(env.auths().iter().any(|(addr, _)| *addr == guardian));
+
+        // Dropping the guardian falls back to a soft lock.
+        client.set_guardian(&owner, &goal_id, &None);
+        assert_eq!(
+            client.get_goal(&goal_id).unwrap().lock_policy,
+            LockPolicy::Soft
+        );
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &None, &100),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+    }
+
+    // --- contribution schedules ---
+
+    fn setup_token(env: &Env, owner: &Address, amount: i128) -> Address {
+        let admin = Address::generate(env);
+        let token = env.register_stellar_asset_contract_v2(admin).address();
+        StellarAssetClient::new(env, &token).mint(owner, &amount);
+        token
+    }
+
+    #[test]
+    fn test_process_due_contributions_pulls_from_allowance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        TokenClient::new(&env, &token).approve(&owner, &id, &250, &1_000);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        client.set_contribution_schedule(&owner, &goal_id, &token, &100, &7);
+
+        assert_eq!(client.process_due_contributions(&10), 1);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 200);
+        assert_eq!(TokenClient::new(&env, &token).balance(&id), 100);
+        // Not due again until a week has passed.
+        assert_eq!(client.process_due_contributions(&10), 0);
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 7 * 86_400);
+        assert_eq!(client.process_due_contributions(&10), 1);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 7 * 86_400);
+        // Only 50 of the allowance remains, so this pull fails and is skipped.
+        assert_eq!(client.process_due_contributions(&10), 0);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 300);
+        let schedule = client.get_contribution_schedule(&goal_id).unwrap();
+        assert!(schedule.next_due > env.ledger().timestamp());
+    }
+
+    #[test]
+    fn test_set_contribution_schedule_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 0);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        assert_eq!(
+            client.try_set_contribution_schedule(&owner, &goal_id, &token, &100, &0),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_set_contribution_schedule(
+                &Address::generate(&env),
+                &goal_id,
+                &token,
+                &100,
+                &7
+            ),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.set_contribution_schedule(&owner, &goal_id, &token, &100, &7);
+        assert!(client.get_contribution_schedule(&goal_id).is_some());
+        client.set_contribution_schedule(&owner, &goal_id, &token, &0, &0);
+        assert!(client.get_contribution_schedule(&goal_id).is_none());
+    }
+
+    // --- contribute_roundup ---
+
+    #[test]
+    fn test_contribute_roundup_credits_difference() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        assert_eq!(
+            client.try_contribute_roundup(&owner, &1_234, &100),
+            Err(Ok(SavingsGoalsError::RoundupGoalNotSet))
+        );
+
+        client.set_roundup_goal(&owner, &goal_id);
+        assert_eq!(client.contribute_roundup(&owner, &1_234, &100), 66);
+        assert_eq!(client.contribute_roundup(&owner, &1_200, &100), 0);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 166);
+        assert_eq!(client.get_contributions_by(&goal_id, &owner), 166);
+        assert_eq!(
+            client.try_contribute_roundup(&owner, &1_234, &0),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+    }
+
+    // --- sweep_to_goal ---
+
+    #[test]
+    fn test_sweep_moves_balance_above_threshold() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_500);
+        let goal_id = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Leftovers"),
+            &10_000,
+            &(env.ledger().timestamp() + 86400 * 365),
+        );
+        client.set_goal_asset(&owner, &goal_id, &token);
+
+        assert_eq!(client.sweep_to_goal(&owner, &token, &1_000, &goal_id), 500);
+        assert_eq!(TokenClient::new(&env, &token).balance(&owner), 1_000);
+        assert_eq!(TokenClient::new(&env, &token).balance(&id), 500);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 500);
+
+        // Nothing left above the threshold.
+        assert_eq!(client.sweep_to_goal(&owner, &token, &1_000, &goal_id), 0);
+
+        let other_token = setup_token(&env, &owner, 100);
+        assert_eq!(
+            client.try_sweep_to_goal(&owner, &other_token, &0, &goal_id),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
+        assert_eq!(
+            client.try_sweep_to_goal(&owner, &token, &-1, &goal_id),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+    }
+
+    // --- get_goals_page / get_active_goals_page ---
+
+    #[test]
+    fn test_get_goals_page_with_counts() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 5);
+        setup_goals(&env, &client, &other, 2);
+
+        let page = client.get_goals_page(&owner, &0, &2);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.next_offset, 2);
+        assert_eq!(page.items.get(0).unwrap().id, 1);
+
+        let last = client.get_goals_page(&owner, &4, &2);
+        assert_eq!(last.items.len(), 1);
+        assert_eq!(last.next_offset, 0);
+    }
+
+    #[test]
+    fn test_get_active_goals_page_skips_completed() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 3);
+        client.add_to_goal(&owner, &1, &None, &1_000);
+
+        let page = client.get_active_goals_page(&owner, &0, &10);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items.get(0).unwrap().id, 2);
+        assert_eq!(page.next_offset, 0);
+        assert_eq!(client.get_goals_page(&owner, &0, &10).total, 3);
+    }
+
+    // --- get_goal_projection ---
+
+    #[test]
+    fn test_goal_projection_on_track_and_behind() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let start = env.ledger().timestamp();
+        let goal_id = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &1_200,
+            &(start + 6 * PROJECTION_PERIOD),
+        );
+        assert_eq!(client.get_goal_projection(&goal_id).projected_completion, 0);
+
+        // 400 over two periods: 200 per 30 days, 800 left takes 4 periods.
+        client.add_to_goal(&owner, &goal_id, &None, &400);
+        env.ledger().set_timestamp(start + 2 * PROJECTION_PERIOD);
+        let projection = client.get_goal_projection(&goal_id);
+        assert_eq!(projection.percent_complete, 33);
+        assert_eq!(projection.avg_per_30_days, 200);
+        assert_eq!(
+            projection.projected_completion,
+            start + 6 * PROJECTION_PERIOD
+        );
+        assert!(projection.on_track);
+
+        env.ledger().set_timestamp(start + 4 * PROJECTION_PERIOD);
+        let behind = client.get_goal_projection(&goal_id);
+        assert_eq!(behind.avg_per_30_days, 100);
+        assert!(!behind.on_track);
+
+        assert_eq!(
+            client.try_get_goal_projection(&99),
+            Err(Ok(SavingsGoalsError::GoalNotFound))
+        );
+    }
+
+    // --- batch_withdraw ---
+
+    #[test]
+    fn test_batch_withdraw_returns_per_item_results() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let a = create_funded_goal(&env, &client, &owner, 500);
+        let b = create_funded_goal(&env, &client, &owner, 300);
+        client.unlock_goal(&owner, &a);
+        client.unlock_goal(&owner, &b);
+
+        let items = Vec::from_array(
+            &env,
+            [
+                WithdrawalItem {
+                    goal_id: a,
+                    amount: 200,
+                },
+                WithdrawalItem {
+                    goal_id: b,
+                    amount: 300,
+                },
+                WithdrawalItem {
+                    goal_id: a,
+                    amount: 100,
+                },
+            ],
+        );
+        let results = client.batch_withdraw(&owner, &items);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.get(0).unwrap().remaining, 300);
+        assert_eq!(results.get(1).unwrap().remaining, 0);
+        assert_eq!(results.get(2).unwrap().remaining, 200);
+        assert_eq!(client.get_goal(&a).unwrap().current_amount, 200);
+    }
+
+    #[test]
+    fn test_batch_withdraw_is_atomic() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let open = create_funded_goal(&env, &client, &owner, 500);
+        let locked = create_funded_goal(&env, &client, &owner, 500);
+        client.unlock_goal(&owner, &open);
+
+        let items = Vec::from_array(
+            &env,
+            [
+                WithdrawalItem {
+                    goal_id: open,
+                    amount: 500,
+                },
+                WithdrawalItem {
+                    goal_id: locked,
+                    amount: 100,
+                },
+            ],
+        );
+        assert_eq!(
+            client.try_batch_withdraw(&owner, &items),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
+        assert_eq!(client.get_goal(&open).unwrap().current_amount, 500);
+    }
+
+    // --- sponsor matching ---
+
+    #[test]
+    fn test_sponsor_match_pulls_until_cap() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let token = setup_token(&env, &sponsor, 1_000);
+        TokenClient::new(&env, &token).approve(&sponsor, &id, &1_000, &1_000);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        client.set_sponsor_match(&sponsor, &goal_id, &token, &5_000, &300);
+
+        assert_eq!(client.add_to_goal(&owner, &goal_id, &None, &400), 700);
+        assert_eq!(client.add_to_goal(&owner, &goal_id, &None, &400), 1_200);
+        assert_eq!(client.add_to_goal(&owner, &goal_id, &None, &400), 1_600);
+
+        let sponsor_match = client.get_sponsor_match(&goal_id).unwrap();
+        assert_eq!(sponsor_match.matched, 300);
+        assert_eq!(client.get_contributions_by(&goal_id, &sponsor), 300);
+        assert_eq!(TokenClient::new(&env, &token).balance(&id), 300);
+    }
+
+    #[test]
+    fn test_sponsor_match_skips_when_allowance_missing() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let sponsor = Address::generate(&env);
+        let token = setup_token(&env, &sponsor, 1_000);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        client.set_sponsor_match(&sponsor, &goal_id, &token, &10_000, &500);
+        assert_eq!(
+            client.try_set_sponsor_match(&owner, &goal_id, &token, &10_000, &500),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+
+        assert_eq!(client.add_to_goal(&owner, &goal_id, &None, &100), 200);
+        assert_eq!(client.get_sponsor_match(&goal_id).unwrap().matched, 0);
+
+        client.remove_sponsor_match(&sponsor, &goal_id);
+        assert!(client.get_sponsor_match(&goal_id).is_none());
+    }
+
+    // --- payout targets ---
+
+    #[test]
+    fn test_completed_goal_pays_out_to_target() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let family = Address::generate(&env);
+        let token = setup_token(&env, &owner, 10_000);
+
+        let goal_id = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Goal"),
+            &10_000,
+            &(env.ledger().timestamp() + 86400 * 365),
+        );
+        assert_eq!(
+            client.try_set_payout_target(&family, &goal_id, &token, &family),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.set_payout_target(&owner, &goal_id, &token, &family);
+
+        assert_eq!(client.sweep_to_goal(&owner, &token, &0, &goal_id), 10_000);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+        assert_eq!(client.get_held_balance(&goal_id, &token), 0);
+        assert_eq!(TokenClient::new(&env, &token).balance(&family), 10_000);
+
+        let events = env.events().all();
+        let (_, _, data) = events
+            .iter()
+            .find(|(_, topics, _)| *topics == (GOAL_PAID_OUT,).into_val(&env))
+            .unwrap();
+        let event = GoalPaidOutEvent::try_from_val(&env, &data).unwrap();
+        assert_eq!(event.recipient, family);
+        assert_eq!(event.amount, 10_000);
+    }
+
+    #[test]
+    fn test_payout_only_sends_tokens_held_for_the_goal() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let family = Address::generate(&env);
+        // Tokens the contract holds for someone else must not back this goal.
+        let token = setup_token(&env, &id, 10_000);
+        StellarAssetClient::new(&env, &token).mint(&owner, &4_000);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 6_000);
+        client.set_payout_target(&owner, &goal_id, &token, &family);
+        assert_eq!(client.sweep_to_goal(&owner, &token, &0, &goal_id), 4_000);
+
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 6_000);
+        assert_eq!(TokenClient::new(&env, &token).balance(&family), 4_000);
+        assert_eq!(TokenClient::new(&env, &token).balance(&id), 10_000);
+        assert_eq!(client.get_held_balance(&goal_id, &token), 0);
+    }
+
+    #[test]
+    fn test_failed_payout_leaves_funds_parked() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let family = Address::generate(&env);
+        // Nothing was transferred in for the goal, so nothing is paid out.
+        let token = setup_token(&env, &owner, 10_000);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 6_000);
+        client.set_payout_target(&owner, &goal_id, &token, &family);
+        client.add_to_goal(&owner, &goal_id, &None, &4_000);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 10_000);
+        assert_eq!(TokenClient::new(&env, &token).balance(&family), 0);
+
+        client.clear_payout_target(&owner, &goal_id);
+        assert!(client.get_payout_target(&goal_id).is_none());
+    }
+
+    // --- beneficiary ---
+
+    #[test]
+    fn test_beneficiary_claims_after_owner_inactivity() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let heir = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 2_500);
+        client.sweep_to_goal(&owner, &token, &0, &goal_id);
+        client.set_beneficiary(&owner, &goal_id, &heir, &90);
+        assert_eq!(
+            client.try_claim_as_beneficiary(&heir, &goal_id),
+            Err(Ok(SavingsGoalsError::OwnerStillActive))
+        );
+
+        // A check-in just before the deadline restarts the clock.
+        let start = env.ledger().timestamp();
+        env.ledger().set_timestamp(start + 89 * 86400);
+        client.check_in(&owner);
+        env.ledger().set_timestamp(start + 91 * 86400);
+        assert_eq!(
+            client.try_claim_as_beneficiary(&heir, &goal_id),
+            Err(Ok(SavingsGoalsError::OwnerStillActive))
+        );
+
+        env.ledger().set_timestamp(start + 179 * 86400);
+        assert_eq!(
+            client.try_claim_as_beneficiary(&Address::generate(&env), &goal_id),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        assert_eq!(client.claim_as_beneficiary(&heir, &goal_id), 3_500);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+        assert_eq!(TokenClient::new(&env, &token).balance(&heir), 1_000);
+        assert_eq!(client.get_held_balance(&goal_id, &token), 0);
+    }
+
+    #[test]
+    fn test_owner_activity_resets_beneficiary_clock() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let heir = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        assert_eq!(
+            client.try_set_beneficiary(&owner, &goal_id, &heir, &0),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
+        client.set_beneficiary(&owner, &goal_id, &heir, &30);
+
+        let later = env.ledger().timestamp() + 29 * 86400;
+        env.ledger().set_timestamp(later);
+        client.add_to_goal(&owner, &goal_id, &None, &100);
+        assert_eq!(client.get_last_active(&owner), Some(later));
+
+        client.remove_beneficiary(&owner, &goal_id);
+        assert!(client.get_beneficiary(&goal_id).is_none());
+    }
+
+    // --- contribution caps ---
+
+    #[test]
+    fn test_goal_contribution_cap_rolling_window() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        client.set_goal_contribution_cap(&owner, &goal_id, &500);
+
+        client.add_to_goal(&owner, &goal_id, &None, &300);
+        assert_eq!(
+            client.try_add_to_goal(&owner, &goal_id, &None, &201),
+            Err(Ok(SavingsGoalsError::ContributionCapExceeded))
+        );
+        client.add_to_goal(&owner, &goal_id, &None, &200);
+        assert_eq!(client.get_goal_contribution_cap(&goal_id), Some((500, 0)));
+
+        // Once the first contribution leaves the window its room frees up.
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + CAP_WINDOW);
+        assert_eq!(client.get_goal_contribution_cap(&goal_id), Some((500, 500)));
+        client.add_to_goal(&owner, &goal_id, &None, &500);
+    }
+
+    #[test]
+    fn test_owner_contribution_cap_spans_goals() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 2);
+        client.set_owner_contribution_cap(&owner, &1_000);
+        client.add_to_goal(&owner, &1, &None, &600);
+        assert_eq!(
+            client.try_add_to_goal(&owner, &2, &None, &500),
+            Err(Ok(SavingsGoalsError::ContributionCapExceeded))
+        );
+        assert_eq!(
+            client.get_owner_contribution_cap(&owner),
+            Some((1_000, 400))
+        );
+
+        client.set_owner_contribution_cap(&owner, &0);
+        assert_eq!(client.get_owner_contribution_cap(&owner), None);
+        client.add_to_goal(&owner, &2, &None, &500);
+    }
+
+    // --- goal storage ---
+
+    #[test]
+    fn test_goals_stored_per_key_with_owner_index() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 3);
+        setup_goals(&env, &client, &other, 1);
+        env.as_contract(&id, || {
+            let stored: Option<SavingsGoal> = env
+                .storage()
+                .persistent()
+                .get(&(symbol_short!("GOAL"), 2u32));
+            assert_eq!(stored.unwrap().target_amount, 2000);
+            assert_eq!(
+                SavingsGoalContract::owner_goal_ids(&env, &owner),
+                soroban_sdk::vec![&env, 1u32, 2, 3]
+            );
+        });
+
+        client.close_goal(&owner, &2);
+        env.as_contract(&id, || {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&(symbol_short!("GOAL"), 2u32)));
+            assert_eq!(
+                SavingsGoalContract::owner_goal_ids(&env, &owner),
+                soroban_sdk::vec![&env, 1u32, 3]
+            );
+        });
+
+        // Import replaces every stored goal and rebuilds the index.
+        let snapshot = client.export_snapshot(&owner);
+        client.close_goal(&owner, &3);
+        client.import_snapshot(&owner, &0, &snapshot);
+        assert_eq!(client.get_all_goals(&owner).len(), 2);
+        assert_eq!(client.get_all_goals(&other).len(), 1);
+        assert!(client.get_goal(&2).is_none());
+    }
+
+    // --- interest ---
+
+    #[test]
+    fn test_accrue_interest_credits_locked_time() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+
+        client.set_upgrade_admin(&admin, &admin);
+        assert_eq!(
+            client.try_set_interest_rate(&owner, &500),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.set_interest_rate(&admin, &500);
+
+        let goal_id = create_funded_goal(&env, &client, &owner, 2_000);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + SECONDS_PER_YEAR);
+        assert_eq!(client.accrue_interest(&goal_id), 100);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 2_100);
+        // Nothing further is owed for the same instant.
+        assert_eq!(client.accrue_interest(&goal_id), 0);
+
+        let history = client.get_accrual_history(&goal_id);
+        assert_eq!(history.len(), 1);
+        let entry = history.get(0).unwrap();
+        assert_eq!(entry.amount, 100);
+        assert_eq!(entry.rate_bps, 500);
+        assert_eq!(entry.to - entry.from, SECONDS_PER_YEAR);
+    }
+
+    #[test]
+    fn test_batch_accrue_interest_skips_unlocked_time() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_interest_rate(&admin, &1_000);
+
+        let locked = create_funded_goal(&env, &client, &owner, 1_000);
+        let unlocked = create_funded_goal(&env, &client, &owner, 1_000);
+        client.unlock_goal(&owner, &unlocked);
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + SECONDS_PER_YEAR);
+        let ids = Vec::from_array(&env, [locked, unlocked]);
+        assert_eq!(client.batch_accrue_interest(&ids), 100);
+        assert_eq!(client.get_goal(&unlocked).unwrap().current_amount, 1_000);
+        assert_eq!(client.get_accrual_history(&unlocked).len(), 0);
+    }
+
+    #[test]
+    fn test_rate_changes_and_deposits_settle_interest_first() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_interest_rate(&admin, &1_000);
+        let goal_id = create_funded_goal(&env, &client, &owner, 1_000);
+
+        // A year at 10% then a year at 0%: the cut is not retroactive.
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + SECONDS_PER_YEAR);
+        client.set_interest_rate(&admin, &0);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + SECONDS_PER_YEAR);
+        assert_eq!(client.accrue_interest(&goal_id), 100);
+        let entry = client.get_accrual_history(&goal_id).get(0).unwrap();
+        assert_eq!(entry.rate_bps, 500);
+
+        // A deposit settles interest on the old balance before adding to it.
+        client.set_interest_rate(&admin, &1_000);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + SECONDS_PER_YEAR);
+        client.add_to_goal(&owner, &goal_id, &None, &1_000);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 2_210);
+        assert_eq!(client.accrue_interest(&goal_id), 0);
+    }
+
+    #[test]
+    fn test_hard_lock_stops_earning_at_target_date() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_interest_rate(&admin, &500);
 
-        if schedule.owner != caller {
-            panic!("Only the schedule owner can modify it");
-        }
+        // Target date one year out.
+        let goal_id = create_funded_goal(&env, &client, &owner, 2_000);
+        client.set_lock_policy(&owner, &goal_id, &LockPolicy::Hard);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 2 * SECONDS_PER_YEAR);
+        assert_eq!(client.accrue_interest(&goal_id), 100);
+    }
 
-        schedule.amount = amount;
-        schedule.next_due = next_due;
-        schedule.interval = interval;
-        schedule.recurring = interval > 0;
+    // --- yield ---
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+    /// Vault stand-in that pays 10% on every withdrawal.
+    #[contract]
+    pub struct MockYieldVault;
 
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleModified),
-            (schedule_id, caller),
-        );
+    #[contractimpl]
+    impl MockYieldVault {
+        pub fn deposit(_env: Env, _from: Address, _token: Address, _amount: i128) {}
 
-        true
+        pub fn withdraw(env: Env, to: Address, token: Address, amount: i128) -> i128 {
+            let returned = amount + amount / 10;
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &to,
+                &returned,
+            );
+            returned
+        }
     }
 
-    pub fn cancel_savings_schedule(env: Env, caller: Address, schedule_id: u32) -> bool {
-        caller.require_auth();
+    #[test]
+    fn test_deploy_and_recall_yield() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let vault = env.register_contract(None, MockYieldVault);
+        let token = setup_token(&env, &owner, 2_000);
+        StellarAssetClient::new(&env, &token).mint(&vault, &1_000);
 
-        Self::extend_instance_ttl(&env);
+        let goal_id = client.create_goal(
+            &owner,
+            &String::from_str(&env, "Land"),
+            &10_000,
+            &(env.ledger().timestamp() + 86400 * 365 * 5),
+        );
+        client.set_goal_asset(&owner, &goal_id, &token);
+        client.sweep_to_goal(&owner, &token, &0, &goal_id);
+        let plain_goal = create_funded_goal(&env, &client, &owner, 100);
+        assert_eq!(
+            client.try_deploy_to_yield(&owner, &goal_id, &500),
+            Err(Ok(SavingsGoalsError::YieldAdapterNotSet))
+        );
+        client.set_upgrade_admin(&admin, &admin);
+        assert_eq!(
+            client.try_set_yield_adapter(&owner, &vault),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.set_yield_adapter(&admin, &vault);
+        assert_eq!(
+            client.try_deploy_to_yield(&owner, &plain_goal, &50),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        // Interest is recorded without a token transfer, so it cannot be
+        // deployed.
+        client.set_interest_rate(&admin, &1_000);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + SECONDS_PER_YEAR);
+        assert_eq!(client.accrue_interest(&goal_id), 200);
+        assert_eq!(
+            client.try_deploy_to_yield(&owner, &goal_id, &2_100),
+            Err(Ok(SavingsGoalsError::InsufficientBalance))
+        );
 
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+        assert_eq!(client.deploy_to_yield(&owner, &goal_id, &1_500), 1_500);
+        assert_eq!(client.get_held_balance(&goal_id, &token), 500);
+        assert_eq!(TokenClient::new(&env, &token).balance(&vault), 2_500);
 
-        if schedule.owner != caller {
-            panic!("Only the schedule owner can cancel it");
-        }
+        // Deployed funds still count towards the goal but cannot leave it.
+        client.unlock_goal(&owner, &goal_id);
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 2_200);
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &goal_id, &Some(token.clone()), &1_600),
+            Err(Ok(SavingsGoalsError::InsufficientBalance))
+        );
+        assert_eq!(
+            client.try_close_goal(&owner, &goal_id),
+            Err(Ok(SavingsGoalsError::GoalLocked))
+        );
 
-        schedule.active = false;
+        assert_eq!(client.recall_from_yield(&owner, &goal_id, &1_000), 1_100);
+        assert_eq!(
+            client.get_yield_position(&goal_id),
+            Some(YieldPosition {
+                token: token.clone(),
+                principal: 500,
+                earned: 100,
+            })
+        );
+        assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 2_300);
+        assert_eq!(client.get_held_balance(&goal_id, &token), 1_600);
+        assert_eq!(
+            client.try_recall_from_yield(&owner, &goal_id, &501),
+            Err(Ok(SavingsGoalsError::InsufficientBalance))
+        );
+    }
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+    // --- savings circles ---
 
-        env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
-            (schedule_id, caller),
+    #[test]
+    fn test_savings_circle_rotates_payouts() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        let token = setup_token(&env, &a, 300);
+        StellarAssetClient::new(&env, &token).mint(&b, &300);
+        StellarAssetClient::new(&env, &token).mint(&c, &300);
+        let tc = TokenClient::new(&env, &token);
+
+        let members = soroban_sdk::vec![&env, a.clone(), b.clone(), c.clone()];
+        let dup = soroban_sdk::vec![&env, a.clone(), a.clone()];
+        assert_eq!(
+            client.try_create_circle(&a, &token, &dup, &100, &7),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
         );
+        let circle_id = client.create_circle(&a, &token, &members, &100, &7);
 
-        true
+        // Round 0: everyone pays, so the pot goes to `a` right away, less
+        // the two rounds `a` still owes.
+        assert_eq!(client.contribute_to_circle(&a, &circle_id), 0);
+        assert_eq!(
+            client.try_contribute_to_circle(&a, &circle_id),
+            Err(Ok(SavingsGoalsError::AlreadyContributed))
+        );
+        assert_eq!(
+            client.try_contribute_to_circle(&Address::generate(&env), &circle_id),
+            Err(Ok(SavingsGoalsError::NotCircleMember))
+        );
+        client.contribute_to_circle(&b, &circle_id);
+        assert_eq!(client.contribute_to_circle(&c, &circle_id), 100);
+        assert_eq!(tc.balance(&a), 300);
+        let circle = client.get_circle(&circle_id).unwrap();
+        assert_eq!(circle.current_round, 1);
+        assert_eq!(circle.collateral.get(a.clone()), Some(200));
+
+        // Round 1: `c` misses, and `b` receives what was collected once the
+        // deadline passes.
+        client.contribute_to_circle(&a, &circle_id);
+        client.contribute_to_circle(&b, &circle_id);
+        assert_eq!(
+            client.try_close_circle_round(&circle_id),
+            Err(Ok(SavingsGoalsError::RoundStillOpen))
+        );
+        env.ledger().with_mut(|li| li.timestamp += 7 * 86_400);
+        assert_eq!(client.close_circle_round(&circle_id), 100);
+        assert_eq!(tc.balance(&b), 200);
+        assert_eq!(client.get_circle_missed(&circle_id, &c), 1);
+        assert_eq!(client.get_circle_missed(&circle_id, &a), 0);
+
+        // Round 2: `a`, already paid out, stops paying, and its collateral
+        // covers the round. The pot pays `c`, completes the circle and
+        // returns the remaining collateral.
+        client.contribute_to_circle(&b, &circle_id);
+        client.contribute_to_circle(&c, &circle_id);
+        env.ledger().with_mut(|li| li.timestamp += 7 * 86_400);
+        assert_eq!(client.close_circle_round(&circle_id), 300);
+        assert_eq!(client.get_circle_missed(&circle_id, &a), 0);
+        assert_eq!(tc.balance(&a), 300);
+        assert_eq!(tc.balance(&b), 200);
+        assert_eq!(tc.balance(&c), 400);
+        assert_eq!(tc.balance(&id), 0);
+        assert!(client.get_circle(&circle_id).unwrap().completed);
+        assert_eq!(
+            client.try_contribute_to_circle(&a, &circle_id),
+            Err(Ok(SavingsGoalsError::CircleCompleted))
+        );
     }
 
-    pub fn execute_due_savings_schedules(env: Env) -> Vec<u32> {
-        Self::extend_instance_ttl(&env);
-
-        let current_time = env.ledger().timestamp();
-        let mut executed = Vec::new(&env);
+    // --- update_goal_target ---
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    #[test]
+    fn test_update_goal_target_logs_changes() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let goal_id = create_funded_goal(&env, &client, &owner, 4_000);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        assert_eq!(
+            client.try_update_goal_target(&Address::generate(&env), &goal_id, &5_000),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        assert_eq!(
+            client.try_update_goal_target(&owner, &goal_id, &0),
+            Err(Ok(SavingsGoalsError::InvalidAmount))
+        );
 
-        for (schedule_id, mut schedule) in schedules.iter() {
-            if !schedule.active || schedule.next_due > current_time {
-                continue;
-            }
+        client.update_goal_target(&owner, &goal_id, &20_000);
+        env.ledger().with_mut(|li| li.timestamp += 100);
+        client.update_goal_target(&owner, &goal_id, &3_000);
 
-            if let Some(mut goal) = goals.get(schedule.goal_id) {
-                goal.current_amount = goal
-                    .current_amount
-                    .checked_add(schedule.amount)
-                    .expect("overflow");
+        let goal = client.get_goal(&goal_id).unwrap();
+        assert_eq!(goal.target_amount, 3_000);
+        assert!(client.is_goal_completed(&goal_id));
+
+        let history = client.get_target_history(&goal_id);
+        assert_eq!(history.len(), 2);
+        let first = history.get(0).unwrap();
+        assert_eq!((first.old_target, first.new_target), (10_000, 20_000));
+        let second = history.get(1).unwrap();
+        assert_eq!((second.old_target, second.new_target), (20_000, 3_000));
+        assert_eq!(second.timestamp, first.timestamp + 100);
+    }
 
-                let is_completed = goal.current_amount >= goal.target_amount;
-                goals.set(schedule.goal_id, goal.clone());
+    // --- categories and templates ---
 
-                env.events().publish(
-                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
-                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
-                );
+    #[test]
+    fn test_create_goal_from_template() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
 
-                if is_completed {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                        (schedule.goal_id, goal.owner),
-                    );
-                }
-            }
+        assert_eq!(
+            client.try_create_goal_from_template(&owner, &1),
+            Err(Ok(SavingsGoalsError::TemplateNotFound))
+        );
 
-            schedule.last_executed = Some(current_time);
+        let template = GoalTemplate {
+            name: String::from_str(&env, "School fees"),
+            category: GoalCategory::Education,
+            target_amount: 50_000,
+            duration: 86_400 * 180,
+            lock_period: 86_400 * 90,
+        };
+        client.set_upgrade_admin(&admin, &admin);
+        assert_eq!(
+            client.try_set_goal_template(&owner, &1, &template),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+        client.set_goal_template(&admin, &1, &template);
 
-            if schedule.recurring && schedule.interval > 0 {
-                let mut missed = 0u32;
-                let mut next = schedule.next_due + schedule.interval;
-                while next <= current_time {
-                    missed += 1;
-                    next += schedule.interval;
-                }
-                schedule.missed_count += missed;
-                schedule.next_due = next;
+        let now = env.ledger().timestamp();
+        let goal_id = client.create_goal_from_template(&owner, &1);
+        let goal = client.get_goal(&goal_id).unwrap();
+        assert_eq!(goal.owner, owner);
+        assert_eq!(goal.name, template.name);
+        assert_eq!(goal.category, GoalCategory::Education);
+        assert_eq!(goal.target_amount, 50_000);
+        assert_eq!(goal.target_date, now + 86_400 * 180);
+        assert_eq!(goal.unlock_date, Some(now + 86_400 * 90));
+    }
 
-                if missed > 0 {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
-                        (schedule_id, missed),
-                    );
-                }
-            } else {
-                schedule.active = false;
-            }
+    #[test]
+    fn test_set_goal_category() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
 
-            schedules.set(schedule_id, schedule);
-            executed.push_back(schedule_id);
+        let goal_id = create_funded_goal(&env, &client, &owner, 100);
+        assert_eq!(
+            client.get_goal(&goal_id).unwrap().category,
+            GoalCategory::General
+        );
+        client.set_goal_category(&owner, &goal_id, &GoalCategory::Medical);
+        assert_eq!(
+            client.get_goal(&goal_id).unwrap().category,
+            GoalCategory::Medical
+        );
+        assert_eq!(
+            client.try_set_goal_category(
+                &Address::generate(&env),
+                &goal_id,
+                &GoalCategory::Housing
+            ),
+            Err(Ok(SavingsGoalsError::Unauthorized))
+        );
+    }
 
-            env.events().publish(
-                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
-                schedule_id,
-            );
-        }
+    // --- multi-asset goals ---
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+    #[test]
+    fn test_goal_asset_validates_token_flows() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let usdc = setup_token(&env, &owner, 1_000);
+        let xlm = setup_token(&env, &owner, 1_000);
 
-        executed
-    }
+        setup_goals(&env, &client, &owner, 2);
+        client.set_goal_asset(&owner, &1, &usdc);
+        assert_eq!(client.get_goal(&1).unwrap().asset, Some(usdc.clone()));
 
-    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        assert_eq!(
+            client.try_set_contribution_schedule(&owner, &1, &xlm, &100, &7),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
+        client.set_contribution_schedule(&owner, &1, &usdc, &100, &7);
+        assert_eq!(
+            client.try_set_payout_target(&owner, &1, &xlm, &owner),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
+        assert_eq!(
+            client.try_set_sponsor_match(&Address::generate(&env), &1, &xlm, &5_000, &100),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
 
-        let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
-                result.push_back(schedule);
-            }
+        // Deposits and withdrawals must move the goal's own asset.
+        for token in [None, Some(xlm.clone())] {
+            assert_eq!(
+                client.try_add_to_goal(&owner, &1, &token, &500),
+                Err(Ok(SavingsGoalsError::AssetMismatch))
+            );
         }
-        result
-    }
+        client.add_to_goal(&owner, &1, &Some(usdc.clone()), &500);
+        assert_eq!(TokenClient::new(&env, &usdc).balance(&id), 500);
+        assert_eq!(client.get_held_balance(&1, &usdc), 500);
+
+        // Paths that move no tokens leave the denominated goal alone.
+        let credited = client.deposit_allocation(&owner, &100);
+        assert_eq!(credited.len(), 1);
+        assert_eq!(credited.get(0).unwrap().goal_id, 2);
+        let items = Vec::from_array(
+            &env,
+            [ContributionItem {
+                goal_id: 1,
+                amount: 10,
+            }],
+        );
+        assert!(client.try_batch_add_to_goals(&owner, &items).is_err());
 
-    pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-        schedules.get(schedule_id)
+        // A funded goal keeps its denomination.
+        assert_eq!(
+            client.try_set_goal_asset(&owner, &1, &xlm),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
+
+        client.unlock_goal(&owner, &1);
+        assert_eq!(
+            client.try_withdraw_from_goal(&owner, &1, &None, &200),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
+        client.withdraw_from_goal(&owner, &1, &Some(usdc.clone()), &200);
+        assert_eq!(TokenClient::new(&env, &usdc).balance(&owner), 700);
+        assert_eq!(client.get_held_balance(&1, &usdc), 300);
     }
-}
 
-// -----------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Env, String,
-    };
+    #[test]
+    fn test_totals_by_asset_and_same_asset_transfers() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let usdc = setup_token(&env, &owner, 1_000);
 
-    fn make_env() -> Env {
-        Env::default()
-    }
+        setup_goals(&env, &client, &owner, 3);
+        client.set_goal_asset(&owner, &1, &usdc);
+        client.set_goal_asset(&owner, &3, &usdc);
+        client.add_to_goal(&owner, &1, &Some(usdc.clone()), &300);
+        client.add_to_goal(&owner, &2, &None, &200);
+        client.add_to_goal(&owner, &3, &Some(usdc.clone()), &400);
+        client.unlock_goal(&owner, &1);
 
-    fn setup_goals(env: &Env, client: &SavingsGoalContractClient, owner: &Address, count: u32) {
-        for i in 0..count {
-            client.create_goal(
-                owner,
-                &String::from_str(env, "Goal"),
-                &(1000i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-            );
-        }
+        assert_eq!(
+            client.try_transfer_between_goals(&owner, &1, &2, &100),
+            Err(Ok(SavingsGoalsError::AssetMismatch))
+        );
+        client.transfer_between_goals(&owner, &1, &3, &100);
+        assert_eq!(client.get_held_balance(&1, &usdc), 200);
+        assert_eq!(client.get_held_balance(&3, &usdc), 500);
+
+        let totals = client.get_totals_by_asset(&owner);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(
+            totals.get(0).unwrap(),
+            AssetTotal {
+                asset: Some(usdc),
+                total: 700,
+                goal_count: 2,
+            }
+        );
+        assert_eq!(totals.get(1).unwrap().asset, None);
+        assert_eq!(totals.get(1).unwrap().total, 200);
     }
 
     // --- get_goals ---
@@ -1717,7 +8164,7 @@ mod test {
         assert!(!client.is_goal_completed(&goal_id));
 
         // Fund after deadline
-        client.add_to_goal(&owner, &goal_id, &10000);
+        client.add_to_goal(&owner, &goal_id, &None, &10000);
         assert!(
             client.is_goal_completed(&goal_id),
             "Goal must complete on amount alone regardless of time"
@@ -1743,7 +8190,7 @@ mod test {
         );
 
         assert!(!client.is_goal_completed(&goal_id));
-        client.add_to_goal(&owner, &goal_id, &5000);
+        client.add_to_goal(&owner, &goal_id, &None, &5000);
         assert!(
             client.is_goal_completed(&goal_id),
             "Goal must complete before target_date when amount is reached"
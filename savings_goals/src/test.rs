@@ -46,7 +46,7 @@ fn test_create_goal_unique_ids() {
 // ============================================================================
 // init() idempotency and NEXT_ID behavior
 //
-// init() bootstraps storage (NEXT_ID and GOALS) only when keys are missing.
+// init() bootstraps storage (NEXT_ID) only when the key is missing.
 // In production or integration, init() may be called more than once (e.g. by
 // different entrypoints or upgrade paths). These tests lock in that:
 // - A second init() must not remove or alter existing goals.
@@ -88,7 +88,11 @@ fn test_init_idempotent_does_not_wipe_goals() {
     assert_eq!(goal_after_second_init.current_amount, 0);
 
     let all_goals = client.get_all_goals(&owner_a);
-    assert_eq!(all_goals.len(), 1, "get_all_goals must still return the one goal");
+    assert_eq!(
+        all_goals.len(),
+        1,
+        "get_all_goals must still return the one goal"
+    );
 
     // Verify NEXT_ID was not reset: next created goal must get goal_id == 2, not 1
     let name2 = String::from_str(&env, "Second Goal");
@@ -157,7 +161,7 @@ fn test_add_to_goal_increments() {
     env.mock_all_auths();
     let id = client.create_goal(&user, &String::from_str(&env, "Save"), &1000, &2000000000);
 
-    let new_balance = client.add_to_goal(&user, &id, &500);
+    let new_balance = client.add_to_goal(&user, &id, &None, &500);
     assert_eq!(new_balance, 500);
 }
 
@@ -170,7 +174,7 @@ fn test_add_to_non_existent_goal() {
 
     client.init();
     env.mock_all_auths();
-    let res = client.try_add_to_goal(&user, &99, &500);
+    let res = client.try_add_to_goal(&user, &99, &None, &500);
     assert_eq!(res, Err(Ok(SavingsGoalError::GoalNotFound)));
 }
 
@@ -228,7 +232,7 @@ fn test_is_goal_completed() {
     );
 
     // 3. Add exactly the target amount
-    client.add_to_goal(&user, &id, &target);
+    client.add_to_goal(&user, &id, &None, &target);
 
     // 4. Verify the balance actually updated in storage
     let goal = client.get_goal(&id).unwrap();
@@ -244,7 +248,7 @@ fn test_is_goal_completed() {
     );
 
     // 6. Bonus: Check that it stays completed if we go over the target
-    client.add_to_goal(&user, &id, &1);
+    client.add_to_goal(&user, &id, &None, &1);
     assert!(
         client.is_goal_completed(&id),
         "Goal should stay completed if overfunded"
@@ -267,7 +271,7 @@ fn test_edge_cases_large_amounts() {
         &2000000000,
     );
 
-    client.add_to_goal(&user, &id, &(i128::MAX - 100));
+    client.add_to_goal(&user, &id, &None, &(i128::MAX - 100));
     let goal = client.get_goal(&id).unwrap();
     assert_eq!(goal.current_amount, i128::MAX - 100);
 }
@@ -297,8 +301,8 @@ fn test_multiple_goals_management() {
     let id1 = client.create_goal(&user, &String::from_str(&env, "G1"), &1000, &2000000000);
     let id2 = client.create_goal(&user, &String::from_str(&env, "G2"), &2000, &2000000000);
 
-    client.add_to_goal(&user, &id1, &500);
-    client.add_to_goal(&user, &id2, &1500);
+    client.add_to_goal(&user, &id1, &None, &500);
+    client.add_to_goal(&user, &id2, &None, &1500);
 
     let g1 = client.get_goal(&id1).unwrap();
     let g2 = client.get_goal(&id2).unwrap();
@@ -321,9 +325,9 @@ fn test_withdraw_from_goal() {
     // Unlock first (created locked)
     client.unlock_goal(&user, &id);
 
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
 
-    let new_balance = client.withdraw_from_goal(&user, &id, &200);
+    let new_balance = client.withdraw_from_goal(&user, &id, &None, &200);
     assert_eq!(new_balance, 300);
 
     let goal = client.get_goal(&id).unwrap();
@@ -342,9 +346,9 @@ fn test_withdraw_too_much() {
     let id = client.create_goal(&user, &String::from_str(&env, "W"), &1000, &2000000000);
 
     client.unlock_goal(&user, &id);
-    client.add_to_goal(&user, &id, &100);
+    client.add_to_goal(&user, &id, &None, &100);
 
-    let res = client.try_withdraw_from_goal(&user, &id, &200);
+    let res = client.try_withdraw_from_goal(&user, &id, &None, &200);
     assert_eq!(res, Err(Ok(SavingsGoalError::InsufficientBalance)));
 }
 
@@ -360,8 +364,8 @@ fn test_withdraw_locked() {
     let id = client.create_goal(&user, &String::from_str(&env, "L"), &1000, &2000000000);
 
     // Goal is locked by default
-    client.add_to_goal(&user, &id, &500);
-    let res = client.try_withdraw_from_goal(&user, &id, &100);
+    client.add_to_goal(&user, &id, &None, &500);
+    let res = client.try_withdraw_from_goal(&user, &id, &None, &100);
     assert_eq!(res, Err(Ok(SavingsGoalError::GoalLocked)));
 }
 
@@ -378,9 +382,9 @@ fn test_withdraw_unauthorized() {
     let id = client.create_goal(&user, &String::from_str(&env, "Auth"), &1000, &2000000000);
 
     client.unlock_goal(&user, &id);
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
 
-    let res = client.try_withdraw_from_goal(&other, &id, &100);
+    let res = client.try_withdraw_from_goal(&other, &id, &None, &100);
     assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
 }
 
@@ -396,15 +400,15 @@ fn test_lock_unlock_goal() {
     let id = client.create_goal(&user, &String::from_str(&env, "Lock"), &1000, &2000000000);
 
     let goal = client.get_goal(&id).unwrap();
-    assert!(goal.locked);
+    assert_eq!(goal.lock_policy, LockPolicy::Soft);
 
     client.unlock_goal(&user, &id);
     let goal = client.get_goal(&id).unwrap();
-    assert!(!goal.locked);
+    assert_eq!(goal.lock_policy, LockPolicy::Unlocked);
 
     client.lock_goal(&user, &id);
     let goal = client.get_goal(&id).unwrap();
-    assert!(goal.locked);
+    assert_eq!(goal.lock_policy, LockPolicy::Soft);
 }
 
 #[test]
@@ -419,10 +423,10 @@ fn test_full_withdrawal() {
     let id = client.create_goal(&user, &String::from_str(&env, "W"), &1000, &2000000000);
 
     client.unlock_goal(&user, &id);
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
 
     // Withdraw everything
-    let new_balance = client.withdraw_from_goal(&user, &id, &500);
+    let new_balance = client.withdraw_from_goal(&user, &id, &None, &500);
     assert_eq!(new_balance, 0);
 
     let goal = client.get_goal(&id).unwrap();
@@ -442,10 +446,10 @@ fn test_exact_goal_completion() {
     let id = client.create_goal(&user, &String::from_str(&env, "Exact"), &1000, &2000000000);
 
     // Add 500 twice
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
     assert!(!client.is_goal_completed(&id));
 
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
     assert!(client.is_goal_completed(&id));
 
     let goal = client.get_goal(&id).unwrap();
@@ -482,11 +486,11 @@ fn test_withdraw_time_locked_goal_before_unlock() {
 
     let goal_id = client.create_goal(&owner, &String::from_str(&env, "Education"), &10000, &5000);
 
-    client.add_to_goal(&owner, &goal_id, &5000);
+    client.add_to_goal(&owner, &goal_id, &None, &5000);
     client.unlock_goal(&owner, &goal_id);
     client.set_time_lock(&owner, &goal_id, &10000);
 
-    let result = client.try_withdraw_from_goal(&owner, &goal_id, &1000);
+    let result = client.try_withdraw_from_goal(&owner, &goal_id, &None, &1000);
     assert!(result.is_err());
 }
 
@@ -502,12 +506,12 @@ fn test_withdraw_time_locked_goal_after_unlock() {
 
     let goal_id = client.create_goal(&owner, &String::from_str(&env, "Education"), &10000, &5000);
 
-    client.add_to_goal(&owner, &goal_id, &5000);
+    client.add_to_goal(&owner, &goal_id, &None, &5000);
     client.unlock_goal(&owner, &goal_id);
     client.set_time_lock(&owner, &goal_id, &3000);
 
     set_time(&env, 3500);
-    let new_amount = client.withdraw_from_goal(&owner, &goal_id, &1000);
+    let new_amount = client.withdraw_from_goal(&owner, &goal_id, &None, &1000);
     assert_eq!(new_amount, 4000);
 }
 
@@ -684,10 +688,13 @@ fn test_lock_goal_success() {
     );
 
     client.unlock_goal(&user, &id);
-    assert!(!client.get_goal(&id).unwrap().locked);
+    assert_eq!(
+        client.get_goal(&id).unwrap().lock_policy,
+        LockPolicy::Unlocked
+    );
 
     client.lock_goal(&user, &id);
-    assert!(client.get_goal(&id).unwrap().locked);
+    assert_eq!(client.get_goal(&id).unwrap().lock_policy, LockPolicy::Soft);
 }
 
 #[test]
@@ -706,10 +713,13 @@ fn test_unlock_goal_success() {
         &2000000000,
     );
 
-    assert!(client.get_goal(&id).unwrap().locked);
+    assert_eq!(client.get_goal(&id).unwrap().lock_policy, LockPolicy::Soft);
 
     client.unlock_goal(&user, &id);
-    assert!(!client.get_goal(&id).unwrap().locked);
+    assert_eq!(
+        client.get_goal(&id).unwrap().lock_policy,
+        LockPolicy::Unlocked
+    );
 }
 
 #[test]
@@ -773,10 +783,10 @@ fn test_withdraw_after_lock_fails() {
     );
 
     client.unlock_goal(&user, &id);
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
     client.lock_goal(&user, &id);
 
-    let res = client.try_withdraw_from_goal(&user, &id, &100);
+    let res = client.try_withdraw_from_goal(&user, &id, &None, &100);
     assert_eq!(res, Err(Ok(SavingsGoalError::GoalLocked)));
 }
 
@@ -797,9 +807,9 @@ fn test_withdraw_after_unlock_succeeds() {
     );
 
     client.unlock_goal(&user, &id);
-    client.add_to_goal(&user, &id, &500);
+    client.add_to_goal(&user, &id, &None, &500);
 
-    let new_balance = client.withdraw_from_goal(&user, &id, &200);
+    let new_balance = client.withdraw_from_goal(&user, &id, &None, &200);
     assert_eq!(new_balance, 300);
 
     let goal = client.get_goal(&id).unwrap();
@@ -892,7 +902,7 @@ fn test_add_to_goal_emits_event() {
     );
 
     // Add funds
-    let new_amount = client.add_to_goal(&user, &goal_id, &1000);
+    let new_amount = client.add_to_goal(&user, &goal_id, &None, &1000);
     assert_eq!(new_amount, 1000);
 
     let events = env.events().all();
@@ -946,7 +956,7 @@ fn test_goal_completed_emits_event() {
     );
 
     // Add funds to complete the goal
-    client.add_to_goal(&user, &goal_id, &1000);
+    client.add_to_goal(&user, &goal_id, &None, &1000);
 
     let events = env.events().all();
     let mut found_completed_struct = false;
@@ -1000,8 +1010,8 @@ fn test_withdraw_from_goal_emits_event() {
         &1735689600,
     );
     client.unlock_goal(&user, &goal_id);
-    client.add_to_goal(&user, &goal_id, &1500);
-    client.withdraw_from_goal(&user, &goal_id, &600);
+    client.add_to_goal(&user, &goal_id, &None, &1500);
+    client.withdraw_from_goal(&user, &goal_id, &None, &600);
 
     let events = env.events().all();
     let mut found_withdrawn_enum = false;
@@ -1228,7 +1238,7 @@ fn test_instance_ttl_refreshed_on_add_to_goal() {
     });
 
     // add_to_goal calls extend_instance_ttl → re-extends TTL to 518,400
-    let new_balance = client.add_to_goal(&user, &goal_id, &500);
+    let new_balance = client.add_to_goal(&user, &goal_id, &None, &500);
     assert_eq!(new_balance, 500);
 
     let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -1284,7 +1294,7 @@ fn test_savings_data_persists_across_ledger_advancements() {
         max_entry_ttl: 700_000,
     });
 
-    client.add_to_goal(&user, &id1, &3000);
+    client.add_to_goal(&user, &id1, &None, &3000);
 
     // Phase 3: Advance to seq 1,020,000 (TTL = 8,400 < 17,280)
     env.ledger().set(LedgerInfo {
@@ -1299,7 +1309,7 @@ fn test_savings_data_persists_across_ledger_advancements() {
     });
 
     // Add more funds to second goal
-    client.add_to_goal(&user, &id2, &10000);
+    client.add_to_goal(&user, &id2, &None, &10000);
 
     // All goals should be accessible with correct data
     let goal1 = client.get_goal(&id1);
@@ -1420,7 +1430,7 @@ fn test_time_drift_is_goal_completed_depends_on_amount_not_time() {
     assert!(!client.is_goal_completed(&goal_id));
 
     // Fund the goal fully after the deadline
-    client.add_to_goal(&owner, &goal_id, &10000);
+    client.add_to_goal(&owner, &goal_id, &None, &10000);
     assert!(
         client.is_goal_completed(&goal_id),
         "Goal must complete on amount alone, regardless of time"
@@ -1446,7 +1456,7 @@ fn test_time_drift_is_goal_completed_early_funding() {
     );
 
     assert!(!client.is_goal_completed(&goal_id));
-    client.add_to_goal(&owner, &goal_id, &5000);
+    client.add_to_goal(&owner, &goal_id, &None, &5000);
     assert!(
         client.is_goal_completed(&goal_id),
         "Goal must complete before target_date when amount is reached"
@@ -1465,12 +1475,7 @@ fn test_time_drift_schedule_executes_at_exact_next_due() {
     env.mock_all_auths();
     set_time(&env, 1000);
 
-    let goal_id = client.create_goal(
-        &owner,
-        &String::from_str(&env, "House"),
-        &50000,
-        &200000,
-    );
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "House"), &50000, &200000);
     let next_due = 3000u64;
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &next_due, &86400);
 
@@ -1488,7 +1493,11 @@ fn test_time_drift_schedule_executes_at_exact_next_due() {
     // Exactly at next_due: must execute
     set_time(&env, next_due);
     let executed = client.execute_due_savings_schedules();
-    assert_eq!(executed.len(), 1, "Schedule must execute exactly at next_due");
+    assert_eq!(
+        executed.len(),
+        1,
+        "Schedule must execute exactly at next_due"
+    );
     assert_eq!(executed.get(0).unwrap(), schedule_id);
     let goal = client.get_goal(&goal_id).unwrap();
     assert_eq!(goal.current_amount, 500);
@@ -1507,12 +1516,7 @@ fn test_time_drift_no_double_execution_after_next_due_advances() {
     env.mock_all_auths();
     set_time(&env, 1000);
 
-    let goal_id = client.create_goal(
-        &owner,
-        &String::from_str(&env, "Car"),
-        &20000,
-        &999999,
-    );
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Car"), &20000, &999999);
     let next_due = 5000u64;
     let interval = 86400u64;
     client.create_savings_schedule(&owner, &goal_id, &1000, &next_due, &interval);
@@ -1534,7 +1538,10 @@ fn test_time_drift_no_double_execution_after_next_due_advances() {
     );
 
     let goal = client.get_goal(&goal_id).unwrap();
-    assert_eq!(goal.current_amount, 1000, "Funds must be added exactly once");
+    assert_eq!(
+        goal.current_amount, 1000,
+        "Funds must be added exactly once"
+    );
 }
 
 /// A large forward jump past multiple intervals marks the correct missed_count
@@ -1549,12 +1556,7 @@ fn test_time_drift_large_jump_marks_missed_count() {
     env.mock_all_auths();
     set_time(&env, 1000);
 
-    let goal_id = client.create_goal(
-        &owner,
-        &String::from_str(&env, "Tuition"),
-        &50000,
-        &9999999,
-    );
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Tuition"), &50000, &9999999);
     let next_due = 2000u64;
     let interval = 86400u64;
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &next_due, &interval);
@@ -1601,7 +1603,7 @@ fn test_add_to_goal_non_owner_auth_failure() {
     }]);
 
     let id = client.create_goal(&user, &String::from_str(&env, "Auth"), &1000, &2000000000);
-    client.add_to_goal(&other, &id, &500);
+    client.add_to_goal(&other, &id, &None, &500);
 }
 
 #[test]
@@ -1631,7 +1633,7 @@ fn test_withdraw_from_goal_non_owner_auth_failure() {
     }]);
 
     let id = client.create_goal(&user, &String::from_str(&env, "Auth"), &1000, &2000000000);
-    client.withdraw_from_goal(&other, &id, &100);
+    client.withdraw_from_goal(&other, &id, &None, &100);
 }
 
 #[test]
@@ -14,7 +14,7 @@
 //! - No explicit caps are imposed by the contract, but overflow/underflow will panic
 //! - batch_add_to_goals has same limitations as add_to_goal for each contribution
 
-use savings_goals::{ContributionItem, SavingsGoalContract, SavingsGoalContractClient};
+use savings_goals::{ContributionItem, LockPolicy, SavingsGoalContract, SavingsGoalContractClient};
 use soroban_sdk::testutils::{Address as AddressTrait, Ledger, LedgerInfo};
 use soroban_sdk::{Env, String, Vec};
 
@@ -74,7 +74,7 @@ fn test_add_to_goal_with_large_amount() {
     );
 
     env.mock_all_auths();
-    let new_total = client.add_to_goal(&owner, &goal_id, &large_contribution);
+    let new_total = client.add_to_goal(&owner, &goal_id, &None, &large_contribution);
 
     assert_eq!(new_total, large_contribution);
 
@@ -102,15 +102,15 @@ fn test_add_to_goal_multiple_large_contributions() {
 
     // Add multiple times safely
     env.mock_all_auths();
-    let total1 = client.add_to_goal(&owner, &goal_id, &contribution);
+    let total1 = client.add_to_goal(&owner, &goal_id, &None, &contribution);
     assert_eq!(total1, contribution);
 
     env.mock_all_auths();
-    let total2 = client.add_to_goal(&owner, &goal_id, &contribution);
+    let total2 = client.add_to_goal(&owner, &goal_id, &None, &contribution);
     assert_eq!(total2, contribution + contribution);
 
     env.mock_all_auths();
-    let total3 = client.add_to_goal(&owner, &goal_id, &contribution);
+    let total3 = client.add_to_goal(&owner, &goal_id, &None, &contribution);
     assert_eq!(total3, contribution + contribution + contribution);
 }
 #[test]
@@ -135,11 +135,11 @@ fn test_add_to_goal_overflow_panics() {
 
     // First addition
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal_id, &overflow_amount);
+    client.add_to_goal(&owner, &goal_id, &None, &overflow_amount);
 
     // Second addition should overflow
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal_id, &overflow_amount);
+    client.add_to_goal(&owner, &goal_id, &None, &overflow_amount);
 }
 #[test]
 fn test_withdraw_from_goal_with_large_amount() {
@@ -162,7 +162,7 @@ fn test_withdraw_from_goal_with_large_amount() {
 
     // Add funds
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal_id, &large_amount);
+    client.add_to_goal(&owner, &goal_id, &None, &large_amount);
 
     // Unlock to allow withdrawal
     env.mock_all_auths();
@@ -170,7 +170,7 @@ fn test_withdraw_from_goal_with_large_amount() {
 
     // Withdraw half
     env.mock_all_auths();
-    let remaining = client.withdraw_from_goal(&owner, &goal_id, &(large_amount / 2));
+    let remaining = client.withdraw_from_goal(&owner, &goal_id, &None, &(large_amount / 2));
 
     assert_eq!(remaining, large_amount / 2);
 }
@@ -194,7 +194,7 @@ fn test_goal_completion_with_large_amounts() {
 
     // Add exactly the target amount
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal_id, &large_target);
+    client.add_to_goal(&owner, &goal_id, &None, &large_target);
 
     // Verify goal is completed
     let is_completed = client.is_goal_completed(&goal_id);
@@ -382,25 +382,25 @@ fn test_lock_unlock_with_large_amounts() {
 
     // Add funds
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal_id, &large_amount);
+    client.add_to_goal(&owner, &goal_id, &None, &large_amount);
 
     // Goal starts locked
     let goal = client.get_goal(&goal_id).unwrap();
-    assert!(goal.locked);
+    assert_eq!(goal.lock_policy, LockPolicy::Soft);
 
     // Unlock
     env.mock_all_auths();
     client.unlock_goal(&owner, &goal_id);
 
     let goal = client.get_goal(&goal_id).unwrap();
-    assert!(!goal.locked);
+    assert_eq!(goal.lock_policy, LockPolicy::Unlocked);
 
     // Lock again
     env.mock_all_auths();
     client.lock_goal(&owner, &goal_id);
 
     let goal = client.get_goal(&goal_id).unwrap();
-    assert!(goal.locked);
+    assert_eq!(goal.lock_policy, LockPolicy::Soft);
 }
 #[test]
 fn test_sequential_large_operations() {
@@ -429,7 +429,7 @@ fn test_sequential_large_operations() {
         );
 
         env.mock_all_auths();
-        client.add_to_goal(&owner, &goal_id, &(amount / 2));
+        client.add_to_goal(&owner, &goal_id, &None, &(amount / 2));
 
         let goal = client.get_goal(&goal_id).unwrap();
         assert_eq!(goal.current_amount, amount / 2);
@@ -461,7 +461,7 @@ fn test_time_lock_with_large_amounts() {
 
     // Add funds
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal_id, &large_amount);
+    client.add_to_goal(&owner, &goal_id, &None, &large_amount);
 
     // Set time lock
     env.mock_all_auths();
@@ -473,7 +473,7 @@ fn test_time_lock_with_large_amounts() {
 
     // Try to withdraw before time lock expires (should fail)
     env.mock_all_auths();
-    let result = client.try_withdraw_from_goal(&owner, &goal_id, &1000);
+    let result = client.try_withdraw_from_goal(&owner, &goal_id, &None, &1000);
     assert!(result.is_err());
 
     // Advance time past the lock
@@ -481,7 +481,7 @@ fn test_time_lock_with_large_amounts() {
 
     // Now withdrawal should succeed
     env.mock_all_auths();
-    let remaining = client.withdraw_from_goal(&owner, &goal_id, &1000);
+    let remaining = client.withdraw_from_goal(&owner, &goal_id, &None, &1000);
     assert_eq!(remaining, large_amount - 1000);
 }
 #[test]
@@ -505,7 +505,7 @@ fn test_export_import_snapshot_with_large_amounts() {
     );
 
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal1, &large_amount);
+    client.add_to_goal(&owner, &goal1, &None, &large_amount);
 
     env.mock_all_auths();
     let goal2 = client.create_goal(
@@ -516,7 +516,7 @@ fn test_export_import_snapshot_with_large_amounts() {
     );
 
     env.mock_all_auths();
-    client.add_to_goal(&owner, &goal2, &large_amount);
+    client.add_to_goal(&owner, &goal2, &None, &large_amount);
 
     // Export snapshot
     env.mock_all_auths();
@@ -78,6 +78,8 @@ fn test_end_to_end_flow() {
         &true,
         &30,
         &String::from_str(&env, "USDC"),
+        &Address::generate(&env),
+        &usdc_id,
     );
 
     // Advance time